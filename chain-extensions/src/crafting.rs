@@ -0,0 +1,126 @@
+// This file is part of Web3Games.
+
+// Copyright (C) 2021-2022 Web3Games https://web3games.org
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use codec::Encode;
+use pallet_contracts::chain_extension::{
+	ChainExtension, Environment, Ext, InitState, Result, RetVal, SysConfig, UncheckedFrom,
+};
+use pallet_crafting::{CraftingInput, CraftingOutput, RecipeId};
+use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
+
+pub struct CraftingExtension;
+
+impl<C> ChainExtension<C> for CraftingExtension
+where
+	C: pallet_contracts::Config + pallet_crafting::Config,
+	<C as pallet_contracts::Config>::Call: From<pallet_crafting::Call<C>>,
+{
+	fn call<E>(func_id: u32, env: Environment<E, InitState>) -> Result<RetVal>
+	where
+		E: Ext<T = C>,
+		<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+	{
+		match func_id {
+			// create_recipe
+			65730 => {
+				let mut env = env.buf_in_buf_out();
+				let caller = env.ext().caller().clone();
+
+				let (inputs, outputs, outcomes): (
+					Vec<CraftingInput>,
+					Vec<CraftingOutput>,
+					Vec<(u32, Vec<CraftingOutput>)>,
+				) = env.read_as_unbounded(env.in_len())?;
+				env.charge_weight(10000)?;
+
+				let recipe_id = pallet_crafting::Pallet::<E::T>::do_create_recipe(
+					&caller, inputs, outputs, outcomes,
+				)?;
+
+				let recipe_id_slice = recipe_id.encode();
+
+				env.write(&recipe_id_slice, false, None).map_err(|_| {
+					DispatchError::Other("ChainExtension failed to call create_recipe")
+				})?;
+			},
+			// set_recipe_enabled
+			65731 => {
+				let mut env = env.buf_in_buf_out();
+				let caller = env.ext().caller().clone();
+
+				let (recipe_id, enabled): (RecipeId, bool) = env.read_as()?;
+				env.charge_weight(10000)?;
+
+				let result = pallet_crafting::Pallet::<E::T>::do_set_recipe_enabled(
+					&caller, recipe_id, enabled,
+				)?;
+
+				let result_slice = result.encode();
+
+				env.write(&result_slice, false, None).map_err(|_| {
+					DispatchError::Other("ChainExtension failed to call set_recipe_enabled")
+				})?;
+			},
+			// remove_recipe
+			65732 => {
+				let mut env = env.buf_in_buf_out();
+				let caller = env.ext().caller().clone();
+
+				let recipe_id: RecipeId = env.read_as()?;
+				env.charge_weight(10000)?;
+
+				let result = pallet_crafting::Pallet::<E::T>::do_remove_recipe(&caller, recipe_id)?;
+
+				let result_slice = result.encode();
+
+				env.write(&result_slice, false, None).map_err(|_| {
+					DispatchError::Other("ChainExtension failed to call remove_recipe")
+				})?;
+			},
+			// craft
+			65733 => {
+				let mut env = env.buf_in_buf_out();
+				let caller = env.ext().caller().clone();
+
+				let (recipe_id, times, token_ids): (RecipeId, u32, Vec<u128>) =
+					env.read_as_unbounded(env.in_len())?;
+				env.charge_weight(pallet_crafting::Pallet::<E::T>::craft_weight(times))?;
+
+				pallet_crafting::Pallet::<E::T>::ensure_deterministic(recipe_id)?;
+				let result = pallet_crafting::Pallet::<E::T>::do_craft(
+					&caller, recipe_id, times, token_ids,
+				)?;
+
+				let result_slice = result.encode();
+
+				env.write(&result_slice, false, None)
+					.map_err(|_| DispatchError::Other("ChainExtension failed to call craft"))?;
+			},
+			_ => {
+				log::error!("call an unregistered `func_id`, func_id:{:}", func_id);
+				return Err(DispatchError::Other("Unimplemented func_id"))
+			},
+		}
+		Ok(RetVal::Converging(0))
+	}
+
+	fn enabled() -> bool {
+		true
+	}
+}
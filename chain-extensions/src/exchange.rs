@@ -0,0 +1,159 @@
+// This file is part of Web3Games.
+
+// Copyright (C) 2021-2022 Web3Games https://web3games.org
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use codec::Encode;
+use frame_support::traits::Get;
+use pallet_contracts::chain_extension::{
+	ChainExtension, Environment, Ext, InitState, Result, RetVal, SysConfig, UncheckedFrom,
+};
+use pallet_exchange::WeightInfo;
+use primitives::Balance;
+use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
+
+pub struct ExchangeExtension;
+
+impl<C> ChainExtension<C> for ExchangeExtension
+where
+	C: pallet_contracts::Config + pallet_exchange::Config,
+	<C as pallet_contracts::Config>::Call: From<pallet_exchange::Call<C>>,
+{
+	fn call<E>(func_id: u32, env: Environment<E, InitState>) -> Result<RetVal>
+	where
+		E: Ext<T = C>,
+		<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+	{
+		match func_id {
+			// flash_swap
+			65794 => {
+				let mut env = env.buf_in_buf_out();
+				let caller = env.ext().caller().clone();
+
+				let (token_a, token_b, fee, amount_a_out, amount_b_out, data): (
+					<E::T as pallet_token_fungible::Config>::FungibleTokenId,
+					<E::T as pallet_token_fungible::Config>::FungibleTokenId,
+					u32,
+					Balance,
+					Balance,
+					Vec<u8>,
+				) = env.read_as_unbounded(env.in_len())?;
+				env.charge_weight(
+					<E::T as pallet_exchange::Config>::WeightInfo::flash_swap().saturating_add(
+						<E::T as pallet_exchange::Config>::MaxFlashCallbackWeight::get(),
+					),
+				)?;
+
+				let result = pallet_exchange::Pallet::<E::T>::do_flash_swap(
+					caller,
+					token_a,
+					token_b,
+					fee,
+					amount_a_out,
+					amount_b_out,
+					&data,
+				)?;
+
+				let result_slice = result.encode();
+
+				env.write(&result_slice, false, None).map_err(|_| {
+					DispatchError::Other("ChainExtension failed to call flash_swap")
+				})?;
+			},
+			// flash_loan
+			65795 => {
+				let mut env = env.buf_in_buf_out();
+				let caller = env.ext().caller().clone();
+
+				let (token, pair_token, fee, amount, data): (
+					<E::T as pallet_token_fungible::Config>::FungibleTokenId,
+					<E::T as pallet_token_fungible::Config>::FungibleTokenId,
+					u32,
+					Balance,
+					Vec<u8>,
+				) = env.read_as_unbounded(env.in_len())?;
+				env.charge_weight(
+					<E::T as pallet_exchange::Config>::WeightInfo::flash_loan().saturating_add(
+						<E::T as pallet_exchange::Config>::MaxFlashCallbackWeight::get(),
+					),
+				)?;
+
+				let result = pallet_exchange::Pallet::<E::T>::do_flash_loan(
+					caller, token, pair_token, fee, amount, &data,
+				)?;
+
+				let result_slice = result.encode();
+
+				env.write(&result_slice, false, None).map_err(|_| {
+					DispatchError::Other("ChainExtension failed to call flash_loan")
+				})?;
+			},
+			// flash_repay
+			65796 => {
+				let mut env = env.buf_in_buf_out();
+				let caller = env.ext().caller().clone();
+
+				let (token_a, token_b, fee, token, amount): (
+					<E::T as pallet_token_fungible::Config>::FungibleTokenId,
+					<E::T as pallet_token_fungible::Config>::FungibleTokenId,
+					u32,
+					<E::T as pallet_token_fungible::Config>::FungibleTokenId,
+					Balance,
+				) = env.read_as()?;
+				env.charge_weight(10000)?;
+
+				let result = pallet_exchange::Pallet::<E::T>::flash_repay(
+					&caller, token_a, token_b, fee, token, amount,
+				)?;
+
+				let result_slice = result.encode();
+
+				env.write(&result_slice, false, None).map_err(|_| {
+					DispatchError::Other("ChainExtension failed to call flash_repay")
+				})?;
+			},
+			// pool_account
+			65797 => {
+				let mut env = env.buf_in_buf_out();
+
+				let (token_a, token_b, fee): (
+					<E::T as pallet_token_fungible::Config>::FungibleTokenId,
+					<E::T as pallet_token_fungible::Config>::FungibleTokenId,
+					u32,
+				) = env.read_as()?;
+				env.charge_weight(10000)?;
+
+				let pool = pallet_exchange::Pallet::<E::T>::get_pool(token_a, token_b, fee)?;
+
+				let account_slice = pool.lp_token_account_id.encode();
+
+				env.write(&account_slice, false, None).map_err(|_| {
+					DispatchError::Other("ChainExtension failed to call pool_account")
+				})?;
+			},
+			_ => {
+				log::error!("call an unregistered `func_id`, func_id:{:}", func_id);
+				return Err(DispatchError::Other("Unimplemented func_id"))
+			},
+		}
+		Ok(RetVal::Converging(0))
+	}
+
+	fn enabled() -> bool {
+		true
+	}
+}
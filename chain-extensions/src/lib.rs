@@ -26,10 +26,14 @@ use pallet_contracts::chain_extension::{
 use sp_runtime::DispatchError;
 use sp_std::marker::PhantomData;
 
+mod crafting;
+mod exchange;
 mod token_fungible;
 mod token_multi;
 mod token_non_fungible;
 
+pub use crafting::CraftingExtension;
+pub use exchange::ExchangeExtension;
 pub use token_fungible::FungibleTokenExtension;
 pub use token_multi::MultiTokenExtension;
 pub use token_non_fungible::NonFungibleTokenExtension;
@@ -38,12 +42,17 @@ pub struct Web3GamesChainExtensions<C>(PhantomData<C>);
 
 impl<C> ChainExtension<C> for Web3GamesChainExtensions<C>
 where
-	C: pallet_contracts::Config + pallet_token_fungible::Config,
-	// + pallet_token_non_fungible::Config
-	// + pallet_token_multi::Config,
+	C: pallet_contracts::Config
+		+ pallet_token_fungible::Config
+		+ pallet_token_non_fungible::Config
+		+ pallet_token_multi::Config
+		+ pallet_crafting::Config
+		+ pallet_exchange::Config,
 	<C as pallet_contracts::Config>::Call: From<pallet_token_fungible::Call<C>>,
-	// <C as pallet_contracts::Config>::Call: From<pallet_token_non_fungible::Call<C>>,
-	// <C as pallet_contracts::Config>::Call: From<pallet_token_multi::Call<C>>,
+	<C as pallet_contracts::Config>::Call: From<pallet_token_non_fungible::Call<C>>,
+	<C as pallet_contracts::Config>::Call: From<pallet_token_multi::Call<C>>,
+	<C as pallet_contracts::Config>::Call: From<pallet_crafting::Call<C>>,
+	<C as pallet_contracts::Config>::Call: From<pallet_exchange::Call<C>>,
 {
 	fn call<E>(func_id: u32, env: Environment<E, InitState>) -> Result<RetVal>
 	where
@@ -74,6 +83,18 @@ where
 			//
 			// // 0x10081-0x100c1(65665-65729): token-multi
 			// id if id >= 65665 && id < 65729 => MultiTokenExtension::call(func_id, env),
+
+			// token-non-fungible: burn_from
+			65609 => NonFungibleTokenExtension::call(func_id, env),
+
+			// token-multi: burn_from, burn_batch_from
+			65676 | 65677 => MultiTokenExtension::call(func_id, env),
+
+			// 0x100c2-0x10101(65730-65793): crafting
+			id if id >= 65730 && id < 65793 => CraftingExtension::call(func_id, env),
+
+			// 0x10102-0x10141(65794-65857): exchange
+			id if id >= 65794 && id < 65857 => ExchangeExtension::call(func_id, env),
 			_ => {
 				log::error!("call an unregistered `func_id`, func_id:{:}", func_id);
 				return Err(DispatchError::Other("Unimplemented func_id"))
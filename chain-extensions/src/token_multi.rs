@@ -20,6 +20,7 @@ use codec::Encode;
 use pallet_contracts::chain_extension::{
 	ChainExtension, Environment, Ext, InitState, Result, RetVal, SysConfig, UncheckedFrom,
 };
+use pallet_support::MultiReceiver;
 use primitives::Balance;
 use sp_runtime::DispatchError;
 use sp_std::vec::Vec;
@@ -97,7 +98,10 @@ where
 					<E::T as pallet_token_multi::Config>::TokenId,
 					Balance,
 				) = env.read_as_unbounded(env.in_len())?;
-				env.charge_weight(10000)?;
+				env.charge_weight(
+					<E::T as pallet_token_multi::Config>::Receiver::max_weight()
+						.saturating_add(10000),
+				)?;
 
 				let id = pallet_token_multi::Pallet::<E::T>::do_transfer_from(
 					&caller, id, &from, &to, token_id, amount,
@@ -123,7 +127,10 @@ where
 					Vec<<E::T as pallet_token_multi::Config>::TokenId>,
 					Vec<Balance>,
 				) = env.read_as_unbounded(env.in_len())?;
-				env.charge_weight(10000)?;
+				env.charge_weight(
+					<E::T as pallet_token_multi::Config>::Receiver::max_weight()
+						.saturating_add(10000),
+				)?;
 
 				let id = pallet_token_multi::Pallet::<E::T>::do_batch_transfer_from(
 					&caller, id, &from, &to, token_ids, amounts,
@@ -306,6 +313,55 @@ where
 					DispatchError::Other("ChainExtension failed to call create collection")
 				})?;
 			},
+
+			//burn_from
+			65676 => {
+				let mut env = env.buf_in_buf_out();
+
+				let caller = env.ext().caller().clone();
+
+				let (id, from, token_id, amount): (
+					<E::T as pallet_token_multi::Config>::MultiTokenId,
+					<E::T as SysConfig>::AccountId,
+					<E::T as pallet_token_multi::Config>::TokenId,
+					Balance,
+				) = env.read_as_unbounded(env.in_len())?;
+				env.charge_weight(10000)?;
+
+				let id = pallet_token_multi::Pallet::<E::T>::do_burn_from(
+					&caller, id, &from, token_id, amount,
+				)?;
+
+				let id_slice = id.encode();
+
+				env.write(&id_slice, false, None)
+					.map_err(|_| DispatchError::Other("ChainExtension failed to call burn_from"))?;
+			},
+
+			//burn_batch_from
+			65677 => {
+				let mut env = env.buf_in_buf_out();
+
+				let caller = env.ext().caller().clone();
+
+				let (id, from, token_ids, amounts): (
+					<E::T as pallet_token_multi::Config>::MultiTokenId,
+					<E::T as SysConfig>::AccountId,
+					Vec<<E::T as pallet_token_multi::Config>::TokenId>,
+					Vec<Balance>,
+				) = env.read_as_unbounded(env.in_len())?;
+				env.charge_weight(10000)?;
+
+				let id = pallet_token_multi::Pallet::<E::T>::do_batch_burn_from(
+					&caller, id, &from, token_ids, amounts,
+				)?;
+
+				let id_slice = id.encode();
+
+				env.write(&id_slice, false, None).map_err(|_| {
+					DispatchError::Other("ChainExtension failed to call burn_batch_from")
+				})?;
+			},
 			_ => {
 				log::error!("call an unregistered `func_id`, func_id:{:}", func_id);
 				return Err(DispatchError::Other("Unimplemented func_id"))
@@ -20,6 +20,7 @@ use codec::Encode;
 use pallet_contracts::chain_extension::{
 	ChainExtension, Environment, Ext, InitState, Result, RetVal, SysConfig, UncheckedFrom,
 };
+use pallet_support::NonFungibleReceiver;
 use sp_runtime::DispatchError;
 use sp_std::vec::Vec;
 
@@ -120,7 +121,10 @@ where
 					<E::T as SysConfig>::AccountId,
 					<E::T as pallet_token_non_fungible::Config>::TokenId,
 				) = env.read_as_unbounded(env.in_len())?;
-				env.charge_weight(10000)?;
+				env.charge_weight(
+					<E::T as pallet_token_non_fungible::Config>::Receiver::max_weight()
+						.saturating_add(10000),
+				)?;
 
 				let id = pallet_token_non_fungible::Pallet::<E::T>::do_transfer_from(
 					&caller, id, &from, &to, token_id,
@@ -217,6 +221,26 @@ where
 					DispatchError::Other("ChainExtension failed to call create collection")
 				})?;
 			},
+			// burn_from
+			65609 => {
+				let mut env = env.buf_in_buf_out();
+
+				let caller = env.ext().caller().clone();
+
+				let (id, token_id): (
+					<E::T as pallet_token_non_fungible::Config>::NonFungibleTokenId,
+					<E::T as pallet_token_non_fungible::Config>::TokenId,
+				) = env.read_as_unbounded(env.in_len())?;
+				env.charge_weight(10000)?;
+
+				let id =
+					pallet_token_non_fungible::Pallet::<E::T>::do_burn_from(&caller, id, token_id)?;
+
+				let id_slice = id.encode();
+
+				env.write(&id_slice, false, None)
+					.map_err(|_| DispatchError::Other("ChainExtension failed to call burn_from"))?;
+			},
 			_ => {
 				log::error!("call an unregistered `func_id`, func_id:{:}", func_id);
 				return Err(DispatchError::Other("Unimplemented func_id"))
@@ -29,8 +29,9 @@ use sp_runtime::traits::{BlakeTwo256, IdentifyAccount, Verify};
 use std::str::FromStr;
 use web3games_runtime::{
 	AccountId, AuraConfig, Balance, BalancesConfig, BaseFeeConfig, EVMConfig,
-	EthereumChainIdConfig, EthereumConfig, GenesisConfig, GrandpaConfig, MartketplaceConfig,
-	Permill, Precompiles, Signature, SudoConfig, SystemConfig, DOLLARS, GIGAWEI, WASM_BINARY,
+	EthereumChainIdConfig, EthereumConfig, ExchangeConfig, GenesisConfig, GrandpaConfig,
+	MartketplaceConfig, Permill, Precompiles, Signature, SudoConfig, SystemConfig, DOLLARS,
+	GIGAWEI, WASM_BINARY,
 };
 
 // The URL for the telemetry server.
@@ -254,6 +255,7 @@ fn testnet_genesis(
 		treasury: Default::default(),
 		transaction_storage: Default::default(),
 		wrap_currency: Default::default(),
+		exchange: ExchangeConfig { fee_tiers: vec![5, 30, 100] },
 		martketplace: MartketplaceConfig { admin_key: marketplace_admin },
 	}
 }
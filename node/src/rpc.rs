@@ -130,6 +130,7 @@ where
 	C::Api: fp_rpc::EthereumRuntimeRPCApi<Block>,
 	C::Api: pallet_contracts_rpc::ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash>,
 	C::Api: pallet_exchange_rpc::ExchangeRuntimeApi<Block, AccountId>,
+	C::Api: pallet_tokens_rpc::TokensRuntimeApi<Block, AccountId>,
 	P: TransactionPool<Block = Block> + 'static,
 	A: ChainApi<Block = Block> + 'static,
 {
@@ -139,6 +140,7 @@ where
 	};
 	use pallet_contracts_rpc::{Contracts, ContractsApiServer};
 	use pallet_exchange_rpc::{ExchangeRpc, ExchangeRpcApiServer};
+	use pallet_tokens_rpc::{TokensRpc, TokensRpcApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
 
@@ -165,6 +167,7 @@ where
 	io.merge(TransactionPayment::new(Arc::clone(&client)).into_rpc())?;
 	io.merge(Contracts::new(Arc::clone(&client)).into_rpc())?;
 	io.merge(ExchangeRpc::new(Arc::clone(&client)).into_rpc())?;
+	io.merge(TokensRpc::new(Arc::clone(&client)).into_rpc())?;
 
 	let mut signers = Vec::new();
 	if enable_dev_signer {
@@ -0,0 +1,121 @@
+//! Benchmarking setup for pallet-template
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use crate::Pallet as Crafting;
+use frame_benchmarking::{account, benchmarks};
+use frame_support::assert_ok;
+use frame_system::RawOrigin;
+use pallet_token_fungible::Pallet as TokenFungible;
+use pallet_token_multi::Pallet as TokenMulti;
+use pallet_token_non_fungible::Pallet as TokenNonFungible;
+
+const GOLD: u128 = 1;
+const SWORD: u128 = 1;
+const ITEMS: u128 = 1;
+const WOOD: u128 = 1;
+
+fn setup<T: Config>() -> DispatchResult {
+	let alice: T::AccountId = account("alice", 0, 0);
+	let bob: T::AccountId = account("bob", 0, 0);
+
+	assert_ok!(TokenFungible::<T>::create_token(
+		RawOrigin::Signed(alice.clone()).into(),
+		FungibleTokenIdOf::<T>::unique_saturated_from(GOLD),
+		b"Gold".to_vec(),
+		b"GOLD".to_vec(),
+		18
+	));
+	assert_ok!(TokenFungible::<T>::mint(
+		RawOrigin::Signed(alice.clone()).into(),
+		FungibleTokenIdOf::<T>::unique_saturated_from(GOLD),
+		bob.clone(),
+		1_000_000u128,
+	));
+	assert_ok!(TokenNonFungible::<T>::create_token(
+		RawOrigin::Signed(alice.clone()).into(),
+		NonFungibleTokenIdOf::<T>::unique_saturated_from(SWORD),
+		b"Sword".to_vec(),
+		b"SWORD".to_vec(),
+		vec![0u8; 20],
+	));
+	assert_ok!(TokenMulti::<T>::create_token(
+		RawOrigin::Signed(alice.clone()).into(),
+		MultiTokenIdOf::<T>::unique_saturated_from(ITEMS),
+		vec![0u8; 20],
+	));
+	assert_ok!(TokenMulti::<T>::mint(
+		RawOrigin::Signed(alice).into(),
+		MultiTokenIdOf::<T>::unique_saturated_from(ITEMS),
+		bob,
+		MultiTokenTokenIdOf::<T>::unique_saturated_from(WOOD),
+		1_000_000u128,
+	));
+	Ok(())
+}
+
+fn inputs() -> Vec<CraftingInput> {
+	vec![CraftingInput::MultiToken(ITEMS, WOOD, 2), CraftingInput::FungibleToken(GOLD, 100)]
+}
+
+benchmarks! {
+	create_recipe {
+		let alice: T::AccountId = account("alice", 0, 0);
+		setup::<T>()?;
+	}: _(RawOrigin::Signed(alice), inputs(), vec![CraftingOutput::NonFungibleToken(SWORD)], vec![])
+
+	set_recipe_enabled {
+		let alice: T::AccountId = account("alice", 0, 0);
+		setup::<T>()?;
+		assert_ok!(Crafting::<T>::create_recipe(
+			RawOrigin::Signed(alice.clone()).into(),
+			inputs(),
+			vec![CraftingOutput::NonFungibleToken(SWORD)],
+			vec![],
+		));
+	}: _(RawOrigin::Signed(alice), 0, false)
+
+	remove_recipe {
+		let alice: T::AccountId = account("alice", 0, 0);
+		setup::<T>()?;
+		assert_ok!(Crafting::<T>::create_recipe(
+			RawOrigin::Signed(alice.clone()).into(),
+			inputs(),
+			vec![CraftingOutput::NonFungibleToken(SWORD)],
+			vec![],
+		));
+	}: _(RawOrigin::Signed(alice), 0)
+
+	craft {
+		let t in 1 .. T::MaxCraftTimes::get();
+		let i in 1 .. T::MaxInputs::get();
+		let o in 1 .. T::MaxOutputs::get();
+		let alice: T::AccountId = account("alice", 0, 0);
+		let bob: T::AccountId = account("bob", 0, 0);
+		setup::<T>()?;
+		// every input but the first is a multi token of its own, every output a non fungible
+		// token, the costliest to mint
+		let mut inputs = inputs();
+		inputs.truncate(1);
+		for k in 1..i {
+			let token_id = WOOD + k as u128;
+			assert_ok!(TokenMulti::<T>::mint(
+				RawOrigin::Signed(alice.clone()).into(),
+				MultiTokenIdOf::<T>::unique_saturated_from(ITEMS),
+				bob.clone(),
+				MultiTokenTokenIdOf::<T>::unique_saturated_from(token_id),
+				1_000_000u128,
+			));
+			inputs.push(CraftingInput::MultiToken(ITEMS, token_id, 1));
+		}
+		assert_ok!(Crafting::<T>::create_recipe(
+			RawOrigin::Signed(alice.clone()).into(),
+			inputs,
+			vec![CraftingOutput::NonFungibleToken(SWORD); o as usize],
+			vec![],
+		));
+	}: _(RawOrigin::Signed(bob), 0, t, vec![])
+
+	impl_benchmark_test_suite!(Crafting, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,531 @@
+// This file is part of Web3Games.
+
+// Copyright (C) 2021-2022 Web3Games https://web3games.org
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::{pallet_prelude::*, traits::Randomness, transactional};
+use frame_system::pallet_prelude::*;
+use primitives::Balance;
+use sp_runtime::{
+	traits::{TrailingZeroInput, UniqueSaturatedFrom},
+	DispatchResult,
+};
+use sp_std::prelude::*;
+
+pub use pallet::*;
+pub mod weights;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+type FungibleTokenIdOf<T> = <T as pallet_token_fungible::Config>::FungibleTokenId;
+type NonFungibleTokenIdOf<T> = <T as pallet_token_non_fungible::Config>::NonFungibleTokenId;
+type NonFungibleTokenTokenIdOf<T> = <T as pallet_token_non_fungible::Config>::TokenId;
+type MultiTokenIdOf<T> = <T as pallet_token_multi::Config>::MultiTokenId;
+type MultiTokenTokenIdOf<T> = <T as pallet_token_multi::Config>::TokenId;
+
+type FungibleTokenId = u128;
+type NonFungibleTokenId = u128;
+type MultiTokenId = u128;
+type TokenId = u128;
+pub type RecipeId = u64;
+
+/// The most token ids tried when a recipe mints a non fungible token and the ids after the last
+/// one it minted are taken.
+pub const MAX_TOKEN_ID_PROBES: u32 = 16;
+
+/// What a recipe consumes from the crafter, once per craft.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum CraftingInput {
+	/// Burn `amount` of `token_id` from the multi token `id`.
+	MultiToken(MultiTokenId, TokenId, Balance),
+	/// Burn a token the crafter picks from the non fungible token `id`.
+	NonFungibleToken(NonFungibleTokenId),
+	/// Pay `amount` of the fungible token `id` to the recipe owner.
+	FungibleToken(FungibleTokenId, Balance),
+}
+
+/// What a recipe mints to the crafter, once per craft.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum CraftingOutput {
+	/// Mint `amount` of `token_id` from the multi token `id`.
+	MultiToken(MultiTokenId, TokenId, Balance),
+	/// Mint the next free token id from the non fungible token `id`.
+	NonFungibleToken(NonFungibleTokenId),
+}
+
+/// One entry of a recipe outcome table, picked with a chance of `weight / total weight`.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Outcome<Outputs> {
+	pub weight: u32,
+	pub outputs: Outputs,
+}
+
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Recipe<AccountId, Inputs, Outputs, Outcomes> {
+	pub owner: AccountId,
+	pub inputs: Inputs,
+	/// Minted on every craft.
+	pub outputs: Outputs,
+	/// If not empty, exactly one outcome is rolled and minted on every craft.
+	pub outcomes: Outcomes,
+	pub enabled: bool,
+}
+
+pub type OutputsOf<T> = BoundedVec<CraftingOutput, <T as Config>::MaxOutputs>;
+
+pub type RecipeOf<T> = Recipe<
+	<T as frame_system::Config>::AccountId,
+	BoundedVec<CraftingInput, <T as Config>::MaxInputs>,
+	OutputsOf<T>,
+	BoundedVec<Outcome<OutputsOf<T>>, <T as Config>::MaxOutcomes>,
+>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config
+		+ pallet_token_fungible::Config
+		+ pallet_token_non_fungible::Config
+		+ pallet_token_multi::Config
+	{
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The maximum number of inputs of a recipe.
+		#[pallet::constant]
+		type MaxInputs: Get<u32>;
+
+		/// The maximum number of outputs of a recipe or of one of its outcomes.
+		#[pallet::constant]
+		type MaxOutputs: Get<u32>;
+
+		/// The maximum number of entries in a recipe outcome table.
+		#[pallet::constant]
+		type MaxOutcomes: Get<u32>;
+
+		/// The maximum `times` of a single craft.
+		#[pallet::constant]
+		type MaxCraftTimes: Get<u32>;
+
+		/// Source of randomness for outcome tables.
+		type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		NoAvailableRecipeId,
+		NoAvailableTokenId,
+		RecipeNotFound,
+		RecipeDisabled,
+		NoPermission,
+		EmptyRecipe,
+		TooManyInputs,
+		TooManyOutputs,
+		TooManyOutcomes,
+		InvalidOutcomeWeight,
+		InvalidTimes,
+		TokenNonExistent,
+		InsufficientTokens,
+		InvalidTokenIds,
+		NotTokenOwner,
+		RandomizedRecipe,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		RecipeCreated(RecipeId, T::AccountId),
+		RecipeEnabled(RecipeId, bool),
+		RecipeRemoved(RecipeId),
+		/// (crafter, recipe_id, times)
+		Crafted(T::AccountId, RecipeId, u32),
+		/// (crafter, recipe_id, outcome index)
+		OutcomeRolled(T::AccountId, RecipeId, u32),
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_recipe_id)]
+	pub(super) type NextRecipeId<T: Config> = StorageValue<_, RecipeId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn recipes)]
+	pub(super) type Recipes<T: Config> = StorageMap<_, Blake2_128Concat, RecipeId, RecipeOf<T>>;
+
+	/// The next token id tried when a recipe mints a non fungible token.
+	#[pallet::storage]
+	pub(super) type NextNonFungibleTokenId<T: Config> =
+		StorageMap<_, Blake2_128Concat, NonFungibleTokenId, TokenId, ValueQuery>;
+
+	#[pallet::storage]
+	pub(super) type Nonce<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::create_recipe())]
+		pub fn create_recipe(
+			origin: OriginFor<T>,
+			inputs: Vec<CraftingInput>,
+			outputs: Vec<CraftingOutput>,
+			outcomes: Vec<(u32, Vec<CraftingOutput>)>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_create_recipe(&who, inputs, outputs, outcomes)?;
+			Ok(())
+		}
+
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_recipe_enabled())]
+		pub fn set_recipe_enabled(
+			origin: OriginFor<T>,
+			recipe_id: RecipeId,
+			enabled: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_set_recipe_enabled(&who, recipe_id, enabled)
+		}
+
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::remove_recipe())]
+		pub fn remove_recipe(origin: OriginFor<T>, recipe_id: RecipeId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_remove_recipe(&who, recipe_id)
+		}
+
+		/// Craft `recipe_id` `times` times, burning `token_ids` for the non fungible token
+		/// inputs of the recipe in their order, craft after craft.
+		#[pallet::weight(Pallet::<T>::craft_weight(*times))]
+		pub fn craft(
+			origin: OriginFor<T>,
+			recipe_id: RecipeId,
+			times: u32,
+			token_ids: Vec<TokenId>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_craft(&who, recipe_id, times, token_ids)
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	pub fn do_create_recipe(
+		who: &T::AccountId,
+		inputs: Vec<CraftingInput>,
+		outputs: Vec<CraftingOutput>,
+		outcomes: Vec<(u32, Vec<CraftingOutput>)>,
+	) -> Result<RecipeId, DispatchError> {
+		ensure!(!outputs.is_empty() || !outcomes.is_empty(), Error::<T>::EmptyRecipe);
+
+		for input in inputs.iter() {
+			Self::ensure_input_exists(input)?;
+		}
+		for output in outputs.iter().chain(outcomes.iter().flat_map(|(_, o)| o.iter())) {
+			Self::ensure_can_mint(who, output)?;
+		}
+
+		let mut total_weight: u32 = 0;
+		let mut bounded_outcomes = Vec::with_capacity(outcomes.len());
+		for (weight, outcome_outputs) in outcomes {
+			ensure!(weight > 0, Error::<T>::InvalidOutcomeWeight);
+			total_weight =
+				total_weight.checked_add(weight).ok_or(Error::<T>::InvalidOutcomeWeight)?;
+			bounded_outcomes.push(Outcome {
+				weight,
+				outputs: outcome_outputs.try_into().map_err(|_| Error::<T>::TooManyOutputs)?,
+			});
+		}
+
+		let recipe = Recipe {
+			owner: who.clone(),
+			inputs: inputs.try_into().map_err(|_| Error::<T>::TooManyInputs)?,
+			outputs: outputs.try_into().map_err(|_| Error::<T>::TooManyOutputs)?,
+			outcomes: bounded_outcomes.try_into().map_err(|_| Error::<T>::TooManyOutcomes)?,
+			enabled: true,
+		};
+
+		let recipe_id = NextRecipeId::<T>::try_mutate(|id| -> Result<RecipeId, DispatchError> {
+			let current_id = *id;
+			*id = id.checked_add(1u64).ok_or(Error::<T>::NoAvailableRecipeId)?;
+			Ok(current_id)
+		})?;
+
+		Recipes::<T>::insert(recipe_id, recipe);
+
+		Self::deposit_event(Event::RecipeCreated(recipe_id, who.clone()));
+
+		Ok(recipe_id)
+	}
+
+	pub fn do_set_recipe_enabled(
+		who: &T::AccountId,
+		recipe_id: RecipeId,
+		enabled: bool,
+	) -> DispatchResult {
+		Recipes::<T>::try_mutate(recipe_id, |maybe_recipe| -> DispatchResult {
+			let recipe = maybe_recipe.as_mut().ok_or(Error::<T>::RecipeNotFound)?;
+			ensure!(recipe.owner == *who, Error::<T>::NoPermission);
+			recipe.enabled = enabled;
+			Ok(())
+		})?;
+
+		Self::deposit_event(Event::RecipeEnabled(recipe_id, enabled));
+
+		Ok(())
+	}
+
+	pub fn do_remove_recipe(who: &T::AccountId, recipe_id: RecipeId) -> DispatchResult {
+		let recipe = Recipes::<T>::get(recipe_id).ok_or(Error::<T>::RecipeNotFound)?;
+		ensure!(recipe.owner == *who, Error::<T>::NoPermission);
+
+		Recipes::<T>::remove(recipe_id);
+
+		Self::deposit_event(Event::RecipeRemoved(recipe_id));
+
+		Ok(())
+	}
+
+	/// Craft `recipe_id` `times` times. Either every input is consumed and every output is
+	/// minted, or nothing changes; the chain extension calls this directly, so it opens its
+	/// own storage layer.
+	#[transactional]
+	pub fn do_craft(
+		who: &T::AccountId,
+		recipe_id: RecipeId,
+		times: u32,
+		token_ids: Vec<TokenId>,
+	) -> DispatchResult {
+		ensure!(times > 0 && times <= T::MaxCraftTimes::get(), Error::<T>::InvalidTimes);
+
+		let recipe = Recipes::<T>::get(recipe_id).ok_or(Error::<T>::RecipeNotFound)?;
+		ensure!(recipe.enabled, Error::<T>::RecipeDisabled);
+
+		let non_fungible_inputs = recipe
+			.inputs
+			.iter()
+			.filter(|input| matches!(input, CraftingInput::NonFungibleToken(_)))
+			.count();
+		ensure!(
+			token_ids.len() == non_fungible_inputs.saturating_mul(times as usize),
+			Error::<T>::InvalidTokenIds
+		);
+		let mut token_ids = token_ids.into_iter();
+
+		for _ in 0..times {
+			for input in recipe.inputs.iter() {
+				Self::consume(who, &recipe.owner, input, &mut token_ids)?;
+			}
+
+			for output in recipe.outputs.iter() {
+				Self::produce(&recipe.owner, who, output)?;
+			}
+
+			if !recipe.outcomes.is_empty() {
+				let index = Self::roll_outcome(who, recipe_id, &recipe.outcomes);
+				for output in recipe.outcomes[index as usize].outputs.iter() {
+					Self::produce(&recipe.owner, who, output)?;
+				}
+				Self::deposit_event(Event::OutcomeRolled(who.clone(), recipe_id, index));
+			}
+		}
+
+		Self::deposit_event(Event::Crafted(who.clone(), recipe_id, times));
+
+		Ok(())
+	}
+
+	/// The weight of crafting `times` times the largest recipe there can be, whose inputs are all
+	/// consumed and whose outputs and largest outcome are all minted on every craft.
+	pub fn craft_weight(times: u32) -> Weight {
+		<T as Config>::WeightInfo::craft(
+			times,
+			T::MaxInputs::get(),
+			T::MaxOutputs::get().saturating_mul(2),
+		)
+	}
+
+	/// Contracts may only craft recipes without an outcome table: a contract could look at the
+	/// outcome rolled and revert until it gets the one it wants.
+	pub fn ensure_deterministic(recipe_id: RecipeId) -> DispatchResult {
+		let recipe = Recipes::<T>::get(recipe_id).ok_or(Error::<T>::RecipeNotFound)?;
+		ensure!(recipe.outcomes.is_empty(), Error::<T>::RandomizedRecipe);
+		Ok(())
+	}
+
+	fn consume(
+		who: &T::AccountId,
+		owner: &T::AccountId,
+		input: &CraftingInput,
+		token_ids: &mut impl Iterator<Item = TokenId>,
+	) -> DispatchResult {
+		match *input {
+			CraftingInput::MultiToken(id, token_id, amount) => {
+				let id = MultiTokenIdOf::<T>::unique_saturated_from(id);
+				let token_id = MultiTokenTokenIdOf::<T>::unique_saturated_from(token_id);
+				ensure!(
+					pallet_token_multi::Pallet::<T>::balance_of(id, (token_id, who)) >= amount,
+					Error::<T>::InsufficientTokens
+				);
+				pallet_token_multi::Pallet::<T>::do_burn(who, id, token_id, amount)
+			},
+			CraftingInput::NonFungibleToken(id) => {
+				let id = NonFungibleTokenIdOf::<T>::unique_saturated_from(id);
+				let token_id = NonFungibleTokenTokenIdOf::<T>::unique_saturated_from(
+					token_ids.next().ok_or(Error::<T>::InvalidTokenIds)?,
+				);
+				ensure!(
+					pallet_token_non_fungible::Pallet::<T>::owner_of(id, token_id).as_ref() ==
+						Some(who),
+					Error::<T>::NotTokenOwner
+				);
+				pallet_token_non_fungible::Pallet::<T>::do_burn(who, id, token_id)
+			},
+			CraftingInput::FungibleToken(id, amount) => {
+				if who == owner || amount == 0 {
+					return Ok(())
+				}
+				pallet_token_fungible::Pallet::<T>::do_transfer(
+					FungibleTokenIdOf::<T>::unique_saturated_from(id),
+					who,
+					owner,
+					amount,
+				)
+			},
+		}
+	}
+
+	fn produce(owner: &T::AccountId, to: &T::AccountId, output: &CraftingOutput) -> DispatchResult {
+		match *output {
+			CraftingOutput::MultiToken(id, token_id, amount) =>
+				pallet_token_multi::Pallet::<T>::do_mint(
+					owner,
+					MultiTokenIdOf::<T>::unique_saturated_from(id),
+					to,
+					MultiTokenTokenIdOf::<T>::unique_saturated_from(token_id),
+					amount,
+				),
+			CraftingOutput::NonFungibleToken(id) => {
+				let token_id = Self::next_non_fungible_token_id(id)?;
+				pallet_token_non_fungible::Pallet::<T>::do_mint(
+					owner,
+					NonFungibleTokenIdOf::<T>::unique_saturated_from(id),
+					to,
+					token_id,
+				)
+			},
+		}
+	}
+
+	fn next_non_fungible_token_id(
+		id: NonFungibleTokenId,
+	) -> Result<NonFungibleTokenTokenIdOf<T>, DispatchError> {
+		let collection = NonFungibleTokenIdOf::<T>::unique_saturated_from(id);
+		NextNonFungibleTokenId::<T>::try_mutate(id, |next| {
+			for _ in 0..MAX_TOKEN_ID_PROBES {
+				let token_id = NonFungibleTokenTokenIdOf::<T>::unique_saturated_from(*next);
+				*next = next.checked_add(1).ok_or(Error::<T>::NoAvailableTokenId)?;
+				if !pallet_token_non_fungible::Pallet::<T>::token_exists(collection, token_id) {
+					return Ok(token_id)
+				}
+			}
+			Err(Error::<T>::NoAvailableTokenId.into())
+		})
+	}
+
+	fn roll_outcome(
+		who: &T::AccountId,
+		recipe_id: RecipeId,
+		outcomes: &[Outcome<OutputsOf<T>>],
+	) -> u32 {
+		let nonce = Nonce::<T>::mutate(|nonce| {
+			*nonce = nonce.wrapping_add(1);
+			*nonce
+		});
+		let (seed, _) =
+			<T as Config>::Randomness::random(&(b"crafting", who, recipe_id, nonce).encode());
+		let random = u32::decode(&mut TrailingZeroInput::new(seed.as_ref())).unwrap_or_default();
+
+		let total_weight = outcomes.iter().fold(0u32, |acc, o| acc.saturating_add(o.weight));
+		let mut roll = random % total_weight.max(1);
+		for (index, outcome) in outcomes.iter().enumerate() {
+			if roll < outcome.weight {
+				return index as u32
+			}
+			roll -= outcome.weight;
+		}
+		0
+	}
+
+	fn ensure_input_exists(input: &CraftingInput) -> DispatchResult {
+		let exists = match *input {
+			CraftingInput::MultiToken(id, _, _) => pallet_token_multi::Pallet::<T>::exists(
+				MultiTokenIdOf::<T>::unique_saturated_from(id),
+			),
+			CraftingInput::NonFungibleToken(id) => pallet_token_non_fungible::Pallet::<T>::exists(
+				NonFungibleTokenIdOf::<T>::unique_saturated_from(id),
+			),
+			CraftingInput::FungibleToken(id, _) => pallet_token_fungible::Pallet::<T>::exists(
+				FungibleTokenIdOf::<T>::unique_saturated_from(id),
+			),
+		};
+		ensure!(exists, Error::<T>::TokenNonExistent);
+		Ok(())
+	}
+
+	/// Outputs are minted with the recipe owner's permission, so the owner has to own every
+	/// output token.
+	fn ensure_can_mint(who: &T::AccountId, output: &CraftingOutput) -> DispatchResult {
+		match *output {
+			CraftingOutput::MultiToken(id, _, _) => {
+				let id = MultiTokenIdOf::<T>::unique_saturated_from(id);
+				ensure!(pallet_token_multi::Pallet::<T>::exists(id), Error::<T>::TokenNonExistent);
+				ensure!(
+					pallet_token_multi::Pallet::<T>::has_permission(id, who),
+					Error::<T>::NoPermission
+				);
+			},
+			CraftingOutput::NonFungibleToken(id) => {
+				let id = NonFungibleTokenIdOf::<T>::unique_saturated_from(id);
+				ensure!(
+					pallet_token_non_fungible::Pallet::<T>::exists(id),
+					Error::<T>::TokenNonExistent
+				);
+				ensure!(
+					pallet_token_non_fungible::Pallet::<T>::has_permission(id, who),
+					Error::<T>::NoPermission
+				);
+			},
+		}
+		Ok(())
+	}
+}
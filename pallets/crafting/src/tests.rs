@@ -0,0 +1,294 @@
+// This file is part of Web3Games.
+
+// Copyright (C) 2021-2022 Web3Games https://web3games.org
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+pub use crate::mock::*;
+use frame_support::{assert_noop, assert_ok};
+
+const ALICE: u64 = 1;
+const BOB: u64 = 2;
+
+const GOLD: u128 = 1;
+const SWORD: u128 = 1;
+const ITEMS: u128 = 1;
+const WOOD: u128 = 1;
+const IRON: u128 = 2;
+const GEM: u128 = 3;
+
+fn create_tokens() {
+	assert_ok!(TokenFungible::create_token(
+		Origin::signed(ALICE),
+		GOLD,
+		b"Gold".to_vec(),
+		b"GOLD".to_vec(),
+		18
+	));
+	assert_ok!(TokenFungible::mint(Origin::signed(ALICE), GOLD, BOB, 1000));
+
+	assert_ok!(TokenNonFungible::create_token(
+		Origin::signed(ALICE),
+		SWORD,
+		b"Sword".to_vec(),
+		b"SWORD".to_vec(),
+		b"https://web3games.com/".to_vec(),
+	));
+
+	assert_ok!(TokenMulti::create_token(
+		Origin::signed(ALICE),
+		ITEMS,
+		b"https://web3games.com/".to_vec()
+	));
+	assert_ok!(TokenMulti::mint(Origin::signed(ALICE), ITEMS, BOB, WOOD, 10));
+	assert_ok!(TokenMulti::mint(Origin::signed(ALICE), ITEMS, BOB, IRON, 10));
+}
+
+fn create_sword_recipe() {
+	assert_ok!(Crafting::create_recipe(
+		Origin::signed(ALICE),
+		vec![
+			CraftingInput::MultiToken(ITEMS, WOOD, 2),
+			CraftingInput::MultiToken(ITEMS, IRON, 3),
+			CraftingInput::FungibleToken(GOLD, 100),
+		],
+		vec![CraftingOutput::NonFungibleToken(SWORD)],
+		vec![],
+	));
+}
+
+#[test]
+fn create_recipe_should_work() {
+	new_test_ext().execute_with(|| {
+		create_tokens();
+		create_sword_recipe();
+
+		let recipe = Crafting::recipes(0).unwrap();
+		assert_eq!(recipe.owner, ALICE);
+		assert_eq!(recipe.inputs.len(), 3);
+		assert!(recipe.enabled);
+		assert_eq!(Crafting::next_recipe_id(), 1);
+	})
+}
+
+#[test]
+fn create_recipe_should_not_work() {
+	new_test_ext().execute_with(|| {
+		create_tokens();
+
+		// BOB does not own the output token
+		assert_noop!(
+			Crafting::create_recipe(
+				Origin::signed(BOB),
+				vec![CraftingInput::MultiToken(ITEMS, WOOD, 2)],
+				vec![CraftingOutput::MultiToken(ITEMS, GEM, 1)],
+				vec![],
+			),
+			Error::<Test>::NoPermission
+		);
+
+		assert_noop!(
+			Crafting::create_recipe(
+				Origin::signed(ALICE),
+				vec![CraftingInput::MultiToken(ITEMS, WOOD, 2)],
+				vec![],
+				vec![],
+			),
+			Error::<Test>::EmptyRecipe
+		);
+
+		assert_noop!(
+			Crafting::create_recipe(
+				Origin::signed(ALICE),
+				vec![CraftingInput::MultiToken(ITEMS, WOOD, 2)],
+				vec![],
+				vec![(0, vec![CraftingOutput::MultiToken(ITEMS, GEM, 1)])],
+			),
+			Error::<Test>::InvalidOutcomeWeight
+		);
+	})
+}
+
+#[test]
+fn craft_should_work() {
+	new_test_ext().execute_with(|| {
+		create_tokens();
+		create_sword_recipe();
+
+		assert_ok!(Crafting::craft(Origin::signed(BOB), 0, 2, vec![]));
+
+		assert_eq!(TokenMulti::balance_of(ITEMS, (WOOD, BOB)), 6);
+		assert_eq!(TokenMulti::balance_of(ITEMS, (IRON, BOB)), 4);
+		assert_eq!(TokenFungible::balance_of(GOLD, BOB), 800);
+		assert_eq!(TokenFungible::balance_of(GOLD, ALICE), 200);
+		assert_eq!(TokenNonFungible::balance_of(SWORD, BOB), 2);
+		assert_eq!(TokenNonFungible::owner_of(SWORD, 0), Some(BOB));
+		assert_eq!(TokenNonFungible::owner_of(SWORD, 1), Some(BOB));
+	})
+}
+
+#[test]
+fn craft_should_be_atomic() {
+	new_test_ext().execute_with(|| {
+		create_tokens();
+		create_sword_recipe();
+
+		// The fourth craft runs out of iron
+		assert_noop!(
+			Crafting::craft(Origin::signed(BOB), 0, 4, vec![]),
+			Error::<Test>::InsufficientTokens
+		);
+		assert_eq!(TokenMulti::balance_of(ITEMS, (WOOD, BOB)), 10);
+		assert_eq!(TokenFungible::balance_of(GOLD, BOB), 1000);
+		assert_eq!(TokenNonFungible::balance_of(SWORD, BOB), 0);
+	})
+}
+
+#[test]
+fn craft_should_not_work() {
+	new_test_ext().execute_with(|| {
+		create_tokens();
+		create_sword_recipe();
+
+		assert_noop!(
+			Crafting::craft(Origin::signed(BOB), 1, 1, vec![]),
+			Error::<Test>::RecipeNotFound
+		);
+		assert_noop!(
+			Crafting::craft(Origin::signed(BOB), 0, 0, vec![]),
+			Error::<Test>::InvalidTimes
+		);
+		assert_noop!(
+			Crafting::craft(Origin::signed(BOB), 0, 11, vec![]),
+			Error::<Test>::InvalidTimes
+		);
+
+		assert_noop!(
+			Crafting::set_recipe_enabled(Origin::signed(BOB), 0, false),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(Crafting::set_recipe_enabled(Origin::signed(ALICE), 0, false));
+		assert_noop!(
+			Crafting::craft(Origin::signed(BOB), 0, 1, vec![]),
+			Error::<Test>::RecipeDisabled
+		);
+	})
+}
+
+#[test]
+fn craft_with_outcome_table_should_work() {
+	new_test_ext().execute_with(|| {
+		create_tokens();
+
+		assert_ok!(TokenNonFungible::mint(Origin::signed(ALICE), SWORD, BOB, 7));
+		assert_ok!(Crafting::create_recipe(
+			Origin::signed(ALICE),
+			vec![CraftingInput::NonFungibleToken(SWORD)],
+			vec![CraftingOutput::MultiToken(ITEMS, IRON, 1)],
+			vec![
+				(1, vec![CraftingOutput::MultiToken(ITEMS, GEM, 1)]),
+				(3, vec![CraftingOutput::MultiToken(ITEMS, WOOD, 1)]),
+			],
+		));
+
+		assert_ok!(Crafting::craft(Origin::signed(BOB), 0, 1, vec![7]));
+
+		assert_eq!(TokenNonFungible::owner_of(SWORD, 7), None);
+		assert_eq!(TokenMulti::balance_of(ITEMS, (IRON, BOB)), 11);
+		assert_eq!(
+			TokenMulti::balance_of(ITEMS, (GEM, BOB)) + TokenMulti::balance_of(ITEMS, (WOOD, BOB)),
+			11
+		);
+
+		assert_ok!(Crafting::remove_recipe(Origin::signed(ALICE), 0));
+		assert_eq!(Crafting::recipes(0), None);
+	})
+}
+
+#[test]
+fn craft_should_stop_probing_taken_token_ids() {
+	new_test_ext().execute_with(|| {
+		create_tokens();
+		create_sword_recipe();
+
+		for token_id in 0..MAX_TOKEN_ID_PROBES as u128 {
+			assert_ok!(TokenNonFungible::mint(Origin::signed(ALICE), SWORD, ALICE, token_id));
+		}
+		assert_noop!(
+			Crafting::craft(Origin::signed(BOB), 0, 1, vec![]),
+			Error::<Test>::NoAvailableTokenId
+		);
+
+		assert_ok!(TokenNonFungible::burn(Origin::signed(ALICE), SWORD, 15));
+		assert_ok!(Crafting::craft(Origin::signed(BOB), 0, 1, vec![]));
+		assert_eq!(TokenNonFungible::owner_of(SWORD, 15), Some(BOB));
+	})
+}
+
+#[test]
+fn craft_should_burn_chosen_tokens() {
+	new_test_ext().execute_with(|| {
+		create_tokens();
+		for token_id in [3, 5, 8] {
+			assert_ok!(TokenNonFungible::mint(Origin::signed(ALICE), SWORD, BOB, token_id));
+		}
+		assert_ok!(TokenNonFungible::mint(Origin::signed(ALICE), SWORD, ALICE, 9));
+		assert_ok!(Crafting::create_recipe(
+			Origin::signed(ALICE),
+			vec![CraftingInput::NonFungibleToken(SWORD), CraftingInput::MultiToken(ITEMS, WOOD, 1)],
+			vec![CraftingOutput::MultiToken(ITEMS, GEM, 1)],
+			vec![],
+		));
+
+		// one token id for each non fungible token input of each craft
+		assert_noop!(
+			Crafting::craft(Origin::signed(BOB), 0, 2, vec![5]),
+			Error::<Test>::InvalidTokenIds
+		);
+		assert_noop!(
+			Crafting::craft(Origin::signed(BOB), 0, 1, vec![5, 8]),
+			Error::<Test>::InvalidTokenIds
+		);
+		assert_noop!(
+			Crafting::craft(Origin::signed(BOB), 0, 1, vec![9]),
+			Error::<Test>::NotTokenOwner
+		);
+
+		assert_ok!(Crafting::craft(Origin::signed(BOB), 0, 2, vec![8, 5]));
+		assert_eq!(TokenNonFungible::owner_of(SWORD, 3), Some(BOB));
+		assert_eq!(TokenNonFungible::owner_of(SWORD, 5), None);
+		assert_eq!(TokenNonFungible::owner_of(SWORD, 8), None);
+		assert_eq!(TokenMulti::balance_of(ITEMS, (GEM, BOB)), 2);
+	})
+}
+
+#[test]
+fn ensure_deterministic_should_reject_outcome_tables() {
+	new_test_ext().execute_with(|| {
+		create_tokens();
+		create_sword_recipe();
+		assert_ok!(Crafting::create_recipe(
+			Origin::signed(ALICE),
+			vec![CraftingInput::MultiToken(ITEMS, WOOD, 1)],
+			vec![],
+			vec![(1, vec![CraftingOutput::MultiToken(ITEMS, GEM, 1)])],
+		));
+
+		assert_ok!(Crafting::ensure_deterministic(0));
+		assert_noop!(Crafting::ensure_deterministic(1), Error::<Test>::RandomizedRecipe);
+		assert_noop!(Crafting::ensure_deterministic(2), Error::<Test>::RecipeNotFound);
+	})
+}
@@ -0,0 +1,136 @@
+// This file is part of Web3Games.
+
+// Copyright (C) 2021-2022 Web3Games https://web3games.org
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Weights for pallet_crafting
+//!
+//! HAND-WRITTEN ESTIMATES, NOT PRODUCED BY THE BENCHMARK CLI: the storage accesses follow the
+//! extrinsics, the times are rough. Replace this file by running the benchmarks with the command
+//! below.
+
+// Command:
+// target/release/web3games-node
+// benchmark
+// pallet
+// --chain=dev
+// --steps=50
+// --repeat=20
+// --pallet=pallet_crafting
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --output=./pallets/crafting/src/weights.rs
+// --template=./.maintain/w3g-weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_crafting.
+pub trait WeightInfo {
+	fn create_recipe() -> Weight;
+	fn set_recipe_enabled() -> Weight;
+	fn remove_recipe() -> Weight;
+	fn craft(t: u32, i: u32, o: u32, ) -> Weight;
+}
+
+/// Weights for pallet_crafting using the Web3Games node and recommended hardware.
+pub struct W3GWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for W3GWeight<T> {
+	// Storage: TokenMulti Tokens (r:1 w:0)
+	// Storage: TokenNonFungible Tokens (r:1 w:0)
+	// Storage: TokenFungible Tokens (r:1 w:0)
+	// Storage: Crafting NextRecipeId (r:1 w:1)
+	// Storage: Crafting Recipes (r:0 w:1)
+	fn create_recipe() -> Weight {
+		(31_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Crafting Recipes (r:1 w:1)
+	fn set_recipe_enabled() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Crafting Recipes (r:1 w:1)
+	fn remove_recipe() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Crafting Recipes (r:1 w:0)
+	// Storage: Crafting Nonce (r:1 w:1)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: TokenNonFungible Owners (r:1 w:1)
+	// Storage: TokenNonFungible Balances (r:1 w:1)
+	// Storage: TokenNonFungible TokenApprovals (r:0 w:1)
+	// Storage: TokenNonFungible TotalSupply (r:1 w:1)
+	// Storage: TokenNonFungible AllTokens (r:1 w:1)
+	// Storage: TokenNonFungible AllTokensIndex (r:0 w:1)
+	// Storage: TokenNonFungible OwnedTokens (r:1 w:1)
+	// Storage: TokenNonFungible OwnedTokensIndex (r:1 w:1)
+	fn craft(t: u32, i: u32, o: u32, ) -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add((4_000_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add((12_000_000 as Weight).saturating_mul(t as Weight).saturating_mul(i as Weight))
+			.saturating_add((12_000_000 as Weight).saturating_mul(t as Weight).saturating_mul(o as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(t as Weight)))
+			.saturating_add(T::DbWeight::get().reads((6 as Weight).saturating_mul(t as Weight).saturating_mul(i as Weight)))
+			.saturating_add(T::DbWeight::get().reads((6 as Weight).saturating_mul(t as Weight).saturating_mul(o as Weight)))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(t as Weight)))
+			.saturating_add(T::DbWeight::get().writes((8 as Weight).saturating_mul(t as Weight).saturating_mul(i as Weight)))
+			.saturating_add(T::DbWeight::get().writes((8 as Weight).saturating_mul(t as Weight).saturating_mul(o as Weight)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn create_recipe() -> Weight {
+		(31_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn set_recipe_enabled() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn remove_recipe() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn craft(t: u32, i: u32, o: u32, ) -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add((4_000_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add((12_000_000 as Weight).saturating_mul(t as Weight).saturating_mul(i as Weight))
+			.saturating_add((12_000_000 as Weight).saturating_mul(t as Weight).saturating_mul(o as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(t as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((6 as Weight).saturating_mul(t as Weight).saturating_mul(i as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((6 as Weight).saturating_mul(t as Weight).saturating_mul(o as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(t as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((8 as Weight).saturating_mul(t as Weight).saturating_mul(i as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((8 as Weight).saturating_mul(t as Weight).saturating_mul(o as Weight)))
+	}
+}
@@ -19,23 +19,93 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::Codec;
-use primitives::Balance;
+pub use pallet_exchange::{LpPosition, PoolInfo, PoolStatus, Route};
+use primitives::{Balance, BlockNumber};
 use sp_api::decl_runtime_apis;
 use sp_std::vec::Vec;
 
 decl_runtime_apis! {
+	#[api_version(2)]
 	pub trait ExchangeRuntimeApi<AccountId> where
 		AccountId: Codec,
 	{
 		fn get_amount_in_price(supply: Balance, path: Vec<u128>) -> Option<Vec<Balance>>;
 		fn get_amount_out_price(supply: Balance, path: Vec<u128>) -> Option<Vec<Balance>>;
+		#[changed_in(2)]
 		fn get_estimate_lp_token(
 			token_0: u128,
 			amount_0: Balance,
 			token_1: u128,
 			amount_1: Balance,
 		) -> Option<Balance>;
+		fn get_estimate_lp_token(
+			token_0: u128,
+			amount_0: Balance,
+			token_1: u128,
+			amount_1: Balance,
+			fee: u32,
+		) -> Option<Balance>;
+		#[changed_in(2)]
 		fn get_estimate_out_token(supply: Balance,token_0:u128,token_1:u128)-> Option<Balance>;
+		fn get_estimate_out_token(supply: Balance,token_0:u128,token_1:u128,fee:u32)-> Option<Balance>;
 		fn get_liquidity_to_tokens(lp_token_0:u128,lp_balance:Balance)-> Option<(Balance,Balance)>;
+		/// The time weighted average prices of `token_a` in `token_b` and of `token_b` in
+		/// `token_a` over the last `window` blocks, as `FixedU128` inner values.
+		fn consult(token_a: u128, token_b: u128, fee: u32, window: BlockNumber) -> Option<(u128, u128)>;
+		fn get_stable_amount_out(
+			pool_id: u128,
+			token_in: u128,
+			token_out: u128,
+			amount_in: Balance,
+		) -> Option<Balance>;
+		fn get_stable_estimate_lp_token(pool_id: u128, amounts: Vec<Balance>) -> Option<Balance>;
+		fn get_stable_liquidity_to_tokens(pool_id: u128, lp_balance: Balance) -> Option<Vec<Balance>>;
+		/// The route from `token_in` to `token_out` of at most `max_hops` hops which pays out
+		/// the most for `amount_in`.
+		fn find_route_out(
+			token_in: u128,
+			token_out: u128,
+			amount_in: Balance,
+			max_hops: u32,
+		) -> Option<Route<u128>>;
+		/// The route from `token_in` to `token_out` of at most `max_hops` hops which asks for
+		/// the least to pay out `amount_out`.
+		fn find_route_in(
+			token_in: u128,
+			token_out: u128,
+			amount_out: Balance,
+			max_hops: u32,
+		) -> Option<Route<u128>>;
+		/// At most `limit` constant product pools ordered by LP token id, skipping the first
+		/// `offset`.
+		fn get_pools(offset: u32, limit: u32) -> Vec<PoolInfo<u128>>;
+		fn get_pool(token_a: u128, token_b: u128, fee: u32) -> Option<PoolInfo<u128>>;
+		fn get_pool_by_lp_token(lp_token: u128) -> Option<PoolInfo<u128>>;
+		fn get_stable_lp_token(pool_id: u128) -> Option<u128>;
+		fn get_stable_pool_by_lp_token(lp_token: u128) -> Option<u128>;
+		fn get_stable_pool_status(pool_id: u128) -> Option<PoolStatus>;
+		fn get_weighted_amount_out(
+			pool_id: u128,
+			token_in: u128,
+			token_out: u128,
+			amount_in: Balance,
+		) -> Option<Balance>;
+		/// The LP tokens minted for depositing `amount_in` of a single token into a weighted pool.
+		fn get_weighted_estimate_lp_token(
+			pool_id: u128,
+			token_in: u128,
+			amount_in: Balance,
+		) -> Option<Balance>;
+		/// The amount of a single token withdrawn from a weighted pool for `lp_balance`.
+		fn get_weighted_estimate_out_token(
+			pool_id: u128,
+			token_out: u128,
+			lp_balance: Balance,
+		) -> Option<Balance>;
+		fn get_weighted_liquidity_to_tokens(pool_id: u128, lp_balance: Balance) -> Option<Vec<Balance>>;
+		fn get_weighted_pool_by_lp_token(lp_token: u128) -> Option<u128>;
+		fn get_weighted_pool_status(pool_id: u128) -> Option<PoolStatus>;
+		/// The LP tokens `who` holds and the underlying token amounts they are worth.
+		fn get_lp_positions(who: AccountId) -> Vec<LpPosition<u128>>;
 	}
 }
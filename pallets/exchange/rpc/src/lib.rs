@@ -22,10 +22,12 @@ use jsonrpsee::{
 	proc_macros::rpc,
 	types::error::{CallError, ErrorObject},
 };
-pub use pallet_exchange_rpc_runtime_api::ExchangeRuntimeApi;
+pub use pallet_exchange_rpc_runtime_api::{
+	ExchangeRuntimeApi, LpPosition, PoolInfo, PoolStatus, Route,
+};
 pub use pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi as TransactionPaymentRuntimeApi;
-use primitives::Balance;
-use sp_api::ProvideRuntimeApi;
+use primitives::{Balance, BlockNumber};
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use std::{marker::PhantomData, sync::Arc};
@@ -66,6 +68,7 @@ pub trait ExchangeRpcApi<BlockHash, AccountId> {
 		amount_0: Balance,
 		token_1: u128,
 		amount_1: Balance,
+		fee: u32,
 		at: Option<BlockHash>,
 	) -> RpcResult<Option<Balance>>;
 
@@ -75,6 +78,7 @@ pub trait ExchangeRpcApi<BlockHash, AccountId> {
 		supply: Balance,
 		token_0: u128,
 		token_1: u128,
+		fee: u32,
 		at: Option<BlockHash>,
 	) -> RpcResult<Option<Balance>>;
 
@@ -85,6 +89,160 @@ pub trait ExchangeRpcApi<BlockHash, AccountId> {
 		lp_balance: Balance,
 		at: Option<BlockHash>,
 	) -> RpcResult<Option<(Balance, Balance)>>;
+
+	#[method(name = "exchange_consult")]
+	fn consult(
+		&self,
+		token_a: u128,
+		token_b: u128,
+		fee: u32,
+		window: BlockNumber,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<(u128, u128)>>;
+
+	#[method(name = "exchange_getStableAmountOut")]
+	fn get_stable_amount_out(
+		&self,
+		pool_id: u128,
+		token_in: u128,
+		token_out: u128,
+		amount_in: Balance,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Balance>>;
+
+	#[method(name = "exchange_getStableEstimateLpToken")]
+	fn get_stable_estimate_lp_token(
+		&self,
+		pool_id: u128,
+		amounts: Vec<Balance>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Balance>>;
+
+	#[method(name = "exchange_getStableLiquidityToTokens")]
+	fn get_stable_liquidity_to_tokens(
+		&self,
+		pool_id: u128,
+		lp_balance: Balance,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Vec<Balance>>>;
+
+	#[method(name = "exchange_findRouteOut")]
+	fn find_route_out(
+		&self,
+		token_in: u128,
+		token_out: u128,
+		amount_in: Balance,
+		max_hops: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Route<u128>>>;
+
+	#[method(name = "exchange_findRouteIn")]
+	fn find_route_in(
+		&self,
+		token_in: u128,
+		token_out: u128,
+		amount_out: Balance,
+		max_hops: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Route<u128>>>;
+
+	#[method(name = "exchange_getPools")]
+	fn get_pools(
+		&self,
+		offset: u32,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<PoolInfo<u128>>>;
+
+	#[method(name = "exchange_getPool")]
+	fn get_pool(
+		&self,
+		token_a: u128,
+		token_b: u128,
+		fee: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<PoolInfo<u128>>>;
+
+	#[method(name = "exchange_getPoolByLpToken")]
+	fn get_pool_by_lp_token(
+		&self,
+		lp_token: u128,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<PoolInfo<u128>>>;
+
+	#[method(name = "exchange_getStableLpToken")]
+	fn get_stable_lp_token(&self, pool_id: u128, at: Option<BlockHash>) -> RpcResult<Option<u128>>;
+
+	#[method(name = "exchange_getStablePoolByLpToken")]
+	fn get_stable_pool_by_lp_token(
+		&self,
+		lp_token: u128,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<u128>>;
+
+	#[method(name = "exchange_getStablePoolStatus")]
+	fn get_stable_pool_status(
+		&self,
+		pool_id: u128,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<PoolStatus>>;
+
+	#[method(name = "exchange_getWeightedAmountOut")]
+	fn get_weighted_amount_out(
+		&self,
+		pool_id: u128,
+		token_in: u128,
+		token_out: u128,
+		amount_in: Balance,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Balance>>;
+
+	#[method(name = "exchange_getWeightedEstimateLpToken")]
+	fn get_weighted_estimate_lp_token(
+		&self,
+		pool_id: u128,
+		token_in: u128,
+		amount_in: Balance,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Balance>>;
+
+	#[method(name = "exchange_getWeightedEstimateOutToken")]
+	fn get_weighted_estimate_out_token(
+		&self,
+		pool_id: u128,
+		token_out: u128,
+		lp_balance: Balance,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Balance>>;
+
+	#[method(name = "exchange_getWeightedLiquidityToTokens")]
+	fn get_weighted_liquidity_to_tokens(
+		&self,
+		pool_id: u128,
+		lp_balance: Balance,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Vec<Balance>>>;
+
+	#[method(name = "exchange_getWeightedPoolByLpToken")]
+	fn get_weighted_pool_by_lp_token(
+		&self,
+		lp_token: u128,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<u128>>;
+
+	#[method(name = "exchange_getWeightedPoolStatus")]
+	fn get_weighted_pool_status(
+		&self,
+		pool_id: u128,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<PoolStatus>>;
+
+	#[method(name = "exchange_getLpPositions")]
+	fn get_lp_positions(
+		&self,
+		who: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<LpPosition<u128>>>;
 }
 
 /// Error type of this RPC api.
@@ -146,6 +304,7 @@ where
 		amount_0: Balance,
 		token_1: u128,
 		amount_1: Balance,
+		fee: u32,
 		at: Option<<Block as BlockT>::Hash>,
 	) -> RpcResult<Option<Balance>> {
 		let api = self.client.runtime_api();
@@ -153,7 +312,18 @@ where
 		// If the block hash is not supplied assume the best block.
 		self.client.info().best_hash));
 
-		api.get_estimate_lp_token(&at, token_0, amount_0, token_1, amount_1)
+		// runtimes before version 2 have pools of a single fee
+		let api_version = api
+			.api_version::<dyn ExchangeRuntimeApi<Block, AccountId>>(&at)
+			.map_err(runtime_error_into_rpc_err)?;
+		if api_version < Some(2) {
+			#[allow(deprecated)]
+			return api
+				.get_estimate_lp_token_before_version_2(&at, token_0, amount_0, token_1, amount_1)
+				.map_err(runtime_error_into_rpc_err)
+		}
+
+		api.get_estimate_lp_token(&at, token_0, amount_0, token_1, amount_1, fee)
 			.map_err(runtime_error_into_rpc_err)
 	}
 	fn get_estimate_out_token(
@@ -161,6 +331,7 @@ where
 		supply: Balance,
 		token_0: u128,
 		token_1: u128,
+		fee: u32,
 		at: Option<<Block as BlockT>::Hash>,
 	) -> RpcResult<Option<Balance>> {
 		let api = self.client.runtime_api();
@@ -168,7 +339,17 @@ where
 		// If the block hash is not supplied assume the best block.
 		self.client.info().best_hash));
 
-		api.get_estimate_out_token(&at, supply, token_0, token_1)
+		let api_version = api
+			.api_version::<dyn ExchangeRuntimeApi<Block, AccountId>>(&at)
+			.map_err(runtime_error_into_rpc_err)?;
+		if api_version < Some(2) {
+			#[allow(deprecated)]
+			return api
+				.get_estimate_out_token_before_version_2(&at, supply, token_0, token_1)
+				.map_err(runtime_error_into_rpc_err)
+		}
+
+		api.get_estimate_out_token(&at, supply, token_0, token_1, fee)
 			.map_err(runtime_error_into_rpc_err)
 	}
 	fn get_liquidity_to_tokens(
@@ -185,6 +366,284 @@ where
 		api.get_liquidity_to_tokens(&at, lp_token, lp_balance)
 			.map_err(runtime_error_into_rpc_err)
 	}
+	fn consult(
+		&self,
+		token_a: u128,
+		token_b: u128,
+		fee: u32,
+		window: BlockNumber,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<(u128, u128)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.consult(&at, token_a, token_b, fee, window)
+			.map_err(runtime_error_into_rpc_err)
+	}
+	fn get_stable_amount_out(
+		&self,
+		pool_id: u128,
+		token_in: u128,
+		token_out: u128,
+		amount_in: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Balance>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_stable_amount_out(&at, pool_id, token_in, token_out, amount_in)
+			.map_err(runtime_error_into_rpc_err)
+	}
+	fn get_stable_estimate_lp_token(
+		&self,
+		pool_id: u128,
+		amounts: Vec<Balance>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Balance>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_stable_estimate_lp_token(&at, pool_id, amounts)
+			.map_err(runtime_error_into_rpc_err)
+	}
+	fn get_stable_liquidity_to_tokens(
+		&self,
+		pool_id: u128,
+		lp_balance: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Vec<Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_stable_liquidity_to_tokens(&at, pool_id, lp_balance)
+			.map_err(runtime_error_into_rpc_err)
+	}
+	fn find_route_out(
+		&self,
+		token_in: u128,
+		token_out: u128,
+		amount_in: Balance,
+		max_hops: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Route<u128>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.find_route_out(&at, token_in, token_out, amount_in, max_hops)
+			.map_err(runtime_error_into_rpc_err)
+	}
+	fn find_route_in(
+		&self,
+		token_in: u128,
+		token_out: u128,
+		amount_out: Balance,
+		max_hops: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Route<u128>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.find_route_in(&at, token_in, token_out, amount_out, max_hops)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_pools(
+		&self,
+		offset: u32,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<PoolInfo<u128>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_pools(&at, offset, limit).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_pool(
+		&self,
+		token_a: u128,
+		token_b: u128,
+		fee: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<PoolInfo<u128>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_pool(&at, token_a, token_b, fee).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_pool_by_lp_token(
+		&self,
+		lp_token: u128,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<PoolInfo<u128>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_pool_by_lp_token(&at, lp_token).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_stable_lp_token(
+		&self,
+		pool_id: u128,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<u128>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_stable_lp_token(&at, pool_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_stable_pool_by_lp_token(
+		&self,
+		lp_token: u128,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<u128>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_stable_pool_by_lp_token(&at, lp_token)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_stable_pool_status(
+		&self,
+		pool_id: u128,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<PoolStatus>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_stable_pool_status(&at, pool_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_weighted_amount_out(
+		&self,
+		pool_id: u128,
+		token_in: u128,
+		token_out: u128,
+		amount_in: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Balance>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_weighted_amount_out(&at, pool_id, token_in, token_out, amount_in)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_weighted_estimate_lp_token(
+		&self,
+		pool_id: u128,
+		token_in: u128,
+		amount_in: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Balance>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_weighted_estimate_lp_token(&at, pool_id, token_in, amount_in)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_weighted_estimate_out_token(
+		&self,
+		pool_id: u128,
+		token_out: u128,
+		lp_balance: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Balance>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_weighted_estimate_out_token(&at, pool_id, token_out, lp_balance)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_weighted_liquidity_to_tokens(
+		&self,
+		pool_id: u128,
+		lp_balance: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Vec<Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_weighted_liquidity_to_tokens(&at, pool_id, lp_balance)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_weighted_pool_by_lp_token(
+		&self,
+		lp_token: u128,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<u128>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_weighted_pool_by_lp_token(&at, lp_token)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_weighted_pool_status(
+		&self,
+		pool_id: u128,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<PoolStatus>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_weighted_pool_status(&at, pool_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_lp_positions(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<LpPosition<u128>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_lp_positions(&at, who).map_err(runtime_error_into_rpc_err)
+	}
 }
 
 /// Converts a runtime trap into an RPC error.
@@ -76,14 +76,108 @@ fn mint_token<T: Config>(who: T::AccountId) -> DispatchResult {
 
 fn init_create_pool<T: Config>(token_a: u128, token_b: u128) -> DispatchResult {
 	let alice: T::AccountId = whitelisted_caller();
+	FeeTiers::<T>::insert(DEFAULT_FEE, ());
 	assert_ok!(Exchange::<T>::create_pool(
 		RawOrigin::Signed(alice.clone()).into(),
 		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(token_a),
 		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(token_b),
+		DEFAULT_FEE,
 	));
 	Ok(())
 }
 
+fn init_add_liquidity<T: Config>() -> DispatchResult {
+	let alice: T::AccountId = whitelisted_caller();
+	assert_ok!(Exchange::<T>::add_liquidity(
+		RawOrigin::Signed(alice.clone()).into(),
+		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
+		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(2u128),
+		DEFAULT_FEE,
+		10_000_000_000_000_000u128,
+		10_000_000_000_000_000u128,
+		0u128,
+		0u128,
+		alice,
+		<T as frame_system::Config>::BlockNumber::unique_saturated_from(10000u128)
+	));
+	Ok(())
+}
+
+fn init_place_order<T: Config>() -> Result<T::OrderId, DispatchError> {
+	let alice: T::AccountId = whitelisted_caller();
+	Exchange::<T>::do_place_order(
+		alice,
+		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
+		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(2u128),
+		1_000_000_000_000_000u128,
+		FixedU128::saturating_from_rational(1, 2),
+		<T as frame_system::Config>::BlockNumber::unique_saturated_from(10000u128),
+	)
+}
+
+fn init_create_w3g_pool<T: Config>() -> DispatchResult {
+	let alice: T::AccountId = whitelisted_caller();
+	FeeTiers::<T>::insert(DEFAULT_FEE, ());
+	assert_ok!(Exchange::<T>::create_pool(
+		RawOrigin::Signed(alice.clone()).into(),
+		T::WW3G::get(),
+		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
+		DEFAULT_FEE,
+	));
+	assert_ok!(Exchange::<T>::add_liquidity_w3g(
+		RawOrigin::Signed(alice.clone()).into(),
+		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
+		DEFAULT_FEE,
+		10_000_000_000_000_000u128,
+		10_000_000_000_000_000u128,
+		0u128,
+		0u128,
+		alice,
+		<T as frame_system::Config>::BlockNumber::unique_saturated_from(10000u128)
+	));
+	Ok(())
+}
+
+fn init_create_stable_pool<T: Config>() -> Result<T::PoolId, DispatchError> {
+	let alice: T::AccountId = whitelisted_caller();
+	FeeTiers::<T>::insert(DEFAULT_FEE, ());
+	Exchange::<T>::do_create_stable_pool(
+		alice,
+		vec![
+			<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
+			<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(2u128),
+		],
+		100u128,
+		DEFAULT_FEE,
+	)
+}
+
+fn init_add_stable_liquidity<T: Config>(pool_id: T::PoolId) -> DispatchResult {
+	let alice: T::AccountId = whitelisted_caller();
+	Exchange::<T>::do_add_stable_liquidity(
+		alice.clone(),
+		pool_id,
+		vec![10_000_000_000_000_000u128, 10_000_000_000_000_000u128],
+		alice,
+	)?;
+	Ok(())
+}
+
+fn init_create_weighted_pool<T: Config>() -> Result<T::PoolId, DispatchError> {
+	let alice: T::AccountId = whitelisted_caller();
+	FeeTiers::<T>::insert(DEFAULT_FEE, ());
+	Exchange::<T>::do_create_weighted_pool(
+		alice,
+		vec![
+			<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
+			<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(2u128),
+		],
+		vec![Permill::from_percent(80), Permill::from_percent(20)],
+		DEFAULT_FEE,
+		vec![10_000_000_000_000_000u128, 10_000_000_000_000_000u128],
+	)
+}
+
 benchmarks! {
 	where_clause {
 		where
@@ -94,9 +188,11 @@ benchmarks! {
 		let alice: T::AccountId = whitelisted_caller();
 		set_balance::<T>(alice.clone())?;
 		create_token::<T>(alice.clone())?;
+		FeeTiers::<T>::insert(DEFAULT_FEE, ());
 	}: _(RawOrigin::Signed(alice),
 		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
-		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(2u128)
+		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(2u128),
+		DEFAULT_FEE
 	)
 	verify {
 		assert!(TokenFungible::Pallet::<T>::exists(<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128)));
@@ -116,6 +212,7 @@ benchmarks! {
 		RawOrigin::Signed(alice.clone()),
 		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
 		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(2u128),
+		DEFAULT_FEE,
 		10_000_000_000_000_000u128,
 		10_000_000_000_000_000u128,
 		0u128,
@@ -138,6 +235,7 @@ benchmarks! {
 				RawOrigin::Signed(alice.clone()).into(),
 				<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
 				<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(2u128),
+				DEFAULT_FEE,
 				10_000_000_000_000_000u128,
 				10_000_000_000_000_000u128,
 				0u128,
@@ -149,6 +247,7 @@ benchmarks! {
 		RawOrigin::Signed(alice.clone()),
 		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
 		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(2u128),
+		DEFAULT_FEE,
 		10_000_000_000u128,
 		0u128,
 		0u128,
@@ -170,6 +269,7 @@ benchmarks! {
 				RawOrigin::Signed(alice.clone()).into(),
 				<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
 				<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(2u128),
+				DEFAULT_FEE,
 				10_000_000_000_000_000u128,
 				10_000_000_000_000_000u128,
 				0u128,
@@ -203,6 +303,7 @@ benchmarks! {
 				RawOrigin::Signed(alice.clone()).into(),
 				<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
 				<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(2u128),
+				DEFAULT_FEE,
 				10_000_000_000_000_000u128,
 				10_000_000_000_000_000u128,
 				0u128,
@@ -222,6 +323,487 @@ benchmarks! {
 		<T as frame_system::Config>::BlockNumber::unique_saturated_from(10000u128)
 	)
 
+	swap_exact_w3g_for_tokens {
+		let alice: T::AccountId = whitelisted_caller();
+		set_balance::<T>(alice.clone())?;
+		create_token::<T>(alice.clone())?;
+		mint_token::<T>(alice.clone())?;
+		init_create_w3g_pool::<T>()?;
+	}: _(
+		RawOrigin::Signed(alice.clone()),
+		1_000_000_000_000_000u128,
+		0u128,
+		vec![
+			T::WW3G::get(),
+			<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
+		],
+		alice.clone(),
+		<T as frame_system::Config>::BlockNumber::unique_saturated_from(10000u128)
+	)
+
+	swap_tokens_for_exact_w3g {
+		let alice: T::AccountId = whitelisted_caller();
+		set_balance::<T>(alice.clone())?;
+		create_token::<T>(alice.clone())?;
+		mint_token::<T>(alice.clone())?;
+		init_create_w3g_pool::<T>()?;
+	}: _(
+		RawOrigin::Signed(alice.clone()),
+		1_000_000_000_000_000u128,
+		100_000_000_000_000_000u128,
+		vec![
+			<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
+			T::WW3G::get(),
+		],
+		alice.clone(),
+		<T as frame_system::Config>::BlockNumber::unique_saturated_from(10000u128)
+	)
+
+	swap_exact_tokens_for_w3g {
+		let alice: T::AccountId = whitelisted_caller();
+		set_balance::<T>(alice.clone())?;
+		create_token::<T>(alice.clone())?;
+		mint_token::<T>(alice.clone())?;
+		init_create_w3g_pool::<T>()?;
+	}: _(
+		RawOrigin::Signed(alice.clone()),
+		1_000_000_000_000_000u128,
+		0u128,
+		vec![
+			<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
+			T::WW3G::get(),
+		],
+		alice.clone(),
+		<T as frame_system::Config>::BlockNumber::unique_saturated_from(10000u128)
+	)
+
+	swap_w3g_for_exact_tokens {
+		let alice: T::AccountId = whitelisted_caller();
+		set_balance::<T>(alice.clone())?;
+		create_token::<T>(alice.clone())?;
+		mint_token::<T>(alice.clone())?;
+		init_create_w3g_pool::<T>()?;
+	}: _(
+		RawOrigin::Signed(alice.clone()),
+		1_000_000_000_000_000u128,
+		100_000_000_000_000_000u128,
+		vec![
+			T::WW3G::get(),
+			<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
+		],
+		alice.clone(),
+		<T as frame_system::Config>::BlockNumber::unique_saturated_from(10000u128)
+	)
+
+	set_fee_tier {
+		let origin = T::UpdateOrigin::successful_origin();
+	}: _<T::Origin>(origin, 100u32, true)
+	verify {
+		assert!(Exchange::<T>::is_fee_tier(100u32));
+	}
+
+	set_protocol_fee_share {
+		let origin = T::UpdateOrigin::successful_origin();
+	}: _<T::Origin>(origin, Permill::from_percent(20))
+	verify {
+		assert_eq!(Exchange::<T>::protocol_fee_share(), Permill::from_percent(20));
+	}
+
+	flash_swap {
+		let alice: T::AccountId = whitelisted_caller();
+		set_balance::<T>(alice.clone())?;
+		create_token::<T>(alice.clone())?;
+		init_create_pool::<T>(
+			1u128,
+			2u128
+		)?;
+		mint_token::<T>(alice.clone())?;
+		assert_ok!(
+			pallet::Pallet::<T>::add_liquidity(
+				RawOrigin::Signed(alice.clone()).into(),
+				<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
+				<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(2u128),
+				DEFAULT_FEE,
+				10_000_000_000_000_000u128,
+				10_000_000_000_000_000u128,
+				0u128,
+				0u128,
+				alice.clone(),
+				<T as frame_system::Config>::BlockNumber::unique_saturated_from(10000u128)
+			));
+		// repay up front, the benchmark has no flash callback
+		let pool = Exchange::<T>::get_pool(
+			<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
+			<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(2u128),
+			DEFAULT_FEE,
+		)?;
+		assert_ok!(TokenFungible::Pallet::<T>::do_transfer(
+			<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
+			&alice,
+			&pool.lp_token_account_id,
+			2_000_000_000_000_000u128,
+		));
+	}: _(
+		RawOrigin::Signed(alice.clone()),
+		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
+		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(2u128),
+		DEFAULT_FEE,
+		0u128,
+		1_000_000_000_000_000u128,
+		Vec::new()
+	)
+
+	flash_loan {
+		let alice: T::AccountId = whitelisted_caller();
+		set_balance::<T>(alice.clone())?;
+		create_token::<T>(alice.clone())?;
+		init_create_pool::<T>(
+			1u128,
+			2u128
+		)?;
+		mint_token::<T>(alice.clone())?;
+		assert_ok!(
+			pallet::Pallet::<T>::add_liquidity(
+				RawOrigin::Signed(alice.clone()).into(),
+				<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
+				<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(2u128),
+				DEFAULT_FEE,
+				10_000_000_000_000_000u128,
+				10_000_000_000_000_000u128,
+				0u128,
+				0u128,
+				alice.clone(),
+				<T as frame_system::Config>::BlockNumber::unique_saturated_from(10000u128)
+			));
+		// pay the premium up front, the benchmark has no flash callback
+		let pool = Exchange::<T>::get_pool(
+			<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
+			<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(2u128),
+			DEFAULT_FEE,
+		)?;
+		assert_ok!(TokenFungible::Pallet::<T>::do_transfer(
+			<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
+			&alice,
+			&pool.lp_token_account_id,
+			Exchange::<T>::flash_loan_premium(1_000_000_000_000_000u128, DEFAULT_FEE)?,
+		));
+	}: _(
+		RawOrigin::Signed(alice.clone()),
+		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
+		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(2u128),
+		DEFAULT_FEE,
+		1_000_000_000_000_000u128,
+		Vec::new()
+	)
+
+	create_stable_pool {
+		let alice: T::AccountId = whitelisted_caller();
+		set_balance::<T>(alice.clone())?;
+		create_token::<T>(alice.clone())?;
+		FeeTiers::<T>::insert(DEFAULT_FEE, ());
+	}: _(RawOrigin::Signed(alice),
+		vec![
+			<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
+			<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(2u128),
+		],
+		100u128,
+		DEFAULT_FEE
+	)
+	verify {
+		assert!(Exchange::<T>::stable_pools(T::PoolId::zero()).is_some());
+	}
+
+	add_stable_liquidity {
+		let alice: T::AccountId = whitelisted_caller();
+		set_balance::<T>(alice.clone())?;
+		create_token::<T>(alice.clone())?;
+		let pool_id = init_create_stable_pool::<T>()?;
+		mint_token::<T>(alice.clone())?;
+		init_add_stable_liquidity::<T>(pool_id)?;
+	}: _(
+		RawOrigin::Signed(alice.clone()),
+		pool_id,
+		vec![10_000_000_000_000_000u128, 1_000_000_000_000_000u128],
+		0u128,
+		alice.clone(),
+		<T as frame_system::Config>::BlockNumber::unique_saturated_from(10000u128)
+	)
+
+	remove_stable_liquidity {
+		let alice: T::AccountId = whitelisted_caller();
+		set_balance::<T>(alice.clone())?;
+		create_token::<T>(alice.clone())?;
+		let pool_id = init_create_stable_pool::<T>()?;
+		mint_token::<T>(alice.clone())?;
+		init_add_stable_liquidity::<T>(pool_id)?;
+	}: _(
+		RawOrigin::Signed(alice.clone()),
+		pool_id,
+		10_000_000_000u128,
+		vec![0u128, 0u128],
+		alice.clone(),
+		<T as frame_system::Config>::BlockNumber::unique_saturated_from(10000u128)
+	)
+
+	swap_stable {
+		let alice: T::AccountId = whitelisted_caller();
+		set_balance::<T>(alice.clone())?;
+		create_token::<T>(alice.clone())?;
+		let pool_id = init_create_stable_pool::<T>()?;
+		mint_token::<T>(alice.clone())?;
+		init_add_stable_liquidity::<T>(pool_id)?;
+	}: _(
+		RawOrigin::Signed(alice.clone()),
+		pool_id,
+		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
+		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(2u128),
+		1_000_000_000_000_000u128,
+		0u128,
+		alice.clone(),
+		<T as frame_system::Config>::BlockNumber::unique_saturated_from(10000u128)
+	)
+
+	ramp_amplification {
+		let alice: T::AccountId = whitelisted_caller();
+		set_balance::<T>(alice.clone())?;
+		create_token::<T>(alice.clone())?;
+		let pool_id = init_create_stable_pool::<T>()?;
+		let origin = T::UpdateOrigin::successful_origin();
+	}: _<T::Origin>(
+		origin,
+		pool_id,
+		200u128,
+		<T as frame_system::Config>::BlockNumber::unique_saturated_from(10000u128)
+	)
+	verify {
+		assert_eq!(Exchange::<T>::stable_pools(pool_id).unwrap().future_a, 200 * A_PRECISION);
+	}
+
+	stop_ramp_amplification {
+		let alice: T::AccountId = whitelisted_caller();
+		set_balance::<T>(alice.clone())?;
+		create_token::<T>(alice.clone())?;
+		let pool_id = init_create_stable_pool::<T>()?;
+		assert_ok!(Exchange::<T>::ramp_amplification(
+			T::UpdateOrigin::successful_origin(),
+			pool_id,
+			200u128,
+			<T as frame_system::Config>::BlockNumber::unique_saturated_from(10000u128)
+		));
+		let origin = T::UpdateOrigin::successful_origin();
+	}: _<T::Origin>(origin, pool_id)
+
+	place_order {
+		let alice: T::AccountId = whitelisted_caller();
+		set_balance::<T>(alice.clone())?;
+		create_token::<T>(alice.clone())?;
+		init_create_pool::<T>(1u128, 2u128)?;
+		mint_token::<T>(alice.clone())?;
+	}: _(
+		RawOrigin::Signed(alice.clone()),
+		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
+		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(2u128),
+		1_000_000_000_000_000u128,
+		FixedU128::saturating_from_rational(1, 2),
+		<T as frame_system::Config>::BlockNumber::unique_saturated_from(10000u128)
+	)
+	verify {
+		assert!(Exchange::<T>::orders(T::OrderId::zero()).is_some());
+	}
+
+	cancel_order {
+		let alice: T::AccountId = whitelisted_caller();
+		set_balance::<T>(alice.clone())?;
+		create_token::<T>(alice.clone())?;
+		init_create_pool::<T>(1u128, 2u128)?;
+		mint_token::<T>(alice.clone())?;
+		let order_id = init_place_order::<T>()?;
+	}: _(RawOrigin::Signed(alice.clone()), order_id)
+	verify {
+		assert!(Exchange::<T>::orders(order_id).is_none());
+	}
+
+	fill_order {
+		let alice: T::AccountId = whitelisted_caller();
+		set_balance::<T>(alice.clone())?;
+		create_token::<T>(alice.clone())?;
+		init_create_pool::<T>(1u128, 2u128)?;
+		mint_token::<T>(alice.clone())?;
+		init_add_liquidity::<T>()?;
+		let order_id = init_place_order::<T>()?;
+	}: _(RawOrigin::Signed(alice.clone()), order_id)
+	verify {
+		assert!(Exchange::<T>::orders(order_id).is_none());
+	}
+
+	zap_in {
+		let alice: T::AccountId = whitelisted_caller();
+		set_balance::<T>(alice.clone())?;
+		create_token::<T>(alice.clone())?;
+		init_create_pool::<T>(1u128, 2u128)?;
+		mint_token::<T>(alice.clone())?;
+		init_add_liquidity::<T>()?;
+	}: _(
+		RawOrigin::Signed(alice.clone()),
+		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
+		1_000_000_000_000_000u128,
+		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
+		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(2u128),
+		DEFAULT_FEE,
+		0u128,
+		alice.clone(),
+		<T as frame_system::Config>::BlockNumber::unique_saturated_from(10000u128)
+	)
+
+	zap_out {
+		let alice: T::AccountId = whitelisted_caller();
+		set_balance::<T>(alice.clone())?;
+		create_token::<T>(alice.clone())?;
+		init_create_pool::<T>(1u128, 2u128)?;
+		mint_token::<T>(alice.clone())?;
+		init_add_liquidity::<T>()?;
+	}: _(
+		RawOrigin::Signed(alice.clone()),
+		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
+		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(2u128),
+		DEFAULT_FEE,
+		1_000_000_000_000_000u128,
+		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
+		0u128,
+		alice.clone(),
+		<T as frame_system::Config>::BlockNumber::unique_saturated_from(10000u128)
+	)
+
+	set_pool_status {
+		let alice: T::AccountId = whitelisted_caller();
+		set_balance::<T>(alice.clone())?;
+		create_token::<T>(alice.clone())?;
+		init_create_pool::<T>(1u128, 2u128)?;
+		let origin = T::PauseOrigin::successful_origin();
+	}: _<T::Origin>(
+		origin,
+		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
+		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(2u128),
+		DEFAULT_FEE,
+		PoolStatus::Emergency
+	)
+
+	set_stable_pool_status {
+		let alice: T::AccountId = whitelisted_caller();
+		set_balance::<T>(alice.clone())?;
+		create_token::<T>(alice.clone())?;
+		let pool_id = init_create_stable_pool::<T>()?;
+		let origin = T::PauseOrigin::successful_origin();
+	}: _<T::Origin>(origin, pool_id, PoolStatus::Emergency)
+	verify {
+		assert_eq!(Exchange::<T>::stable_pool_status(pool_id), PoolStatus::Emergency);
+	}
+
+	create_weighted_pool {
+		let alice: T::AccountId = whitelisted_caller();
+		set_balance::<T>(alice.clone())?;
+		create_token::<T>(alice.clone())?;
+		mint_token::<T>(alice.clone())?;
+		FeeTiers::<T>::insert(DEFAULT_FEE, ());
+	}: _(
+		RawOrigin::Signed(alice),
+		vec![<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128), <T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(2u128)],
+		vec![Permill::from_percent(80), Permill::from_percent(20)],
+		DEFAULT_FEE,
+		vec![10_000_000_000_000_000u128, 10_000_000_000_000_000u128]
+	)
+
+	add_weighted_liquidity {
+		let alice: T::AccountId = whitelisted_caller();
+		set_balance::<T>(alice.clone())?;
+		create_token::<T>(alice.clone())?;
+		mint_token::<T>(alice.clone())?;
+		let pool_id = init_create_weighted_pool::<T>()?;
+	}: _(
+		RawOrigin::Signed(alice.clone()),
+		pool_id,
+		1_000_000_000_000_000_000u128,
+		vec![10_000_000_000_000_000u128, 10_000_000_000_000_000u128],
+		alice.clone(),
+		<T as frame_system::Config>::BlockNumber::unique_saturated_from(10000u128)
+	)
+
+	remove_weighted_liquidity {
+		let alice: T::AccountId = whitelisted_caller();
+		set_balance::<T>(alice.clone())?;
+		create_token::<T>(alice.clone())?;
+		mint_token::<T>(alice.clone())?;
+		let pool_id = init_create_weighted_pool::<T>()?;
+	}: _(
+		RawOrigin::Signed(alice.clone()),
+		pool_id,
+		1_000_000_000_000_000_000u128,
+		vec![0u128, 0u128],
+		alice.clone(),
+		<T as frame_system::Config>::BlockNumber::unique_saturated_from(10000u128)
+	)
+
+	add_weighted_liquidity_single {
+		let alice: T::AccountId = whitelisted_caller();
+		set_balance::<T>(alice.clone())?;
+		create_token::<T>(alice.clone())?;
+		mint_token::<T>(alice.clone())?;
+		let pool_id = init_create_weighted_pool::<T>()?;
+	}: _(
+		RawOrigin::Signed(alice.clone()),
+		pool_id,
+		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(2u128),
+		1_000_000_000_000_000u128,
+		0u128,
+		alice.clone(),
+		<T as frame_system::Config>::BlockNumber::unique_saturated_from(10000u128)
+	)
+
+	remove_weighted_liquidity_single {
+		let alice: T::AccountId = whitelisted_caller();
+		set_balance::<T>(alice.clone())?;
+		create_token::<T>(alice.clone())?;
+		mint_token::<T>(alice.clone())?;
+		let pool_id = init_create_weighted_pool::<T>()?;
+	}: _(
+		RawOrigin::Signed(alice.clone()),
+		pool_id,
+		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(2u128),
+		1_000_000_000_000_000_000u128,
+		0u128,
+		alice.clone(),
+		<T as frame_system::Config>::BlockNumber::unique_saturated_from(10000u128)
+	)
+
+	swap_weighted {
+		let alice: T::AccountId = whitelisted_caller();
+		set_balance::<T>(alice.clone())?;
+		create_token::<T>(alice.clone())?;
+		mint_token::<T>(alice.clone())?;
+		let pool_id = init_create_weighted_pool::<T>()?;
+	}: _(
+		RawOrigin::Signed(alice.clone()),
+		pool_id,
+		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(1u128),
+		<T as TokenFungible::Config>::FungibleTokenId::unique_saturated_from(2u128),
+		1_000_000_000_000_000u128,
+		0u128,
+		alice.clone(),
+		<T as frame_system::Config>::BlockNumber::unique_saturated_from(10000u128)
+	)
+
+	set_weighted_pool_status {
+		let alice: T::AccountId = whitelisted_caller();
+		set_balance::<T>(alice.clone())?;
+		create_token::<T>(alice.clone())?;
+		mint_token::<T>(alice.clone())?;
+		let pool_id = init_create_weighted_pool::<T>()?;
+		let origin = T::PauseOrigin::successful_origin();
+	}: _<T::Origin>(origin, pool_id, PoolStatus::Emergency)
+	verify {
+		assert_eq!(Exchange::<T>::weighted_pool_status(pool_id), PoolStatus::Emergency);
+	}
+
 	impl_benchmark_test_suite!(Exchange, crate::mock::new_test_ext(), crate::mock::Test);
 
 }
@@ -22,23 +22,38 @@ use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
 	dispatch::{DispatchError, DispatchResult},
 	ensure,
-	traits::{Currency, ExistenceRequirement::AllowDeath, Get, Randomness, ReservableCurrency},
-	PalletId,
+	storage::{with_transaction, TransactionOutcome},
+	traits::{
+		ConstU32, Currency, EnsureOrigin, ExistenceRequirement::AllowDeath, Get, ReservableCurrency,
+	},
+	weights::Weight,
+	BoundedVec, PalletId,
 };
 use integer_sqrt::IntegerSquareRoot;
+use pallet_support::FungibleMetadata;
 use primitives::Balance;
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 use sp_core::U256;
 use sp_runtime::{
-	traits::{AccountIdConversion, AtLeast32BitUnsigned, CheckedAdd, One, Zero},
-	RuntimeDebug,
+	traits::{
+		AccountIdConversion, AtLeast32BitUnsigned, CheckedAdd, CheckedSub, One, Saturating,
+		UniqueSaturatedFrom, UniqueSaturatedInto, Zero,
+	},
+	FixedPointNumber, FixedU128, Permill, RuntimeDebug,
 };
 use sp_std::{cmp, prelude::*};
 
 pub use pallet::*;
+pub mod migrations;
+pub mod stable_swap;
+pub mod weighted_math;
 pub mod weights;
 pub use weights::WeightInfo;
 
+use stable_swap::{A_PRECISION, MAX_A, MAX_A_CHANGE};
+
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
@@ -54,6 +69,12 @@ type FungibleTokenIdOf<T> = <T as pallet_token_fungible::Config>::FungibleTokenI
 
 pub const MINIMUM_LIQUIDITY: u128 = 1000; // 10**3;
 
+/// Pool fees are expressed in basis points of this denominator.
+pub const FEE_DENOMINATOR: u32 = 10_000;
+
+/// The fee tier of the pools created before fees became configurable, in basis points.
+pub const DEFAULT_FEE: u32 = 30;
+
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
 pub struct Pool<AccountId, FungibleTokenId> {
 	/// The id of first token
@@ -64,6 +85,254 @@ pub struct Pool<AccountId, FungibleTokenId> {
 	pub lp_token: FungibleTokenId,
 	/// The id of liquidity pool token
 	pub lp_token_account_id: AccountId,
+	/// The swap fee in basis points
+	pub fee: u32,
+}
+
+/// What a pool allows, set by the `PauseOrigin`.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum PoolStatus {
+	/// Swaps and liquidity changes are allowed
+	Active,
+	/// Liquidity can be added and removed, but the pool cannot be swapped through
+	SwapsPaused,
+	/// The pool can be swapped through, but liquidity can be neither added nor removed
+	LiquidityPaused,
+	/// Neither swaps nor liquidity changes are allowed
+	Paused,
+	/// Liquidity can only be removed
+	Emergency,
+}
+
+impl Default for PoolStatus {
+	fn default() -> Self {
+		PoolStatus::Active
+	}
+}
+
+impl PoolStatus {
+	/// Whether the pool can be swapped through, flash swaps and loans included.
+	pub fn can_swap(&self) -> bool {
+		matches!(self, PoolStatus::Active | PoolStatus::LiquidityPaused)
+	}
+
+	pub fn can_add_liquidity(&self) -> bool {
+		matches!(self, PoolStatus::Active | PoolStatus::SwapsPaused)
+	}
+
+	pub fn can_remove_liquidity(&self) -> bool {
+		matches!(self, PoolStatus::Active | PoolStatus::SwapsPaused | PoolStatus::Emergency)
+	}
+}
+
+/// An escrowed order selling `amount_in` of `token_in` for at least `price` of `token_out` per
+/// unit, filled against the pools of the pair until `expiry`.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct LimitOrder<AccountId, FungibleTokenId, BlockNumber> {
+	/// The account placing the order
+	pub owner: AccountId,
+	/// The token sold
+	pub token_in: FungibleTokenId,
+	/// The token bought
+	pub token_out: FungibleTokenId,
+	/// The amount of `token_in` still to be sold
+	pub amount_in: Balance,
+	/// The minimum amount of `token_out` per unit of `token_in`
+	pub price: FixedU128,
+	/// The last block the order can be filled at
+	pub expiry: BlockNumber,
+}
+
+/// The most hops a route found by `find_route_out` or `find_route_in` can take.
+pub const MAX_ROUTE_HOPS: u32 = 4;
+
+/// A route between two tokens through the constant product pools.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct Route<FungibleTokenId> {
+	/// The tokens swapped through, from the input to the output token
+	pub path: Vec<FungibleTokenId>,
+	/// The expected amount of each token of the path
+	pub amounts: Vec<Balance>,
+	/// The fee tier of the pool used at each hop
+	pub fees: Vec<u32>,
+	/// The fee paid at each hop, in the input token of the hop
+	pub fee_amounts: Vec<Balance>,
+	/// How much less the route pays out than at the spot prices of its pools, fees aside
+	pub price_impact: Permill,
+}
+
+/// The most ids tried for the LP token of a new pool when the ids derived before it are taken.
+pub const MAX_LP_TOKEN_ID_PROBES: u32 = 16;
+
+/// The most pools `get_pools` returns at once.
+pub const MAX_POOLS_PER_PAGE: u32 = 100;
+
+/// A constant product pool with its reserves and LP token supply.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PoolInfo<FungibleTokenId> {
+	/// The id of first token
+	pub token_0: FungibleTokenId,
+	/// The id of second token
+	pub token_1: FungibleTokenId,
+	/// The swap fee in basis points
+	pub fee: u32,
+	/// The id of liquidity pool token
+	pub lp_token: FungibleTokenId,
+	/// The reserve of the first token
+	pub reserve_0: Balance,
+	/// The reserve of the second token
+	pub reserve_1: Balance,
+	/// The total supply of the liquidity pool token
+	pub total_supply: Balance,
+	/// What the pool allows
+	pub status: PoolStatus,
+}
+
+/// The LP tokens an account holds in a constant product pool.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct LpPosition<FungibleTokenId> {
+	/// The pool the position is in
+	pub pool: PoolInfo<FungibleTokenId>,
+	/// The LP tokens held
+	pub lp_balance: Balance,
+	/// The share of the reserve of the first token the LP tokens are worth
+	pub amount_0: Balance,
+	/// The share of the reserve of the second token the LP tokens are worth
+	pub amount_1: Balance,
+}
+
+/// The most tokens a StableSwap pool can hold.
+pub const MAX_STABLE_TOKENS: u32 = 8;
+
+/// The decimals StableSwap balances are normalized to.
+pub const STABLE_DECIMALS: u8 = 18;
+
+/// A Curve-style StableSwap pool of 2 to `MAX_STABLE_TOKENS` pegged tokens.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct StablePool<AccountId, FungibleTokenId, BlockNumber> {
+	/// The ids of the pooled tokens
+	pub tokens: BoundedVec<FungibleTokenId, ConstU32<MAX_STABLE_TOKENS>>,
+	/// The multipliers normalizing the balance of each token to `STABLE_DECIMALS`
+	pub rates: BoundedVec<Balance, ConstU32<MAX_STABLE_TOKENS>>,
+	/// The id of liquidity pool token
+	pub lp_token: FungibleTokenId,
+	/// The account holding the pooled tokens
+	pub lp_token_account_id: AccountId,
+	/// The swap fee in basis points
+	pub fee: u32,
+	/// The amplification coefficient at `initial_a_block`, times `A_PRECISION`
+	pub initial_a: u128,
+	/// The amplification coefficient ramped to at `future_a_block`, times `A_PRECISION`
+	pub future_a: u128,
+	/// The block a ramp of the amplification coefficient started at
+	pub initial_a_block: BlockNumber,
+	/// The block a ramp of the amplification coefficient ends at
+	pub future_a_block: BlockNumber,
+}
+
+/// The most tokens a weighted pool can hold.
+pub const MAX_WEIGHTED_TOKENS: u32 = 8;
+
+/// The lowest weight of a token of a weighted pool.
+pub const MIN_WEIGHT: Permill = Permill::from_parts(10_000);
+
+/// The decimals of the LP tokens of weighted pools.
+pub const WEIGHTED_DECIMALS: u8 = 18;
+
+/// The LP tokens minted for the deposit creating a weighted pool.
+pub const INIT_WEIGHTED_SUPPLY: Balance = 100 * weighted_math::ONE;
+
+/// The largest part of its balance of a token a weighted pool takes in or pays out at once.
+pub const MAX_WEIGHTED_RATIO: Permill = Permill::from_percent(50);
+
+/// A Balancer-style pool of 2 to `MAX_WEIGHTED_TOKENS` tokens, each making up a fixed share of
+/// the value of the pool.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct WeightedPool<AccountId, FungibleTokenId> {
+	/// The ids of the pooled tokens
+	pub tokens: BoundedVec<FungibleTokenId, ConstU32<MAX_WEIGHTED_TOKENS>>,
+	/// The share of the value of the pool of each token, adding up to one
+	pub weights: BoundedVec<Permill, ConstU32<MAX_WEIGHTED_TOKENS>>,
+	/// The id of liquidity pool token
+	pub lp_token: FungibleTokenId,
+	/// The account holding the pooled tokens
+	pub lp_token_account_id: AccountId,
+	/// The swap fee in basis points
+	pub fee: u32,
+}
+
+/// The cumulative prices of a pool at a block. Prices are `FixedU128` inner values, summed once
+/// per block and wrapping on overflow, so only differences between observations are meaningful.
+#[derive(Encode, Decode, Clone, Default, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Observation<BlockNumber> {
+	/// The block of the observation
+	pub block_number: BlockNumber,
+	/// The sum of the price of token_0 in token_1 over each block
+	pub price_0_cumulative: u128,
+	/// The sum of the price of token_1 in token_0 over each block
+	pub price_1_cumulative: u128,
+}
+
+/// Called back on the borrower in the middle of a flash swap or flash loan, after the borrowed
+/// amounts were sent. The borrower must repay the pool before returning, see
+/// [`Pallet::flash_repay`].
+pub trait FlashCallback<AccountId, FungibleTokenId> {
+	/// `who` received `amount_a_out` of `token_a` and `amount_b_out` of `token_b` from the pool
+	/// with `fee`. Returns false to abort the flash swap.
+	fn on_flash_swap(
+		who: &AccountId,
+		token_a: FungibleTokenId,
+		token_b: FungibleTokenId,
+		fee: u32,
+		amount_a_out: Balance,
+		amount_b_out: Balance,
+		data: &[u8],
+	) -> bool;
+
+	/// `who` borrowed `amount` of `token` from the pool of `token` and `pair_token` with `fee`,
+	/// and owes `amount + premium`. Returns false to abort the flash loan.
+	fn on_flash_loan(
+		who: &AccountId,
+		token: FungibleTokenId,
+		pair_token: FungibleTokenId,
+		fee: u32,
+		amount: Balance,
+		premium: Balance,
+		data: &[u8],
+	) -> bool;
+}
+
+impl<AccountId, FungibleTokenId> FlashCallback<AccountId, FungibleTokenId> for () {
+	fn on_flash_swap(
+		_who: &AccountId,
+		_token_a: FungibleTokenId,
+		_token_b: FungibleTokenId,
+		_fee: u32,
+		_amount_a_out: Balance,
+		_amount_b_out: Balance,
+		_data: &[u8],
+	) -> bool {
+		true
+	}
+
+	fn on_flash_loan(
+		_who: &AccountId,
+		_token: FungibleTokenId,
+		_pair_token: FungibleTokenId,
+		_fee: u32,
+		_amount: Balance,
+		_premium: Balance,
+		_data: &[u8],
+	) -> bool {
+		true
+	}
 }
 
 #[frame_support::pallet]
@@ -92,22 +361,54 @@ pub mod pallet {
 
 		type Currency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
 
-		type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
+		/// The origin which may manage fee tiers and the protocol fee share.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin which may pause pools and put them in emergency mode.
+		type PauseOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The callback of flash swap and flash loan borrowers.
+		type FlashCallback: FlashCallback<Self::AccountId, Self::FungibleTokenId>;
+
+		/// The maximum weight of a flash callback, charged on top of the flash extrinsics.
+		#[pallet::constant]
+		type MaxFlashCallbackWeight: Get<Weight>;
+
+		/// The size of the observation ring buffer of each pool, zero to keep no observations.
+		#[pallet::constant]
+		type MaxObservations: Get<u32>;
+
+		/// The minimum number of blocks between two observations of a pool.
+		#[pallet::constant]
+		type ObservationPeriod: Get<Self::BlockNumber>;
+
+		type OrderId: Member + Parameter + AtLeast32BitUnsigned + Default + Copy + MaxEncodedLen;
+
+		/// The maximum number of limit orders checked in `on_idle` each block.
+		#[pallet::constant]
+		type MaxOrdersPerBlock: Get<u32>;
 
 		/// runtime weights.
 		type WeightInfo: WeightInfo;
 	}
 
+	/// The current storage version.
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
+	/// The pools of each token pair, keyed by their fee in basis points.
 	#[pallet::storage]
 	#[pallet::getter(fn pools)]
-	pub(super) type Pools<T: Config> = StorageMap<
+	pub(super) type Pools<T: Config> = StorageDoubleMap<
 		_,
 		Blake2_128,
 		(T::FungibleTokenId, T::FungibleTokenId),
+		Blake2_128Concat,
+		u32,
 		Pool<T::AccountId, T::FungibleTokenId>,
 	>;
 
@@ -117,30 +418,198 @@ pub mod pallet {
 
 	#[pallet::storage]
 	#[pallet::getter(fn lp_token_to_token)]
-	pub(super) type LpTokenToToken<T: Config> =
-		StorageMap<_, Blake2_128, T::FungibleTokenId, (T::FungibleTokenId, T::FungibleTokenId)>;
+	pub(super) type LpTokenToToken<T: Config> = StorageMap<
+		_,
+		Blake2_128,
+		T::FungibleTokenId,
+		(T::FungibleTokenId, T::FungibleTokenId, u32),
+	>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn reserves)]
 	pub(super) type Reserves<T: Config> = StorageMap<
 		_,
 		Blake2_128,
-		(T::FungibleTokenId, T::FungibleTokenId),
+		(T::FungibleTokenId, T::FungibleTokenId, u32),
 		(Balance, Balance),
 		ValueQuery,
 	>;
 
 	#[pallet::storage]
-	pub(super) type KLast<T: Config> =
-		StorageMap<_, Blake2_128, (T::FungibleTokenId, T::FungibleTokenId), Balance, ValueQuery>;
+	pub(super) type KLast<T: Config> = StorageMap<
+		_,
+		Blake2_128,
+		(T::FungibleTokenId, T::FungibleTokenId, u32),
+		Balance,
+		ValueQuery,
+	>;
 
 	#[pallet::storage]
 	pub(super) type FeeTo<T: Config> = StorageValue<_, T::AccountId>;
 
+	/// The fees in basis points that new pools may be created with.
+	#[pallet::storage]
+	pub(super) type FeeTiers<T: Config> = StorageMap<_, Blake2_128Concat, u32, (), OptionQuery>;
+
+	/// The pools in the middle of a flash swap or flash loan, which can only be repaid.
+	#[pallet::storage]
+	pub(super) type FlashLocks<T: Config> =
+		StorageMap<_, Blake2_128, (T::FungibleTokenId, T::FungibleTokenId, u32), (), OptionQuery>;
+
+	/// The cumulative prices of each pool, as of the block it was last updated.
+	#[pallet::storage]
+	#[pallet::getter(fn price_cumulatives)]
+	pub(super) type PriceCumulatives<T: Config> = StorageMap<
+		_,
+		Blake2_128,
+		(T::FungibleTokenId, T::FungibleTokenId, u32),
+		Observation<T::BlockNumber>,
+		ValueQuery,
+	>;
+
+	/// The ring buffer of the cumulative prices of each pool, at most one per
+	/// `ObservationPeriod`.
+	#[pallet::storage]
+	pub(super) type Observations<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128,
+		(T::FungibleTokenId, T::FungibleTokenId, u32),
+		Twox64Concat,
+		u32,
+		Observation<T::BlockNumber>,
+	>;
+
+	/// The next slot of the observation ring buffer of each pool.
+	#[pallet::storage]
+	pub(super) type ObservationIndex<T: Config> =
+		StorageMap<_, Blake2_128, (T::FungibleTokenId, T::FungibleTokenId, u32), u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn stable_pools)]
+	pub(super) type StablePools<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::PoolId,
+		StablePool<T::AccountId, T::FungibleTokenId, T::BlockNumber>,
+	>;
+
+	/// The balances of each StableSwap pool, in the order of its tokens.
+	#[pallet::storage]
+	#[pallet::getter(fn stable_reserves)]
+	pub(super) type StableReserves<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::PoolId,
+		BoundedVec<Balance, ConstU32<MAX_STABLE_TOKENS>>,
+		ValueQuery,
+	>;
+
+	/// The status of each constant product pool which is not active.
+	#[pallet::storage]
+	#[pallet::getter(fn pool_status)]
+	pub(super) type PoolStatuses<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(T::FungibleTokenId, T::FungibleTokenId, u32),
+		PoolStatus,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn weighted_pools)]
+	pub(super) type WeightedPools<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::PoolId, WeightedPool<T::AccountId, T::FungibleTokenId>>;
+
+	/// The balances of each weighted pool, in the order of its tokens.
+	#[pallet::storage]
+	#[pallet::getter(fn weighted_reserves)]
+	pub(super) type WeightedReserves<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::PoolId,
+		BoundedVec<Balance, ConstU32<MAX_WEIGHTED_TOKENS>>,
+		ValueQuery,
+	>;
+
+	/// The weighted pool of each weighted pool LP token.
+	#[pallet::storage]
+	#[pallet::getter(fn weighted_lp_token_to_pool)]
+	pub(super) type WeightedLpTokenToPool<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::FungibleTokenId, T::PoolId>;
+
+	/// The status of each weighted pool which is not active.
+	#[pallet::storage]
+	#[pallet::getter(fn weighted_pool_status)]
+	pub(super) type WeightedPoolStatuses<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::PoolId, PoolStatus, ValueQuery>;
+
+	/// The status of each StableSwap pool which is not active.
+	#[pallet::storage]
+	#[pallet::getter(fn stable_pool_status)]
+	pub(super) type StablePoolStatuses<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::PoolId, PoolStatus, ValueQuery>;
+
+	/// The creator of each pool and the deposit reserved from them.
+	#[pallet::storage]
+	#[pallet::getter(fn pool_deposit)]
+	pub(super) type PoolDeposits<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::PoolId, (T::AccountId, BalanceOf<T>)>;
+
+	/// The StableSwap pool of each StableSwap LP token.
+	#[pallet::storage]
+	#[pallet::getter(fn stable_lp_token_to_pool)]
+	pub(super) type StableLpTokenToPool<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::FungibleTokenId, T::PoolId>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_order_id)]
+	pub(super) type NextOrderId<T: Config> = StorageValue<_, T::OrderId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn orders)]
+	pub(super) type Orders<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::OrderId,
+		LimitOrder<T::AccountId, T::FungibleTokenId, T::BlockNumber>,
+	>;
+
+	/// The last order checked by `on_idle`, which resumes after it in the next block.
+	#[pallet::storage]
+	pub(super) type OrderCursor<T: Config> = StorageValue<_, T::OrderId>;
+
+	#[pallet::type_value]
+	pub fn DefaultProtocolFeeShare() -> Permill {
+		Permill::from_rational(1u32, 6u32)
+	}
+
+	/// The share of the swap fees minted to `FeeTo` as liquidity.
+	#[pallet::storage]
+	#[pallet::getter(fn protocol_fee_share)]
+	pub(super) type ProtocolFeeShare<T: Config> =
+		StorageValue<_, Permill, ValueQuery, DefaultProtocolFeeShare>;
+
+	#[pallet::genesis_config]
+	#[derive(Default)]
+	pub struct GenesisConfig {
+		/// The fee tiers in basis points enabled at genesis.
+		pub fee_tiers: Vec<u32>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			for fee in &self.fee_tiers {
+				assert!(*fee < FEE_DENOMINATOR, "fee tier out of range");
+				FeeTiers::<T>::insert(fee, ());
+			}
+		}
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		PoolCreated(T::PoolId, T::FungibleTokenId, T::FungibleTokenId, T::AccountId),
+		PoolCreated(T::PoolId, T::FungibleTokenId, T::FungibleTokenId, u32, T::AccountId),
 		LiquidityAdded(T::FungibleTokenId, Balance, Balance, Balance),
 		LiquidityRemoved(T::FungibleTokenId, Balance, Balance, Balance),
 		Swap(T::AccountId, Balance, Balance, Balance, Balance, T::AccountId),
@@ -148,6 +617,78 @@ pub mod pallet {
 		Burn(T::AccountId, Balance, Balance, T::AccountId),
 		Mint(T::AccountId, Balance, Balance, T::AccountId),
 		Sync(Balance, Balance),
+		/// A fee tier was enabled or disabled. \[fee, enabled\]
+		FeeTierSet(u32, bool),
+		ProtocolFeeShareSet(Permill),
+		/// \[who, token_a, token_b, fee, amount_a_out, amount_b_out\]
+		FlashSwap(T::AccountId, T::FungibleTokenId, T::FungibleTokenId, u32, Balance, Balance),
+		/// \[who, token, pair_token, fee, amount, premium\]
+		FlashLoan(T::AccountId, T::FungibleTokenId, T::FungibleTokenId, u32, Balance, Balance),
+		/// \[pool_id, tokens, amplification, fee, who\]
+		StablePoolCreated(T::PoolId, Vec<T::FungibleTokenId>, u128, u32, T::AccountId),
+		/// \[pool_id, who, amounts, liquidity\]
+		StableLiquidityAdded(T::PoolId, T::AccountId, Vec<Balance>, Balance),
+		/// \[pool_id, who, amounts, liquidity\]
+		StableLiquidityRemoved(T::PoolId, T::AccountId, Vec<Balance>, Balance),
+		/// \[pool_id, who, token_in, token_out, amount_in, amount_out, to\]
+		StableSwap(
+			T::PoolId,
+			T::AccountId,
+			T::FungibleTokenId,
+			T::FungibleTokenId,
+			Balance,
+			Balance,
+			T::AccountId,
+		),
+		/// The amplification coefficients are times `A_PRECISION`.
+		/// \[pool_id, initial_a, future_a, future_a_block\]
+		AmplificationRamped(T::PoolId, u128, u128, T::BlockNumber),
+		/// \[pool_id, amplification\]
+		AmplificationRampStopped(T::PoolId, u128),
+		/// \[order_id, who, token_in, token_out, amount_in, price, expiry\]
+		OrderPlaced(
+			T::OrderId,
+			T::AccountId,
+			T::FungibleTokenId,
+			T::FungibleTokenId,
+			Balance,
+			FixedU128,
+			T::BlockNumber,
+		),
+		/// \[order_id, amount_in, amount_out, remaining\]
+		OrderFilled(T::OrderId, Balance, Balance, Balance),
+		/// \[order_id, who, refunded\]
+		OrderCancelled(T::OrderId, T::AccountId, Balance),
+		/// \[order_id, who, refunded\]
+		OrderExpired(T::OrderId, T::AccountId, Balance),
+		/// \[who, token_in, amount_in, lp_token, liquidity\]
+		ZappedIn(T::AccountId, T::FungibleTokenId, Balance, T::FungibleTokenId, Balance),
+		/// \[who, lp_token, liquidity, token_out, amount_out\]
+		ZappedOut(T::AccountId, T::FungibleTokenId, Balance, T::FungibleTokenId, Balance),
+		/// \[pool_id, who, deposit\]
+		PoolDepositReserved(T::PoolId, T::AccountId, BalanceOf<T>),
+		/// \[token_0, token_1, fee, status\]
+		PoolStatusChanged(T::FungibleTokenId, T::FungibleTokenId, u32, PoolStatus),
+		/// \[pool_id, status\]
+		StablePoolStatusChanged(T::PoolId, PoolStatus),
+		/// \[pool_id, tokens, weights, fee, who\]
+		WeightedPoolCreated(T::PoolId, Vec<T::FungibleTokenId>, Vec<Permill>, u32, T::AccountId),
+		/// \[pool_id, who, amounts, liquidity\]
+		WeightedLiquidityAdded(T::PoolId, T::AccountId, Vec<Balance>, Balance),
+		/// \[pool_id, who, amounts, liquidity\]
+		WeightedLiquidityRemoved(T::PoolId, T::AccountId, Vec<Balance>, Balance),
+		/// \[pool_id, who, token_in, token_out, amount_in, amount_out, to\]
+		WeightedSwap(
+			T::PoolId,
+			T::AccountId,
+			T::FungibleTokenId,
+			T::FungibleTokenId,
+			Balance,
+			Balance,
+			T::AccountId,
+		),
+		/// \[pool_id, status\]
+		WeightedPoolStatusChanged(T::PoolId, PoolStatus),
 	}
 
 	#[pallet::error]
@@ -155,10 +696,13 @@ pub mod pallet {
 		Overflow,
 		PoolNotFound,
 		NoAvailablePoolId,
+		NoAvailableLpTokenId,
+		PoolPaused,
 		InsufficientAmount,
 		InsufficientOutAmount,
 		InsufficientInputAmount,
 		InsufficientOutputAmount,
+		ExcessiveInputAmount,
 		InsufficientLiquidity,
 		AdjustedError,
 		InsufficientAAmount,
@@ -170,6 +714,43 @@ pub mod pallet {
 		PoolAlreadyCreated,
 		TokenRepeat,
 		Deadline,
+		FeeTierNotFound,
+		InvalidFeeTier,
+		PoolLocked,
+		PoolNotLocked,
+		FlashCallbackFailed,
+		FlashLoanNotRepaid,
+		InvalidWindow,
+		ObservationNotFound,
+		StablePoolNotFound,
+		InvalidStableTokens,
+		InvalidAmplification,
+		InvalidRamp,
+		InvalidAmounts,
+		TokenNotInPool,
+		StableMathFailed,
+		WeightedPoolNotFound,
+		InvalidWeightedTokens,
+		InvalidWeights,
+		WeightedRatioExceeded,
+		WeightedMathFailed,
+		RouteNotFound,
+		NoAvailableOrderId,
+		OrderNotFound,
+		NotOrderOwner,
+		InvalidOrder,
+		OrderNotFillable,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate::<T>()
+		}
+
+		fn on_idle(_n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			Self::match_orders(remaining_weight)
+		}
 	}
 
 	#[pallet::call]
@@ -180,19 +761,21 @@ pub mod pallet {
 			origin: OriginFor<T>,
 			token_a: T::FungibleTokenId,
 			token_b: T::FungibleTokenId,
+			fee: u32,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
 			ensure!(token_a != token_b, Error::<T>::TokenRepeat);
+			ensure!(FeeTiers::<T>::contains_key(fee), Error::<T>::FeeTierNotFound);
 			ensure!(
 				pallet_token_fungible::Pallet::<T>::exists(token_a) &&
 					pallet_token_fungible::Pallet::<T>::exists(token_b),
 				Error::<T>::TokenAccountNotFound,
 			);
 
-			ensure!(!Self::exists(token_a, token_b), Error::<T>::PoolAlreadyCreated);
+			ensure!(!Self::exists(token_a, token_b, fee), Error::<T>::PoolAlreadyCreated);
 
-			Self::do_create_pool(who, token_a, token_b)?;
+			Self::do_create_pool(who, token_a, token_b, fee)?;
 
 			Ok(())
 		}
@@ -203,6 +786,7 @@ pub mod pallet {
 			origin: OriginFor<T>,
 			token_a: T::FungibleTokenId,
 			token_b: T::FungibleTokenId,
+			fee: u32,
 			#[pallet::compact] amount_a_desired: Balance,
 			#[pallet::compact] amount_b_desired: Balance,
 			#[pallet::compact] amount_a_min: Balance,
@@ -230,11 +814,12 @@ pub mod pallet {
 				amount_1_min = amount_a_min;
 			}
 
-			let pool = Pools::<T>::get((token_0, token_1)).ok_or(Error::<T>::PoolNotFound)?;
+			let pool = Pools::<T>::get((token_0, token_1), fee).ok_or(Error::<T>::PoolNotFound)?;
 
 			let (amount_a, amount_b) = Self::do_add_liquidity(
 				token_0,
 				token_1,
+				fee,
 				amount_0,
 				amount_1,
 				amount_0_min,
@@ -253,7 +838,7 @@ pub mod pallet {
 				&pool.lp_token_account_id,
 				amount_b,
 			)?;
-			let liquidity = Self::mint(who, token_0, token_1, to)?;
+			let liquidity = Self::mint(who, token_0, token_1, fee, to)?;
 			//
 			Self::deposit_event(Event::LiquidityAdded(
 				pool.lp_token,
@@ -270,6 +855,7 @@ pub mod pallet {
 		pub fn add_liquidity_w3g(
 			origin: OriginFor<T>,
 			token: T::FungibleTokenId,
+			fee: u32,
 			#[pallet::compact] amount_w3g_desired: Balance,
 			#[pallet::compact] amount_desired: Balance,
 			#[pallet::compact] amount_w3g_min: Balance,
@@ -280,21 +866,23 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 			ensure!(deadline > Self::now(), Error::<T>::Deadline);
 
-			pallet_wrap_currency::Pallet::<T>::do_deposit(who.clone(), amount_w3g_desired)?;
-
 			let (token_0, token_1) = Self::sort_tokens(T::WW3G::get(), token);
 
-			let pool = Pools::<T>::get((token_0, token_1)).ok_or(Error::<T>::PoolNotFound)?;
+			let pool = Pools::<T>::get((token_0, token_1), fee).ok_or(Error::<T>::PoolNotFound)?;
 
 			let (amount_a, amount_b) = Self::do_add_liquidity(
 				T::WW3G::get(),
 				token,
+				fee,
 				amount_w3g_desired,
 				amount_desired,
 				amount_w3g_min,
 				amount_min,
 			)?;
 
+			// only the native currency taken into the pool is wrapped
+			pallet_wrap_currency::Pallet::<T>::do_deposit(who.clone(), amount_a)?;
+
 			pallet_token_fungible::Pallet::<T>::do_transfer(
 				pool.token_0,
 				&who,
@@ -307,7 +895,7 @@ pub mod pallet {
 				&pool.lp_token_account_id,
 				amount_b,
 			)?;
-			let liquidity = Self::mint(who, token_0, token_1, to)?;
+			let liquidity = Self::mint(who, token_0, token_1, fee, to)?;
 			//
 			Self::deposit_event(Event::LiquidityAdded(
 				pool.lp_token,
@@ -325,6 +913,7 @@ pub mod pallet {
 			origin: OriginFor<T>,
 			token_a: T::FungibleTokenId,
 			token_b: T::FungibleTokenId,
+			fee: u32,
 			#[pallet::compact] liquidity: Balance,
 			#[pallet::compact] amount_a_min: Balance,
 			#[pallet::compact] amount_b_min: Balance,
@@ -335,7 +924,7 @@ pub mod pallet {
 			ensure!(deadline > Self::now(), Error::<T>::Deadline);
 
 			let (token_0, token_1) = Self::sort_tokens(token_a, token_b);
-			let pool = Pools::<T>::get((token_0, token_1)).ok_or(Error::<T>::PoolNotFound)?;
+			let pool = Pools::<T>::get((token_0, token_1), fee).ok_or(Error::<T>::PoolNotFound)?;
 
 			// return Lp to Pallet
 			pallet_token_fungible::Pallet::<T>::do_transfer(
@@ -345,7 +934,7 @@ pub mod pallet {
 				liquidity,
 			)?;
 
-			let (amount_0, amount_1) = Self::burn(who, token_0, token_1, to)?;
+			let (amount_0, amount_1) = Self::burn(who, token_0, token_1, fee, to)?;
 
 			ensure!(amount_0 >= amount_a_min, Error::<T>::InsufficientAAmount);
 			ensure!(amount_1 >= amount_b_min, Error::<T>::InsufficientBAmount);
@@ -364,6 +953,7 @@ pub mod pallet {
 		pub fn remove_liquidity_w3g(
 			origin: OriginFor<T>,
 			token: T::FungibleTokenId,
+			fee: u32,
 			#[pallet::compact] liquidity: Balance,
 			#[pallet::compact] amount_w3g_min: Balance,
 			#[pallet::compact] amount_min: Balance,
@@ -374,7 +964,7 @@ pub mod pallet {
 			ensure!(deadline > Self::now(), Error::<T>::Deadline);
 
 			let (token_0, token_1) = Self::sort_tokens(T::WW3G::get(), token);
-			let pool = Pools::<T>::get((token_0, token_1)).ok_or(Error::<T>::PoolNotFound)?;
+			let pool = Pools::<T>::get((token_0, token_1), fee).ok_or(Error::<T>::PoolNotFound)?;
 
 			// return Lp to Pallet
 			pallet_token_fungible::Pallet::<T>::do_transfer(
@@ -384,11 +974,14 @@ pub mod pallet {
 				liquidity,
 			)?;
 
-			let (amount_0, amount_1) = Self::burn(who.clone(), token_0, token_1, to)?;
+			let (amount_0, amount_1) = Self::burn(who.clone(), token_0, token_1, fee, who.clone())?;
 			ensure!(amount_0 >= amount_w3g_min, Error::<T>::InsufficientAAmount);
 			ensure!(amount_1 >= amount_min, Error::<T>::InsufficientBAmount);
 
-			pallet_wrap_currency::Pallet::<T>::do_withdraw(who.clone(), amount_0)?;
+			Self::unwrap_to(&who, &to, amount_0)?;
+			if to != who {
+				pallet_token_fungible::Pallet::<T>::do_transfer(token_1, &who, &to, amount_1)?;
+			}
 
 			Self::deposit_event(Event::LiquidityRemoved(
 				pool.lp_token,
@@ -413,10 +1006,8 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 			ensure!(deadline > Self::now(), Error::<T>::Deadline);
 
-			let (token_0, token_1) = Self::sort_tokens(path[0], path[1]);
-			let pool = Pools::<T>::get((token_0, token_1)).ok_or(Error::<T>::PoolNotFound)?;
-
-			let amounts = Self::get_amounts_out(amount_in, path.clone())?;
+			let (amounts, fees) = Self::get_route_out(amount_in, path.clone())?;
+			let pool = Self::get_pool(path[0], path[1], fees[0])?;
 
 			ensure!(
 				amounts[amounts.len() - 1] >= amount_out_min,
@@ -429,12 +1020,12 @@ pub mod pallet {
 				&pool.lp_token_account_id,
 				amounts[0],
 			)?;
-			Self::do_swap(who, amounts, path, to)?;
+			Self::do_swap(who, amounts, fees, path, to)?;
 
 			Ok(())
 		}
 
-		#[pallet::weight(<T as pallet::Config>::WeightInfo::swap_exact_tokens_for_tokens())]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::swap_exact_w3g_for_tokens())]
 		#[transactional]
 		pub fn swap_exact_w3g_for_tokens(
 			origin: OriginFor<T>,
@@ -444,14 +1035,12 @@ pub mod pallet {
 			to: T::AccountId,
 			#[pallet::compact] deadline: T::BlockNumber,
 		) -> DispatchResult {
-			let who = ensure_signed(origin.clone())?;
-			ensure!(path[0] == T::WW3G::get(), Error::<T>::InvalidPath);
+			let who = ensure_signed(origin)?;
+			ensure!(path.len() >= 2 && path[0] == T::WW3G::get(), Error::<T>::InvalidPath);
 			ensure!(deadline > Self::now(), Error::<T>::Deadline);
 
-			let (token_0, token_1) = Self::sort_tokens(path[0], path[1]);
-			let pool = Pools::<T>::get((token_0, token_1)).ok_or(Error::<T>::PoolNotFound)?;
-
-			let amounts = Self::get_amounts_out(amount_in_w3g, path.clone())?;
+			let (amounts, fees) = Self::get_route_out(amount_in_w3g, path.clone())?;
+			let pool = Self::get_pool(path[0], path[1], fees[0])?;
 
 			ensure!(
 				amounts[amounts.len() - 1] >= amount_out_min,
@@ -466,7 +1055,7 @@ pub mod pallet {
 				&pool.lp_token_account_id,
 				amounts[0],
 			)?;
-			Self::do_swap(who, amounts, path, to)?;
+			Self::do_swap(who, amounts, fees, path, to)?;
 
 			Ok(())
 		}
@@ -484,10 +1073,9 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 			ensure!(deadline > Self::now(), Error::<T>::Deadline);
 
-			let (token_0, token_1) = Self::sort_tokens(path[0], path[1]);
-			let pool = Pools::<T>::get((token_0, token_1)).ok_or(Error::<T>::PoolNotFound)?;
+			let (amounts, fees) = Self::get_route_in(amount_out, path.clone())?;
+			let pool = Self::get_pool(path[0], path[1], fees[0])?;
 
-			let amounts = Self::get_amounts_in(amount_out, path.clone())?;
 			ensure!(amounts[0] <= amount_in_max, Error::<T>::InsufficientInputAmount);
 
 			pallet_token_fungible::Pallet::<T>::do_transfer(
@@ -496,12 +1084,12 @@ pub mod pallet {
 				&pool.lp_token_account_id,
 				amounts[0],
 			)?;
-			Self::do_swap(who, amounts, path, to)?;
+			Self::do_swap(who, amounts, fees, path, to)?;
 
 			Ok(())
 		}
 
-		#[pallet::weight(<T as pallet::Config>::WeightInfo::swap_tokens_for_exact_tokens())]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::swap_tokens_for_exact_w3g())]
 		#[transactional]
 		pub fn swap_tokens_for_exact_w3g(
 			origin: OriginFor<T>,
@@ -511,14 +1099,16 @@ pub mod pallet {
 			to: T::AccountId,
 			#[pallet::compact] deadline: T::BlockNumber,
 		) -> DispatchResult {
-			let who = ensure_signed(origin.clone())?;
-			ensure!(path[path.len() - 1] == T::WW3G::get(), Error::<T>::InvalidPath);
+			let who = ensure_signed(origin)?;
+			ensure!(
+				path.len() >= 2 && path[path.len() - 1] == T::WW3G::get(),
+				Error::<T>::InvalidPath
+			);
 			ensure!(deadline > Self::now(), Error::<T>::Deadline);
 
-			let (token_0, token_1) = Self::sort_tokens(path[0], path[1]);
-			let pool = Pools::<T>::get((token_0, token_1)).ok_or(Error::<T>::PoolNotFound)?;
+			let (amounts, fees) = Self::get_route_in(amount_out_w3g, path.clone())?;
+			let pool = Self::get_pool(path[0], path[1], fees[0])?;
 
-			let amounts = Self::get_amounts_in(amount_out_w3g, path.clone())?;
 			ensure!(amounts[0] <= amount_in_max, Error::<T>::InsufficientInputAmount);
 
 			pallet_token_fungible::Pallet::<T>::do_transfer(
@@ -527,9 +1117,80 @@ pub mod pallet {
 				&pool.lp_token_account_id,
 				amounts[0],
 			)?;
-			Self::do_swap(who, amounts.clone(), path, to.clone())?;
+			Self::do_swap(who.clone(), amounts.clone(), fees, path, who.clone())?;
+
+			Self::unwrap_to(&who, &to, amounts[amounts.len() - 1])?;
+
+			Ok(())
+		}
+
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::swap_exact_tokens_for_w3g())]
+		#[transactional]
+		pub fn swap_exact_tokens_for_w3g(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount_in: Balance,
+			#[pallet::compact] amount_out_w3g_min: Balance,
+			path: Vec<T::FungibleTokenId>,
+			to: T::AccountId,
+			#[pallet::compact] deadline: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				path.len() >= 2 && path[path.len() - 1] == T::WW3G::get(),
+				Error::<T>::InvalidPath
+			);
+			ensure!(deadline > Self::now(), Error::<T>::Deadline);
+
+			let (amounts, fees) = Self::get_route_out(amount_in, path.clone())?;
+			let pool = Self::get_pool(path[0], path[1], fees[0])?;
+
+			ensure!(
+				amounts[amounts.len() - 1] >= amount_out_w3g_min,
+				Error::<T>::InsufficientOutAmount
+			);
+
+			pallet_token_fungible::Pallet::<T>::do_transfer(
+				path[0],
+				&who,
+				&pool.lp_token_account_id,
+				amounts[0],
+			)?;
+			Self::do_swap(who.clone(), amounts.clone(), fees, path, who.clone())?;
+
+			Self::unwrap_to(&who, &to, amounts[amounts.len() - 1])?;
+
+			Ok(())
+		}
+
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::swap_w3g_for_exact_tokens())]
+		#[transactional]
+		pub fn swap_w3g_for_exact_tokens(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount_out: Balance,
+			#[pallet::compact] amount_in_w3g_max: Balance,
+			path: Vec<T::FungibleTokenId>,
+			to: T::AccountId,
+			#[pallet::compact] deadline: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(path.len() >= 2 && path[0] == T::WW3G::get(), Error::<T>::InvalidPath);
+			ensure!(deadline > Self::now(), Error::<T>::Deadline);
+
+			let (amounts, fees) = Self::get_route_in(amount_out, path.clone())?;
+			let pool = Self::get_pool(path[0], path[1], fees[0])?;
 
-			pallet_wrap_currency::Pallet::<T>::do_withdraw(to, amounts[amounts.len() - 1])?;
+			ensure!(amounts[0] <= amount_in_w3g_max, Error::<T>::InsufficientInputAmount);
+
+			// only the native currency spent is wrapped, the rest of the maximum stays unwrapped
+			pallet_wrap_currency::Pallet::<T>::do_deposit(who.clone(), amounts[0])?;
+
+			pallet_token_fungible::Pallet::<T>::do_transfer(
+				path[0],
+				&who,
+				&pool.lp_token_account_id,
+				amounts[0],
+			)?;
+			Self::do_swap(who, amounts, fees, path, to)?;
 
 			Ok(())
 		}
@@ -543,652 +1204,3224 @@ pub mod pallet {
 				Ok(())
 			})
 		}
-	}
-}
 
-impl<T: Config> Pallet<T> {
-	// The account ID of the vault
-	fn account_id() -> T::AccountId {
-		<T as Config>::PalletId::get().into_account_truncating()
-	}
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_fee_tier())]
+		pub fn set_fee_tier(origin: OriginFor<T>, fee: u32, enabled: bool) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(fee < FEE_DENOMINATOR, Error::<T>::InvalidFeeTier);
 
-	pub fn token_id_to_account_id(token: T::FungibleTokenId) -> T::AccountId {
-		<T as Config>::PalletId::get().into_sub_account_truncating(token)
-	}
+			if enabled {
+				FeeTiers::<T>::insert(fee, ());
+			} else {
+				FeeTiers::<T>::remove(fee);
+			}
 
-	fn now() -> T::BlockNumber {
-		frame_system::Pallet::<T>::block_number()
-	}
+			Self::deposit_event(Event::FeeTierSet(fee, enabled));
+			Ok(())
+		}
 
-	pub fn exists(token_a: T::FungibleTokenId, token_b: T::FungibleTokenId) -> bool {
-		let (token_0, token_1) = Self::sort_tokens(token_a, token_b);
-		Pools::<T>::get((token_0, token_1)).is_some()
-	}
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_protocol_fee_share())]
+		pub fn set_protocol_fee_share(origin: OriginFor<T>, share: Permill) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
 
-	pub fn do_create_pool(
-		who: T::AccountId,
-		token_a: T::FungibleTokenId,
-		token_b: T::FungibleTokenId,
-	) -> Result<T::PoolId, DispatchError> {
-		let id = NextPoolId::<T>::try_mutate(|id| -> Result<T::PoolId, DispatchError> {
-			let current_id = *id;
-			*id = id.checked_add(&One::one()).ok_or(Error::<T>::NoAvailablePoolId)?;
-			Ok(current_id)
-		})?;
-		// Creating a pool requires payment
-		let deposit = T::CreatePoolDeposit::get();
-		<T as Config>::Currency::transfer(&who, &Self::account_id(), deposit, AllowDeath)?;
+			ProtocolFeeShare::<T>::put(share);
 
-		let (token_0, token_1) = Self::sort_tokens(token_a, token_b);
+			Self::deposit_event(Event::ProtocolFeeShareSet(share));
+			Ok(())
+		}
 
-		let lp_token = Self::generate_lp_token_id(token_0, token_1);
-		let lp_token_account_id = Self::token_id_to_account_id(lp_token);
-		let name: Vec<u8> = "LP Token".as_bytes().to_vec();
-		let symbol: Vec<u8> = "LPV1".as_bytes().to_vec();
+		/// Pause swaps or liquidity changes on a pool, put it in emergency mode where liquidity
+		/// can only be removed, or make it active again.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_pool_status())]
+		pub fn set_pool_status(
+			origin: OriginFor<T>,
+			token_a: T::FungibleTokenId,
+			token_b: T::FungibleTokenId,
+			fee: u32,
+			status: PoolStatus,
+		) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+			let (token_0, token_1) = Self::sort_tokens(token_a, token_b);
+			ensure!(Self::exists(token_0, token_1, fee), Error::<T>::PoolNotFound);
 
-		pallet_token_fungible::Pallet::<T>::do_create_token(
-			&Self::account_id(),
-			lp_token,
-			name,
-			symbol,
-			18,
+			if status == PoolStatus::Active {
+				PoolStatuses::<T>::remove((token_0, token_1, fee));
+			} else {
+				PoolStatuses::<T>::insert((token_0, token_1, fee), status);
+			}
+
+			Self::deposit_event(Event::PoolStatusChanged(token_0, token_1, fee, status));
+			Ok(())
+		}
+
+		/// Pause swaps or liquidity changes on a StableSwap pool, put it in emergency mode
+		/// where liquidity can only be removed, or make it active again.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_stable_pool_status())]
+		pub fn set_stable_pool_status(
+			origin: OriginFor<T>,
+			pool_id: T::PoolId,
+			status: PoolStatus,
+		) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+			ensure!(StablePools::<T>::contains_key(pool_id), Error::<T>::StablePoolNotFound);
+
+			if status == PoolStatus::Active {
+				StablePoolStatuses::<T>::remove(pool_id);
+			} else {
+				StablePoolStatuses::<T>::insert(pool_id, status);
+			}
+
+			Self::deposit_event(Event::StablePoolStatusChanged(pool_id, status));
+			Ok(())
+		}
+
+		/// Borrow the given amounts of both tokens of a pool. The borrower's flash callback
+		/// must repay the pool so that its k invariant holds, fees included.
+		#[pallet::weight(
+			<T as pallet::Config>::WeightInfo::flash_swap()
+				.saturating_add(T::MaxFlashCallbackWeight::get())
+		)]
+		#[transactional]
+		pub fn flash_swap(
+			origin: OriginFor<T>,
+			token_a: T::FungibleTokenId,
+			token_b: T::FungibleTokenId,
+			fee: u32,
+			#[pallet::compact] amount_a_out: Balance,
+			#[pallet::compact] amount_b_out: Balance,
+			data: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::do_flash_swap(who, token_a, token_b, fee, amount_a_out, amount_b_out, &data)
+		}
+
+		/// Borrow `amount` of `token` from its pool with `pair_token`. The borrower's flash
+		/// callback must repay it plus a premium of the pool fee.
+		#[pallet::weight(
+			<T as pallet::Config>::WeightInfo::flash_loan()
+				.saturating_add(T::MaxFlashCallbackWeight::get())
+		)]
+		#[transactional]
+		pub fn flash_loan(
+			origin: OriginFor<T>,
+			token: T::FungibleTokenId,
+			pair_token: T::FungibleTokenId,
+			fee: u32,
+			#[pallet::compact] amount: Balance,
+			data: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::do_flash_loan(who, token, pair_token, fee, amount, &data)
+		}
+
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::create_stable_pool())]
+		#[transactional]
+		pub fn create_stable_pool(
+			origin: OriginFor<T>,
+			tokens: Vec<T::FungibleTokenId>,
+			amplification: u128,
+			fee: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::do_create_stable_pool(who, tokens, amplification, fee)?;
+
+			Ok(())
+		}
+
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::add_stable_liquidity())]
+		#[transactional]
+		pub fn add_stable_liquidity(
+			origin: OriginFor<T>,
+			pool_id: T::PoolId,
+			amounts: Vec<Balance>,
+			#[pallet::compact] min_liquidity: Balance,
+			to: T::AccountId,
+			#[pallet::compact] deadline: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(deadline > Self::now(), Error::<T>::Deadline);
+
+			let liquidity = Self::do_add_stable_liquidity(who, pool_id, amounts, to)?;
+			ensure!(liquidity >= min_liquidity, Error::<T>::InsufficientLiquidityMinted);
+
+			Ok(())
+		}
+
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::remove_stable_liquidity())]
+		#[transactional]
+		pub fn remove_stable_liquidity(
+			origin: OriginFor<T>,
+			pool_id: T::PoolId,
+			#[pallet::compact] liquidity: Balance,
+			min_amounts: Vec<Balance>,
+			to: T::AccountId,
+			#[pallet::compact] deadline: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(deadline > Self::now(), Error::<T>::Deadline);
+
+			let amounts = Self::do_remove_stable_liquidity(who, pool_id, liquidity, to)?;
+			ensure!(min_amounts.len() == amounts.len(), Error::<T>::InvalidAmounts);
+			ensure!(
+				amounts.iter().zip(min_amounts.iter()).all(|(amount, min)| amount >= min),
+				Error::<T>::InsufficientAmount
+			);
+
+			Ok(())
+		}
+
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::swap_stable())]
+		#[transactional]
+		pub fn swap_stable(
+			origin: OriginFor<T>,
+			pool_id: T::PoolId,
+			token_in: T::FungibleTokenId,
+			token_out: T::FungibleTokenId,
+			#[pallet::compact] amount_in: Balance,
+			#[pallet::compact] amount_out_min: Balance,
+			to: T::AccountId,
+			#[pallet::compact] deadline: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(deadline > Self::now(), Error::<T>::Deadline);
+
+			let amount_out =
+				Self::do_swap_stable(who, pool_id, token_in, token_out, amount_in, to)?;
+			ensure!(amount_out >= amount_out_min, Error::<T>::InsufficientOutAmount);
+
+			Ok(())
+		}
+
+		/// Ramp the amplification coefficient of a StableSwap pool linearly until
+		/// `future_block`, by a factor of at most `MAX_A_CHANGE`.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::ramp_amplification())]
+		pub fn ramp_amplification(
+			origin: OriginFor<T>,
+			pool_id: T::PoolId,
+			future_amplification: u128,
+			future_block: T::BlockNumber,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			StablePools::<T>::try_mutate(pool_id, |maybe_pool| -> DispatchResult {
+				let pool = maybe_pool.as_mut().ok_or(Error::<T>::StablePoolNotFound)?;
+				let now = Self::now();
+				ensure!(future_block > now, Error::<T>::InvalidRamp);
+				ensure!(
+					future_amplification > 0 && future_amplification <= MAX_A,
+					Error::<T>::InvalidAmplification
+				);
+
+				let initial_a = Self::stable_amplification(pool);
+				let future_a = future_amplification * A_PRECISION;
+				ensure!(
+					future_a <= initial_a.saturating_mul(MAX_A_CHANGE) &&
+						future_a.saturating_mul(MAX_A_CHANGE) >= initial_a,
+					Error::<T>::InvalidRamp
+				);
+
+				pool.initial_a = initial_a;
+				pool.future_a = future_a;
+				pool.initial_a_block = now;
+				pool.future_a_block = future_block;
+
+				Self::deposit_event(Event::AmplificationRamped(
+					pool_id,
+					initial_a,
+					future_a,
+					future_block,
+				));
+				Ok(())
+			})
+		}
+
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::stop_ramp_amplification())]
+		pub fn stop_ramp_amplification(origin: OriginFor<T>, pool_id: T::PoolId) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			StablePools::<T>::try_mutate(pool_id, |maybe_pool| -> DispatchResult {
+				let pool = maybe_pool.as_mut().ok_or(Error::<T>::StablePoolNotFound)?;
+				let now = Self::now();
+				let amplification = Self::stable_amplification(pool);
+
+				pool.initial_a = amplification;
+				pool.future_a = amplification;
+				pool.initial_a_block = now;
+				pool.future_a_block = now;
+
+				Self::deposit_event(Event::AmplificationRampStopped(pool_id, amplification));
+				Ok(())
+			})
+		}
+
+		/// Create a weighted pool of `tokens`, each making up its share in `weights` of the
+		/// value of the pool, funded with `amounts` of them.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::create_weighted_pool())]
+		#[transactional]
+		pub fn create_weighted_pool(
+			origin: OriginFor<T>,
+			tokens: Vec<T::FungibleTokenId>,
+			weights: Vec<Permill>,
+			fee: u32,
+			amounts: Vec<Balance>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::do_create_weighted_pool(who, tokens, weights, fee, amounts)?;
+
+			Ok(())
+		}
+
+		/// Mint `liquidity` for depositing each token of a weighted pool in proportion to its
+		/// balances, taking at most `max_amounts` of them.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::add_weighted_liquidity())]
+		#[transactional]
+		pub fn add_weighted_liquidity(
+			origin: OriginFor<T>,
+			pool_id: T::PoolId,
+			#[pallet::compact] liquidity: Balance,
+			max_amounts: Vec<Balance>,
+			to: T::AccountId,
+			#[pallet::compact] deadline: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(deadline > Self::now(), Error::<T>::Deadline);
+
+			let amounts = Self::do_add_weighted_liquidity(who, pool_id, liquidity, to)?;
+			ensure!(max_amounts.len() == amounts.len(), Error::<T>::InvalidAmounts);
+			ensure!(
+				amounts.iter().zip(max_amounts.iter()).all(|(amount, max)| amount <= max),
+				Error::<T>::ExcessiveInputAmount
+			);
+
+			Ok(())
+		}
+
+		/// Burn `liquidity` for each token of a weighted pool in proportion to its balances.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::remove_weighted_liquidity())]
+		#[transactional]
+		pub fn remove_weighted_liquidity(
+			origin: OriginFor<T>,
+			pool_id: T::PoolId,
+			#[pallet::compact] liquidity: Balance,
+			min_amounts: Vec<Balance>,
+			to: T::AccountId,
+			#[pallet::compact] deadline: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(deadline > Self::now(), Error::<T>::Deadline);
+
+			let amounts = Self::do_remove_weighted_liquidity(who, pool_id, liquidity, to)?;
+			ensure!(min_amounts.len() == amounts.len(), Error::<T>::InvalidAmounts);
+			ensure!(
+				amounts.iter().zip(min_amounts.iter()).all(|(amount, min)| amount >= min),
+				Error::<T>::InsufficientAmount
+			);
+
+			Ok(())
+		}
+
+		/// Deposit `amount_in` of a single token of a weighted pool.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::add_weighted_liquidity_single())]
+		#[transactional]
+		pub fn add_weighted_liquidity_single(
+			origin: OriginFor<T>,
+			pool_id: T::PoolId,
+			token_in: T::FungibleTokenId,
+			#[pallet::compact] amount_in: Balance,
+			#[pallet::compact] min_liquidity: Balance,
+			to: T::AccountId,
+			#[pallet::compact] deadline: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(deadline > Self::now(), Error::<T>::Deadline);
+
+			let liquidity =
+				Self::do_add_weighted_liquidity_single(who, pool_id, token_in, amount_in, to)?;
+			ensure!(liquidity >= min_liquidity, Error::<T>::InsufficientLiquidityMinted);
+
+			Ok(())
+		}
+
+		/// Burn `liquidity` for a single token of a weighted pool.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::remove_weighted_liquidity_single())]
+		#[transactional]
+		pub fn remove_weighted_liquidity_single(
+			origin: OriginFor<T>,
+			pool_id: T::PoolId,
+			token_out: T::FungibleTokenId,
+			#[pallet::compact] liquidity: Balance,
+			#[pallet::compact] amount_out_min: Balance,
+			to: T::AccountId,
+			#[pallet::compact] deadline: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(deadline > Self::now(), Error::<T>::Deadline);
+
+			let amount_out =
+				Self::do_remove_weighted_liquidity_single(who, pool_id, token_out, liquidity, to)?;
+			ensure!(amount_out >= amount_out_min, Error::<T>::InsufficientOutAmount);
+
+			Ok(())
+		}
+
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::swap_weighted())]
+		#[transactional]
+		pub fn swap_weighted(
+			origin: OriginFor<T>,
+			pool_id: T::PoolId,
+			token_in: T::FungibleTokenId,
+			token_out: T::FungibleTokenId,
+			#[pallet::compact] amount_in: Balance,
+			#[pallet::compact] amount_out_min: Balance,
+			to: T::AccountId,
+			#[pallet::compact] deadline: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(deadline > Self::now(), Error::<T>::Deadline);
+
+			let amount_out =
+				Self::do_swap_weighted(who, pool_id, token_in, token_out, amount_in, to)?;
+			ensure!(amount_out >= amount_out_min, Error::<T>::InsufficientOutAmount);
+
+			Ok(())
+		}
+
+		/// Pause swaps or liquidity changes on a weighted pool, put it in emergency mode where
+		/// liquidity can only be removed, or make it active again.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_weighted_pool_status())]
+		pub fn set_weighted_pool_status(
+			origin: OriginFor<T>,
+			pool_id: T::PoolId,
+			status: PoolStatus,
+		) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+			ensure!(WeightedPools::<T>::contains_key(pool_id), Error::<T>::WeightedPoolNotFound);
+
+			if status == PoolStatus::Active {
+				WeightedPoolStatuses::<T>::remove(pool_id);
+			} else {
+				WeightedPoolStatuses::<T>::insert(pool_id, status);
+			}
+
+			Self::deposit_event(Event::WeightedPoolStatusChanged(pool_id, status));
+			Ok(())
+		}
+
+		/// Escrow `amount_in` of `token_in` to be sold for at least `price` of `token_out`
+		/// per unit until `expiry`.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::place_order())]
+		#[transactional]
+		pub fn place_order(
+			origin: OriginFor<T>,
+			token_in: T::FungibleTokenId,
+			token_out: T::FungibleTokenId,
+			#[pallet::compact] amount_in: Balance,
+			price: FixedU128,
+			expiry: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::do_place_order(who, token_in, token_out, amount_in, price, expiry)?;
+
+			Ok(())
+		}
+
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::cancel_order())]
+		#[transactional]
+		pub fn cancel_order(origin: OriginFor<T>, order_id: T::OrderId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let order = Orders::<T>::get(order_id).ok_or(Error::<T>::OrderNotFound)?;
+			ensure!(order.owner == who, Error::<T>::NotOrderOwner);
+
+			Self::close_order(order_id, order)?;
+			Ok(())
+		}
+
+		/// Fill as much of an order as the pools of its pair allow at its price. Anyone may
+		/// fill orders, and filling an expired order refunds it.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::fill_order())]
+		#[transactional]
+		pub fn fill_order(origin: OriginFor<T>, order_id: T::OrderId) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			Self::do_fill_order(order_id)?;
+
+			Ok(())
+		}
+
+		/// Add liquidity to the pool of `token_a` and `token_b` from `token_in` only, swapping
+		/// the part of `amount_in` which leaves both tokens in the ratio of the pool. What the
+		/// pool does not take stays with the caller.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::zap_in())]
+		#[transactional]
+		pub fn zap_in(
+			origin: OriginFor<T>,
+			token_in: T::FungibleTokenId,
+			#[pallet::compact] amount_in: Balance,
+			token_a: T::FungibleTokenId,
+			token_b: T::FungibleTokenId,
+			fee: u32,
+			#[pallet::compact] min_liquidity: Balance,
+			to: T::AccountId,
+			#[pallet::compact] deadline: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(deadline > Self::now(), Error::<T>::Deadline);
+
+			let token_other = Self::other_token(token_in, token_a, token_b)?;
+			Self::do_zap_in(who, token_in, token_other, fee, amount_in, min_liquidity, to)?;
+
+			Ok(())
+		}
+
+		/// Remove liquidity from the pool of `token_a` and `token_b` and swap the other token
+		/// of the pair to `token_out`.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::zap_out())]
+		#[transactional]
+		pub fn zap_out(
+			origin: OriginFor<T>,
+			token_a: T::FungibleTokenId,
+			token_b: T::FungibleTokenId,
+			fee: u32,
+			#[pallet::compact] liquidity: Balance,
+			token_out: T::FungibleTokenId,
+			#[pallet::compact] amount_out_min: Balance,
+			to: T::AccountId,
+			#[pallet::compact] deadline: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(deadline > Self::now(), Error::<T>::Deadline);
+
+			let token_other = Self::other_token(token_out, token_a, token_b)?;
+			Self::do_zap_out(who, token_other, token_out, fee, liquidity, amount_out_min, to)?;
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	// The account ID of the vault
+	pub fn account_id() -> T::AccountId {
+		<T as Config>::PalletId::get().into_account_truncating()
+	}
+
+	pub fn token_id_to_account_id(token: T::FungibleTokenId) -> T::AccountId {
+		<T as Config>::PalletId::get().into_sub_account_truncating(token)
+	}
+
+	fn now() -> T::BlockNumber {
+		frame_system::Pallet::<T>::block_number()
+	}
+
+	/// Unwrap `amount` of the WW3G of `who` and pay it to `to` in the native currency.
+	fn unwrap_to(who: &T::AccountId, to: &T::AccountId, amount: Balance) -> DispatchResult {
+		pallet_wrap_currency::Pallet::<T>::do_withdraw(who.clone(), amount)?;
+		if to != who {
+			<T as Config>::Currency::transfer(
+				who,
+				to,
+				BalanceOf::<T>::unique_saturated_from(amount),
+				AllowDeath,
+			)?;
+		}
+		Ok(())
+	}
+
+	pub fn exists(token_a: T::FungibleTokenId, token_b: T::FungibleTokenId, fee: u32) -> bool {
+		let (token_0, token_1) = Self::sort_tokens(token_a, token_b);
+		Pools::<T>::contains_key((token_0, token_1), fee)
+	}
+
+	pub fn get_pool(
+		token_a: T::FungibleTokenId,
+		token_b: T::FungibleTokenId,
+		fee: u32,
+	) -> Result<Pool<T::AccountId, T::FungibleTokenId>, DispatchError> {
+		let (token_0, token_1) = Self::sort_tokens(token_a, token_b);
+		Pools::<T>::get((token_0, token_1), fee).ok_or_else(|| Error::<T>::PoolNotFound.into())
+	}
+
+	pub fn is_fee_tier(fee: u32) -> bool {
+		FeeTiers::<T>::contains_key(fee)
+	}
+
+	fn ensure_unlocked(
+		token_0: T::FungibleTokenId,
+		token_1: T::FungibleTokenId,
+		fee: u32,
+	) -> DispatchResult {
+		ensure!(!FlashLocks::<T>::contains_key((token_0, token_1, fee)), Error::<T>::PoolLocked);
+		Ok(())
+	}
+
+	fn ensure_status(
+		token_0: T::FungibleTokenId,
+		token_1: T::FungibleTokenId,
+		fee: u32,
+		allowed: fn(&PoolStatus) -> bool,
+	) -> DispatchResult {
+		ensure!(allowed(&PoolStatuses::<T>::get((token_0, token_1, fee))), Error::<T>::PoolPaused);
+		Ok(())
+	}
+
+	fn ensure_stable_status(
+		pool_id: T::PoolId,
+		allowed: fn(&PoolStatus) -> bool,
+	) -> DispatchResult {
+		ensure!(allowed(&StablePoolStatuses::<T>::get(pool_id)), Error::<T>::PoolPaused);
+		Ok(())
+	}
+
+	fn ensure_weighted_status(
+		pool_id: T::PoolId,
+		allowed: fn(&PoolStatus) -> bool,
+	) -> DispatchResult {
+		ensure!(allowed(&WeightedPoolStatuses::<T>::get(pool_id)), Error::<T>::PoolPaused);
+		Ok(())
+	}
+
+	fn new_pool_id(who: &T::AccountId) -> Result<T::PoolId, DispatchError> {
+		let id = NextPoolId::<T>::try_mutate(|id| -> Result<T::PoolId, DispatchError> {
+			let current_id = *id;
+			*id = id.checked_add(&One::one()).ok_or(Error::<T>::NoAvailablePoolId)?;
+			Ok(current_id)
+		})?;
+		// the deposit stays reserved from the creator for the life of the pool
+		let deposit = T::CreatePoolDeposit::get();
+		<T as Config>::Currency::reserve(who, deposit)?;
+		PoolDeposits::<T>::insert(id, (who.clone(), deposit));
+		Self::deposit_event(Event::PoolDepositReserved(id, who.clone(), deposit));
+
+		Ok(id)
+	}
+
+	pub fn do_create_pool(
+		who: T::AccountId,
+		token_a: T::FungibleTokenId,
+		token_b: T::FungibleTokenId,
+		fee: u32,
+	) -> Result<T::PoolId, DispatchError> {
+		let id = Self::new_pool_id(&who)?;
+
+		let (token_0, token_1) = Self::sort_tokens(token_a, token_b);
+
+		let lp_token = Self::generate_lp_token_id(token_0, token_1, fee)?;
+		let lp_token_account_id = Self::token_id_to_account_id(lp_token);
+		let name = Self::lp_token_name(b"W3G-LP", &[token_0, token_1]);
+		let symbol: Vec<u8> = "W3G-LP".as_bytes().to_vec();
+
+		pallet_token_fungible::Pallet::<T>::do_create_reserved_token(
+			&Self::account_id(),
+			lp_token,
+			name,
+			symbol,
+			18,
+		)?;
+
+		let pool = Pool { token_0, token_1, lp_token, lp_token_account_id, fee };
+
+		Pools::<T>::insert((token_0, token_1), fee, pool);
+		LpTokenToToken::<T>::insert(lp_token, (token_0, token_1, fee));
+
+		Self::deposit_event(Event::PoolCreated(id, token_0, token_1, fee, who));
+
+		Ok(id)
+	}
+
+	fn do_add_liquidity(
+		token_0: T::FungibleTokenId,
+		token_1: T::FungibleTokenId,
+		fee: u32,
+		amount_a_desired: Balance,
+		amount_b_desired: Balance,
+		amount_a_min: Balance,
+		amount_b_min: Balance,
+	) -> Result<(Balance, Balance), DispatchError> {
+		let (reserve_a, reserve_b) = Self::get_reserves(token_0, token_1, fee)?;
+
+		let amount_a;
+		let amount_b;
+		// amount_a_desired amount_b_desired = amount_a_desired amount_b_desired
+		if reserve_a == Zero::zero() && reserve_b == Zero::zero() {
+			amount_a = amount_a_desired;
+			amount_b = amount_b_desired;
+		} else {
+			let amount_b_optimal = Self::quote(amount_a_desired, reserve_a, reserve_b)?;
+			if amount_b_optimal <= amount_b_desired {
+				ensure!(amount_b_optimal >= amount_b_min, Error::<T>::InsufficientBAmount);
+				amount_a = amount_a_desired;
+				amount_b = amount_b_optimal;
+			} else {
+				let amount_a_optimal = Self::quote(amount_b_desired, reserve_b, reserve_a)?;
+				ensure!(amount_a_optimal <= amount_a_desired, Error::<T>::InsufficientAmount);
+				ensure!(amount_a_optimal >= amount_a_min, Error::<T>::InsufficientAAmount);
+				amount_a = amount_a_optimal;
+				amount_b = amount_b_desired;
+			}
+		}
+
+		Ok((amount_a, amount_b))
+	}
+
+	// requires the initial amount to have already been sent to the first pair
+	fn do_swap(
+		who: T::AccountId,
+		amounts: Vec<Balance>,
+		fees: Vec<u32>,
+		path: Vec<T::FungibleTokenId>,
+		to: T::AccountId,
+	) -> DispatchResult {
+		for i in 0..(path.len() - 1) {
+			let (input, output) = (path[i], path[i + 1]);
+			let (token_0, token_1) = Self::sort_tokens(input, output);
+
+			let amount_out = amounts[i + 1];
+			let (amount_0_out, amount_1_out) = if input == token_0 {
+				(Balance::from(0u128), amount_out)
+			} else {
+				(amount_out, Balance::from(0u128))
+			};
+
+			let receiver = if i < path.len() - 2 {
+				Self::get_pool(output, path[i + 2], fees[i + 1])?.lp_token_account_id
+			} else {
+				to.clone()
+			};
+
+			Self::swap(
+				who.clone(),
+				token_0,
+				token_1,
+				fees[i],
+				amount_0_out,
+				amount_1_out,
+				receiver,
+			)?;
+		}
+
+		Ok(())
+	}
+
+	pub fn swap(
+		who: T::AccountId,
+		token_0: T::FungibleTokenId,
+		token_1: T::FungibleTokenId,
+		fee: u32,
+		amount_0_out: Balance,
+		amount_1_out: Balance,
+		to: T::AccountId,
+	) -> DispatchResult {
+		Self::ensure_unlocked(token_0, token_1, fee)?;
+		Self::ensure_status(token_0, token_1, fee, PoolStatus::can_swap)?;
+		Self::swap_out(token_0, token_1, fee, amount_0_out, amount_1_out, &to)?;
+		Self::swap_settle(who, token_0, token_1, fee, amount_0_out, amount_1_out, to)
+	}
+
+	// sends the output of a swap before its input is checked
+	fn swap_out(
+		token_0: T::FungibleTokenId,
+		token_1: T::FungibleTokenId,
+		fee: u32,
+		amount_0_out: Balance,
+		amount_1_out: Balance,
+		to: &T::AccountId,
+	) -> DispatchResult {
+		ensure!(
+			amount_0_out > Zero::zero() || amount_1_out > Zero::zero(),
+			Error::<T>::InsufficientOutAmount
+		);
+
+		let (reserve_0, reserve_1) = Self::get_reserves(token_0, token_1, fee)?;
+		let pool = Pools::<T>::get((token_0, token_1), fee).ok_or(Error::<T>::PoolNotFound)?;
+
+		ensure!(
+			amount_0_out < reserve_0 && amount_1_out < reserve_1,
+			Error::<T>::InsufficientLiquidity
+		);
+
+		if amount_0_out > Zero::zero() {
+			pallet_token_fungible::Pallet::<T>::do_transfer(
+				pool.token_0,
+				&pool.lp_token_account_id,
+				to,
+				amount_0_out,
+			)?;
+		}
+		if amount_1_out > Zero::zero() {
+			pallet_token_fungible::Pallet::<T>::do_transfer(
+				pool.token_1,
+				&pool.lp_token_account_id,
+				to,
+				amount_1_out,
+			)?;
+		}
+
+		Ok(())
+	}
+
+	// checks the k invariant against the pool balances once the input was received
+	fn swap_settle(
+		who: T::AccountId,
+		token_0: T::FungibleTokenId,
+		token_1: T::FungibleTokenId,
+		fee: u32,
+		amount_0_out: Balance,
+		amount_1_out: Balance,
+		to: T::AccountId,
+	) -> DispatchResult {
+		let (reserve_0, reserve_1) = Self::get_reserves(token_0, token_1, fee)?;
+		let pool = Pools::<T>::get((token_0, token_1), fee).ok_or(Error::<T>::PoolNotFound)?;
+
+		let balance_0 =
+			pallet_token_fungible::Pallet::<T>::balance_of(pool.token_0, &pool.lp_token_account_id);
+		let balance_1 =
+			pallet_token_fungible::Pallet::<T>::balance_of(pool.token_1, &pool.lp_token_account_id);
+
+		let amount_0_in = Self::init_amount_in(balance_0, reserve_0, amount_0_out)?;
+		let amount_1_in = Self::init_amount_in(balance_1, reserve_1, amount_1_out)?;
+
+		ensure!(
+			amount_0_in > Zero::zero() || amount_1_in > Zero::zero(),
+			Error::<T>::InsufficientInputAmount
+		);
+
+		let balance_0_adjusted = Self::init_balance_adjusted(balance_0, amount_0_in, fee)?;
+		let balance_1_adjusted = Self::init_balance_adjusted(balance_1, amount_1_in, fee)?;
+
+		let k_adjusted =
+			balance_0_adjusted.checked_mul(balance_1_adjusted).ok_or(Error::<T>::Overflow)?;
+		let k_required = U256::from(reserve_0)
+			.checked_mul(U256::from(reserve_1))
+			.and_then(|k| k.checked_mul(U256::from(FEE_DENOMINATOR * FEE_DENOMINATOR)))
+			.ok_or(Error::<T>::Overflow)?;
+		ensure!(k_adjusted >= k_required, Error::<T>::AdjustedError);
+
+		Self::do_update(token_0, token_1, fee, balance_0, balance_1)?;
+		Self::deposit_event(Event::Swap(
+			who,
+			amount_0_in,
+			amount_1_in,
+			amount_0_out,
+			amount_1_out,
+			to,
+		));
+		Ok(())
+	}
+
+	pub fn do_flash_swap(
+		who: T::AccountId,
+		token_a: T::FungibleTokenId,
+		token_b: T::FungibleTokenId,
+		fee: u32,
+		amount_a_out: Balance,
+		amount_b_out: Balance,
+		data: &[u8],
+	) -> DispatchResult {
+		Self::begin_flash_swap(&who, token_a, token_b, fee, amount_a_out, amount_b_out)?;
+		ensure!(
+			T::FlashCallback::on_flash_swap(
+				&who,
+				token_a,
+				token_b,
+				fee,
+				amount_a_out,
+				amount_b_out,
+				data
+			),
+			Error::<T>::FlashCallbackFailed
+		);
+		Self::end_flash_swap(&who, token_a, token_b, fee, amount_a_out, amount_b_out)
+	}
+
+	/// Sends the borrowed amounts of a flash swap to `who` and locks the pool until
+	/// `end_flash_swap`.
+	pub fn begin_flash_swap(
+		who: &T::AccountId,
+		token_a: T::FungibleTokenId,
+		token_b: T::FungibleTokenId,
+		fee: u32,
+		amount_a_out: Balance,
+		amount_b_out: Balance,
+	) -> DispatchResult {
+		let (token_0, token_1) = Self::sort_tokens(token_a, token_b);
+		let (amount_0_out, amount_1_out) = if token_a == token_0 {
+			(amount_a_out, amount_b_out)
+		} else {
+			(amount_b_out, amount_a_out)
+		};
+
+		Self::ensure_unlocked(token_0, token_1, fee)?;
+		Self::ensure_status(token_0, token_1, fee, PoolStatus::can_swap)?;
+		Self::swap_out(token_0, token_1, fee, amount_0_out, amount_1_out, who)?;
+		FlashLocks::<T>::insert((token_0, token_1, fee), ());
+
+		Ok(())
+	}
+
+	/// Unlocks the pool of a flash swap and checks that it was repaid.
+	pub fn end_flash_swap(
+		who: &T::AccountId,
+		token_a: T::FungibleTokenId,
+		token_b: T::FungibleTokenId,
+		fee: u32,
+		amount_a_out: Balance,
+		amount_b_out: Balance,
+	) -> DispatchResult {
+		let (token_0, token_1) = Self::sort_tokens(token_a, token_b);
+		let (amount_0_out, amount_1_out) = if token_a == token_0 {
+			(amount_a_out, amount_b_out)
+		} else {
+			(amount_b_out, amount_a_out)
+		};
+
+		ensure!(
+			FlashLocks::<T>::take((token_0, token_1, fee)).is_some(),
+			Error::<T>::PoolNotLocked
+		);
+		Self::swap_settle(
+			who.clone(),
+			token_0,
+			token_1,
+			fee,
+			amount_0_out,
+			amount_1_out,
+			who.clone(),
+		)?;
+
+		Self::deposit_event(Event::FlashSwap(
+			who.clone(),
+			token_a,
+			token_b,
+			fee,
+			amount_a_out,
+			amount_b_out,
+		));
+		Ok(())
+	}
+
+	pub fn do_flash_loan(
+		who: T::AccountId,
+		token: T::FungibleTokenId,
+		pair_token: T::FungibleTokenId,
+		fee: u32,
+		amount: Balance,
+		data: &[u8],
+	) -> DispatchResult {
+		let premium = Self::begin_flash_loan(&who, token, pair_token, fee, amount)?;
+		ensure!(
+			T::FlashCallback::on_flash_loan(&who, token, pair_token, fee, amount, premium, data),
+			Error::<T>::FlashCallbackFailed
+		);
+		Self::end_flash_loan(&who, token, pair_token, fee, amount, premium)
+	}
+
+	/// Lends `amount` of `token` to `who` and locks the pool until `end_flash_loan`. Returns
+	/// the premium owed on top of `amount`.
+	pub fn begin_flash_loan(
+		who: &T::AccountId,
+		token: T::FungibleTokenId,
+		pair_token: T::FungibleTokenId,
+		fee: u32,
+		amount: Balance,
+	) -> Result<Balance, DispatchError> {
+		let (token_0, token_1) = Self::sort_tokens(token, pair_token);
+		Self::ensure_unlocked(token_0, token_1, fee)?;
+		Self::ensure_status(token_0, token_1, fee, PoolStatus::can_swap)?;
+
+		let pool = Self::get_pool(token, pair_token, fee)?;
+		let (reserve, _) = Self::get_reserves(token, pair_token, fee)?;
+		ensure!(amount > Zero::zero(), Error::<T>::InsufficientOutAmount);
+		ensure!(amount < reserve, Error::<T>::InsufficientLiquidity);
+
+		let premium = Self::flash_loan_premium(amount, fee)?;
+		pallet_token_fungible::Pallet::<T>::do_transfer(
+			token,
+			&pool.lp_token_account_id,
+			who,
+			amount,
+		)?;
+		FlashLocks::<T>::insert((token_0, token_1, fee), ());
+
+		Ok(premium)
+	}
+
+	/// Unlocks the pool of a flash loan and checks that it was repaid with its premium.
+	pub fn end_flash_loan(
+		who: &T::AccountId,
+		token: T::FungibleTokenId,
+		pair_token: T::FungibleTokenId,
+		fee: u32,
+		amount: Balance,
+		premium: Balance,
+	) -> DispatchResult {
+		let (token_0, token_1) = Self::sort_tokens(token, pair_token);
+		ensure!(
+			FlashLocks::<T>::take((token_0, token_1, fee)).is_some(),
+			Error::<T>::PoolNotLocked
+		);
+
+		let pool = Self::get_pool(token, pair_token, fee)?;
+		let (reserve, pair_reserve) = Self::get_reserves(token, pair_token, fee)?;
+		let balance =
+			pallet_token_fungible::Pallet::<T>::balance_of(token, &pool.lp_token_account_id);
+		let pair_balance =
+			pallet_token_fungible::Pallet::<T>::balance_of(pair_token, &pool.lp_token_account_id);
+
+		let reserve_owed = reserve.checked_add(premium).ok_or(Error::<T>::Overflow)?;
+		ensure!(
+			balance >= reserve_owed && pair_balance >= pair_reserve,
+			Error::<T>::FlashLoanNotRepaid
+		);
+
+		let (balance_0, balance_1) =
+			if token == token_0 { (balance, pair_balance) } else { (pair_balance, balance) };
+		Self::do_update(token_0, token_1, fee, balance_0, balance_1)?;
+
+		Self::deposit_event(Event::FlashLoan(who.clone(), token, pair_token, fee, amount, premium));
+		Ok(())
+	}
+
+	/// Transfers `amount` of `token` from `who` to a pool in the middle of a flash swap or
+	/// flash loan, for borrowers that cannot address the pool account themselves.
+	pub fn flash_repay(
+		who: &T::AccountId,
+		token_a: T::FungibleTokenId,
+		token_b: T::FungibleTokenId,
+		fee: u32,
+		token: T::FungibleTokenId,
+		amount: Balance,
+	) -> DispatchResult {
+		let (token_0, token_1) = Self::sort_tokens(token_a, token_b);
+		ensure!(token == token_0 || token == token_1, Error::<T>::InvalidPath);
+		ensure!(FlashLocks::<T>::contains_key((token_0, token_1, fee)), Error::<T>::PoolNotLocked);
+
+		let pool = Self::get_pool(token_a, token_b, fee)?;
+		pallet_token_fungible::Pallet::<T>::do_transfer(
+			token,
+			who,
+			&pool.lp_token_account_id,
+			amount,
+		)
+	}
+
+	/// The premium of a flash loan, the pool fee rounded up.
+	pub fn flash_loan_premium(amount: Balance, fee: u32) -> Result<Balance, DispatchError> {
+		U256::from(amount)
+			.checked_mul(U256::from(fee))
+			.and_then(|p| p.checked_add(U256::from(FEE_DENOMINATOR - 1)))
+			.and_then(|p| p.checked_div(U256::from(FEE_DENOMINATOR)))
+			.and_then(|p| TryInto::<Balance>::try_into(p).ok())
+			.ok_or_else(|| Error::<T>::Overflow.into())
+	}
+
+	pub fn mint_fee(
+		token_0: T::FungibleTokenId,
+		token_1: T::FungibleTokenId,
+		fee: u32,
+		reserve_0: Balance,
+		reserve_1: Balance,
+	) -> Result<bool, DispatchError> {
+		let k_last = KLast::<T>::get((token_0, token_1, fee));
+		if let Some(fee_on) = FeeTo::<T>::get() {
+			let pool = Pools::<T>::get((token_0, token_1), fee).ok_or(Error::<T>::PoolNotFound)?;
+			let total_supply = pallet_token_fungible::Pallet::<T>::total_supply(pool.lp_token);
+			if k_last != 0 {
+				let root_k =
+					(U256::from(reserve_0).saturating_mul(U256::from(reserve_1))).integer_sqrt();
+				let root_k_last = U256::from(k_last).integer_sqrt();
+				if root_k > root_k_last {
+					// The protocol receives `share` of the growth in sqrt(k), which is
+					// supply * (rk - rkl) * share / (rk * (1 - share) + rkl * share).
+					let share = U256::from(Self::protocol_fee_share().deconstruct());
+					let one = U256::from(Permill::one().deconstruct());
+					let numerator = U256::from(total_supply)
+						.saturating_mul(
+							root_k.checked_sub(root_k_last).ok_or(Error::<T>::Overflow)?,
+						)
+						.saturating_mul(share);
+					let denominator = root_k
+						.saturating_mul(one.saturating_sub(share))
+						.checked_add(root_k_last.saturating_mul(share))
+						.ok_or(Error::<T>::Overflow)?;
+					let liquidity = numerator
+						.checked_div(denominator)
+						.and_then(|l| TryInto::<Balance>::try_into(l).ok())
+						.ok_or(Error::<T>::Overflow)?;
+					if liquidity > 0 {
+						pallet_token_fungible::Pallet::<T>::do_mint(
+							pool.lp_token,
+							&Self::account_id(),
+							fee_on,
+							liquidity,
+						)?;
+					}
+				}
+			}
+			Ok(true)
+		} else {
+			if k_last != 0 {
+				KLast::<T>::mutate((token_0, token_1, fee), |k| *k = 0);
+			}
+			Ok(false)
+		}
+	}
+
+	pub fn mint(
+		who: T::AccountId,
+		token_0: T::FungibleTokenId,
+		token_1: T::FungibleTokenId,
+		fee: u32,
+		to: T::AccountId,
+	) -> Result<Balance, DispatchError> {
+		Self::ensure_unlocked(token_0, token_1, fee)?;
+		Self::ensure_status(token_0, token_1, fee, PoolStatus::can_add_liquidity)?;
+		let pool = Pools::<T>::get((token_0, token_1), fee).ok_or(Error::<T>::PoolNotFound)?;
+
+		let (reserve_0, reserve_1) = Reserves::<T>::get((token_0, token_1, fee));
+
+		let balance_0 =
+			pallet_token_fungible::Pallet::<T>::balance_of(pool.token_0, &pool.lp_token_account_id);
+		let balance_1 =
+			pallet_token_fungible::Pallet::<T>::balance_of(pool.token_1, &pool.lp_token_account_id);
+
+		let amount_0 = balance_0.checked_sub(reserve_0).ok_or(Error::<T>::Overflow)?;
+		let amount_1 = balance_1.checked_sub(reserve_1).ok_or(Error::<T>::Overflow)?;
+
+		let fee_on = Self::mint_fee(token_0, token_1, fee, reserve_0, reserve_1)?;
+
+		let liquidity: Balance;
+
+		let total_supply = pallet_token_fungible::Pallet::<T>::total_supply(pool.lp_token);
+		if total_supply == Zero::zero() {
+			liquidity = (amount_0 * amount_1)
+				.integer_sqrt()
+				.checked_sub(Balance::from(MINIMUM_LIQUIDITY))
+				.ok_or(Error::<T>::Overflow)?;
+			// permanently lock the first MINIMUM_LIQUIDITY tokens
+			pallet_token_fungible::Pallet::<T>::do_mint(
+				pool.lp_token,
+				&Self::account_id(),
+				Self::account_id(),
+				Balance::from(MINIMUM_LIQUIDITY),
+			)?;
+		} else {
+			liquidity = cmp::min(
+				U256::from(amount_0)
+					.checked_mul(U256::from(total_supply))
+					.and_then(|l| l.checked_div(U256::from(reserve_0)))
+					.and_then(|l| TryInto::<Balance>::try_into(l).ok())
+					.ok_or(Error::<T>::Overflow)?,
+				U256::from(amount_1)
+					.checked_mul(U256::from(total_supply))
+					.and_then(|l| l.checked_div(U256::from(reserve_1)))
+					.and_then(|l| TryInto::<Balance>::try_into(l).ok())
+					.ok_or(Error::<T>::Overflow)?,
+			);
+		}
+		ensure!(liquidity >= Zero::zero(), Error::<T>::InsufficientLiquidityMinted);
+		pallet_token_fungible::Pallet::<T>::do_mint(
+			pool.lp_token,
+			&Self::account_id(),
+			to.clone(),
+			liquidity,
+		)?;
+
+		Self::do_update(token_0, token_1, fee, balance_0, balance_1)?;
+
+		if fee_on {
+			let (_reserve_0, _reserve_1) = Reserves::<T>::get((token_0, token_1, fee));
+			KLast::<T>::mutate((token_0, token_1, fee), |k| *k = _reserve_0 * _reserve_1);
+		}
+		Self::deposit_event(Event::Mint(who, amount_0, amount_1, to));
+		Ok(liquidity)
+	}
+
+	pub fn get_liquidity(
+		token_a: T::FungibleTokenId,
+		amount_a: Balance,
+		token_b: T::FungibleTokenId,
+		amount_b: Balance,
+		fee: u32,
+	) -> Result<Balance, DispatchError> {
+		let (token_0, token_1) = Self::sort_tokens(token_a, token_b);
+		let amount_0;
+		let amount_1;
+		if token_a == token_0 {
+			amount_0 = amount_a;
+			amount_1 = amount_b;
+		} else {
+			amount_0 = amount_b;
+			amount_1 = amount_a;
+		}
+		let pool = Pools::<T>::get((token_0, token_1), fee).ok_or(Error::<T>::PoolNotFound)?;
+		let (reserve_0, reserve_1) = Reserves::<T>::get((token_0, token_1, fee));
+		let liquidity: Balance;
+		let total_supply = pallet_token_fungible::Pallet::<T>::total_supply(pool.lp_token);
+		if total_supply == Zero::zero() {
+			liquidity = (amount_0 * amount_1)
+				.integer_sqrt()
+				.checked_sub(MINIMUM_LIQUIDITY)
+				.ok_or(Error::<T>::Overflow)?;
+		} else {
+			liquidity = cmp::min(
+				U256::from(amount_0)
+					.checked_mul(U256::from(total_supply))
+					.and_then(|l| l.checked_div(U256::from(reserve_0)))
+					.and_then(|l| TryInto::<Balance>::try_into(l).ok())
+					.ok_or(Error::<T>::Overflow)?,
+				U256::from(amount_1)
+					.checked_mul(U256::from(total_supply))
+					.and_then(|l| l.checked_div(U256::from(reserve_1)))
+					.and_then(|l| TryInto::<Balance>::try_into(l).ok())
+					.ok_or(Error::<T>::Overflow)?,
+			);
+		}
+		ensure!(liquidity >= Zero::zero(), Error::<T>::InsufficientLiquidityMinted);
+
+		Ok(liquidity)
+	}
+
+	pub fn liquidity_to_token(
+		lp_token: T::FungibleTokenId,
+		lp_balance: Balance,
+	) -> Result<(Balance, Balance), DispatchError> {
+		let (token_0, token_1, fee) =
+			LpTokenToToken::<T>::get(lp_token).ok_or(Error::<T>::PoolNotFound)?;
+		ensure!(token_0 != token_1, Error::<T>::PoolNotFound);
+		let pool = Pools::<T>::get((token_0, token_1), fee).ok_or(Error::<T>::PoolNotFound)?;
+
+		let balance_0 =
+			pallet_token_fungible::Pallet::<T>::balance_of(pool.token_0, &pool.lp_token_account_id);
+		let balance_1 =
+			pallet_token_fungible::Pallet::<T>::balance_of(pool.token_1, &pool.lp_token_account_id);
+
+		let liquidity_balance = pallet_token_fungible::Pallet::<T>::balance_of(
+			pool.lp_token,
+			&pool.lp_token_account_id,
+		);
+
+		let liquidity = liquidity_balance + lp_balance;
+
+		let total_supply = pallet_token_fungible::Pallet::<T>::total_supply(pool.lp_token);
+
+		let amount_0 = U256::from(liquidity)
+			.checked_mul(U256::from(balance_0))
+			.and_then(|l| l.checked_div(U256::from(total_supply)))
+			.and_then(|l| TryInto::<Balance>::try_into(l).ok())
+			.ok_or(Error::<T>::Overflow)?;
+		let amount_1 = U256::from(liquidity)
+			.checked_mul(U256::from(balance_1))
+			.and_then(|l| l.checked_div(U256::from(total_supply)))
+			.and_then(|l| TryInto::<Balance>::try_into(l).ok())
+			.ok_or(Error::<T>::Overflow)?;
+		ensure!(
+			amount_0 > Zero::zero() && amount_1 > Zero::zero(),
+			Error::<T>::InsufficientLiquidityBurned
+		);
+		Ok((amount_0, amount_1))
+	}
+
+	pub fn burn(
+		who: T::AccountId,
+		token_0: T::FungibleTokenId,
+		token_1: T::FungibleTokenId,
+		fee: u32,
+		to: T::AccountId,
+	) -> Result<(Balance, Balance), DispatchError> {
+		Self::ensure_unlocked(token_0, token_1, fee)?;
+		Self::ensure_status(token_0, token_1, fee, PoolStatus::can_remove_liquidity)?;
+		let pool = Pools::<T>::get((token_0, token_1), fee).ok_or(Error::<T>::PoolNotFound)?;
+		let (reserve_0, reserve_1) = Reserves::<T>::get((token_0, token_1, fee));
+
+		let mut balance_0 =
+			pallet_token_fungible::Pallet::<T>::balance_of(pool.token_0, &pool.lp_token_account_id);
+		let mut balance_1 =
+			pallet_token_fungible::Pallet::<T>::balance_of(pool.token_1, &pool.lp_token_account_id);
+
+		let liquidity = pallet_token_fungible::Pallet::<T>::balance_of(
+			pool.lp_token,
+			&pool.lp_token_account_id,
+		);
+
+		let fee_on = Self::mint_fee(token_0, token_1, fee, reserve_0, reserve_1)?;
+
+		let total_supply = pallet_token_fungible::Pallet::<T>::total_supply(pool.lp_token);
+
+		let amount_0 = U256::from(liquidity)
+			.checked_mul(U256::from(balance_0))
+			.and_then(|l| l.checked_div(U256::from(total_supply)))
+			.and_then(|l| TryInto::<Balance>::try_into(l).ok())
+			.ok_or(Error::<T>::Overflow)?;
+		let amount_1 = U256::from(liquidity)
+			.checked_mul(U256::from(balance_1))
+			.and_then(|l| l.checked_div(U256::from(total_supply)))
+			.and_then(|l| TryInto::<Balance>::try_into(l).ok())
+			.ok_or(Error::<T>::Overflow)?;
+		ensure!(
+			amount_0 > Zero::zero() && amount_1 > Zero::zero(),
+			Error::<T>::InsufficientLiquidityBurned
+		);
+
+		pallet_token_fungible::Pallet::<T>::do_burn(
+			pool.lp_token,
+			&pool.lp_token_account_id,
+			liquidity,
+		)?;
+		pallet_token_fungible::Pallet::<T>::do_transfer(
+			pool.token_0,
+			&pool.lp_token_account_id,
+			&to,
+			amount_0,
+		)?;
+		pallet_token_fungible::Pallet::<T>::do_transfer(
+			pool.token_1,
+			&pool.lp_token_account_id,
+			&to,
+			amount_1,
+		)?;
+
+		balance_0 =
+			pallet_token_fungible::Pallet::<T>::balance_of(pool.token_0, &pool.lp_token_account_id);
+		balance_1 =
+			pallet_token_fungible::Pallet::<T>::balance_of(pool.token_1, &pool.lp_token_account_id);
+
+		Self::do_update(token_0, token_1, fee, balance_0, balance_1)?;
+
+		if fee_on {
+			let (_reserve_0, _reserve_1) = Reserves::<T>::get((token_0, token_1, fee));
+			KLast::<T>::mutate((token_0, token_1, fee), |k| *k = _reserve_0 * _reserve_1);
+		}
+		Self::deposit_event(Event::Burn(who, amount_0, amount_1, to));
+		Ok((amount_0, amount_1))
+	}
+
+	fn do_update(
+		token_0: T::FungibleTokenId,
+		token_1: T::FungibleTokenId,
+		fee: u32,
+		balance_0: Balance,
+		balance_1: Balance,
+	) -> DispatchResult {
+		let key = (token_0, token_1, fee);
+		let (reserve_0, reserve_1) = Reserves::<T>::get(key);
+		// accumulate the prices of the previous reserves, at most once per block
+		let cumulative = PriceCumulatives::<T>::mutate(key, |cumulative| {
+			Self::accumulate(cumulative, reserve_0, reserve_1, Self::now());
+			cumulative.clone()
+		});
+		Self::observe(key, cumulative);
+
+		Reserves::<T>::mutate(key, |reserve| *reserve = (balance_0, balance_1));
+		Self::deposit_event(Event::Sync(balance_0, balance_1));
+		Ok(())
+	}
+
+	fn accumulate(
+		cumulative: &mut Observation<T::BlockNumber>,
+		reserve_0: Balance,
+		reserve_1: Balance,
+		now: T::BlockNumber,
+	) {
+		let elapsed: u128 = now.saturating_sub(cumulative.block_number).unique_saturated_into();
+		// pools updated before the oracle existed start accumulating from their next update
+		if !cumulative.block_number.is_zero() &&
+			elapsed > 0 && reserve_0 > Zero::zero() &&
+			reserve_1 > Zero::zero()
+		{
+			let (price_0, price_1) = Self::spot_prices(reserve_0, reserve_1);
+			cumulative.price_0_cumulative = cumulative
+				.price_0_cumulative
+				.wrapping_add(price_0.into_inner().wrapping_mul(elapsed));
+			cumulative.price_1_cumulative = cumulative
+				.price_1_cumulative
+				.wrapping_add(price_1.into_inner().wrapping_mul(elapsed));
+		}
+		cumulative.block_number = now;
+	}
+
+	fn observe(
+		key: (T::FungibleTokenId, T::FungibleTokenId, u32),
+		cumulative: Observation<T::BlockNumber>,
+	) {
+		let max_observations = T::MaxObservations::get();
+		if max_observations == 0 {
+			return
+		}
+
+		let index = ObservationIndex::<T>::get(key) % max_observations;
+		let last_index = (index + max_observations - 1) % max_observations;
+		if let Some(last) = Observations::<T>::get(key, last_index) {
+			if cumulative.block_number <
+				last.block_number.saturating_add(T::ObservationPeriod::get())
+			{
+				return
+			}
+		}
+
+		Observations::<T>::insert(key, index, cumulative);
+		ObservationIndex::<T>::insert(key, (index + 1) % max_observations);
+	}
+
+	/// The prices of token_0 in token_1 and of token_1 in token_0 for the given reserves.
+	pub fn spot_prices(reserve_0: Balance, reserve_1: Balance) -> (FixedU128, FixedU128) {
+		(
+			FixedU128::checked_from_rational(reserve_1, reserve_0)
+				.unwrap_or_else(|| FixedU128::from_inner(u128::MAX)),
+			FixedU128::checked_from_rational(reserve_0, reserve_1)
+				.unwrap_or_else(|| FixedU128::from_inner(u128::MAX)),
+		)
+	}
+
+	/// The time weighted average prices of `token_a` in `token_b` and of `token_b` in `token_a`
+	/// over at least the last `window` blocks, measured from the newest observation old enough.
+	pub fn consult(
+		token_a: T::FungibleTokenId,
+		token_b: T::FungibleTokenId,
+		fee: u32,
+		window: T::BlockNumber,
+	) -> Result<(FixedU128, FixedU128), DispatchError> {
+		ensure!(!window.is_zero(), Error::<T>::InvalidWindow);
+		let (token_0, token_1) = Self::sort_tokens(token_a, token_b);
+		ensure!(Pools::<T>::contains_key((token_0, token_1), fee), Error::<T>::PoolNotFound);
+
+		let key = (token_0, token_1, fee);
+		let now = Self::now();
+		let target = now.checked_sub(&window).ok_or(Error::<T>::ObservationNotFound)?;
+		let observation = Observations::<T>::iter_prefix_values(key)
+			.filter(|observation| observation.block_number <= target)
+			.max_by_key(|observation| observation.block_number)
+			.ok_or(Error::<T>::ObservationNotFound)?;
+
+		// the cumulative prices as if the pool was updated now
+		let mut current = PriceCumulatives::<T>::get(key);
+		let (reserve_0, reserve_1) = Reserves::<T>::get(key);
+		Self::accumulate(&mut current, reserve_0, reserve_1, now);
+
+		let elapsed: u128 = now.saturating_sub(observation.block_number).unique_saturated_into();
+		let price_0 = FixedU128::from_inner(
+			current.price_0_cumulative.wrapping_sub(observation.price_0_cumulative) / elapsed,
+		);
+		let price_1 = FixedU128::from_inner(
+			current.price_1_cumulative.wrapping_sub(observation.price_1_cumulative) / elapsed,
+		);
+
+		if token_a == token_0 {
+			Ok((price_0, price_1))
+		} else {
+			Ok((price_1, price_0))
+		}
+	}
+
+	fn init_amount_in(
+		balance: Balance,
+		reserve: Balance,
+		amount_out: Balance,
+	) -> Result<Balance, DispatchError> {
+		if balance > reserve {
+			let temp = U256::from(reserve)
+				.checked_sub(U256::from(amount_out))
+				.ok_or(Error::<T>::Overflow)?;
+			Ok(U256::from(balance)
+				.checked_sub(temp)
+				.and_then(|l| TryInto::<Balance>::try_into(l).ok())
+				.ok_or(Error::<T>::Overflow)?)
+		} else {
+			Ok(Zero::zero())
+		}
+	}
+
+	fn init_balance_adjusted(
+		balance: Balance,
+		amount_in: Balance,
+		fee: u32,
+	) -> Result<U256, DispatchError> {
+		let temp_0 = U256::from(balance)
+			.checked_mul(U256::from(FEE_DENOMINATOR))
+			.ok_or(Error::<T>::Overflow)?;
+		let temp_1 =
+			U256::from(amount_in).checked_mul(U256::from(fee)).ok_or(Error::<T>::Overflow)?;
+		temp_0.checked_sub(temp_1).ok_or_else(|| Error::<T>::Overflow.into())
+	}
+
+	fn sort_tokens(
+		token_a: T::FungibleTokenId,
+		token_b: T::FungibleTokenId,
+	) -> (T::FungibleTokenId, T::FungibleTokenId) {
+		if token_a < token_b {
+			(token_a, token_b)
+		} else {
+			(token_b, token_a)
+		}
+	}
+
+	pub fn get_reserves(
+		token_a: T::FungibleTokenId,
+		token_b: T::FungibleTokenId,
+		fee: u32,
+	) -> Result<(Balance, Balance), DispatchError> {
+		let (token_0, token_1) = Self::sort_tokens(token_a, token_b);
+
+		let (reserve_0, reserve_1) = Reserves::<T>::get((token_0, token_1, fee));
+		let (reserve_a, reserve_b) =
+			if token_a == token_0 { (reserve_0, reserve_1) } else { (reserve_1, reserve_0) };
+
+		Ok((reserve_a, reserve_b))
+	}
+
+	pub fn quote(
+		amount_a: Balance,
+		reserve_a: Balance,
+		reserve_b: Balance,
+	) -> Result<Balance, DispatchError> {
+		ensure!(amount_a > Zero::zero(), Error::<T>::InsufficientAmount);
+		ensure!(
+			reserve_a > Zero::zero() && reserve_b > Zero::zero(),
+			Error::<T>::InsufficientLiquidity
+		);
+		let amount_b = U256::from(amount_a)
+			.checked_mul(U256::from(reserve_b))
+			.and_then(|l| l.checked_div(U256::from(reserve_a)))
+			.and_then(|l| TryInto::<Balance>::try_into(l).ok())
+			.ok_or(Error::<T>::Overflow)?;
+
+		Ok(amount_b)
+	}
+
+	pub fn get_amount_out(
+		amount_in: Balance,
+		reserve_in: Balance,
+		reserve_out: Balance,
+		fee: u32,
+	) -> Result<Balance, DispatchError> {
+		ensure!(amount_in > Zero::zero(), Error::<T>::InsufficientInputAmount);
+		ensure!(
+			reserve_in > Zero::zero() && reserve_out > Zero::zero(),
+			Error::<T>::InsufficientLiquidity
+		);
+
+		let amount_in_with_fee: U256 =
+			U256::from(amount_in).saturating_mul(U256::from(FEE_DENOMINATOR.saturating_sub(fee)));
+
+		let numerator: U256 =
+			U256::from(amount_in_with_fee).saturating_mul(U256::from(reserve_out));
+
+		let denominator: U256 = U256::from(reserve_in)
+			.saturating_mul(U256::from(FEE_DENOMINATOR))
+			.saturating_add(amount_in_with_fee);
+
+		let amount_out = numerator
+			.checked_div(denominator)
+			.and_then(|n| TryInto::<Balance>::try_into(n).ok())
+			.ok_or(Error::<T>::Overflow)?;
+		Ok(amount_out)
+	}
+
+	pub fn get_amount_in(
+		amount_out: Balance,
+		reserve_in: Balance,
+		reserve_out: Balance,
+		fee: u32,
+	) -> Result<Balance, DispatchError> {
+		ensure!(amount_out > Zero::zero(), Error::<T>::InsufficientOutputAmount);
+		ensure!(
+			reserve_in > Zero::zero() && reserve_out > Zero::zero(),
+			Error::<T>::InsufficientLiquidity
+		);
+
+		let numerator: U256 = U256::from(reserve_in)
+			.saturating_mul(U256::from(amount_out))
+			.saturating_mul(U256::from(FEE_DENOMINATOR));
+		let denominator: U256 = (U256::from(reserve_out).saturating_sub(U256::from(amount_out)))
+			.saturating_mul(U256::from(FEE_DENOMINATOR.saturating_sub(fee)));
+
+		let amount_in = U256::from(numerator)
+			.checked_div(U256::from(denominator))
+			.and_then(|r| r.checked_add(U256::one()))
+			.and_then(|n| TryInto::<Balance>::try_into(n).ok())
+			.ok_or(Error::<T>::Overflow)?;
+
+		Ok(amount_in)
+	}
+
+	pub fn get_amounts_out(
+		amount_in: Balance,
+		path: Vec<T::FungibleTokenId>,
+	) -> Result<Vec<Balance>, DispatchError> {
+		Ok(Self::get_route_out(amount_in, path)?.0)
+	}
+
+	pub fn get_amounts_in(
+		amount_out: Balance,
+		path: Vec<T::FungibleTokenId>,
+	) -> Result<Vec<Balance>, DispatchError> {
+		Ok(Self::get_route_in(amount_out, path)?.0)
+	}
+
+	/// The amounts along `path` for an exact input, together with the fee tier used at each
+	/// hop. Every hop goes through the pool of its pair which pays out the most.
+	pub fn get_route_out(
+		amount_in: Balance,
+		path: Vec<T::FungibleTokenId>,
+	) -> Result<(Vec<Balance>, Vec<u32>), DispatchError> {
+		ensure!(path.len() >= 2, Error::<T>::InvalidPath);
+
+		let mut amounts = vec![Balance::from(0u128); path.len()];
+		let mut fees = vec![0u32; path.len() - 1];
+		amounts[0] = amount_in;
+		for i in 0..(path.len() - 1) {
+			let mut best: Option<(Balance, u32)> = None;
+			for fee in Self::pool_fees(path[i], path[i + 1]) {
+				let (reserve_in, reserve_out) = Self::get_reserves(path[i], path[i + 1], fee)?;
+				if let Ok(amount) = Self::get_amount_out(amounts[i], reserve_in, reserve_out, fee) {
+					if best.map_or(true, |(best_amount, _)| amount > best_amount) {
+						best = Some((amount, fee));
+					}
+				}
+			}
+			let (amount, fee) = Self::best_or_error(best, path[i], path[i + 1])?;
+			amounts[i + 1] = amount;
+			fees[i] = fee;
+		}
+		Ok((amounts, fees))
+	}
+
+	/// The amounts along `path` for an exact output, together with the fee tier used at each
+	/// hop. Every hop goes through the pool of its pair which asks for the least.
+	pub fn get_route_in(
+		amount_out: Balance,
+		path: Vec<T::FungibleTokenId>,
+	) -> Result<(Vec<Balance>, Vec<u32>), DispatchError> {
+		ensure!(path.len() >= 2, Error::<T>::InvalidPath);
+
+		let mut amounts = vec![Balance::from(0u128); path.len()];
+		let mut fees = vec![0u32; path.len() - 1];
+		amounts[path.len() - 1] = amount_out;
+		for i in (1..path.len()).rev() {
+			let mut best: Option<(Balance, u32)> = None;
+			for fee in Self::pool_fees(path[i - 1], path[i]) {
+				let (reserve_in, reserve_out) = Self::get_reserves(path[i - 1], path[i], fee)?;
+				if amounts[i] >= reserve_out {
+					continue
+				}
+				if let Ok(amount) = Self::get_amount_in(amounts[i], reserve_in, reserve_out, fee) {
+					if best.map_or(true, |(best_amount, _)| amount < best_amount) {
+						best = Some((amount, fee));
+					}
+				}
+			}
+			let (amount, fee) = Self::best_or_error(best, path[i - 1], path[i])?;
+			amounts[i - 1] = amount;
+			fees[i - 1] = fee;
+		}
+
+		Ok((amounts, fees))
+	}
+
+	/// The fee tiers of the pools created for a pair.
+	pub fn pool_fees(token_a: T::FungibleTokenId, token_b: T::FungibleTokenId) -> Vec<u32> {
+		let (token_0, token_1) = Self::sort_tokens(token_a, token_b);
+		Pools::<T>::iter_key_prefix((token_0, token_1)).collect()
+	}
+
+	fn best_or_error(
+		best: Option<(Balance, u32)>,
+		token_a: T::FungibleTokenId,
+		token_b: T::FungibleTokenId,
+	) -> Result<(Balance, u32), DispatchError> {
+		match best {
+			Some(best) => Ok(best),
+			None if Self::pool_fees(token_a, token_b).is_empty() =>
+				Err(Error::<T>::PoolNotFound.into()),
+			None => Err(Error::<T>::InsufficientLiquidity.into()),
+		}
+	}
+
+	/// The route from `token_in` to `token_out` of at most `max_hops` hops which pays out the
+	/// most for `amount_in`.
+	pub fn find_route_out(
+		token_in: T::FungibleTokenId,
+		token_out: T::FungibleTokenId,
+		amount_in: Balance,
+		max_hops: u32,
+	) -> Result<Route<T::FungibleTokenId>, DispatchError> {
+		let mut best: Option<(Vec<T::FungibleTokenId>, Vec<Balance>, Vec<u32>)> = None;
+		for path in Self::candidate_paths(token_in, token_out, max_hops)? {
+			if let Ok((amounts, fees)) = Self::get_route_out(amount_in, path.clone()) {
+				if best
+					.as_ref()
+					.map_or(true, |(_, best_amounts, _)| amounts.last() > best_amounts.last())
+				{
+					best = Some((path, amounts, fees));
+				}
+			}
+		}
+
+		let (path, amounts, fees) = best.ok_or(Error::<T>::RouteNotFound)?;
+		Self::build_route(path, amounts, fees)
+	}
+
+	/// The route from `token_in` to `token_out` of at most `max_hops` hops which asks for the
+	/// least to pay out `amount_out`.
+	pub fn find_route_in(
+		token_in: T::FungibleTokenId,
+		token_out: T::FungibleTokenId,
+		amount_out: Balance,
+		max_hops: u32,
+	) -> Result<Route<T::FungibleTokenId>, DispatchError> {
+		let mut best: Option<(Vec<T::FungibleTokenId>, Vec<Balance>, Vec<u32>)> = None;
+		for path in Self::candidate_paths(token_in, token_out, max_hops)? {
+			if let Ok((amounts, fees)) = Self::get_route_in(amount_out, path.clone()) {
+				if best.as_ref().map_or(true, |(_, best_amounts, _)| amounts[0] < best_amounts[0]) {
+					best = Some((path, amounts, fees));
+				}
+			}
+		}
+
+		let (path, amounts, fees) = best.ok_or(Error::<T>::RouteNotFound)?;
+		Self::build_route(path, amounts, fees)
+	}
+
+	/// The paths from `token_in` to `token_out` of at most `max_hops` hops which visit no
+	/// token twice, searched depth first over the pairs of `Pools`.
+	fn candidate_paths(
+		token_in: T::FungibleTokenId,
+		token_out: T::FungibleTokenId,
+		max_hops: u32,
+	) -> Result<Vec<Vec<T::FungibleTokenId>>, DispatchError> {
+		ensure!(token_in != token_out, Error::<T>::TokenRepeat);
+		ensure!(max_hops > 0, Error::<T>::InvalidPath);
+		let max_hops = cmp::min(max_hops, MAX_ROUTE_HOPS) as usize;
+
+		let mut pairs: Vec<(T::FungibleTokenId, T::FungibleTokenId)> =
+			Pools::<T>::iter_values().map(|pool| (pool.token_0, pool.token_1)).collect();
+		pairs.sort();
+		pairs.dedup();
+
+		let mut paths = Vec::new();
+		let mut stack = vec![vec![token_in]];
+		while let Some(path) = stack.pop() {
+			let last = path[path.len() - 1];
+			if last == token_out {
+				paths.push(path);
+				continue
+			}
+			if path.len() > max_hops {
+				continue
+			}
+			for (token_0, token_1) in pairs.iter() {
+				let next = if *token_0 == last {
+					*token_1
+				} else if *token_1 == last {
+					*token_0
+				} else {
+					continue
+				};
+				if !path.contains(&next) {
+					let mut next_path = path.clone();
+					next_path.push(next);
+					stack.push(next_path);
+				}
+			}
+		}
+
+		Ok(paths)
+	}
+
+	fn build_route(
+		path: Vec<T::FungibleTokenId>,
+		amounts: Vec<Balance>,
+		fees: Vec<u32>,
+	) -> Result<Route<T::FungibleTokenId>, DispatchError> {
+		let mut fee_amounts = Vec::with_capacity(fees.len());
+		// the output at the spot prices of the pools, after their fees
+		let mut spot_amount = U256::from(amounts[0]);
+		for (i, fee) in fees.iter().enumerate() {
+			fee_amounts.push(
+				U256::from(amounts[i])
+					.saturating_mul(U256::from(*fee))
+					.checked_div(U256::from(FEE_DENOMINATOR))
+					.and_then(|f| TryInto::<Balance>::try_into(f).ok())
+					.ok_or(Error::<T>::Overflow)?,
+			);
+			let (reserve_in, reserve_out) = Self::get_reserves(path[i], path[i + 1], *fee)?;
+			spot_amount = spot_amount
+				.saturating_mul(U256::from(FEE_DENOMINATOR - fee))
+				.saturating_mul(U256::from(reserve_out))
+				.checked_div(U256::from(reserve_in).saturating_mul(U256::from(FEE_DENOMINATOR)))
+				.ok_or(Error::<T>::InsufficientLiquidity)?;
+		}
+
+		let amount_out = U256::from(amounts[amounts.len() - 1]);
+		let price_impact = if spot_amount > amount_out {
+			let scale = spot_amount / U256::from(u64::MAX) + 1;
+			Permill::from_rational(
+				((spot_amount - amount_out) / scale).low_u64(),
+				(spot_amount / scale).low_u64(),
+			)
+		} else {
+			Permill::zero()
+		};
+
+		Ok(Route { path, amounts, fees, fee_amounts, price_impact })
+	}
+
+	/// A page of the constant product pools ordered by LP token id, starting at `offset`.
+	pub fn get_pools(offset: u32, limit: u32) -> Vec<PoolInfo<T::FungibleTokenId>> {
+		let mut pools: Vec<_> = Pools::<T>::iter_values().collect();
+		pools.sort_by(|a, b| a.lp_token.cmp(&b.lp_token));
+		pools
+			.into_iter()
+			.skip(offset as usize)
+			.take(cmp::min(limit, MAX_POOLS_PER_PAGE) as usize)
+			.map(Self::pool_info)
+			.collect()
+	}
+
+	/// The pool of `token_a` and `token_b` with the fee tier `fee`.
+	pub fn get_pool_info(
+		token_a: T::FungibleTokenId,
+		token_b: T::FungibleTokenId,
+		fee: u32,
+	) -> Option<PoolInfo<T::FungibleTokenId>> {
+		let (token_0, token_1) = Self::sort_tokens(token_a, token_b);
+		Pools::<T>::get((token_0, token_1), fee).map(Self::pool_info)
+	}
+
+	/// The pool `lp_token` is the liquidity pool token of.
+	pub fn get_pool_info_by_lp_token(
+		lp_token: T::FungibleTokenId,
+	) -> Option<PoolInfo<T::FungibleTokenId>> {
+		let (token_0, token_1, fee) = LpTokenToToken::<T>::get(lp_token)?;
+		Pools::<T>::get((token_0, token_1), fee).map(Self::pool_info)
+	}
+
+	/// The LP tokens `who` holds in each constant product pool, with the share of the reserves
+	/// they are worth.
+	pub fn get_lp_positions(who: &T::AccountId) -> Vec<LpPosition<T::FungibleTokenId>> {
+		let mut positions: Vec<_> = Pools::<T>::iter_values()
+			.filter_map(|pool| {
+				let lp_balance = pallet_token_fungible::Pallet::<T>::balance_of(pool.lp_token, who);
+				if lp_balance.is_zero() {
+					return None
+				}
+				let pool = Self::pool_info(pool);
+				let share = |reserve: Balance| {
+					U256::from(reserve)
+						.saturating_mul(U256::from(lp_balance))
+						.checked_div(U256::from(pool.total_supply))
+						.and_then(|amount| TryInto::<Balance>::try_into(amount).ok())
+						.unwrap_or_default()
+				};
+				let (amount_0, amount_1) = (share(pool.reserve_0), share(pool.reserve_1));
+				Some(LpPosition { pool, lp_balance, amount_0, amount_1 })
+			})
+			.collect();
+		positions.sort_by(|a, b| a.pool.lp_token.cmp(&b.pool.lp_token));
+		positions
+	}
+
+	fn pool_info(pool: Pool<T::AccountId, T::FungibleTokenId>) -> PoolInfo<T::FungibleTokenId> {
+		let (reserve_0, reserve_1) = Reserves::<T>::get((pool.token_0, pool.token_1, pool.fee));
+		PoolInfo {
+			token_0: pool.token_0,
+			token_1: pool.token_1,
+			fee: pool.fee,
+			lp_token: pool.lp_token,
+			reserve_0,
+			reserve_1,
+			total_supply: pallet_token_fungible::Pallet::<T>::total_supply(pool.lp_token),
+			status: PoolStatuses::<T>::get((pool.token_0, pool.token_1, pool.fee)),
+		}
+	}
+
+	/// The part of `amount_in` to swap into a pool holding `reserve_in` of the input token so
+	/// that the rest and the output of the swap are in the ratio of the reserves after it.
+	pub fn zap_swap_amount(
+		amount_in: Balance,
+		reserve_in: Balance,
+		fee: u32,
+	) -> Result<Balance, DispatchError> {
+		// solves (amount_in - s) / reserve_in' = amount_out(s) / reserve_out' for s
+		let fee_denominator = U256::from(FEE_DENOMINATOR);
+		let fee_rest = U256::from(FEE_DENOMINATOR.checked_sub(fee).ok_or(Error::<T>::Overflow)?);
+		let b = fee_denominator
+			.checked_add(fee_rest)
+			.and_then(|b| b.checked_mul(U256::from(reserve_in)))
+			.ok_or(Error::<T>::Overflow)?;
+		let discriminant = b
+			.checked_mul(b)
+			.and_then(|b2| {
+				U256::from(4u32)
+					.checked_mul(fee_rest)
+					.and_then(|d| d.checked_mul(fee_denominator))
+					.and_then(|d| d.checked_mul(U256::from(amount_in)))
+					.and_then(|d| d.checked_mul(U256::from(reserve_in)))
+					.and_then(|d| d.checked_add(b2))
+			})
+			.ok_or(Error::<T>::Overflow)?;
+		discriminant
+			.integer_sqrt()
+			.checked_sub(b)
+			.and_then(|s| s.checked_div(fee_rest.saturating_mul(U256::from(2u32))))
+			.and_then(|s| TryInto::<Balance>::try_into(s).ok())
+			.ok_or_else(|| Error::<T>::Overflow.into())
+	}
+
+	// the token of the pair `token_a`, `token_b` which is not `token`
+	fn other_token(
+		token: T::FungibleTokenId,
+		token_a: T::FungibleTokenId,
+		token_b: T::FungibleTokenId,
+	) -> Result<T::FungibleTokenId, DispatchError> {
+		ensure!(token_a != token_b, Error::<T>::TokenRepeat);
+		if token == token_a {
+			Ok(token_b)
+		} else if token == token_b {
+			Ok(token_a)
+		} else {
+			Err(Error::<T>::InvalidPath.into())
+		}
+	}
+
+	/// Add liquidity to the pool of `token_in` and `token_other` from `amount_in` of
+	/// `token_in` only.
+	pub fn do_zap_in(
+		who: T::AccountId,
+		token_in: T::FungibleTokenId,
+		token_other: T::FungibleTokenId,
+		fee: u32,
+		amount_in: Balance,
+		min_liquidity: Balance,
+		to: T::AccountId,
+	) -> Result<Balance, DispatchError> {
+		ensure!(amount_in > Zero::zero(), Error::<T>::InsufficientAmount);
+		let pool = Self::get_pool(token_in, token_other, fee)?;
+
+		let (reserve_in, reserve_out) = Self::get_reserves(token_in, token_other, fee)?;
+		ensure!(
+			reserve_in > Zero::zero() && reserve_out > Zero::zero(),
+			Error::<T>::InsufficientLiquidity
+		);
+		let swap_amount = Self::zap_swap_amount(amount_in, reserve_in, fee)?;
+		let amount_out = Self::get_amount_out(swap_amount, reserve_in, reserve_out, fee)?;
+
+		pallet_token_fungible::Pallet::<T>::do_transfer(
+			token_in,
+			&who,
+			&pool.lp_token_account_id,
+			swap_amount,
+		)?;
+		Self::do_swap(
+			who.clone(),
+			vec![swap_amount, amount_out],
+			vec![fee],
+			vec![token_in, token_other],
+			who.clone(),
 		)?;
 
-		let pool = Pool { token_0, token_1, lp_token, lp_token_account_id };
+		let amount_rest = amount_in.checked_sub(swap_amount).ok_or(Error::<T>::Overflow)?;
+		let (desired_0, desired_1) = if token_in == pool.token_0 {
+			(amount_rest, amount_out)
+		} else {
+			(amount_out, amount_rest)
+		};
+		let (amount_0, amount_1) = Self::do_add_liquidity(
+			pool.token_0,
+			pool.token_1,
+			fee,
+			desired_0,
+			desired_1,
+			Zero::zero(),
+			Zero::zero(),
+		)?;
 
-		Pools::<T>::insert((token_0, token_1), pool);
-		LpTokenToToken::<T>::insert(lp_token, (token_0, token_1));
+		// the dust the pool does not take is never moved from `who`
+		pallet_token_fungible::Pallet::<T>::do_transfer(
+			pool.token_0,
+			&who,
+			&pool.lp_token_account_id,
+			amount_0,
+		)?;
+		pallet_token_fungible::Pallet::<T>::do_transfer(
+			pool.token_1,
+			&who,
+			&pool.lp_token_account_id,
+			amount_1,
+		)?;
+		let liquidity = Self::mint(who.clone(), pool.token_0, pool.token_1, fee, to)?;
+		ensure!(liquidity >= min_liquidity, Error::<T>::InsufficientLiquidityMinted);
 
-		Self::deposit_event(Event::PoolCreated(id, token_0, token_1, who));
+		Self::deposit_event(Event::LiquidityAdded(pool.lp_token, amount_0, amount_1, liquidity));
+		Self::deposit_event(Event::ZappedIn(who, token_in, amount_in, pool.lp_token, liquidity));
 
-		Ok(id)
+		Ok(liquidity)
 	}
 
-	fn do_add_liquidity(
-		token_0: T::FungibleTokenId,
-		token_1: T::FungibleTokenId,
-		amount_a_desired: Balance,
-		amount_b_desired: Balance,
-		amount_a_min: Balance,
-		amount_b_min: Balance,
-	) -> Result<(Balance, Balance), DispatchError> {
-		let (reserve_a, reserve_b) = Self::get_reserves(token_0, token_1)?;
+	/// Remove `liquidity` from the pool of `token_other` and `token_out` and swap the
+	/// `token_other` withdrawn to `token_out`.
+	pub fn do_zap_out(
+		who: T::AccountId,
+		token_other: T::FungibleTokenId,
+		token_out: T::FungibleTokenId,
+		fee: u32,
+		liquidity: Balance,
+		amount_out_min: Balance,
+		to: T::AccountId,
+	) -> Result<Balance, DispatchError> {
+		let pool = Self::get_pool(token_out, token_other, fee)?;
 
-		let amount_a;
-		let amount_b;
-		// amount_a_desired amount_b_desired = amount_a_desired amount_b_desired
-		if reserve_a == Zero::zero() && reserve_b == Zero::zero() {
-			amount_a = amount_a_desired;
-			amount_b = amount_b_desired;
-		} else {
-			let amount_b_optimal = Self::quote(amount_a_desired, reserve_a, reserve_b)?;
-			if amount_b_optimal <= amount_b_desired {
-				ensure!(amount_b_optimal >= amount_b_min, Error::<T>::InsufficientBAmount);
-				amount_a = amount_a_desired;
-				amount_b = amount_b_optimal;
-			} else {
-				let amount_a_optimal = Self::quote(amount_b_desired, reserve_b, reserve_a)?;
-				ensure!(amount_a_optimal <= amount_a_desired, Error::<T>::InsufficientAmount);
-				ensure!(amount_a_optimal >= amount_a_min, Error::<T>::InsufficientAAmount);
-				amount_a = amount_a_optimal;
-				amount_b = amount_b_desired;
-			}
+		pallet_token_fungible::Pallet::<T>::do_transfer(
+			pool.lp_token,
+			&who,
+			&pool.lp_token_account_id,
+			liquidity,
+		)?;
+		let (amount_0, amount_1) =
+			Self::burn(who.clone(), pool.token_0, pool.token_1, fee, who.clone())?;
+		Self::deposit_event(Event::LiquidityRemoved(pool.lp_token, amount_0, amount_1, liquidity));
+
+		let (amount_kept, amount_swapped) =
+			if token_out == pool.token_0 { (amount_0, amount_1) } else { (amount_1, amount_0) };
+		let (reserve_in, reserve_out) = Self::get_reserves(token_other, token_out, fee)?;
+		let amount_swapped_out =
+			Self::get_amount_out(amount_swapped, reserve_in, reserve_out, fee)?;
+		let amount_out = amount_kept.checked_add(amount_swapped_out).ok_or(Error::<T>::Overflow)?;
+		ensure!(amount_out >= amount_out_min, Error::<T>::InsufficientOutAmount);
+
+		pallet_token_fungible::Pallet::<T>::do_transfer(
+			token_other,
+			&who,
+			&pool.lp_token_account_id,
+			amount_swapped,
+		)?;
+		Self::do_swap(
+			who.clone(),
+			vec![amount_swapped, amount_swapped_out],
+			vec![fee],
+			vec![token_other, token_out],
+			to.clone(),
+		)?;
+		if to != who {
+			pallet_token_fungible::Pallet::<T>::do_transfer(token_out, &who, &to, amount_kept)?;
 		}
 
-		Ok((amount_a, amount_b))
+		Self::deposit_event(Event::ZappedOut(who, pool.lp_token, liquidity, token_out, amount_out));
+
+		Ok(amount_out)
 	}
 
-	// requires the initial amount to have already been sent to the first pair
-	fn do_swap(
+	pub fn do_place_order(
 		who: T::AccountId,
-		amounts: Vec<Balance>,
-		path: Vec<T::FungibleTokenId>,
-		to: T::AccountId,
-	) -> DispatchResult {
-		for i in 0..(path.len() - 1) {
-			let (input, output) = (path[i], path[i + 1]);
-			let (token_0, token_1) = Self::sort_tokens(input, output);
+		token_in: T::FungibleTokenId,
+		token_out: T::FungibleTokenId,
+		amount_in: Balance,
+		price: FixedU128,
+		expiry: T::BlockNumber,
+	) -> Result<T::OrderId, DispatchError> {
+		ensure!(token_in != token_out, Error::<T>::TokenRepeat);
+		ensure!(
+			amount_in > Zero::zero() && !price.is_zero() && expiry > Self::now(),
+			Error::<T>::InvalidOrder
+		);
+		ensure!(!Self::pool_fees(token_in, token_out).is_empty(), Error::<T>::PoolNotFound);
 
-			let amount_out = amounts[i + 1];
-			let (amount_0_out, amount_1_out) = if input == token_0 {
-				(Balance::from(0u128), amount_out)
-			} else {
-				(amount_out, Balance::from(0u128))
-			};
+		let id = NextOrderId::<T>::try_mutate(|id| -> Result<T::OrderId, DispatchError> {
+			let current_id = *id;
+			*id = id.checked_add(&One::one()).ok_or(Error::<T>::NoAvailableOrderId)?;
+			Ok(current_id)
+		})?;
 
-			let receiver = if i < path.len() - 2 {
-				let (lp_token_0, lp_token_1) = Self::sort_tokens(output, path[i + 2]);
-				let pool =
-					Pools::<T>::get((lp_token_0, lp_token_1)).ok_or(Error::<T>::PoolNotFound)?;
-				pool.lp_token_account_id
-			} else {
-				to.clone()
+		pallet_token_fungible::Pallet::<T>::do_transfer(
+			token_in,
+			&who,
+			&Self::account_id(),
+			amount_in,
+		)?;
+
+		let order =
+			LimitOrder { owner: who.clone(), token_in, token_out, amount_in, price, expiry };
+		Orders::<T>::insert(id, order);
+
+		Self::deposit_event(Event::OrderPlaced(
+			id, who, token_in, token_out, amount_in, price, expiry,
+		));
+
+		Ok(id)
+	}
+
+	/// Fill as much of an order as possible, returning the amounts sold and bought. Expired
+	/// orders are refunded instead.
+	pub fn do_fill_order(order_id: T::OrderId) -> Result<(Balance, Balance), DispatchError> {
+		let mut order = Orders::<T>::get(order_id).ok_or(Error::<T>::OrderNotFound)?;
+		if Self::now() > order.expiry {
+			Self::close_order(order_id, order)?;
+			return Ok((Zero::zero(), Zero::zero()))
+		}
+
+		let (amount_in, amount_out, fee) =
+			Self::fillable_amount(&order).ok_or(Error::<T>::OrderNotFillable)?;
+		let path = vec![order.token_in, order.token_out];
+		let pool = Self::get_pool(order.token_in, order.token_out, fee)?;
+
+		pallet_token_fungible::Pallet::<T>::do_transfer(
+			order.token_in,
+			&Self::account_id(),
+			&pool.lp_token_account_id,
+			amount_in,
+		)?;
+		Self::do_swap(
+			Self::account_id(),
+			vec![amount_in, amount_out],
+			vec![fee],
+			path,
+			order.owner.clone(),
+		)?;
+
+		order.amount_in = order.amount_in.saturating_sub(amount_in);
+		let remaining = order.amount_in;
+		if remaining.is_zero() {
+			Orders::<T>::remove(order_id);
+		} else {
+			Orders::<T>::insert(order_id, order);
+		}
+
+		Self::deposit_event(Event::OrderFilled(order_id, amount_in, amount_out, remaining));
+
+		Ok((amount_in, amount_out))
+	}
+
+	/// The largest amount of an order a pool of its pair can fill at the order price, with the
+	/// amount bought and the fee tier of the pool.
+	pub fn fillable_amount(
+		order: &LimitOrder<T::AccountId, T::FungibleTokenId, T::BlockNumber>,
+	) -> Option<(Balance, Balance, u32)> {
+		let price = U256::from(order.price.into_inner());
+		let accuracy = U256::from(FixedU128::accuracy());
+
+		let mut best: Option<(Balance, Balance, u32)> = None;
+		for fee in Self::pool_fees(order.token_in, order.token_out) {
+			let (reserve_in, reserve_out) =
+				Self::get_reserves(order.token_in, order.token_out, fee).ok()?;
+			let fill = |amount_in: Balance| -> Option<Balance> {
+				let amount_out =
+					Self::get_amount_out(amount_in, reserve_in, reserve_out, fee).ok()?;
+				let min_out = (U256::from(amount_in) * price + accuracy - U256::one()) / accuracy;
+				if U256::from(amount_out) >= min_out {
+					Some(amount_out)
+				} else {
+					None
+				}
 			};
 
-			Self::swap(who.clone(), token_0, token_1, amount_0_out, amount_1_out, receiver)?;
+			// out(x) >= x * price as long as x <= reserve_out / price - reserve_in / (1 - fee)
+			let after_fee = U256::from(FEE_DENOMINATOR - fee);
+			let limit = U256::from(reserve_out).saturating_mul(accuracy) / price;
+			let reserve = (U256::from(reserve_in) * U256::from(FEE_DENOMINATOR) + after_fee -
+				U256::one()) / after_fee;
+			if limit <= reserve {
+				continue
+			}
+			let mut amount_in = cmp::min(U256::from(order.amount_in), limit - reserve).low_u128();
+
+			// rounding may push the bound just past the price, search below it
+			if fill(amount_in).is_none() {
+				let (mut low, mut high) = (Balance::zero(), amount_in);
+				while low + 1 < high {
+					let mid = low + (high - low) / 2;
+					if fill(mid).is_some() {
+						low = mid;
+					} else {
+						high = mid;
+					}
+				}
+				amount_in = low;
+			}
+
+			if let Some(amount_out) = fill(amount_in) {
+				if best.map_or(true, |(best_in, _, _)| amount_in > best_in) {
+					best = Some((amount_in, amount_out, fee));
+				}
+			}
 		}
 
-		Ok(())
+		best
 	}
 
-	pub fn swap(
-		who: T::AccountId,
-		token_0: T::FungibleTokenId,
-		token_1: T::FungibleTokenId,
-		amount_0_out: Balance,
-		amount_1_out: Balance,
-		to: T::AccountId,
+	/// Refund the rest of an order to its owner and remove it, as cancelled when it has not
+	/// expired yet.
+	fn close_order(
+		order_id: T::OrderId,
+		order: LimitOrder<T::AccountId, T::FungibleTokenId, T::BlockNumber>,
 	) -> DispatchResult {
-		ensure!(
-			amount_0_out > Zero::zero() || amount_1_out > Zero::zero(),
-			Error::<T>::InsufficientOutAmount
-		);
+		pallet_token_fungible::Pallet::<T>::do_transfer(
+			order.token_in,
+			&Self::account_id(),
+			&order.owner,
+			order.amount_in,
+		)?;
+		Orders::<T>::remove(order_id);
 
-		let (reserve_0, reserve_1) = Self::get_reserves(token_0, token_1)?;
-		let pool = Pools::<T>::get((token_0, token_1)).ok_or(Error::<T>::PoolNotFound)?;
+		if Self::now() > order.expiry {
+			Self::deposit_event(Event::OrderExpired(order_id, order.owner, order.amount_in));
+		} else {
+			Self::deposit_event(Event::OrderCancelled(order_id, order.owner, order.amount_in));
+		}
+		Ok(())
+	}
 
-		ensure!(
-			amount_0_out < reserve_0 && amount_1_out < reserve_1,
-			Error::<T>::InsufficientLiquidity
-		);
+	/// Fill or expire the orders after `OrderCursor`, as many as `remaining_weight` and
+	/// `MaxOrdersPerBlock` allow.
+	fn match_orders(remaining_weight: Weight) -> Weight {
+		let base_weight = T::DbWeight::get().reads_writes(1, 1);
+		let order_weight = <T as Config>::WeightInfo::fill_order();
+		let max_orders = cmp::min(
+			remaining_weight.saturating_sub(base_weight) / order_weight.max(1),
+			T::MaxOrdersPerBlock::get().into(),
+		) as usize;
+		if max_orders == 0 {
+			return 0
+		}
 
-		if amount_0_out > Zero::zero() {
-			pallet_token_fungible::Pallet::<T>::do_transfer(
-				pool.token_0,
-				&pool.lp_token_account_id,
-				&to,
-				amount_0_out,
-			)?;
+		let order_ids: Vec<T::OrderId> = match OrderCursor::<T>::get() {
+			Some(cursor) => Orders::<T>::iter_keys_from(Orders::<T>::hashed_key_for(cursor)),
+			None => Orders::<T>::iter_keys(),
 		}
-		if amount_1_out > Zero::zero() {
-			pallet_token_fungible::Pallet::<T>::do_transfer(
-				pool.token_1,
-				&pool.lp_token_account_id,
-				&to,
-				amount_1_out,
-			)?;
+		.take(max_orders)
+		.collect();
+
+		if order_ids.len() < max_orders {
+			OrderCursor::<T>::kill();
+		} else {
+			OrderCursor::<T>::set(order_ids.last().copied());
 		}
 
-		let balance_0 =
-			pallet_token_fungible::Pallet::<T>::balance_of(pool.token_0, &pool.lp_token_account_id);
-		let balance_1 =
-			pallet_token_fungible::Pallet::<T>::balance_of(pool.token_1, &pool.lp_token_account_id);
+		for order_id in order_ids.iter() {
+			let _ = with_transaction(|| match Self::do_fill_order(*order_id) {
+				Ok(_) => TransactionOutcome::Commit(Ok(())),
+				Err(e) => TransactionOutcome::Rollback(Err::<(), DispatchError>(e)),
+			});
+		}
 
-		let amount_0_in = Self::init_amount_in(balance_0, reserve_0, amount_0_out)?;
-		let amount_1_in = Self::init_amount_in(balance_1, reserve_1, amount_1_out)?;
+		base_weight.saturating_add(order_weight.saturating_mul(order_ids.len() as Weight))
+	}
 
-		ensure!(
-			amount_0_in > Zero::zero() || amount_1_in > Zero::zero(),
-			Error::<T>::InsufficientInputAmount
-		);
+	pub fn get_stable_pool(
+		pool_id: T::PoolId,
+	) -> Result<StablePool<T::AccountId, T::FungibleTokenId, T::BlockNumber>, DispatchError> {
+		StablePools::<T>::get(pool_id).ok_or_else(|| Error::<T>::StablePoolNotFound.into())
+	}
 
-		let balance_0_adjusted = Self::init_balance_adjusted(balance_0, amount_0_in)?;
-		let balance_1_adjusted = Self::init_balance_adjusted(balance_1, amount_1_in)?;
+	/// The current amplification coefficient of a StableSwap pool times `A_PRECISION`,
+	/// interpolated linearly while it is being ramped.
+	pub fn stable_amplification(
+		pool: &StablePool<T::AccountId, T::FungibleTokenId, T::BlockNumber>,
+	) -> u128 {
+		let now = Self::now();
+		if now >= pool.future_a_block {
+			return pool.future_a
+		}
+
+		let elapsed: u128 = now.saturating_sub(pool.initial_a_block).unique_saturated_into();
+		let duration: u128 =
+			pool.future_a_block.saturating_sub(pool.initial_a_block).unique_saturated_into();
+		if pool.future_a > pool.initial_a {
+			pool.initial_a
+				.saturating_add((pool.future_a - pool.initial_a).saturating_mul(elapsed) / duration)
+		} else {
+			pool.initial_a
+				.saturating_sub((pool.initial_a - pool.future_a).saturating_mul(elapsed) / duration)
+		}
+	}
 
+	pub fn do_create_stable_pool(
+		who: T::AccountId,
+		tokens: Vec<T::FungibleTokenId>,
+		amplification: u128,
+		fee: u32,
+	) -> Result<T::PoolId, DispatchError> {
 		ensure!(
-			balance_0_adjusted * balance_1_adjusted >=
-				reserve_0 * reserve_1 * Balance::from(1000u128) * Balance::from(1000u128),
-			Error::<T>::AdjustedError
+			tokens.len() >= 2 && tokens.len() <= MAX_STABLE_TOKENS as usize,
+			Error::<T>::InvalidStableTokens
 		);
+		ensure!(amplification > 0 && amplification <= MAX_A, Error::<T>::InvalidAmplification);
+		ensure!(FeeTiers::<T>::contains_key(fee), Error::<T>::FeeTierNotFound);
 
-		Self::do_update(token_0, token_1, balance_0, balance_1)?;
-		Self::deposit_event(Event::Swap(
-			who,
-			amount_0_in,
-			amount_1_in,
-			amount_0_out,
-			amount_1_out,
-			to,
-		));
-		Ok(())
-	}
+		let mut rates = Vec::with_capacity(tokens.len());
+		for (i, token) in tokens.iter().enumerate() {
+			ensure!(!tokens[..i].contains(token), Error::<T>::TokenRepeat);
+			ensure!(
+				pallet_token_fungible::Pallet::<T>::exists(*token),
+				Error::<T>::TokenAccountNotFound
+			);
+			let decimals = pallet_token_fungible::Pallet::<T>::token_decimals(*token);
+			ensure!(decimals <= STABLE_DECIMALS, Error::<T>::InvalidStableTokens);
+			rates.push(10u128.pow((STABLE_DECIMALS - decimals).into()));
+		}
+		let tokens: BoundedVec<T::FungibleTokenId, ConstU32<MAX_STABLE_TOKENS>> =
+			tokens.try_into().map_err(|_| Error::<T>::InvalidStableTokens)?;
+		let rates: BoundedVec<Balance, ConstU32<MAX_STABLE_TOKENS>> =
+			rates.try_into().map_err(|_| Error::<T>::InvalidStableTokens)?;
+		let reserves: BoundedVec<Balance, ConstU32<MAX_STABLE_TOKENS>> =
+			vec![Zero::zero(); tokens.len()]
+				.try_into()
+				.map_err(|_| Error::<T>::InvalidStableTokens)?;
+
+		let id = Self::new_pool_id(&who)?;
+
+		let lp_token = Self::generate_stable_lp_token_id(id)?;
+		let lp_token_account_id = Self::token_id_to_account_id(lp_token);
+		let name = Self::lp_token_name(b"W3G-SLP", &tokens);
+		let symbol: Vec<u8> = "W3G-SLP".as_bytes().to_vec();
 
-	pub fn mint_fee(
-		token_0: T::FungibleTokenId,
-		token_1: T::FungibleTokenId,
-		reserve_0: Balance,
-		reserve_1: Balance,
-	) -> Result<bool, DispatchError> {
-		let k_last = KLast::<T>::get((token_0, token_1));
-		if let Some(fee_on) = FeeTo::<T>::get() {
-			let pool = Pools::<T>::get((token_0, token_1)).ok_or(Error::<T>::PoolNotFound)?;
-			let total_supply = pallet_token_fungible::Pallet::<T>::total_supply(pool.lp_token);
-			if k_last != 0 {
-				let root_k =
-					(U256::from(reserve_0).saturating_mul(U256::from(reserve_1))).integer_sqrt();
-				let root_k_last = U256::from(k_last).integer_sqrt();
-				if root_k > root_k_last {
-					let numerator = U256::from(total_supply).saturating_mul(
-						root_k.checked_sub(root_k_last).ok_or(Error::<T>::Overflow)?,
-					);
-					let denominator = root_k
-						.saturating_mul(U256::from(5u128))
-						.checked_add(root_k_last)
-						.ok_or(Error::<T>::Overflow)?;
-					let liquidity = numerator
-						.checked_div(denominator)
-						.and_then(|l| TryInto::<Balance>::try_into(l).ok())
-						.ok_or(Error::<T>::Overflow)?;
-					if liquidity > 0 {
-						pallet_token_fungible::Pallet::<T>::do_mint(
-							pool.lp_token,
-							&Self::account_id(),
-							fee_on,
-							liquidity,
-						)?;
-					}
-				}
+		pallet_token_fungible::Pallet::<T>::do_create_reserved_token(
+			&Self::account_id(),
+			lp_token,
+			name,
+			symbol,
+			STABLE_DECIMALS,
+		)?;
+
+		let now = Self::now();
+		let a = amplification * A_PRECISION;
+		let pool = StablePool {
+			tokens: tokens.clone(),
+			rates,
+			lp_token,
+			lp_token_account_id,
+			fee,
+			initial_a: a,
+			future_a: a,
+			initial_a_block: now,
+			future_a_block: now,
+		};
+
+		StablePools::<T>::insert(id, pool);
+		StableReserves::<T>::insert(id, reserves);
+		StableLpTokenToPool::<T>::insert(lp_token, id);
+
+		Self::deposit_event(Event::StablePoolCreated(
+			id,
+			tokens.into_inner(),
+			amplification,
+			fee,
+			who,
+		));
+
+		Ok(id)
+	}
+
+	pub fn do_add_stable_liquidity(
+		who: T::AccountId,
+		pool_id: T::PoolId,
+		amounts: Vec<Balance>,
+		to: T::AccountId,
+	) -> Result<Balance, DispatchError> {
+		let pool = Self::get_stable_pool(pool_id)?;
+		Self::ensure_stable_status(pool_id, PoolStatus::can_add_liquidity)?;
+		let (liquidity, balances) =
+			Self::calc_stable_liquidity(&pool, &StableReserves::<T>::get(pool_id), &amounts)?;
+		ensure!(liquidity > Zero::zero(), Error::<T>::InsufficientLiquidityMinted);
+
+		for (token, amount) in pool.tokens.iter().zip(amounts.iter()) {
+			if !amount.is_zero() {
+				pallet_token_fungible::Pallet::<T>::do_transfer(
+					*token,
+					&who,
+					&pool.lp_token_account_id,
+					*amount,
+				)?;
 			}
-			Ok(true)
-		} else {
-			if k_last != 0 {
-				KLast::<T>::mutate((token_0, token_1), |k| *k = 0);
+		}
+		pallet_token_fungible::Pallet::<T>::do_mint(
+			pool.lp_token,
+			&Self::account_id(),
+			to,
+			liquidity,
+		)?;
+		StableReserves::<T>::insert(
+			pool_id,
+			BoundedVec::try_from(balances).map_err(|_| Error::<T>::InvalidAmounts)?,
+		);
+
+		Self::deposit_event(Event::StableLiquidityAdded(pool_id, who, amounts, liquidity));
+
+		Ok(liquidity)
+	}
+
+	pub fn do_remove_stable_liquidity(
+		who: T::AccountId,
+		pool_id: T::PoolId,
+		liquidity: Balance,
+		to: T::AccountId,
+	) -> Result<Vec<Balance>, DispatchError> {
+		let pool = Self::get_stable_pool(pool_id)?;
+		Self::ensure_stable_status(pool_id, PoolStatus::can_remove_liquidity)?;
+		let amounts = Self::stable_liquidity_to_tokens(pool_id, liquidity)?;
+		ensure!(
+			amounts.iter().any(|amount| !amount.is_zero()),
+			Error::<T>::InsufficientLiquidityBurned
+		);
+
+		pallet_token_fungible::Pallet::<T>::do_burn(pool.lp_token, &who, liquidity)?;
+		for (token, amount) in pool.tokens.iter().zip(amounts.iter()) {
+			if !amount.is_zero() {
+				pallet_token_fungible::Pallet::<T>::do_transfer(
+					*token,
+					&pool.lp_token_account_id,
+					&to,
+					*amount,
+				)?;
 			}
-			Ok(false)
 		}
+		let reserves = StableReserves::<T>::get(pool_id)
+			.iter()
+			.zip(amounts.iter())
+			.map(|(reserve, amount)| reserve.checked_sub(*amount).ok_or(Error::<T>::Overflow))
+			.collect::<Result<Vec<Balance>, _>>()?;
+		StableReserves::<T>::insert(
+			pool_id,
+			BoundedVec::try_from(reserves).map_err(|_| Error::<T>::InvalidAmounts)?,
+		);
+
+		Self::deposit_event(Event::StableLiquidityRemoved(
+			pool_id,
+			who,
+			amounts.clone(),
+			liquidity,
+		));
+
+		Ok(amounts)
 	}
 
-	pub fn mint(
+	pub fn do_swap_stable(
 		who: T::AccountId,
-		token_0: T::FungibleTokenId,
-		token_1: T::FungibleTokenId,
+		pool_id: T::PoolId,
+		token_in: T::FungibleTokenId,
+		token_out: T::FungibleTokenId,
+		amount_in: Balance,
 		to: T::AccountId,
 	) -> Result<Balance, DispatchError> {
-		let pool = Pools::<T>::get((token_0, token_1)).ok_or(Error::<T>::PoolNotFound)?;
+		let pool = Self::get_stable_pool(pool_id)?;
+		Self::ensure_stable_status(pool_id, PoolStatus::can_swap)?;
+		let (i, j) = Self::stable_token_indexes(&pool, token_in, token_out)?;
+		let mut reserves = StableReserves::<T>::get(pool_id).into_inner();
+		let amount_out = Self::calc_stable_amount_out(&pool, &reserves, i, j, amount_in)?;
 
-		let (reserve_0, reserve_1) = Reserves::<T>::get((token_0, token_1));
+		pallet_token_fungible::Pallet::<T>::do_transfer(
+			token_in,
+			&who,
+			&pool.lp_token_account_id,
+			amount_in,
+		)?;
+		pallet_token_fungible::Pallet::<T>::do_transfer(
+			token_out,
+			&pool.lp_token_account_id,
+			&to,
+			amount_out,
+		)?;
+		reserves[i] = reserves[i].checked_add(amount_in).ok_or(Error::<T>::Overflow)?;
+		reserves[j] = reserves[j].checked_sub(amount_out).ok_or(Error::<T>::Overflow)?;
+		StableReserves::<T>::insert(
+			pool_id,
+			BoundedVec::try_from(reserves).map_err(|_| Error::<T>::InvalidAmounts)?,
+		);
 
-		let balance_0 =
-			pallet_token_fungible::Pallet::<T>::balance_of(pool.token_0, &pool.lp_token_account_id);
-		let balance_1 =
-			pallet_token_fungible::Pallet::<T>::balance_of(pool.token_1, &pool.lp_token_account_id);
+		Self::deposit_event(Event::StableSwap(
+			pool_id, who, token_in, token_out, amount_in, amount_out, to,
+		));
 
-		let amount_0 = balance_0.checked_sub(reserve_0).ok_or(Error::<T>::Overflow)?;
-		let amount_1 = balance_1.checked_sub(reserve_1).ok_or(Error::<T>::Overflow)?;
+		Ok(amount_out)
+	}
 
-		let fee_on = Self::mint_fee(token_0, token_1, reserve_0, reserve_1)?;
+	/// The amount of `token_out` a StableSwap pool gives for `amount_in` of `token_in`.
+	pub fn get_stable_amount_out(
+		pool_id: T::PoolId,
+		token_in: T::FungibleTokenId,
+		token_out: T::FungibleTokenId,
+		amount_in: Balance,
+	) -> Result<Balance, DispatchError> {
+		let pool = Self::get_stable_pool(pool_id)?;
+		let (i, j) = Self::stable_token_indexes(&pool, token_in, token_out)?;
+		Self::calc_stable_amount_out(&pool, &StableReserves::<T>::get(pool_id), i, j, amount_in)
+	}
 
-		let liquidity: Balance;
+	/// The liquidity minted for depositing `amounts` into a StableSwap pool.
+	pub fn get_stable_liquidity(
+		pool_id: T::PoolId,
+		amounts: &[Balance],
+	) -> Result<Balance, DispatchError> {
+		let pool = Self::get_stable_pool(pool_id)?;
+		Self::calc_stable_liquidity(&pool, &StableReserves::<T>::get(pool_id), amounts)
+			.map(|(liquidity, _)| liquidity)
+	}
 
+	/// The amounts withdrawn from a StableSwap pool for burning `liquidity`.
+	pub fn stable_liquidity_to_tokens(
+		pool_id: T::PoolId,
+		liquidity: Balance,
+	) -> Result<Vec<Balance>, DispatchError> {
+		let pool = Self::get_stable_pool(pool_id)?;
 		let total_supply = pallet_token_fungible::Pallet::<T>::total_supply(pool.lp_token);
-		if total_supply == Zero::zero() {
-			liquidity = (amount_0 * amount_1)
-				.integer_sqrt()
-				.checked_sub(Balance::from(MINIMUM_LIQUIDITY))
-				.ok_or(Error::<T>::Overflow)?;
-			// permanently lock the first MINIMUM_LIQUIDITY tokens
-			pallet_token_fungible::Pallet::<T>::do_mint(
-				pool.lp_token,
-				&Self::account_id(),
-				Self::account_id(),
-				Balance::from(MINIMUM_LIQUIDITY),
-			)?;
-		} else {
-			liquidity = cmp::min(
-				U256::from(amount_0)
-					.checked_mul(U256::from(total_supply))
-					.and_then(|l| l.checked_div(U256::from(reserve_0)))
-					.and_then(|l| TryInto::<Balance>::try_into(l).ok())
-					.ok_or(Error::<T>::Overflow)?,
-				U256::from(amount_1)
-					.checked_mul(U256::from(total_supply))
-					.and_then(|l| l.checked_div(U256::from(reserve_1)))
-					.and_then(|l| TryInto::<Balance>::try_into(l).ok())
-					.ok_or(Error::<T>::Overflow)?,
-			);
-		}
-		ensure!(liquidity >= Zero::zero(), Error::<T>::InsufficientLiquidityMinted);
-		pallet_token_fungible::Pallet::<T>::do_mint(
-			pool.lp_token,
-			&Self::account_id(),
-			to.clone(),
-			liquidity,
-		)?;
+		ensure!(liquidity <= total_supply, Error::<T>::InsufficientLiquidity);
+
+		StableReserves::<T>::get(pool_id)
+			.iter()
+			.map(|reserve| {
+				U256::from(*reserve)
+					.checked_mul(U256::from(liquidity))
+					.and_then(|a| a.checked_div(U256::from(total_supply)))
+					.and_then(|a| TryInto::<Balance>::try_into(a).ok())
+					.ok_or_else(|| Error::<T>::Overflow.into())
+			})
+			.collect()
+	}
 
-		Self::do_update(token_0, token_1, balance_0, balance_1)?;
+	fn stable_token_indexes(
+		pool: &StablePool<T::AccountId, T::FungibleTokenId, T::BlockNumber>,
+		token_in: T::FungibleTokenId,
+		token_out: T::FungibleTokenId,
+	) -> Result<(usize, usize), DispatchError> {
+		ensure!(token_in != token_out, Error::<T>::TokenRepeat);
+		let i = pool.tokens.iter().position(|t| *t == token_in);
+		let j = pool.tokens.iter().position(|t| *t == token_out);
+		i.zip(j).ok_or_else(|| Error::<T>::TokenNotInPool.into())
+	}
 
-		if fee_on {
-			let (_reserve_0, _reserve_1) = Reserves::<T>::get((token_0, token_1));
-			KLast::<T>::mutate((token_0, token_1), |k| *k = _reserve_0 * _reserve_1);
-		}
-		Self::deposit_event(Event::Mint(who, amount_0, amount_1, to));
-		Ok(liquidity)
+	fn stable_xp(balances: &[Balance], rates: &[Balance]) -> Result<Vec<U256>, DispatchError> {
+		balances
+			.iter()
+			.zip(rates.iter())
+			.map(|(balance, rate)| {
+				U256::from(*balance)
+					.checked_mul(U256::from(*rate))
+					.ok_or_else(|| Error::<T>::Overflow.into())
+			})
+			.collect()
 	}
 
-	pub fn get_liquidity(
-		token_a: T::FungibleTokenId,
-		amount_a: Balance,
-		token_b: T::FungibleTokenId,
-		amount_b: Balance,
+	fn calc_stable_amount_out(
+		pool: &StablePool<T::AccountId, T::FungibleTokenId, T::BlockNumber>,
+		reserves: &[Balance],
+		i: usize,
+		j: usize,
+		amount_in: Balance,
 	) -> Result<Balance, DispatchError> {
-		let (token_0, token_1) = Self::sort_tokens(token_a, token_b);
-		let amount_0;
-		let amount_1;
-		if token_a == token_0 {
-			amount_0 = amount_a;
-			amount_1 = amount_b;
+		ensure!(amount_in > Zero::zero(), Error::<T>::InsufficientInputAmount);
+		let amp = U256::from(Self::stable_amplification(pool));
+		let xp = Self::stable_xp(reserves, &pool.rates)?;
+		ensure!(xp.iter().all(|x| !x.is_zero()), Error::<T>::InsufficientLiquidity);
+
+		let x = U256::from(amount_in)
+			.checked_mul(U256::from(pool.rates[i]))
+			.and_then(|dx| dx.checked_add(xp[i]))
+			.ok_or(Error::<T>::Overflow)?;
+		let amount_out = stable_swap::get_d(&xp, amp)
+			.and_then(|d| stable_swap::get_y(i, j, x, &xp, amp, d))
+			.and_then(|y| xp[j].checked_sub(y))
+			.and_then(|dy| dy.checked_sub(U256::one()))
+			.and_then(|dy| {
+				let fee = dy.checked_mul(U256::from(pool.fee))? / U256::from(FEE_DENOMINATOR);
+				dy.checked_sub(fee)?.checked_div(U256::from(pool.rates[j]))
+			})
+			.and_then(|dy| TryInto::<Balance>::try_into(dy).ok())
+			.ok_or(Error::<T>::StableMathFailed)?;
+		ensure!(amount_out > Zero::zero(), Error::<T>::InsufficientOutputAmount);
+
+		Ok(amount_out)
+	}
+
+	/// The liquidity minted for depositing `amounts`, and the balances of the pool after it.
+	/// Deposits off the pool's proportions pay the swap fee on their imbalance.
+	fn calc_stable_liquidity(
+		pool: &StablePool<T::AccountId, T::FungibleTokenId, T::BlockNumber>,
+		reserves: &[Balance],
+		amounts: &[Balance],
+	) -> Result<(Balance, Vec<Balance>), DispatchError> {
+		let n = pool.tokens.len();
+		ensure!(amounts.len() == n, Error::<T>::InvalidAmounts);
+		let amp = U256::from(Self::stable_amplification(pool));
+		let total_supply = pallet_token_fungible::Pallet::<T>::total_supply(pool.lp_token);
+
+		let mut balances = reserves.to_vec();
+		for (balance, amount) in balances.iter_mut().zip(amounts.iter()) {
+			ensure!(!total_supply.is_zero() || !amount.is_zero(), Error::<T>::InvalidAmounts);
+			*balance = balance.checked_add(*amount).ok_or(Error::<T>::Overflow)?;
+		}
+
+		let d0 = if total_supply.is_zero() {
+			U256::zero()
 		} else {
-			amount_0 = amount_b;
-			amount_1 = amount_a;
+			stable_swap::get_d(&Self::stable_xp(reserves, &pool.rates)?, amp)
+				.ok_or(Error::<T>::StableMathFailed)?
+		};
+		let d1 = stable_swap::get_d(&Self::stable_xp(&balances, &pool.rates)?, amp)
+			.ok_or(Error::<T>::StableMathFailed)?;
+		ensure!(d1 > d0, Error::<T>::InsufficientLiquidityMinted);
+
+		if total_supply.is_zero() {
+			let liquidity = TryInto::<Balance>::try_into(d1).map_err(|_| Error::<T>::Overflow)?;
+			return Ok((liquidity, balances))
 		}
-		let pool = Pools::<T>::get((token_0, token_1)).ok_or(Error::<T>::PoolNotFound)?;
-		let (reserve_0, reserve_1) = Reserves::<T>::get((token_0, token_1));
-		let liquidity: Balance;
-		let total_supply = pallet_token_fungible::Pallet::<T>::total_supply(pool.lp_token);
-		if total_supply == Zero::zero() {
-			liquidity = (amount_0 * amount_1)
-				.integer_sqrt()
-				.checked_sub(MINIMUM_LIQUIDITY)
+
+		let fee = U256::from(pool.fee) * U256::from(n) / U256::from(4 * (n - 1));
+		let mut adjusted = Vec::with_capacity(n);
+		for (old, new) in reserves.iter().zip(balances.iter()) {
+			let ideal = d1
+				.checked_mul(U256::from(*old))
+				.and_then(|b| b.checked_div(d0))
 				.ok_or(Error::<T>::Overflow)?;
-		} else {
-			liquidity = cmp::min(
-				U256::from(amount_0)
-					.checked_mul(U256::from(total_supply))
-					.and_then(|l| l.checked_div(U256::from(reserve_0)))
-					.and_then(|l| TryInto::<Balance>::try_into(l).ok())
-					.ok_or(Error::<T>::Overflow)?,
-				U256::from(amount_1)
-					.checked_mul(U256::from(total_supply))
-					.and_then(|l| l.checked_div(U256::from(reserve_1)))
-					.and_then(|l| TryInto::<Balance>::try_into(l).ok())
-					.ok_or(Error::<T>::Overflow)?,
+			let new = U256::from(*new);
+			let difference = if ideal > new { ideal - new } else { new - ideal };
+			let charged = difference.saturating_mul(fee) / U256::from(FEE_DENOMINATOR);
+			adjusted.push(
+				TryInto::<Balance>::try_into(new.saturating_sub(charged))
+					.map_err(|_| Error::<T>::Overflow)?,
 			);
 		}
-		ensure!(liquidity >= Zero::zero(), Error::<T>::InsufficientLiquidityMinted);
+		let d2 = stable_swap::get_d(&Self::stable_xp(&adjusted, &pool.rates)?, amp)
+			.ok_or(Error::<T>::StableMathFailed)?;
+		let liquidity = U256::from(total_supply)
+			.checked_mul(d2.saturating_sub(d0))
+			.and_then(|l| l.checked_div(d0))
+			.and_then(|l| TryInto::<Balance>::try_into(l).ok())
+			.ok_or(Error::<T>::Overflow)?;
 
-		Ok(liquidity)
+		Ok((liquidity, balances))
 	}
 
-	pub fn liquidity_to_token(
-		lp_token: T::FungibleTokenId,
-		lp_balance: Balance,
-	) -> Result<(Balance, Balance), DispatchError> {
-		let (token_0, token_1) =
-			LpTokenToToken::<T>::get(lp_token).ok_or(Error::<T>::PoolNotFound)?;
-		ensure!(token_0 != token_1, Error::<T>::PoolNotFound);
-		let pool = Pools::<T>::get((token_0, token_1)).ok_or(Error::<T>::PoolNotFound)?;
+	pub fn get_weighted_pool(
+		pool_id: T::PoolId,
+	) -> Result<WeightedPool<T::AccountId, T::FungibleTokenId>, DispatchError> {
+		WeightedPools::<T>::get(pool_id).ok_or_else(|| Error::<T>::WeightedPoolNotFound.into())
+	}
 
-		let balance_0 =
-			pallet_token_fungible::Pallet::<T>::balance_of(pool.token_0, &pool.lp_token_account_id);
-		let balance_1 =
-			pallet_token_fungible::Pallet::<T>::balance_of(pool.token_1, &pool.lp_token_account_id);
+	pub fn do_create_weighted_pool(
+		who: T::AccountId,
+		tokens: Vec<T::FungibleTokenId>,
+		weights: Vec<Permill>,
+		fee: u32,
+		amounts: Vec<Balance>,
+	) -> Result<T::PoolId, DispatchError> {
+		ensure!(
+			tokens.len() >= 2 && tokens.len() <= MAX_WEIGHTED_TOKENS as usize,
+			Error::<T>::InvalidWeightedTokens
+		);
+		ensure!(weights.len() == tokens.len(), Error::<T>::InvalidWeights);
+		ensure!(
+			weights.iter().all(|weight| *weight >= MIN_WEIGHT) &&
+				weights.iter().map(|weight| weight.deconstruct()).sum::<u32>() ==
+					Permill::one().deconstruct(),
+			Error::<T>::InvalidWeights
+		);
+		ensure!(
+			amounts.len() == tokens.len() && amounts.iter().all(|amount| !amount.is_zero()),
+			Error::<T>::InvalidAmounts
+		);
+		ensure!(FeeTiers::<T>::contains_key(fee), Error::<T>::FeeTierNotFound);
+		for (i, token) in tokens.iter().enumerate() {
+			ensure!(!tokens[..i].contains(token), Error::<T>::TokenRepeat);
+			ensure!(
+				pallet_token_fungible::Pallet::<T>::exists(*token),
+				Error::<T>::TokenAccountNotFound
+			);
+		}
+		let tokens: BoundedVec<T::FungibleTokenId, ConstU32<MAX_WEIGHTED_TOKENS>> =
+			tokens.try_into().map_err(|_| Error::<T>::InvalidWeightedTokens)?;
+		let weights: BoundedVec<Permill, ConstU32<MAX_WEIGHTED_TOKENS>> =
+			weights.try_into().map_err(|_| Error::<T>::InvalidWeights)?;
+		let reserves: BoundedVec<Balance, ConstU32<MAX_WEIGHTED_TOKENS>> =
+			amounts.clone().try_into().map_err(|_| Error::<T>::InvalidAmounts)?;
 
-		let liquidity_balance = pallet_token_fungible::Pallet::<T>::balance_of(
+		let id = Self::new_pool_id(&who)?;
+
+		let lp_token = Self::generate_weighted_lp_token_id(id)?;
+		let lp_token_account_id = Self::token_id_to_account_id(lp_token);
+		let name = Self::lp_token_name(b"W3G-WLP", &tokens);
+		let symbol: Vec<u8> = "W3G-WLP".as_bytes().to_vec();
+
+		pallet_token_fungible::Pallet::<T>::do_create_reserved_token(
+			&Self::account_id(),
+			lp_token,
+			name,
+			symbol,
+			WEIGHTED_DECIMALS,
+		)?;
+
+		for (token, amount) in tokens.iter().zip(amounts.iter()) {
+			pallet_token_fungible::Pallet::<T>::do_transfer(
+				*token,
+				&who,
+				&lp_token_account_id,
+				*amount,
+			)?;
+		}
+		pallet_token_fungible::Pallet::<T>::do_mint(
+			lp_token,
+			&Self::account_id(),
+			who.clone(),
+			INIT_WEIGHTED_SUPPLY,
+		)?;
+
+		let pool = WeightedPool {
+			tokens: tokens.clone(),
+			weights: weights.clone(),
+			lp_token,
+			lp_token_account_id,
+			fee,
+		};
+
+		WeightedPools::<T>::insert(id, pool);
+		WeightedReserves::<T>::insert(id, reserves);
+		WeightedLpTokenToPool::<T>::insert(lp_token, id);
+
+		Self::deposit_event(Event::WeightedPoolCreated(
+			id,
+			tokens.into_inner(),
+			weights.into_inner(),
+			fee,
+			who.clone(),
+		));
+		Self::deposit_event(Event::WeightedLiquidityAdded(id, who, amounts, INIT_WEIGHTED_SUPPLY));
+
+		Ok(id)
+	}
+
+	pub fn do_add_weighted_liquidity(
+		who: T::AccountId,
+		pool_id: T::PoolId,
+		liquidity: Balance,
+		to: T::AccountId,
+	) -> Result<Vec<Balance>, DispatchError> {
+		let pool = Self::get_weighted_pool(pool_id)?;
+		Self::ensure_weighted_status(pool_id, PoolStatus::can_add_liquidity)?;
+		ensure!(liquidity > Zero::zero(), Error::<T>::InsufficientLiquidityMinted);
+		let total_supply = pallet_token_fungible::Pallet::<T>::total_supply(pool.lp_token);
+		ensure!(!total_supply.is_zero(), Error::<T>::InsufficientLiquidity);
+
+		// the deposit is rounded up so that minting never dilutes the pool
+		let amounts = WeightedReserves::<T>::get(pool_id)
+			.iter()
+			.map(|reserve| {
+				U256::from(*reserve)
+					.checked_mul(U256::from(liquidity))
+					.and_then(|a| a.checked_add(U256::from(total_supply - 1)))
+					.and_then(|a| a.checked_div(U256::from(total_supply)))
+					.and_then(|a| TryInto::<Balance>::try_into(a).ok())
+					.ok_or_else(|| Error::<T>::Overflow.into())
+			})
+			.collect::<Result<Vec<Balance>, DispatchError>>()?;
+
+		for (token, amount) in pool.tokens.iter().zip(amounts.iter()) {
+			pallet_token_fungible::Pallet::<T>::do_transfer(
+				*token,
+				&who,
+				&pool.lp_token_account_id,
+				*amount,
+			)?;
+		}
+		pallet_token_fungible::Pallet::<T>::do_mint(
 			pool.lp_token,
-			&pool.lp_token_account_id,
+			&Self::account_id(),
+			to,
+			liquidity,
+		)?;
+		let reserves = WeightedReserves::<T>::get(pool_id)
+			.iter()
+			.zip(amounts.iter())
+			.map(|(reserve, amount)| reserve.checked_add(*amount).ok_or(Error::<T>::Overflow))
+			.collect::<Result<Vec<Balance>, _>>()?;
+		WeightedReserves::<T>::insert(
+			pool_id,
+			BoundedVec::try_from(reserves).map_err(|_| Error::<T>::InvalidAmounts)?,
 		);
 
-		let liquidity = liquidity_balance + lp_balance;
+		Self::deposit_event(Event::WeightedLiquidityAdded(
+			pool_id,
+			who,
+			amounts.clone(),
+			liquidity,
+		));
 
-		let total_supply = pallet_token_fungible::Pallet::<T>::total_supply(pool.lp_token);
+		Ok(amounts)
+	}
 
-		let amount_0 = U256::from(liquidity)
-			.checked_mul(U256::from(balance_0))
-			.and_then(|l| l.checked_div(U256::from(total_supply)))
-			.and_then(|l| TryInto::<Balance>::try_into(l).ok())
-			.ok_or(Error::<T>::Overflow)?;
-		let amount_1 = U256::from(liquidity)
-			.checked_mul(U256::from(balance_1))
-			.and_then(|l| l.checked_div(U256::from(total_supply)))
-			.and_then(|l| TryInto::<Balance>::try_into(l).ok())
-			.ok_or(Error::<T>::Overflow)?;
+	pub fn do_remove_weighted_liquidity(
+		who: T::AccountId,
+		pool_id: T::PoolId,
+		liquidity: Balance,
+		to: T::AccountId,
+	) -> Result<Vec<Balance>, DispatchError> {
+		let pool = Self::get_weighted_pool(pool_id)?;
+		Self::ensure_weighted_status(pool_id, PoolStatus::can_remove_liquidity)?;
+		let amounts = Self::weighted_liquidity_to_tokens(pool_id, liquidity)?;
 		ensure!(
-			amount_0 > Zero::zero() && amount_1 > Zero::zero(),
+			amounts.iter().any(|amount| !amount.is_zero()),
 			Error::<T>::InsufficientLiquidityBurned
 		);
-		Ok((amount_0, amount_1))
+
+		pallet_token_fungible::Pallet::<T>::do_burn(pool.lp_token, &who, liquidity)?;
+		for (token, amount) in pool.tokens.iter().zip(amounts.iter()) {
+			if !amount.is_zero() {
+				pallet_token_fungible::Pallet::<T>::do_transfer(
+					*token,
+					&pool.lp_token_account_id,
+					&to,
+					*amount,
+				)?;
+			}
+		}
+		let reserves = WeightedReserves::<T>::get(pool_id)
+			.iter()
+			.zip(amounts.iter())
+			.map(|(reserve, amount)| reserve.checked_sub(*amount).ok_or(Error::<T>::Overflow))
+			.collect::<Result<Vec<Balance>, _>>()?;
+		WeightedReserves::<T>::insert(
+			pool_id,
+			BoundedVec::try_from(reserves).map_err(|_| Error::<T>::InvalidAmounts)?,
+		);
+
+		Self::deposit_event(Event::WeightedLiquidityRemoved(
+			pool_id,
+			who,
+			amounts.clone(),
+			liquidity,
+		));
+
+		Ok(amounts)
 	}
 
-	pub fn burn(
+	pub fn do_add_weighted_liquidity_single(
 		who: T::AccountId,
-		token_0: T::FungibleTokenId,
-		token_1: T::FungibleTokenId,
+		pool_id: T::PoolId,
+		token_in: T::FungibleTokenId,
+		amount_in: Balance,
 		to: T::AccountId,
-	) -> Result<(Balance, Balance), DispatchError> {
-		let pool = Pools::<T>::get((token_0, token_1)).ok_or(Error::<T>::PoolNotFound)?;
-		let (reserve_0, reserve_1) = Reserves::<T>::get((token_0, token_1));
-
-		let mut balance_0 =
-			pallet_token_fungible::Pallet::<T>::balance_of(pool.token_0, &pool.lp_token_account_id);
-		let mut balance_1 =
-			pallet_token_fungible::Pallet::<T>::balance_of(pool.token_1, &pool.lp_token_account_id);
+	) -> Result<Balance, DispatchError> {
+		let pool = Self::get_weighted_pool(pool_id)?;
+		Self::ensure_weighted_status(pool_id, PoolStatus::can_add_liquidity)?;
+		let i = Self::weighted_token_index(&pool, token_in)?;
+		let mut reserves = WeightedReserves::<T>::get(pool_id).into_inner();
+		let liquidity = Self::calc_weighted_single_in(&pool, &reserves, i, amount_in)?;
 
-		let liquidity = pallet_token_fungible::Pallet::<T>::balance_of(
-			pool.lp_token,
+		pallet_token_fungible::Pallet::<T>::do_transfer(
+			token_in,
+			&who,
 			&pool.lp_token_account_id,
+			amount_in,
+		)?;
+		pallet_token_fungible::Pallet::<T>::do_mint(
+			pool.lp_token,
+			&Self::account_id(),
+			to,
+			liquidity,
+		)?;
+		reserves[i] = reserves[i].checked_add(amount_in).ok_or(Error::<T>::Overflow)?;
+		WeightedReserves::<T>::insert(
+			pool_id,
+			BoundedVec::try_from(reserves).map_err(|_| Error::<T>::InvalidAmounts)?,
 		);
 
-		let fee_on = Self::mint_fee(token_0, token_1, reserve_0, reserve_1)?;
+		let mut amounts = vec![Zero::zero(); pool.tokens.len()];
+		amounts[i] = amount_in;
+		Self::deposit_event(Event::WeightedLiquidityAdded(pool_id, who, amounts, liquidity));
 
-		let total_supply = pallet_token_fungible::Pallet::<T>::total_supply(pool.lp_token);
+		Ok(liquidity)
+	}
 
-		let amount_0 = U256::from(liquidity)
-			.checked_mul(U256::from(balance_0))
-			.and_then(|l| l.checked_div(U256::from(total_supply)))
-			.and_then(|l| TryInto::<Balance>::try_into(l).ok())
-			.ok_or(Error::<T>::Overflow)?;
-		let amount_1 = U256::from(liquidity)
-			.checked_mul(U256::from(balance_1))
-			.and_then(|l| l.checked_div(U256::from(total_supply)))
-			.and_then(|l| TryInto::<Balance>::try_into(l).ok())
-			.ok_or(Error::<T>::Overflow)?;
-		ensure!(
-			amount_0 > Zero::zero() && amount_1 > Zero::zero(),
-			Error::<T>::InsufficientLiquidityBurned
-		);
+	pub fn do_remove_weighted_liquidity_single(
+		who: T::AccountId,
+		pool_id: T::PoolId,
+		token_out: T::FungibleTokenId,
+		liquidity: Balance,
+		to: T::AccountId,
+	) -> Result<Balance, DispatchError> {
+		let pool = Self::get_weighted_pool(pool_id)?;
+		Self::ensure_weighted_status(pool_id, PoolStatus::can_remove_liquidity)?;
+		let i = Self::weighted_token_index(&pool, token_out)?;
+		let mut reserves = WeightedReserves::<T>::get(pool_id).into_inner();
+		let amount_out = Self::calc_weighted_single_out(&pool, &reserves, i, liquidity)?;
 
-		pallet_token_fungible::Pallet::<T>::do_burn(
-			pool.lp_token,
+		pallet_token_fungible::Pallet::<T>::do_burn(pool.lp_token, &who, liquidity)?;
+		pallet_token_fungible::Pallet::<T>::do_transfer(
+			token_out,
 			&pool.lp_token_account_id,
-			liquidity,
+			&to,
+			amount_out,
 		)?;
+		reserves[i] = reserves[i].checked_sub(amount_out).ok_or(Error::<T>::Overflow)?;
+		WeightedReserves::<T>::insert(
+			pool_id,
+			BoundedVec::try_from(reserves).map_err(|_| Error::<T>::InvalidAmounts)?,
+		);
+
+		let mut amounts = vec![Zero::zero(); pool.tokens.len()];
+		amounts[i] = amount_out;
+		Self::deposit_event(Event::WeightedLiquidityRemoved(pool_id, who, amounts, liquidity));
+
+		Ok(amount_out)
+	}
+
+	pub fn do_swap_weighted(
+		who: T::AccountId,
+		pool_id: T::PoolId,
+		token_in: T::FungibleTokenId,
+		token_out: T::FungibleTokenId,
+		amount_in: Balance,
+		to: T::AccountId,
+	) -> Result<Balance, DispatchError> {
+		let pool = Self::get_weighted_pool(pool_id)?;
+		Self::ensure_weighted_status(pool_id, PoolStatus::can_swap)?;
+		ensure!(token_in != token_out, Error::<T>::TokenRepeat);
+		let i = Self::weighted_token_index(&pool, token_in)?;
+		let j = Self::weighted_token_index(&pool, token_out)?;
+		let mut reserves = WeightedReserves::<T>::get(pool_id).into_inner();
+		let amount_out = Self::calc_weighted_amount_out(&pool, &reserves, i, j, amount_in)?;
+
 		pallet_token_fungible::Pallet::<T>::do_transfer(
-			pool.token_0,
+			token_in,
+			&who,
 			&pool.lp_token_account_id,
-			&to,
-			amount_0,
+			amount_in,
 		)?;
 		pallet_token_fungible::Pallet::<T>::do_transfer(
-			pool.token_1,
+			token_out,
 			&pool.lp_token_account_id,
 			&to,
-			amount_1,
+			amount_out,
 		)?;
+		reserves[i] = reserves[i].checked_add(amount_in).ok_or(Error::<T>::Overflow)?;
+		reserves[j] = reserves[j].checked_sub(amount_out).ok_or(Error::<T>::Overflow)?;
+		WeightedReserves::<T>::insert(
+			pool_id,
+			BoundedVec::try_from(reserves).map_err(|_| Error::<T>::InvalidAmounts)?,
+		);
 
-		balance_0 =
-			pallet_token_fungible::Pallet::<T>::balance_of(pool.token_0, &pool.lp_token_account_id);
-		balance_1 =
-			pallet_token_fungible::Pallet::<T>::balance_of(pool.token_1, &pool.lp_token_account_id);
-
-		Self::do_update(token_0, token_1, balance_0, balance_1)?;
+		Self::deposit_event(Event::WeightedSwap(
+			pool_id, who, token_in, token_out, amount_in, amount_out, to,
+		));
 
-		if fee_on {
-			let (_reserve_0, _reserve_1) = Reserves::<T>::get((token_0, token_1));
-			KLast::<T>::mutate((token_0, token_1), |k| *k = _reserve_0 * _reserve_1);
-		}
-		Self::deposit_event(Event::Burn(who, amount_0, amount_1, to));
-		Ok((amount_0, amount_1))
+		Ok(amount_out)
 	}
 
-	fn do_update(
-		token_0: T::FungibleTokenId,
-		token_1: T::FungibleTokenId,
-		balance_0: Balance,
-		balance_1: Balance,
-	) -> DispatchResult {
-		Reserves::<T>::mutate((token_0, token_1), |reserve| *reserve = (balance_0, balance_1));
-		Self::deposit_event(Event::Sync(balance_0, balance_1));
-		Ok(())
+	/// The amount of `token_out` a weighted pool gives for `amount_in` of `token_in`.
+	pub fn get_weighted_amount_out(
+		pool_id: T::PoolId,
+		token_in: T::FungibleTokenId,
+		token_out: T::FungibleTokenId,
+		amount_in: Balance,
+	) -> Result<Balance, DispatchError> {
+		let pool = Self::get_weighted_pool(pool_id)?;
+		ensure!(token_in != token_out, Error::<T>::TokenRepeat);
+		let i = Self::weighted_token_index(&pool, token_in)?;
+		let j = Self::weighted_token_index(&pool, token_out)?;
+		Self::calc_weighted_amount_out(&pool, &WeightedReserves::<T>::get(pool_id), i, j, amount_in)
 	}
 
-	fn init_amount_in(
-		balance: Balance,
-		reserve: Balance,
-		amount_out: Balance,
+	/// The liquidity minted for depositing `amount_in` of a single token into a weighted pool.
+	pub fn get_weighted_single_in_liquidity(
+		pool_id: T::PoolId,
+		token_in: T::FungibleTokenId,
+		amount_in: Balance,
 	) -> Result<Balance, DispatchError> {
-		if balance > reserve {
-			let temp = U256::from(reserve)
-				.checked_sub(U256::from(amount_out))
-				.ok_or(Error::<T>::Overflow)?;
-			Ok(U256::from(balance)
-				.checked_sub(temp)
-				.and_then(|l| TryInto::<Balance>::try_into(l).ok())
-				.ok_or(Error::<T>::Overflow)?)
-		} else {
-			Ok(Zero::zero())
-		}
+		let pool = Self::get_weighted_pool(pool_id)?;
+		let i = Self::weighted_token_index(&pool, token_in)?;
+		Self::calc_weighted_single_in(&pool, &WeightedReserves::<T>::get(pool_id), i, amount_in)
 	}
 
-	fn init_balance_adjusted(
-		balance: Balance,
-		amount_in: Balance,
+	/// The amount of `token_out` withdrawn from a weighted pool for burning `liquidity`.
+	pub fn get_weighted_single_out(
+		pool_id: T::PoolId,
+		token_out: T::FungibleTokenId,
+		liquidity: Balance,
 	) -> Result<Balance, DispatchError> {
-		let temp_0 = U256::from(balance)
-			.checked_mul(U256::from(1000u128))
-			.ok_or(Error::<T>::Overflow)?;
-		let temp_1 = U256::from(amount_in)
-			.checked_mul(U256::from(3u128))
-			.ok_or(Error::<T>::Overflow)?;
-		Ok(temp_0
-			.checked_sub(temp_1)
-			.and_then(|l| TryInto::<Balance>::try_into(l).ok())
-			.ok_or(Error::<T>::Overflow)?)
+		let pool = Self::get_weighted_pool(pool_id)?;
+		let i = Self::weighted_token_index(&pool, token_out)?;
+		Self::calc_weighted_single_out(&pool, &WeightedReserves::<T>::get(pool_id), i, liquidity)
 	}
 
-	fn sort_tokens(
-		token_a: T::FungibleTokenId,
-		token_b: T::FungibleTokenId,
-	) -> (T::FungibleTokenId, T::FungibleTokenId) {
-		if token_a < token_b {
-			(token_a, token_b)
-		} else {
-			(token_b, token_a)
-		}
+	/// The amounts withdrawn from a weighted pool for burning `liquidity`.
+	pub fn weighted_liquidity_to_tokens(
+		pool_id: T::PoolId,
+		liquidity: Balance,
+	) -> Result<Vec<Balance>, DispatchError> {
+		let pool = Self::get_weighted_pool(pool_id)?;
+		let total_supply = pallet_token_fungible::Pallet::<T>::total_supply(pool.lp_token);
+		ensure!(liquidity <= total_supply, Error::<T>::InsufficientLiquidity);
+
+		WeightedReserves::<T>::get(pool_id)
+			.iter()
+			.map(|reserve| {
+				U256::from(*reserve)
+					.checked_mul(U256::from(liquidity))
+					.and_then(|a| a.checked_div(U256::from(total_supply)))
+					.and_then(|a| TryInto::<Balance>::try_into(a).ok())
+					.ok_or_else(|| Error::<T>::Overflow.into())
+			})
+			.collect()
 	}
 
-	pub fn get_reserves(
-		token_a: T::FungibleTokenId,
-		token_b: T::FungibleTokenId,
-	) -> Result<(Balance, Balance), DispatchError> {
-		let (token_0, token_1) = Self::sort_tokens(token_a, token_b);
+	fn weighted_token_index(
+		pool: &WeightedPool<T::AccountId, T::FungibleTokenId>,
+		token: T::FungibleTokenId,
+	) -> Result<usize, DispatchError> {
+		pool.tokens
+			.iter()
+			.position(|t| *t == token)
+			.ok_or_else(|| Error::<T>::TokenNotInPool.into())
+	}
 
-		let (reserve_0, reserve_1) = Reserves::<T>::get((token_0, token_1));
-		let (reserve_a, reserve_b) =
-			if token_a == token_0 { (reserve_0, reserve_1) } else { (reserve_1, reserve_0) };
+	// a weight as a fixed point number of `weighted_math`
+	fn weight_to_fixed(weight: Permill) -> U256 {
+		U256::from(weight.deconstruct()) * U256::from(weighted_math::ONE / 1_000_000)
+	}
 
-		Ok((reserve_a, reserve_b))
+	// a fee in basis points as a fixed point number of `weighted_math`
+	fn fee_to_fixed(fee: u32) -> U256 {
+		U256::from(fee) * U256::from(weighted_math::ONE / FEE_DENOMINATOR as u128)
 	}
 
-	pub fn quote(
-		amount_a: Balance,
-		reserve_a: Balance,
-		reserve_b: Balance,
+	// whether `amount` is within the part of `balance` a weighted pool trades at once
+	fn within_weighted_ratio(amount: Balance, balance: Balance) -> bool {
+		amount <= MAX_WEIGHTED_RATIO * balance
+	}
+
+	fn calc_weighted_amount_out(
+		pool: &WeightedPool<T::AccountId, T::FungibleTokenId>,
+		reserves: &[Balance],
+		i: usize,
+		j: usize,
+		amount_in: Balance,
 	) -> Result<Balance, DispatchError> {
-		ensure!(amount_a > Zero::zero(), Error::<T>::InsufficientAmount);
+		ensure!(amount_in > Zero::zero(), Error::<T>::InsufficientInputAmount);
 		ensure!(
-			reserve_a > Zero::zero() && reserve_b > Zero::zero(),
+			!reserves[i].is_zero() && !reserves[j].is_zero(),
 			Error::<T>::InsufficientLiquidity
 		);
-		let amount_b = U256::from(amount_a)
-			.checked_mul(U256::from(reserve_b))
-			.and_then(|l| l.checked_div(U256::from(reserve_a)))
-			.and_then(|l| TryInto::<Balance>::try_into(l).ok())
-			.ok_or(Error::<T>::Overflow)?;
+		ensure!(
+			Self::within_weighted_ratio(amount_in, reserves[i]),
+			Error::<T>::WeightedRatioExceeded
+		);
 
-		Ok(amount_b)
+		let amount_out = weighted_math::out_given_in(
+			U256::from(reserves[i]),
+			Self::weight_to_fixed(pool.weights[i]),
+			U256::from(reserves[j]),
+			Self::weight_to_fixed(pool.weights[j]),
+			U256::from(amount_in),
+			Self::fee_to_fixed(pool.fee),
+		)
+		.and_then(|a| TryInto::<Balance>::try_into(a).ok())
+		.ok_or(Error::<T>::WeightedMathFailed)?;
+		ensure!(amount_out > Zero::zero(), Error::<T>::InsufficientOutputAmount);
+		ensure!(
+			Self::within_weighted_ratio(amount_out, reserves[j]),
+			Error::<T>::WeightedRatioExceeded
+		);
+
+		Ok(amount_out)
 	}
 
-	pub fn get_amount_out(
+	fn calc_weighted_single_in(
+		pool: &WeightedPool<T::AccountId, T::FungibleTokenId>,
+		reserves: &[Balance],
+		i: usize,
 		amount_in: Balance,
-		reserve_in: Balance,
-		reserve_out: Balance,
 	) -> Result<Balance, DispatchError> {
 		ensure!(amount_in > Zero::zero(), Error::<T>::InsufficientInputAmount);
+		let total_supply = pallet_token_fungible::Pallet::<T>::total_supply(pool.lp_token);
 		ensure!(
-			reserve_in > Zero::zero() && reserve_out > Zero::zero(),
+			!reserves[i].is_zero() && !total_supply.is_zero(),
 			Error::<T>::InsufficientLiquidity
 		);
+		ensure!(
+			Self::within_weighted_ratio(amount_in, reserves[i]),
+			Error::<T>::WeightedRatioExceeded
+		);
 
-		let amount_in_with_fee: U256 = U256::from(amount_in).saturating_mul(U256::from(997u128));
-
-		let numerator: U256 =
-			U256::from(amount_in_with_fee).saturating_mul(U256::from(reserve_out));
-
-		let denominator: U256 = (U256::from(reserve_in).saturating_mul(U256::from(1000u128)))
-			.saturating_add(amount_in_with_fee);
+		let liquidity = weighted_math::pool_out_given_single_in(
+			U256::from(reserves[i]),
+			Self::weight_to_fixed(pool.weights[i]),
+			U256::from(total_supply),
+			U256::from(amount_in),
+			Self::fee_to_fixed(pool.fee),
+		)
+		.and_then(|l| TryInto::<Balance>::try_into(l).ok())
+		.ok_or(Error::<T>::WeightedMathFailed)?;
+		ensure!(liquidity > Zero::zero(), Error::<T>::InsufficientLiquidityMinted);
 
-		let amount_out = numerator
-			.checked_div(denominator)
-			.and_then(|n| TryInto::<Balance>::try_into(n).ok())
-			.ok_or(Error::<T>::Overflow)?;
-		Ok(amount_out)
+		Ok(liquidity)
 	}
 
-	pub fn get_amount_in(
-		amount_out: Balance,
-		reserve_in: Balance,
-		reserve_out: Balance,
+	fn calc_weighted_single_out(
+		pool: &WeightedPool<T::AccountId, T::FungibleTokenId>,
+		reserves: &[Balance],
+		i: usize,
+		liquidity: Balance,
 	) -> Result<Balance, DispatchError> {
+		let total_supply = pallet_token_fungible::Pallet::<T>::total_supply(pool.lp_token);
+		ensure!(liquidity > Zero::zero(), Error::<T>::InsufficientLiquidityBurned);
+		ensure!(
+			Self::within_weighted_ratio(liquidity, total_supply),
+			Error::<T>::WeightedRatioExceeded
+		);
+
+		let amount_out = weighted_math::single_out_given_pool_in(
+			U256::from(reserves[i]),
+			Self::weight_to_fixed(pool.weights[i]),
+			U256::from(total_supply),
+			U256::from(liquidity),
+			Self::fee_to_fixed(pool.fee),
+		)
+		.and_then(|a| TryInto::<Balance>::try_into(a).ok())
+		.ok_or(Error::<T>::WeightedMathFailed)?;
 		ensure!(amount_out > Zero::zero(), Error::<T>::InsufficientOutputAmount);
 		ensure!(
-			reserve_in > Zero::zero() && reserve_out > Zero::zero(),
-			Error::<T>::InsufficientLiquidity
+			Self::within_weighted_ratio(amount_out, reserves[i]),
+			Error::<T>::WeightedRatioExceeded
 		);
 
-		let numerator: U256 = U256::from(reserve_in)
-			.saturating_mul(U256::from(amount_out))
-			.saturating_mul(U256::from(1000u128));
-		let denominator: U256 = (U256::from(reserve_out).saturating_sub(U256::from(amount_out)))
-			.saturating_mul(U256::from(997u128));
+		Ok(amount_out)
+	}
 
-		let amount_in = U256::from(numerator)
-			.checked_div(U256::from(denominator))
-			.and_then(|r| r.checked_add(U256::one()))
-			.and_then(|n| TryInto::<Balance>::try_into(n).ok())
-			.ok_or(Error::<T>::Overflow)?;
+	/// The id the LP token of the weighted pool `id` is created with.
+	pub fn generate_weighted_lp_token_id(
+		id: T::PoolId,
+	) -> Result<T::FungibleTokenId, DispatchError> {
+		Self::derive_lp_token_id((b"w3g/wlp", id))
+	}
 
-		Ok(amount_in)
+	/// The id the LP token of the StableSwap pool `id` is created with.
+	pub fn generate_stable_lp_token_id(id: T::PoolId) -> Result<T::FungibleTokenId, DispatchError> {
+		Self::derive_lp_token_id((b"w3g/slp", id))
 	}
 
-	pub fn get_amounts_out(
-		amount_in: Balance,
-		path: Vec<T::FungibleTokenId>,
-	) -> Result<Vec<Balance>, DispatchError> {
-		ensure!(path.len() >= 2, Error::<T>::InvalidPath);
+	/// The id the LP token of the pool of `token_a` and `token_b` with the fee tier `fee` is
+	/// created with.
+	pub fn generate_lp_token_id(
+		token_a: T::FungibleTokenId,
+		token_b: T::FungibleTokenId,
+		fee: u32,
+	) -> Result<T::FungibleTokenId, DispatchError> {
+		let (token_0, token_1) = Self::sort_tokens(token_a, token_b);
+		Self::derive_lp_token_id((b"w3g/lp", token_0, token_1, fee))
+	}
 
-		let mut amounts = vec![Balance::from(0u128); path.len()];
-		amounts[0] = amount_in;
-		for i in 0..(path.len() - 1) {
-			let (reserve_in, reserve_out) = Self::get_reserves(path[i], path[i + 1])?;
-			amounts[i + 1] = Self::get_amount_out(amounts[i], reserve_in, reserve_out)?;
+	// the first id hashed from `seed` and a nonce which no token was created with, moved into the
+	// range of ids `create_token` rejects so that no one can take it before the pool is created
+	fn derive_lp_token_id(seed: impl Encode) -> Result<T::FungibleTokenId, DispatchError> {
+		let start = pallet_token_fungible::Pallet::<T>::reserved_token_id_start();
+		for nonce in 0..MAX_LP_TOKEN_ID_PROBES {
+			let hash = sp_io::hashing::blake2_256(&(&seed, nonce).encode());
+			let id = T::FungibleTokenId::decode(&mut hash.as_ref())
+				.map_err(|_| Error::<T>::NoAvailableLpTokenId)?;
+			let id = start + id % start;
+			if !pallet_token_fungible::Pallet::<T>::exists(id) {
+				return Ok(id)
+			}
 		}
-		Ok(amounts)
+		Err(Error::<T>::NoAvailableLpTokenId.into())
 	}
 
-	pub fn get_amounts_in(
-		amount_out: Balance,
-		path: Vec<T::FungibleTokenId>,
-	) -> Result<Vec<Balance>, DispatchError> {
-		ensure!(path.len() >= 2, Error::<T>::InvalidPath);
+	// "<prefix> A/B" from the symbols of the pooled tokens
+	fn lp_token_name(prefix: &[u8], tokens: &[T::FungibleTokenId]) -> Vec<u8> {
+		let mut name = prefix.to_vec();
+		name.push(b' ');
+		for (i, token) in tokens.iter().enumerate() {
+			if i > 0 {
+				name.push(b'/');
+			}
+			name.extend(pallet_token_fungible::Pallet::<T>::token_symbol(*token));
+		}
+		name.truncate(<T as pallet_token_fungible::Config>::StringLimit::get() as usize);
+		name
+	}
+}
 
-		let mut amounts = vec![Balance::from(0u128); path.len()];
-		amounts[path.len() - 1] = amount_out;
-		for i in (1..path.len()).rev() {
-			let (reserve_in, reserve_out) = Self::get_reserves(path[i - 1], path[i])?;
-			amounts[i - 1] = Self::get_amount_in(amounts[i], reserve_in, reserve_out)?;
+impl<T: Config> pallet_support::LiquidityProvider<T::AccountId, T::FungibleTokenId> for Pallet<T> {
+	fn ensure_pool(
+		who: &T::AccountId,
+		token_a: T::FungibleTokenId,
+		token_b: T::FungibleTokenId,
+		fee: u32,
+	) -> DispatchResult {
+		if Self::exists(token_a, token_b, fee) {
+			return Ok(())
 		}
+		ensure!(token_a != token_b, Error::<T>::TokenRepeat);
+		ensure!(FeeTiers::<T>::contains_key(fee), Error::<T>::FeeTierNotFound);
+		ensure!(
+			pallet_token_fungible::Pallet::<T>::exists(token_a) &&
+				pallet_token_fungible::Pallet::<T>::exists(token_b),
+			Error::<T>::TokenAccountNotFound,
+		);
 
-		Ok(amounts)
+		Self::do_create_pool(who.clone(), token_a, token_b, fee).map(|_| ())
 	}
 
-	fn generate_lp_token_id(
-		token_0: T::FungibleTokenId,
-		token_1: T::FungibleTokenId,
-	) -> T::FungibleTokenId {
-		let (random_seed, _) =
-			T::Randomness::random(&(Self::account_id(), token_0, token_1).encode());
-		let lp_token_id = <T::FungibleTokenId>::decode(&mut random_seed.as_ref())
-			.expect("Failed to decode random seed");
-		lp_token_id
+	fn lp_token(
+		token_a: T::FungibleTokenId,
+		token_b: T::FungibleTokenId,
+		fee: u32,
+	) -> Option<T::FungibleTokenId> {
+		let (token_0, token_1) = Self::sort_tokens(token_a, token_b);
+		Pools::<T>::get((token_0, token_1), fee).map(|pool| pool.lp_token)
+	}
+
+	fn add_liquidity(
+		who: &T::AccountId,
+		token_a: T::FungibleTokenId,
+		token_b: T::FungibleTokenId,
+		fee: u32,
+		(amount_a, amount_b): (Balance, Balance),
+		(min_a, min_b): (Balance, Balance),
+		to: &T::AccountId,
+	) -> Result<(Balance, Balance, Balance), DispatchError> {
+		let (token_0, token_1) = Self::sort_tokens(token_a, token_b);
+		let pool = Pools::<T>::get((token_0, token_1), fee).ok_or(Error::<T>::PoolNotFound)?;
+		let ((desired_0, desired_1), (min_0, min_1)) = if token_a == token_0 {
+			((amount_a, amount_b), (min_a, min_b))
+		} else {
+			((amount_b, amount_a), (min_b, min_a))
+		};
+		let (amount_0, amount_1) =
+			Self::do_add_liquidity(token_0, token_1, fee, desired_0, desired_1, min_0, min_1)?;
+
+		pallet_token_fungible::Pallet::<T>::do_transfer(
+			token_0,
+			who,
+			&pool.lp_token_account_id,
+			amount_0,
+		)?;
+		pallet_token_fungible::Pallet::<T>::do_transfer(
+			token_1,
+			who,
+			&pool.lp_token_account_id,
+			amount_1,
+		)?;
+		let liquidity = Self::mint(who.clone(), token_0, token_1, fee, to.clone())?;
+		Self::deposit_event(Event::LiquidityAdded(pool.lp_token, amount_0, amount_1, liquidity));
+
+		if token_a == token_0 {
+			Ok((amount_0, amount_1, liquidity))
+		} else {
+			Ok((amount_1, amount_0, liquidity))
+		}
+	}
+}
+
+impl<T: Config> pallet_support::PriceOracle<T::FungibleTokenId, T::BlockNumber> for Pallet<T> {
+	fn consult(
+		token_a: T::FungibleTokenId,
+		token_b: T::FungibleTokenId,
+		fee: u32,
+		window: T::BlockNumber,
+	) -> Option<FixedU128> {
+		Self::consult(token_a, token_b, fee, window).ok().map(|(price, _)| price)
 	}
 }
@@ -0,0 +1,95 @@
+// This file is part of Web3Games.
+
+// Copyright (C) 2021-2022 Web3Games https://web3games.org
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use frame_support::{
+	storage::migration::{storage_iter, take_storage_item},
+	traits::{GetStorageVersion, PalletInfoAccess, StorageVersion},
+	weights::Weight,
+	Blake2_128,
+};
+
+/// Run every pending migration of the pallet storage.
+pub fn migrate<T: Config>() -> Weight {
+	let mut weight: Weight = 0;
+
+	if Pallet::<T>::on_chain_storage_version() < 1 {
+		weight = weight.saturating_add(v1::migrate::<T>());
+		StorageVersion::new(1).put::<Pallet<T>>();
+	}
+
+	weight.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+}
+
+/// Key every pool by its fee and move the existing pools to the `DEFAULT_FEE` tier.
+pub mod v1 {
+	use super::*;
+
+	#[derive(Decode)]
+	struct OldPool<AccountId, FungibleTokenId> {
+		token_0: FungibleTokenId,
+		token_1: FungibleTokenId,
+		lp_token: FungibleTokenId,
+		lp_token_account_id: AccountId,
+	}
+
+	pub fn migrate<T: Config>() -> Weight {
+		let pallet = <Pallet<T> as PalletInfoAccess>::name().as_bytes();
+
+		// Drain the old pools first, the new keys share their prefix.
+		let old_pools: Vec<OldPool<T::AccountId, T::FungibleTokenId>> =
+			storage_iter::<OldPool<T::AccountId, T::FungibleTokenId>>(pallet, b"Pools")
+				.drain()
+				.map(|(_, pool)| pool)
+				.collect();
+
+		let count = old_pools.len() as u64;
+		for pool in old_pools {
+			let old_key = (pool.token_0, pool.token_1);
+			let new_key = (pool.token_0, pool.token_1, DEFAULT_FEE);
+
+			if let Some(reserves) =
+				take_storage_item::<_, (Balance, Balance), Blake2_128>(pallet, b"Reserves", old_key)
+			{
+				Reserves::<T>::insert(new_key, reserves);
+			}
+			if let Some(k_last) =
+				take_storage_item::<_, Balance, Blake2_128>(pallet, b"KLast", old_key)
+			{
+				KLast::<T>::insert(new_key, k_last);
+			}
+			LpTokenToToken::<T>::insert(pool.lp_token, new_key);
+			Pools::<T>::insert(
+				old_key,
+				DEFAULT_FEE,
+				Pool {
+					token_0: pool.token_0,
+					token_1: pool.token_1,
+					lp_token: pool.lp_token,
+					lp_token_account_id: pool.lp_token_account_id,
+					fee: DEFAULT_FEE,
+				},
+			);
+		}
+		FeeTiers::<T>::insert(DEFAULT_FEE, ());
+
+		log::info!(target: "runtime::exchange", "moved {} pools to the {} bps fee tier", count, DEFAULT_FEE);
+
+		T::DbWeight::get().reads_writes(count.saturating_mul(3), count.saturating_mul(7) + 1)
+	}
+}
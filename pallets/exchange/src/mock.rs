@@ -17,12 +17,13 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate as pallet_exchange;
+use codec::Decode;
 use frame_benchmarking::whitelisted_caller;
 use frame_support::{
 	construct_runtime,
 	pallet_prelude::GenesisBuild,
 	parameter_types,
-	traits::{ConstU16, ConstU64},
+	traits::{ConstU16, ConstU32, ConstU64},
 	PalletId,
 };
 use primitives::{AccountId, Balance};
@@ -48,10 +49,9 @@ construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
-		Randomness: pallet_randomness_collective_flip::{Pallet, Storage},
 		TokenFungible: pallet_token_fungible::{Pallet, Call, Storage, Event<T>},
-		Exchange: pallet_exchange::{Pallet, Call, Storage, Event<T>},
-		WrapCurrency: pallet_wrap_currency::{Pallet, Call, Storage, Event<T>},
+		Exchange: pallet_exchange::{Pallet, Call, Config, Storage, Event<T>},
+		WrapCurrency: pallet_wrap_currency::{Pallet, Call, Config, Storage, Event<T>},
 	}
 );
 
@@ -98,8 +98,6 @@ impl pallet_balances::Config for Test {
 	type ReserveIdentifier = [u8; 8];
 }
 
-impl pallet_randomness_collective_flip::Config for Test {}
-
 parameter_types! {
 	pub const TokenFungiblePalletId: PalletId = PalletId(*b"w3g/tfpi");
 	pub const StringLimit: u32 = 50;
@@ -130,11 +128,59 @@ impl pallet_exchange::Config for Test {
 	type PoolId = u32;
 	type CreatePoolDeposit = CreatePoolDeposit;
 	type Currency = Balances;
-	type Randomness = Randomness;
 	type WW3G = WW3G;
+	type UpdateOrigin = frame_system::EnsureRoot<u64>;
+	type PauseOrigin = frame_system::EnsureRoot<u64>;
+	type FlashCallback = MockFlashCallback;
+	type MaxFlashCallbackWeight = ConstU64<0>;
+	type MaxObservations = ConstU32<4>;
+	type ObservationPeriod = ConstU64<10>;
+	type OrderId = u64;
+	type MaxOrdersPerBlock = ConstU32<2>;
 	type WeightInfo = ();
 }
 
+/// Repays the `(token, amount)` list encoded in the callback data.
+pub struct MockFlashCallback;
+
+impl MockFlashCallback {
+	fn repay(who: &u64, token_a: u128, token_b: u128, fee: u32, data: &[u8]) -> bool {
+		let repayments: Vec<(u128, Balance)> = match Decode::decode(&mut &data[..]) {
+			Ok(repayments) => repayments,
+			Err(_) => return false,
+		};
+		repayments.into_iter().all(|(token, amount)| {
+			Exchange::flash_repay(who, token_a, token_b, fee, token, amount).is_ok()
+		})
+	}
+}
+
+impl pallet_exchange::FlashCallback<u64, u128> for MockFlashCallback {
+	fn on_flash_swap(
+		who: &u64,
+		token_a: u128,
+		token_b: u128,
+		fee: u32,
+		_amount_a_out: Balance,
+		_amount_b_out: Balance,
+		data: &[u8],
+	) -> bool {
+		Self::repay(who, token_a, token_b, fee, data)
+	}
+
+	fn on_flash_loan(
+		who: &u64,
+		token: u128,
+		pair_token: u128,
+		fee: u32,
+		_amount: Balance,
+		_premium: Balance,
+		data: &[u8],
+	) -> bool {
+		Self::repay(who, token, pair_token, fee, data)
+	}
+}
+
 impl pallet_wrap_currency::Config for Test {
 	type Event = Event;
 	type PalletId = WrapCurrencyPalletId;
@@ -155,6 +201,13 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 	}
 	.assimilate_storage(&mut t)
 	.unwrap();
+	GenesisBuild::<Test>::assimilate_storage(
+		&pallet_exchange::GenesisConfig { fee_tiers: vec![5, 30, 100] },
+		&mut t,
+	)
+	.unwrap();
+	GenesisBuild::<Test>::assimilate_storage(&pallet_wrap_currency::GenesisConfig {}, &mut t)
+		.unwrap();
 	let mut ext = sp_io::TestExternalities::new(t);
 	ext.execute_with(|| System::set_block_number(1));
 	ext
@@ -0,0 +1,115 @@
+// This file is part of Web3Games.
+
+// Copyright (C) 2021-2022 Web3Games https://web3games.org
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The StableSwap invariant of Curve, over balances normalized to 18 decimals.
+
+use sp_core::U256;
+
+/// The precision of the amplification coefficients.
+pub const A_PRECISION: u128 = 100;
+
+/// The highest amplification coefficient.
+pub const MAX_A: u128 = 1_000_000;
+
+/// The largest factor an amplification coefficient can be ramped up or down by.
+pub const MAX_A_CHANGE: u128 = 10;
+
+const MAX_ITERATIONS: u32 = 255;
+
+fn abs_diff(a: U256, b: U256) -> U256 {
+	if a > b {
+		a - b
+	} else {
+		b - a
+	}
+}
+
+/// The invariant `D` of the normalized balances `xp`, for an amplification coefficient `amp`
+/// times `A_PRECISION`. `None` on overflow, on an empty balance or without convergence.
+pub fn get_d(xp: &[U256], amp: U256) -> Option<U256> {
+	let n = U256::from(xp.len());
+	let sum = xp.iter().try_fold(U256::zero(), |sum, x| sum.checked_add(*x))?;
+	if sum.is_zero() {
+		return Some(U256::zero())
+	}
+
+	let ann = amp.checked_mul(n)?;
+	let precision = U256::from(A_PRECISION);
+	let mut d = sum;
+	for _ in 0..MAX_ITERATIONS {
+		let mut d_p = d;
+		for x in xp {
+			d_p = d_p.checked_mul(d)?.checked_div(x.checked_mul(n)?)?;
+		}
+		let d_prev = d;
+		let numerator = ann
+			.checked_mul(sum)?
+			.checked_div(precision)?
+			.checked_add(d_p.checked_mul(n)?)?
+			.checked_mul(d)?;
+		let denominator = ann
+			.checked_sub(precision)?
+			.checked_mul(d)?
+			.checked_div(precision)?
+			.checked_add(n.checked_add(U256::one())?.checked_mul(d_p)?)?;
+		d = numerator.checked_div(denominator)?;
+		if abs_diff(d, d_prev) <= U256::one() {
+			return Some(d)
+		}
+	}
+
+	None
+}
+
+/// The normalized balance of token `j` which keeps the invariant `d` once the normalized
+/// balance of token `i` becomes `x`.
+pub fn get_y(i: usize, j: usize, x: U256, xp: &[U256], amp: U256, d: U256) -> Option<U256> {
+	let n = U256::from(xp.len());
+	let ann = amp.checked_mul(n)?;
+	let precision = U256::from(A_PRECISION);
+
+	let mut c = d;
+	let mut sum = U256::zero();
+	for (k, xp_k) in xp.iter().enumerate() {
+		let x_k = if k == i {
+			x
+		} else if k == j {
+			continue
+		} else {
+			*xp_k
+		};
+		sum = sum.checked_add(x_k)?;
+		c = c.checked_mul(d)?.checked_div(x_k.checked_mul(n)?)?;
+	}
+	c = c.checked_mul(d)?.checked_mul(precision)?.checked_div(ann.checked_mul(n)?)?;
+	let b = sum.checked_add(d.checked_mul(precision)?.checked_div(ann)?)?;
+
+	let mut y = d;
+	for _ in 0..MAX_ITERATIONS {
+		let y_prev = y;
+		y = y
+			.checked_mul(y)?
+			.checked_add(c)?
+			.checked_div(y.checked_mul(U256::from(2))?.checked_add(b)?.checked_sub(d)?)?;
+		if abs_diff(y, y_prev) <= U256::one() {
+			return Some(y)
+		}
+	}
+
+	None
+}
@@ -18,7 +18,7 @@
 
 use super::*;
 pub use crate::mock::*;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
 
 const ALICE: u64 = 1;
 const BOB: u64 = 2;
@@ -67,9 +67,9 @@ fn set_balance() {
 fn create_pool_should_work() {
 	new_test_ext().execute_with(|| {
 		create_tokens();
-		assert_ok!(Exchange::create_pool(Origin::signed(1), TOKENA, TOKENB));
-		assert_eq!(Exchange::exists(TOKENA, TOKENB), true);
-		assert_eq!(Exchange::exists(TOKENA, TOKENC), false);
+		assert_ok!(Exchange::create_pool(Origin::signed(1), TOKENA, TOKENB, 30));
+		assert_eq!(Exchange::exists(TOKENA, TOKENB, 30), true);
+		assert_eq!(Exchange::exists(TOKENA, TOKENC, 30), false);
 		// assert_eq!(1, Exchange::generate_lp_token_id(1000000, TOKENB));
 	})
 }
@@ -78,19 +78,19 @@ fn create_pool_should_work() {
 fn create_pool_should_not_work() {
 	new_test_ext().execute_with(|| {
 		assert_noop!(
-			Exchange::create_pool(Origin::signed(ALICE), TOKENA, TOKENB),
+			Exchange::create_pool(Origin::signed(ALICE), TOKENA, TOKENB, 30),
 			Error::<Test>::TokenAccountNotFound
 		);
 
 		create_tokens();
 		assert_noop!(
-			Exchange::create_pool(Origin::signed(ALICE), TOKENA, TOKENA),
+			Exchange::create_pool(Origin::signed(ALICE), TOKENA, TOKENA, 30),
 			Error::<Test>::TokenRepeat
 		);
 
-		assert_ok!(Exchange::create_pool(Origin::signed(ALICE), TOKENA, TOKENB));
+		assert_ok!(Exchange::create_pool(Origin::signed(ALICE), TOKENA, TOKENB, 30));
 		assert_noop!(
-			Exchange::create_pool(Origin::signed(ALICE), TOKENA, TOKENB),
+			Exchange::create_pool(Origin::signed(ALICE), TOKENA, TOKENB, 30),
 			Error::<Test>::PoolAlreadyCreated
 		);
 	})
@@ -100,17 +100,19 @@ fn create_pool_should_not_work() {
 fn add_liquidity_should_work() {
 	new_test_ext().execute_with(|| {
 		create_tokens();
-		assert_ok!(Exchange::create_pool(Origin::signed(ALICE), TOKENA, TOKENB));
+		assert_ok!(Exchange::create_pool(Origin::signed(ALICE), TOKENA, TOKENB, 30));
 
 		set_balance();
 
 		let liquidity: u128 =
-			Exchange::get_liquidity(TOKENA, TOKENA_LIQUIDITY, TOKENB, TOKENB_LIQUIDITY).unwrap();
+			Exchange::get_liquidity(TOKENA, TOKENA_LIQUIDITY, TOKENB, TOKENB_LIQUIDITY, 30)
+				.unwrap();
 
 		assert_ok!(Exchange::add_liquidity(
 			Origin::signed(ALICE),
 			TOKENA,
 			TOKENB,
+			30,
 			TOKENA_LIQUIDITY,
 			TOKENB_LIQUIDITY,
 			0u128,
@@ -122,7 +124,7 @@ fn add_liquidity_should_work() {
 		assert_eq!(TokenFungible::balance_of(1, ALICE), INITIAL_BALANCE - TOKENA_LIQUIDITY);
 		assert_eq!(TokenFungible::balance_of(2, ALICE), INITIAL_BALANCE - TOKENB_LIQUIDITY);
 
-		let lp_token: u128 = Exchange::generate_lp_token_id(TOKENA, TOKENB);
+		let lp_token: u128 = Exchange::pools((TOKENA, TOKENB), 30).unwrap().lp_token;
 		let lp_token_account: u64 = Exchange::token_id_to_account_id(lp_token);
 
 		assert_eq!(TokenFungible::balance_of(lp_token, ALICE), liquidity);
@@ -131,21 +133,83 @@ fn add_liquidity_should_work() {
 	})
 }
 
+#[test]
+fn lp_tokens_should_be_derived() {
+	new_test_ext().execute_with(|| {
+		create_tokens();
+		let lp_token = Exchange::generate_lp_token_id(TOKENB, TOKENA, 30).unwrap();
+		assert_eq!(Exchange::generate_lp_token_id(TOKENA, TOKENB, 30), Ok(lp_token));
+		assert_ne!(Exchange::generate_lp_token_id(TOKENA, TOKENB, 5), Ok(lp_token));
+
+		assert_ok!(Exchange::create_pool(Origin::signed(ALICE), TOKENB, TOKENA, 30));
+		assert_eq!(Exchange::pools((TOKENA, TOKENB), 30).unwrap().lp_token, lp_token);
+		assert_eq!(Exchange::lp_token_to_token(lp_token), Some((TOKENA, TOKENB, 30)));
+		assert_eq!(TokenFungible::token_name(lp_token), b"W3G-LP W3G1/W3G2".to_vec());
+		assert_eq!(TokenFungible::token_symbol(lp_token), b"W3G-LP".to_vec());
+		assert_eq!(TokenFungible::token_decimals(lp_token), 18);
+
+		// the id of a pool not created yet can't be taken by a user token
+		let lp_token = Exchange::generate_lp_token_id(TOKENA, TOKENC, 30).unwrap();
+		assert!(TokenFungible::is_reserved_token_id(lp_token));
+		assert_noop!(
+			TokenFungible::create_token(
+				Origin::signed(BOB),
+				lp_token,
+				b"W3G3".to_vec(),
+				b"W3G3".to_vec(),
+				18
+			),
+			pallet_token_fungible::Error::<Test>::ReservedId
+		);
+		assert_ok!(Exchange::create_pool(Origin::signed(ALICE), TOKENA, TOKENC, 30));
+		assert_eq!(Exchange::pools((TOKENA, TOKENC), 30).unwrap().lp_token, lp_token);
+
+		// an id already taken by a token is skipped
+		let taken = Exchange::generate_lp_token_id(TOKENB, TOKENC, 30).unwrap();
+		assert_ok!(TokenFungible::do_create_reserved_token(
+			&BOB,
+			taken,
+			b"W3G3".to_vec(),
+			b"W3G3".to_vec(),
+			18
+		));
+		let lp_token = Exchange::generate_lp_token_id(TOKENB, TOKENC, 30).unwrap();
+		assert_ne!(lp_token, taken);
+		assert!(TokenFungible::is_reserved_token_id(lp_token));
+
+		let pool_id = Exchange::next_pool_id();
+		let lp_token = Exchange::generate_stable_lp_token_id(pool_id).unwrap();
+		assert_ok!(Exchange::create_stable_pool(
+			Origin::signed(ALICE),
+			vec![TOKENA, TOKENB, TOKENC],
+			100,
+			30
+		));
+		assert_eq!(Exchange::stable_pools(pool_id).unwrap().lp_token, lp_token);
+		assert_eq!(Exchange::stable_lp_token_to_pool(lp_token), Some(pool_id));
+		assert!(TokenFungible::is_reserved_token_id(lp_token));
+		assert_eq!(TokenFungible::token_name(lp_token), b"W3G-SLP W3G1/W3G2/W3G2".to_vec());
+		assert_eq!(TokenFungible::token_symbol(lp_token), b"W3G-SLP".to_vec());
+	})
+}
+
 #[test]
 fn remove_liquidity_should_work() {
 	new_test_ext().execute_with(|| {
 		create_tokens();
-		assert_ok!(Exchange::create_pool(Origin::signed(ALICE), TOKENA, TOKENB));
+		assert_ok!(Exchange::create_pool(Origin::signed(ALICE), TOKENA, TOKENB, 30));
 
 		set_balance();
 
 		let liquidity: u128 =
-			Exchange::get_liquidity(TOKENA, TOKENA_LIQUIDITY, TOKENB, TOKENB_LIQUIDITY).unwrap();
+			Exchange::get_liquidity(TOKENA, TOKENA_LIQUIDITY, TOKENB, TOKENB_LIQUIDITY, 30)
+				.unwrap();
 
 		assert_ok!(Exchange::add_liquidity(
 			Origin::signed(ALICE),
 			TOKENA,
 			TOKENB,
+			30,
 			TOKENA_LIQUIDITY,
 			TOKENB_LIQUIDITY,
 			0u128,
@@ -154,7 +218,7 @@ fn remove_liquidity_should_work() {
 			100
 		));
 
-		let lp_token: u128 = Exchange::generate_lp_token_id(TOKENA, TOKENB);
+		let lp_token: u128 = Exchange::pools((TOKENA, TOKENB), 30).unwrap().lp_token;
 		let lp_token_account: u64 = Exchange::token_id_to_account_id(lp_token);
 
 		let balance_0 = TokenFungible::balance_of(TOKENA, lp_token_account);
@@ -172,6 +236,7 @@ fn remove_liquidity_should_work() {
 			Origin::signed(ALICE),
 			TOKENA,
 			TOKENB,
+			30,
 			remove_liquidity,
 			0u128,
 			0u128,
@@ -207,7 +272,7 @@ fn remove_liquidity_should_work() {
 fn swap_exact_tokens_for_tokens_should_work() {
 	new_test_ext().execute_with(|| {
 		create_tokens();
-		assert_ok!(Exchange::create_pool(Origin::signed(ALICE), TOKENA, TOKENB));
+		assert_ok!(Exchange::create_pool(Origin::signed(ALICE), TOKENA, TOKENB, 30));
 
 		set_balance();
 
@@ -215,6 +280,7 @@ fn swap_exact_tokens_for_tokens_should_work() {
 			Origin::signed(ALICE),
 			TOKENA,
 			TOKENB,
+			30,
 			TOKENA_LIQUIDITY,
 			TOKENB_LIQUIDITY,
 			0u128,
@@ -223,15 +289,16 @@ fn swap_exact_tokens_for_tokens_should_work() {
 			100
 		));
 
-		let (reserve_in, reserve_out) = Exchange::get_reserves(TOKENA, TOKENB).unwrap();
+		let (reserve_in, reserve_out) = Exchange::get_reserves(TOKENA, TOKENB, 30).unwrap();
 		assert!(reserve_in == TOKENA_LIQUIDITY && reserve_out == TOKENB_LIQUIDITY);
 
-		let amount_out_1 = Exchange::get_amount_out(SWAP_VALUE, reserve_in, reserve_out).unwrap();
+		let amount_out_1 =
+			Exchange::get_amount_out(SWAP_VALUE, reserve_in, reserve_out, 30).unwrap();
 
-		let amount_in_with_fee: U256 = U256::from(SWAP_VALUE).saturating_mul(U256::from(997u128));
+		let amount_in_with_fee: U256 = U256::from(SWAP_VALUE).saturating_mul(U256::from(9970u128));
 		let numerator: U256 =
 			U256::from(amount_in_with_fee).saturating_mul(U256::from(reserve_out));
-		let denominator: U256 = (U256::from(reserve_in).saturating_mul(U256::from(1000u128)))
+		let denominator: U256 = (U256::from(reserve_in).saturating_mul(U256::from(10000u128)))
 			.saturating_add(amount_in_with_fee);
 		let amount_out_2 = numerator
 			.checked_div(denominator)
@@ -250,7 +317,7 @@ fn swap_exact_tokens_for_tokens_should_work() {
 			100
 		));
 
-		let (reserve_in_2, reserve_out_2) = Exchange::get_reserves(TOKENA, TOKENB).unwrap();
+		let (reserve_in_2, reserve_out_2) = Exchange::get_reserves(TOKENA, TOKENB, 30).unwrap();
 		assert_eq!(reserve_in_2, TOKENA_LIQUIDITY + SWAP_VALUE);
 		assert_eq!(reserve_out_2, TOKENB_LIQUIDITY - amount_out_2);
 
@@ -269,7 +336,7 @@ fn swap_exact_tokens_for_tokens_should_work() {
 fn swap_tokens_for_exact_tokens_should_works() {
 	new_test_ext().execute_with(|| {
 		create_tokens();
-		assert_ok!(Exchange::create_pool(Origin::signed(ALICE), TOKENA, TOKENB));
+		assert_ok!(Exchange::create_pool(Origin::signed(ALICE), TOKENA, TOKENB, 30));
 
 		set_balance();
 
@@ -277,6 +344,7 @@ fn swap_tokens_for_exact_tokens_should_works() {
 			Origin::signed(ALICE),
 			TOKENA,
 			TOKENB,
+			30,
 			TOKENA_LIQUIDITY,
 			TOKENB_LIQUIDITY,
 			0u128,
@@ -285,16 +353,16 @@ fn swap_tokens_for_exact_tokens_should_works() {
 			100
 		));
 
-		let (reserve_in, reserve_out) = Exchange::get_reserves(TOKENA, TOKENB).unwrap();
+		let (reserve_in, reserve_out) = Exchange::get_reserves(TOKENA, TOKENB, 30).unwrap();
 		assert!(reserve_in == TOKENA_LIQUIDITY && reserve_out == TOKENB_LIQUIDITY);
 
-		let amount_in_1 = Exchange::get_amount_in(SWAP_VALUE, reserve_in, reserve_out).unwrap();
+		let amount_in_1 = Exchange::get_amount_in(SWAP_VALUE, reserve_in, reserve_out, 30).unwrap();
 
 		let numerator: U256 = U256::from(reserve_in)
 			.saturating_mul(U256::from(SWAP_VALUE))
-			.saturating_mul(U256::from(1000u128));
+			.saturating_mul(U256::from(10000u128));
 		let denominator: U256 = (U256::from(reserve_out).saturating_sub(U256::from(SWAP_VALUE)))
-			.saturating_mul(U256::from(997u128));
+			.saturating_mul(U256::from(9970u128));
 		let amount_in_2 = numerator
 			.checked_div(denominator)
 			.and_then(|r| r.checked_add(U256::one()))
@@ -313,7 +381,7 @@ fn swap_tokens_for_exact_tokens_should_works() {
 			100
 		));
 
-		let (reserve_in_2, reserve_out_2) = Exchange::get_reserves(TOKENA, TOKENB).unwrap();
+		let (reserve_in_2, reserve_out_2) = Exchange::get_reserves(TOKENA, TOKENB, 30).unwrap();
 		assert_eq!(reserve_in_2, TOKENA_LIQUIDITY + amount_in_1);
 		assert_eq!(reserve_out_2, TOKENB_LIQUIDITY - SWAP_VALUE);
 
@@ -327,3 +395,1680 @@ fn swap_tokens_for_exact_tokens_should_works() {
 		);
 	})
 }
+
+#[test]
+fn fee_tiers_should_work() {
+	new_test_ext().execute_with(|| {
+		create_tokens();
+
+		assert_ok!(Exchange::create_pool(Origin::signed(ALICE), TOKENA, TOKENB, 30));
+		assert_ok!(Exchange::create_pool(Origin::signed(ALICE), TOKENA, TOKENB, 100));
+		assert_eq!(Exchange::exists(TOKENA, TOKENB, 30), true);
+		assert_eq!(Exchange::exists(TOKENA, TOKENB, 100), true);
+		assert_eq!(Exchange::exists(TOKENA, TOKENB, 5), false);
+		assert_ne!(
+			Exchange::get_pool(TOKENA, TOKENB, 30).unwrap().lp_token,
+			Exchange::get_pool(TOKENA, TOKENB, 100).unwrap().lp_token
+		);
+
+		assert_noop!(
+			Exchange::create_pool(Origin::signed(ALICE), TOKENA, TOKENB, 50),
+			Error::<Test>::FeeTierNotFound
+		);
+		assert_noop!(
+			Exchange::set_fee_tier(Origin::signed(ALICE), 50, true),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Exchange::set_fee_tier(Origin::root(), FEE_DENOMINATOR, true),
+			Error::<Test>::InvalidFeeTier
+		);
+
+		assert_ok!(Exchange::set_fee_tier(Origin::root(), 50, true));
+		System::assert_last_event(crate::mock::Event::Exchange(crate::Event::FeeTierSet(50, true)));
+		assert_ok!(Exchange::create_pool(Origin::signed(ALICE), TOKENA, TOKENB, 50));
+
+		assert_ok!(Exchange::set_fee_tier(Origin::root(), 50, false));
+		assert_noop!(
+			Exchange::create_pool(Origin::signed(ALICE), TOKENA, TOKENC, 50),
+			Error::<Test>::FeeTierNotFound
+		);
+	})
+}
+
+#[test]
+fn swap_should_use_best_fee_tier() {
+	new_test_ext().execute_with(|| {
+		create_tokens();
+		set_balance();
+
+		for fee in [30, 100] {
+			assert_ok!(Exchange::create_pool(Origin::signed(ALICE), TOKENA, TOKENB, fee));
+			assert_ok!(Exchange::add_liquidity(
+				Origin::signed(ALICE),
+				TOKENA,
+				TOKENB,
+				fee,
+				TOKENA_LIQUIDITY,
+				TOKENB_LIQUIDITY,
+				0u128,
+				0u128,
+				ALICE,
+				100
+			));
+		}
+
+		let amount_out_30 =
+			Exchange::get_amount_out(SWAP_VALUE, TOKENA_LIQUIDITY, TOKENB_LIQUIDITY, 30).unwrap();
+		let amount_out_100 =
+			Exchange::get_amount_out(SWAP_VALUE, TOKENA_LIQUIDITY, TOKENB_LIQUIDITY, 100).unwrap();
+		assert!(amount_out_30 > amount_out_100);
+
+		assert_eq!(
+			Exchange::get_route_out(SWAP_VALUE, vec![TOKENA, TOKENB]).unwrap(),
+			(vec![SWAP_VALUE, amount_out_30], vec![30])
+		);
+
+		assert_ok!(Exchange::swap_exact_tokens_for_tokens(
+			Origin::signed(ALICE),
+			SWAP_VALUE,
+			0,
+			vec![TOKENA, TOKENB],
+			ALICE,
+			100
+		));
+
+		assert_eq!(
+			Exchange::get_reserves(TOKENA, TOKENB, 30).unwrap(),
+			(TOKENA_LIQUIDITY + SWAP_VALUE, TOKENB_LIQUIDITY - amount_out_30)
+		);
+		assert_eq!(
+			Exchange::get_reserves(TOKENA, TOKENB, 100).unwrap(),
+			(TOKENA_LIQUIDITY, TOKENB_LIQUIDITY)
+		);
+	})
+}
+
+#[test]
+fn set_protocol_fee_share_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Exchange::protocol_fee_share(), Permill::from_rational(1u32, 6u32));
+
+		assert_noop!(
+			Exchange::set_protocol_fee_share(Origin::signed(ALICE), Permill::from_percent(20)),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(Exchange::set_protocol_fee_share(Origin::root(), Permill::from_percent(20)));
+		assert_eq!(Exchange::protocol_fee_share(), Permill::from_percent(20));
+	})
+}
+
+fn create_pool_with_liquidity() {
+	create_tokens();
+	set_balance();
+	assert_ok!(Exchange::create_pool(Origin::signed(ALICE), TOKENA, TOKENB, 30));
+	assert_ok!(Exchange::add_liquidity(
+		Origin::signed(ALICE),
+		TOKENA,
+		TOKENB,
+		30,
+		TOKENA_LIQUIDITY,
+		TOKENB_LIQUIDITY,
+		0u128,
+		0u128,
+		ALICE,
+		100
+	));
+}
+
+#[test]
+fn flash_swap_should_work() {
+	new_test_ext().execute_with(|| {
+		create_pool_with_liquidity();
+
+		let amount_in =
+			Exchange::get_amount_in(SWAP_VALUE, TOKENA_LIQUIDITY, TOKENB_LIQUIDITY, 30).unwrap();
+
+		assert_noop!(
+			Exchange::flash_swap(Origin::signed(ALICE), TOKENA, TOKENB, 30, 0, SWAP_VALUE, vec![4]),
+			Error::<Test>::FlashCallbackFailed
+		);
+		assert_noop!(
+			Exchange::flash_swap(
+				Origin::signed(ALICE),
+				TOKENA,
+				TOKENB,
+				30,
+				0,
+				SWAP_VALUE,
+				Vec::<(u128, Balance)>::new().encode()
+			),
+			Error::<Test>::InsufficientInputAmount
+		);
+		assert_noop!(
+			Exchange::flash_swap(
+				Origin::signed(ALICE),
+				TOKENA,
+				TOKENB,
+				30,
+				0,
+				SWAP_VALUE,
+				vec![(TOKENA, amount_in / 2)].encode()
+			),
+			Error::<Test>::AdjustedError
+		);
+
+		assert_ok!(Exchange::flash_swap(
+			Origin::signed(ALICE),
+			TOKENA,
+			TOKENB,
+			30,
+			0,
+			SWAP_VALUE,
+			vec![(TOKENA, amount_in)].encode()
+		));
+		System::assert_last_event(crate::mock::Event::Exchange(crate::Event::FlashSwap(
+			ALICE, TOKENA, TOKENB, 30, 0, SWAP_VALUE,
+		)));
+		assert_eq!(
+			Exchange::get_reserves(TOKENA, TOKENB, 30).unwrap(),
+			(TOKENA_LIQUIDITY + amount_in, TOKENB_LIQUIDITY - SWAP_VALUE)
+		);
+
+		// the pool is only unlocked within a flash swap
+		assert_noop!(
+			Exchange::flash_repay(&ALICE, TOKENA, TOKENB, 30, TOKENA, amount_in),
+			Error::<Test>::PoolNotLocked
+		);
+	})
+}
+
+#[test]
+fn flash_loan_should_work() {
+	new_test_ext().execute_with(|| {
+		create_pool_with_liquidity();
+
+		let premium = Exchange::flash_loan_premium(SWAP_VALUE, 30).unwrap();
+		assert_eq!(premium, SWAP_VALUE * 30 / 10000);
+
+		assert_noop!(
+			Exchange::flash_loan(
+				Origin::signed(ALICE),
+				TOKENA,
+				TOKENB,
+				30,
+				SWAP_VALUE,
+				vec![(TOKENA, SWAP_VALUE)].encode()
+			),
+			Error::<Test>::FlashLoanNotRepaid
+		);
+		assert_noop!(
+			Exchange::flash_loan(
+				Origin::signed(ALICE),
+				TOKENA,
+				TOKENB,
+				30,
+				TOKENA_LIQUIDITY,
+				Vec::<(u128, Balance)>::new().encode()
+			),
+			Error::<Test>::InsufficientLiquidity
+		);
+
+		assert_ok!(Exchange::flash_loan(
+			Origin::signed(ALICE),
+			TOKENA,
+			TOKENB,
+			30,
+			SWAP_VALUE,
+			vec![(TOKENA, SWAP_VALUE + premium)].encode()
+		));
+		System::assert_last_event(crate::mock::Event::Exchange(crate::Event::FlashLoan(
+			ALICE, TOKENA, TOKENB, 30, SWAP_VALUE, premium,
+		)));
+		assert_eq!(
+			Exchange::get_reserves(TOKENA, TOKENB, 30).unwrap(),
+			(TOKENA_LIQUIDITY + premium, TOKENB_LIQUIDITY)
+		);
+	})
+}
+
+fn swap_a_for_b() {
+	assert_ok!(Exchange::swap_exact_tokens_for_tokens(
+		Origin::signed(ALICE),
+		SWAP_VALUE,
+		0,
+		vec![TOKENA, TOKENB],
+		ALICE,
+		1000
+	));
+}
+
+#[test]
+fn liquidity_provider_should_work() {
+	use pallet_support::LiquidityProvider;
+
+	new_test_ext().execute_with(|| {
+		create_tokens();
+		set_balance();
+		assert_eq!(<Exchange as LiquidityProvider<u64, u128>>::lp_token(TOKENB, TOKENA, 30), None);
+		assert_noop!(
+			<Exchange as LiquidityProvider<u64, u128>>::ensure_pool(&ALICE, TOKENA, TOKENB, 31),
+			Error::<Test>::FeeTierNotFound
+		);
+		assert_ok!(<Exchange as LiquidityProvider<u64, u128>>::ensure_pool(
+			&ALICE, TOKENB, TOKENA, 30
+		));
+		// an existing pool is left as it is
+		assert_ok!(<Exchange as LiquidityProvider<u64, u128>>::ensure_pool(
+			&ALICE, TOKENA, TOKENB, 30
+		));
+		let lp_token = Exchange::pools((TOKENA, TOKENB), 30).unwrap().lp_token;
+		assert_eq!(
+			<Exchange as LiquidityProvider<u64, u128>>::lp_token(TOKENB, TOKENA, 30),
+			Some(lp_token)
+		);
+
+		assert_ok!(<Exchange as LiquidityProvider<u64, u128>>::add_liquidity(
+			&ALICE,
+			TOKENB,
+			TOKENA,
+			30,
+			(TOKENB_LIQUIDITY, TOKENA_LIQUIDITY),
+			(0, 0),
+			&BOB
+		));
+		// the amounts follow the order of the tokens given, and only what the price asks for
+		// is taken
+		let (amount_b, amount_a, liquidity) =
+			<Exchange as LiquidityProvider<u64, u128>>::add_liquidity(
+				&ALICE,
+				TOKENB,
+				TOKENA,
+				30,
+				(TOKENB_LIQUIDITY, TOKENB_LIQUIDITY),
+				(TOKENB_LIQUIDITY, TOKENA_LIQUIDITY),
+				&BOB,
+			)
+			.unwrap();
+		assert_eq!((amount_b, amount_a), (TOKENB_LIQUIDITY, TOKENA_LIQUIDITY));
+		// unless the price of the pool takes less than the minimum amounts
+		assert_noop!(
+			<Exchange as LiquidityProvider<u64, u128>>::add_liquidity(
+				&ALICE,
+				TOKENB,
+				TOKENA,
+				30,
+				(TOKENB_LIQUIDITY, TOKENB_LIQUIDITY),
+				(0, TOKENB_LIQUIDITY),
+				&BOB,
+			),
+			Error::<Test>::InsufficientAAmount
+		);
+		assert_eq!(TokenFungible::balance_of(lp_token, BOB), liquidity * 2 - MINIMUM_LIQUIDITY);
+	})
+}
+
+#[test]
+fn consult_should_work() {
+	new_test_ext().execute_with(|| {
+		create_pool_with_liquidity();
+
+		assert_noop!(Exchange::consult(TOKENA, TOKENB, 30, 0), Error::<Test>::InvalidWindow);
+		assert_noop!(Exchange::consult(TOKENA, TOKENC, 30, 1), Error::<Test>::PoolNotFound);
+		assert_noop!(Exchange::consult(TOKENA, TOKENB, 30, 1), Error::<Test>::ObservationNotFound);
+
+		let (price_0, price_1) = Exchange::spot_prices(TOKENA_LIQUIDITY, TOKENB_LIQUIDITY);
+		assert_eq!(price_0, FixedU128::saturating_from_integer(2));
+
+		System::set_block_number(11);
+		swap_a_for_b();
+		let (reserve_a, reserve_b) = Exchange::get_reserves(TOKENA, TOKENB, 30).unwrap();
+		let (new_price_0, new_price_1) = Exchange::spot_prices(reserve_a, reserve_b);
+		assert_eq!(Exchange::price_cumulatives((TOKENA, TOKENB, 30)).block_number, 11);
+
+		System::set_block_number(21);
+		assert_eq!(Exchange::consult(TOKENA, TOKENB, 30, 10), Ok((new_price_0, new_price_1)));
+		assert_eq!(Exchange::consult(TOKENB, TOKENA, 30, 10), Ok((new_price_1, new_price_0)));
+		assert_eq!(
+			Exchange::consult(TOKENA, TOKENB, 30, 20),
+			Ok((
+				FixedU128::from_inner(
+					(price_0.into_inner() * 10 + new_price_0.into_inner() * 10) / 20
+				),
+				FixedU128::from_inner(
+					(price_1.into_inner() * 10 + new_price_1.into_inner() * 10) / 20
+				),
+			))
+		);
+		assert_eq!(
+			<Exchange as pallet_support::PriceOracle<u128, u64>>::consult(TOKENA, TOKENB, 30, 10),
+			Some(new_price_0)
+		);
+	})
+}
+
+#[test]
+fn observations_should_be_a_ring_buffer() {
+	new_test_ext().execute_with(|| {
+		create_pool_with_liquidity();
+
+		for block in [11, 21, 31, 41, 45, 51] {
+			System::set_block_number(block);
+			swap_a_for_b();
+		}
+
+		// the update at block 45 was within the observation period
+		let mut blocks = Observations::<Test>::iter_prefix_values((TOKENA, TOKENB, 30))
+			.map(|observation| observation.block_number)
+			.collect::<Vec<_>>();
+		blocks.sort();
+		assert_eq!(blocks, vec![21, 31, 41, 51]);
+
+		System::set_block_number(61);
+		assert_ok!(Exchange::consult(TOKENA, TOKENB, 30, 40));
+		assert_noop!(Exchange::consult(TOKENA, TOKENB, 30, 45), Error::<Test>::ObservationNotFound);
+	})
+}
+
+fn create_w3g_pool_with_liquidity() {
+	create_tokens();
+	set_balance();
+	assert_ok!(Exchange::create_pool(Origin::signed(ALICE), WW3G::get(), TOKENA, 30));
+	assert_ok!(Exchange::add_liquidity_w3g(
+		Origin::signed(ALICE),
+		TOKENA,
+		30,
+		TOKENA_LIQUIDITY,
+		TOKENA_LIQUIDITY,
+		0u128,
+		0u128,
+		ALICE,
+		100
+	));
+}
+
+#[test]
+fn add_and_remove_liquidity_w3g_should_work() {
+	new_test_ext().execute_with(|| {
+		create_w3g_pool_with_liquidity();
+
+		// only the native currency used is wrapped
+		let native = Balances::free_balance(ALICE);
+		assert_ok!(Exchange::add_liquidity_w3g(
+			Origin::signed(ALICE),
+			TOKENA,
+			30,
+			TOKENA_LIQUIDITY * 2,
+			TOKENA_LIQUIDITY,
+			0u128,
+			0u128,
+			ALICE,
+			100
+		));
+		assert_eq!(Balances::free_balance(ALICE), native - TOKENA_LIQUIDITY);
+		assert_eq!(TokenFungible::balance_of(WW3G::get(), ALICE), 0);
+
+		let lp_token = Exchange::get_pool(WW3G::get(), TOKENA, 30).unwrap().lp_token;
+		let native = Balances::free_balance(BOB);
+		assert_ok!(Exchange::remove_liquidity_w3g(
+			Origin::signed(ALICE),
+			TOKENA,
+			30,
+			TOKENA_LIQUIDITY,
+			0u128,
+			0u128,
+			BOB,
+			100
+		));
+		assert_eq!(Balances::free_balance(BOB), native + TOKENA_LIQUIDITY);
+		assert_eq!(TokenFungible::balance_of(TOKENA, BOB), TOKENA_LIQUIDITY);
+		assert_eq!(TokenFungible::balance_of(WW3G::get(), ALICE), 0);
+		assert_eq!(
+			TokenFungible::balance_of(lp_token, ALICE),
+			2 * TOKENA_LIQUIDITY - MINIMUM_LIQUIDITY - TOKENA_LIQUIDITY
+		);
+	})
+}
+
+#[test]
+fn swap_exact_tokens_for_w3g_should_work() {
+	new_test_ext().execute_with(|| {
+		create_w3g_pool_with_liquidity();
+
+		assert_noop!(
+			Exchange::swap_exact_tokens_for_w3g(
+				Origin::signed(ALICE),
+				SWAP_VALUE,
+				0,
+				vec![],
+				BOB,
+				100
+			),
+			Error::<Test>::InvalidPath
+		);
+		assert_noop!(
+			Exchange::swap_exact_tokens_for_w3g(
+				Origin::signed(ALICE),
+				SWAP_VALUE,
+				0,
+				vec![WW3G::get(), TOKENA],
+				BOB,
+				100
+			),
+			Error::<Test>::InvalidPath
+		);
+
+		let amounts = Exchange::get_amounts_out(SWAP_VALUE, vec![TOKENA, WW3G::get()]).unwrap();
+		let native = Balances::free_balance(BOB);
+		assert_ok!(Exchange::swap_exact_tokens_for_w3g(
+			Origin::signed(ALICE),
+			SWAP_VALUE,
+			amounts[1],
+			vec![TOKENA, WW3G::get()],
+			BOB,
+			100
+		));
+		assert_eq!(Balances::free_balance(BOB), native + amounts[1]);
+		assert_eq!(TokenFungible::balance_of(WW3G::get(), ALICE), 0);
+		assert_eq!(TokenFungible::balance_of(WW3G::get(), BOB), 0);
+	})
+}
+
+#[test]
+fn swap_w3g_for_exact_tokens_should_work() {
+	new_test_ext().execute_with(|| {
+		create_w3g_pool_with_liquidity();
+
+		let amounts = Exchange::get_amounts_in(SWAP_VALUE, vec![WW3G::get(), TOKENA]).unwrap();
+		assert_noop!(
+			Exchange::swap_w3g_for_exact_tokens(
+				Origin::signed(ALICE),
+				SWAP_VALUE,
+				amounts[0] - 1,
+				vec![WW3G::get(), TOKENA],
+				BOB,
+				100
+			),
+			Error::<Test>::InsufficientInputAmount
+		);
+
+		// only the native currency spent leaves the account
+		let native = Balances::free_balance(ALICE);
+		assert_ok!(Exchange::swap_w3g_for_exact_tokens(
+			Origin::signed(ALICE),
+			SWAP_VALUE,
+			amounts[0] * 2,
+			vec![WW3G::get(), TOKENA],
+			BOB,
+			100
+		));
+		assert_eq!(Balances::free_balance(ALICE), native - amounts[0]);
+		assert_eq!(TokenFungible::balance_of(WW3G::get(), ALICE), 0);
+		assert_eq!(TokenFungible::balance_of(TOKENA, BOB), SWAP_VALUE);
+	})
+}
+
+#[test]
+fn swap_tokens_for_exact_w3g_should_pay_native() {
+	new_test_ext().execute_with(|| {
+		create_w3g_pool_with_liquidity();
+
+		let amounts = Exchange::get_amounts_in(SWAP_VALUE, vec![TOKENA, WW3G::get()]).unwrap();
+		let native = Balances::free_balance(BOB);
+		assert_ok!(Exchange::swap_tokens_for_exact_w3g(
+			Origin::signed(ALICE),
+			SWAP_VALUE,
+			amounts[0],
+			vec![TOKENA, WW3G::get()],
+			BOB,
+			100
+		));
+		assert_eq!(Balances::free_balance(BOB), native + SWAP_VALUE);
+		assert_eq!(TokenFungible::balance_of(WW3G::get(), BOB), 0);
+	})
+}
+
+#[test]
+fn find_route_should_work() {
+	new_test_ext().execute_with(|| {
+		create_pool_with_liquidity();
+		assert_ok!(Exchange::create_pool(Origin::signed(ALICE), TOKENB, TOKENC, 30));
+		assert_ok!(Exchange::add_liquidity(
+			Origin::signed(ALICE),
+			TOKENB,
+			TOKENC,
+			30,
+			TOKENB_LIQUIDITY,
+			TOKENB_LIQUIDITY,
+			0u128,
+			0u128,
+			ALICE,
+			100
+		));
+		// a shallow direct pool
+		assert_ok!(Exchange::create_pool(Origin::signed(ALICE), TOKENA, TOKENC, 30));
+		assert_ok!(Exchange::add_liquidity(
+			Origin::signed(ALICE),
+			TOKENA,
+			TOKENC,
+			30,
+			SWAP_VALUE,
+			SWAP_VALUE,
+			0u128,
+			0u128,
+			ALICE,
+			100
+		));
+
+		assert_noop!(
+			Exchange::find_route_out(TOKENA, TOKENA, SWAP_VALUE, 2),
+			Error::<Test>::TokenRepeat
+		);
+		assert_noop!(
+			Exchange::find_route_out(TOKENA, TOKENC, SWAP_VALUE, 0),
+			Error::<Test>::InvalidPath
+		);
+		assert_noop!(
+			Exchange::find_route_out(TOKENA, 4, SWAP_VALUE, 2),
+			Error::<Test>::RouteNotFound
+		);
+
+		let route = Exchange::find_route_out(TOKENA, TOKENC, SWAP_VALUE, 1).unwrap();
+		assert_eq!(route.path, vec![TOKENA, TOKENC]);
+		assert!(route.price_impact > Permill::from_percent(40));
+
+		let route = Exchange::find_route_out(TOKENA, TOKENC, SWAP_VALUE, 2).unwrap();
+		assert_eq!(route.path, vec![TOKENA, TOKENB, TOKENC]);
+		assert_eq!(
+			route.amounts,
+			Exchange::get_amounts_out(SWAP_VALUE, route.path.clone()).unwrap()
+		);
+		assert_eq!(route.fees, vec![30, 30]);
+		assert_eq!(
+			route.fee_amounts,
+			vec![SWAP_VALUE * 30 / 10_000, route.amounts[1] * 30 / 10_000]
+		);
+		assert!(route.price_impact > Permill::zero());
+		assert!(route.price_impact < Permill::from_percent(1));
+
+		let route = Exchange::find_route_in(TOKENA, TOKENC, SWAP_VALUE, 3).unwrap();
+		assert_eq!(route.path, vec![TOKENA, TOKENB, TOKENC]);
+		assert_eq!(
+			route.amounts,
+			Exchange::get_amounts_in(SWAP_VALUE, route.path.clone()).unwrap()
+		);
+		assert_eq!(route.amounts[2], SWAP_VALUE);
+	})
+}
+
+#[test]
+fn pool_queries_should_work() {
+	new_test_ext().execute_with(|| {
+		create_pool_with_liquidity();
+		assert_ok!(Exchange::create_pool(Origin::signed(ALICE), TOKENB, TOKENC, 30));
+		let pool_ab = Exchange::pools((TOKENA, TOKENB), 30).unwrap();
+		let pool_bc = Exchange::pools((TOKENB, TOKENC), 30).unwrap();
+
+		let info = Exchange::get_pool_info(TOKENB, TOKENA, 30).unwrap();
+		assert_eq!(info.token_0, TOKENA);
+		assert_eq!(info.token_1, TOKENB);
+		assert_eq!(info.lp_token, pool_ab.lp_token);
+		assert_eq!((info.reserve_0, info.reserve_1), (TOKENA_LIQUIDITY, TOKENB_LIQUIDITY));
+		assert_eq!(info.total_supply, TokenFungible::total_supply(pool_ab.lp_token));
+		assert_eq!(Exchange::get_pool_info_by_lp_token(pool_ab.lp_token), Some(info.clone()));
+		assert_eq!(Exchange::get_pool_info(TOKENA, TOKENC, 30), None);
+		assert_eq!(Exchange::get_pool_info_by_lp_token(TOKENA), None);
+
+		let pools = Exchange::get_pools(0, 10);
+		assert_eq!(pools.len(), 2);
+		assert!(pools[0].lp_token < pools[1].lp_token);
+		assert_eq!(Exchange::get_pools(1, 10), pools[1..].to_vec());
+		assert_eq!(Exchange::get_pools(0, 1), pools[..1].to_vec());
+		assert!(Exchange::get_pools(2, 10).is_empty());
+		assert_eq!(Exchange::get_pool_info_by_lp_token(pool_bc.lp_token).unwrap().total_supply, 0);
+
+		let lp_balance = TokenFungible::balance_of(pool_ab.lp_token, &ALICE);
+		let positions = Exchange::get_lp_positions(&ALICE);
+		assert_eq!(positions.len(), 1);
+		assert_eq!(positions[0].pool, info);
+		assert_eq!(positions[0].lp_balance, lp_balance);
+		assert_eq!(positions[0].amount_0, TOKENA_LIQUIDITY * lp_balance / info.total_supply);
+		assert_eq!(positions[0].amount_1, TOKENB_LIQUIDITY * lp_balance / info.total_supply);
+		assert!(Exchange::get_lp_positions(&BOB).is_empty());
+	})
+}
+
+#[test]
+fn place_and_cancel_order_should_work() {
+	new_test_ext().execute_with(|| {
+		create_pool_with_liquidity();
+		let price = FixedU128::saturating_from_integer(3);
+
+		assert_noop!(
+			Exchange::place_order(Origin::signed(ALICE), TOKENA, TOKENA, SWAP_VALUE, price, 100),
+			Error::<Test>::TokenRepeat
+		);
+		assert_noop!(
+			Exchange::place_order(Origin::signed(ALICE), TOKENA, TOKENB, 0, price, 100),
+			Error::<Test>::InvalidOrder
+		);
+		assert_noop!(
+			Exchange::place_order(
+				Origin::signed(ALICE),
+				TOKENA,
+				TOKENB,
+				SWAP_VALUE,
+				FixedU128::zero(),
+				100
+			),
+			Error::<Test>::InvalidOrder
+		);
+		assert_noop!(
+			Exchange::place_order(Origin::signed(ALICE), TOKENA, TOKENB, SWAP_VALUE, price, 1),
+			Error::<Test>::InvalidOrder
+		);
+		assert_noop!(
+			Exchange::place_order(Origin::signed(ALICE), TOKENA, TOKENC, SWAP_VALUE, price, 100),
+			Error::<Test>::PoolNotFound
+		);
+
+		let balance = TokenFungible::balance_of(TOKENA, ALICE);
+		assert_ok!(Exchange::place_order(
+			Origin::signed(ALICE),
+			TOKENA,
+			TOKENB,
+			SWAP_VALUE,
+			price,
+			100
+		));
+		System::assert_last_event(crate::mock::Event::Exchange(crate::Event::OrderPlaced(
+			0, ALICE, TOKENA, TOKENB, SWAP_VALUE, price, 100,
+		)));
+		assert_eq!(TokenFungible::balance_of(TOKENA, ALICE), balance - SWAP_VALUE);
+
+		// the pools sell at about 2 B per A
+		assert_noop!(Exchange::fill_order(Origin::signed(BOB), 0), Error::<Test>::OrderNotFillable);
+
+		assert_noop!(Exchange::cancel_order(Origin::signed(BOB), 0), Error::<Test>::NotOrderOwner);
+		assert_ok!(Exchange::cancel_order(Origin::signed(ALICE), 0));
+		System::assert_last_event(crate::mock::Event::Exchange(crate::Event::OrderCancelled(
+			0, ALICE, SWAP_VALUE,
+		)));
+		assert_eq!(TokenFungible::balance_of(TOKENA, ALICE), balance);
+		assert_eq!(Exchange::orders(0), None);
+		assert_noop!(
+			Exchange::cancel_order(Origin::signed(ALICE), 0),
+			Error::<Test>::OrderNotFound
+		);
+	})
+}
+
+#[test]
+fn fill_order_should_work() {
+	new_test_ext().execute_with(|| {
+		create_pool_with_liquidity();
+		let price = FixedU128::saturating_from_rational(19, 10);
+		let amount = SWAP_VALUE * 100;
+		assert_ok!(Exchange::place_order(
+			Origin::signed(ALICE),
+			TOKENA,
+			TOKENB,
+			amount,
+			price,
+			100
+		));
+
+		// the pool only fills the order until its price drops to the order price
+		let balance = TokenFungible::balance_of(TOKENB, ALICE);
+		assert_ok!(Exchange::fill_order(Origin::signed(BOB), 0));
+		let order = Exchange::orders(0).unwrap();
+		let filled = amount - order.amount_in;
+		let bought = TokenFungible::balance_of(TOKENB, ALICE) - balance;
+		assert!(filled > amount / 3 && filled < amount);
+		assert!(bought >= price.saturating_mul_int(filled));
+		System::assert_last_event(crate::mock::Event::Exchange(crate::Event::OrderFilled(
+			0,
+			filled,
+			bought,
+			order.amount_in,
+		)));
+		assert_noop!(Exchange::fill_order(Origin::signed(BOB), 0), Error::<Test>::OrderNotFillable);
+
+		// buying A back lifts its price over the order
+		assert_ok!(TokenFungible::transfer(Origin::signed(ALICE), TOKENB, BOB, SWAP_VALUE * 100));
+		assert_ok!(Exchange::swap_exact_tokens_for_tokens(
+			Origin::signed(BOB),
+			SWAP_VALUE * 100,
+			0,
+			vec![TOKENB, TOKENA],
+			BOB,
+			100
+		));
+		assert_ok!(Exchange::fill_order(Origin::signed(BOB), 0));
+		assert_eq!(Exchange::orders(0), None);
+		assert!(
+			TokenFungible::balance_of(TOKENB, ALICE) + SWAP_VALUE * 100 - balance >=
+				price.saturating_mul_int(amount)
+		);
+	})
+}
+
+#[test]
+fn orders_should_be_matched_on_idle() {
+	new_test_ext().execute_with(|| {
+		create_pool_with_liquidity();
+		let price = FixedU128::saturating_from_integer(1);
+		for _ in 0..3 {
+			assert_ok!(Exchange::place_order(
+				Origin::signed(ALICE),
+				TOKENA,
+				TOKENB,
+				SWAP_VALUE,
+				price,
+				100
+			));
+		}
+		assert_ok!(Exchange::place_order(
+			Origin::signed(ALICE),
+			TOKENB,
+			TOKENA,
+			SWAP_VALUE,
+			price,
+			5
+		));
+
+		assert_eq!(Exchange::on_idle(1, 0), 0);
+		assert_eq!(Orders::<Test>::iter().count(), 4);
+
+		// two orders a block
+		System::set_block_number(6);
+		Exchange::on_idle(6, Weight::MAX);
+		assert_eq!(Orders::<Test>::iter().count(), 2);
+		Exchange::on_idle(6, Weight::MAX);
+		assert_eq!(Orders::<Test>::iter().count(), 0);
+		System::assert_has_event(crate::mock::Event::Exchange(crate::Event::OrderExpired(
+			3, ALICE, SWAP_VALUE,
+		)));
+	})
+}
+
+#[test]
+fn zap_in_and_out_should_work() {
+	new_test_ext().execute_with(|| {
+		create_pool_with_liquidity();
+		let lp_token = Exchange::pools((TOKENA, TOKENB), 30).unwrap().lp_token;
+		let amount_in = SWAP_VALUE * 100;
+		assert_ok!(TokenFungible::mint(Origin::signed(ALICE), TOKENA, BOB, amount_in));
+
+		assert_noop!(
+			Exchange::zap_in(
+				Origin::signed(BOB),
+				TOKENC,
+				amount_in,
+				TOKENA,
+				TOKENB,
+				30,
+				0,
+				BOB,
+				100
+			),
+			Error::<Test>::InvalidPath
+		);
+		assert_noop!(
+			Exchange::zap_in(
+				Origin::signed(BOB),
+				TOKENA,
+				amount_in,
+				TOKENA,
+				TOKENB,
+				5,
+				0,
+				BOB,
+				100
+			),
+			Error::<Test>::PoolNotFound
+		);
+		assert_noop!(
+			Exchange::zap_in(
+				Origin::signed(BOB),
+				TOKENA,
+				amount_in,
+				TOKENA,
+				TOKENB,
+				30,
+				amount_in,
+				BOB,
+				100
+			),
+			Error::<Test>::InsufficientLiquidityMinted
+		);
+
+		assert_ok!(Exchange::zap_in(
+			Origin::signed(BOB),
+			TOKENA,
+			amount_in,
+			TOKENB,
+			TOKENA,
+			30,
+			0,
+			BOB,
+			100
+		));
+		let liquidity = TokenFungible::balance_of(lp_token, &BOB);
+		assert!(liquidity > 0);
+		System::assert_last_event(crate::mock::Event::Exchange(crate::Event::ZappedIn(
+			BOB, TOKENA, amount_in, lp_token, liquidity,
+		)));
+		// only dust is left over
+		assert!(TokenFungible::balance_of(TOKENA, &BOB) < amount_in / 1_000_000);
+		assert!(TokenFungible::balance_of(TOKENB, &BOB) < amount_in / 1_000_000);
+
+		assert_noop!(
+			Exchange::zap_out(
+				Origin::signed(BOB),
+				TOKENA,
+				TOKENB,
+				30,
+				liquidity,
+				TOKENA,
+				amount_in,
+				CHARLIE,
+				100
+			),
+			Error::<Test>::InsufficientOutAmount
+		);
+		assert_ok!(Exchange::zap_out(
+			Origin::signed(BOB),
+			TOKENA,
+			TOKENB,
+			30,
+			liquidity,
+			TOKENA,
+			amount_in * 99 / 100,
+			CHARLIE,
+			100
+		));
+		let amount_out = TokenFungible::balance_of(TOKENA, &CHARLIE);
+		assert!(amount_out >= amount_in * 99 / 100);
+		assert_eq!(TokenFungible::balance_of(lp_token, &BOB), 0);
+		assert_eq!(TokenFungible::balance_of(TOKENB, &CHARLIE), 0);
+		System::assert_last_event(crate::mock::Event::Exchange(crate::Event::ZappedOut(
+			BOB, lp_token, liquidity, TOKENA, amount_out,
+		)));
+	})
+}
+
+#[test]
+fn pool_status_should_work() {
+	new_test_ext().execute_with(|| {
+		create_pool_with_liquidity();
+		let deposit = CreatePoolDeposit::get();
+		assert_eq!(Balances::reserved_balance(ALICE), deposit);
+		assert_eq!(Exchange::pool_deposit(0), Some((ALICE, deposit)));
+		let lp_token = Exchange::pools((TOKENA, TOKENB), 30).unwrap().lp_token;
+		let liquidity = TokenFungible::balance_of(lp_token, &ALICE) / 10;
+		let path = vec![TOKENA, TOKENB];
+
+		assert_noop!(
+			Exchange::set_pool_status(
+				Origin::signed(ALICE),
+				TOKENA,
+				TOKENB,
+				30,
+				PoolStatus::Paused
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Exchange::set_pool_status(Origin::root(), TOKENA, TOKENC, 30, PoolStatus::Paused),
+			Error::<Test>::PoolNotFound
+		);
+
+		assert_ok!(Exchange::set_pool_status(
+			Origin::root(),
+			TOKENB,
+			TOKENA,
+			30,
+			PoolStatus::SwapsPaused
+		));
+		System::assert_last_event(crate::mock::Event::Exchange(crate::Event::PoolStatusChanged(
+			TOKENA,
+			TOKENB,
+			30,
+			PoolStatus::SwapsPaused,
+		)));
+		assert_eq!(
+			Exchange::get_pool_info(TOKENA, TOKENB, 30).unwrap().status,
+			PoolStatus::SwapsPaused
+		);
+		assert_noop!(
+			Exchange::swap_exact_tokens_for_tokens(
+				Origin::signed(ALICE),
+				SWAP_VALUE,
+				0,
+				path.clone(),
+				ALICE,
+				100
+			),
+			Error::<Test>::PoolPaused
+		);
+		assert_ok!(Exchange::add_liquidity(
+			Origin::signed(ALICE),
+			TOKENA,
+			TOKENB,
+			30,
+			SWAP_VALUE,
+			SWAP_VALUE * 2,
+			0,
+			0,
+			ALICE,
+			100
+		));
+
+		assert_ok!(Exchange::set_pool_status(
+			Origin::root(),
+			TOKENA,
+			TOKENB,
+			30,
+			PoolStatus::Emergency
+		));
+		assert_noop!(
+			Exchange::add_liquidity(
+				Origin::signed(ALICE),
+				TOKENA,
+				TOKENB,
+				30,
+				SWAP_VALUE,
+				SWAP_VALUE * 2,
+				0,
+				0,
+				ALICE,
+				100
+			),
+			Error::<Test>::PoolPaused
+		);
+		assert_ok!(Exchange::remove_liquidity(
+			Origin::signed(ALICE),
+			TOKENA,
+			TOKENB,
+			30,
+			liquidity,
+			0,
+			0,
+			ALICE,
+			100
+		));
+
+		assert_ok!(Exchange::set_pool_status(
+			Origin::root(),
+			TOKENA,
+			TOKENB,
+			30,
+			PoolStatus::LiquidityPaused
+		));
+		assert_noop!(
+			Exchange::remove_liquidity(
+				Origin::signed(ALICE),
+				TOKENA,
+				TOKENB,
+				30,
+				liquidity,
+				0,
+				0,
+				ALICE,
+				100
+			),
+			Error::<Test>::PoolPaused
+		);
+		assert_ok!(Exchange::swap_exact_tokens_for_tokens(
+			Origin::signed(ALICE),
+			SWAP_VALUE,
+			0,
+			path.clone(),
+			ALICE,
+			100
+		));
+
+		assert_ok!(Exchange::set_pool_status(
+			Origin::root(),
+			TOKENA,
+			TOKENB,
+			30,
+			PoolStatus::Active
+		));
+		assert_eq!(Exchange::pool_status((TOKENA, TOKENB, 30)), PoolStatus::Active);
+		assert_ok!(Exchange::swap_exact_tokens_for_tokens(
+			Origin::signed(ALICE),
+			SWAP_VALUE,
+			0,
+			path,
+			ALICE,
+			100
+		));
+
+		let pool_id = Exchange::next_pool_id();
+		assert_ok!(Exchange::create_stable_pool(
+			Origin::signed(ALICE),
+			vec![TOKENA, TOKENB],
+			100,
+			30
+		));
+		assert_eq!(Balances::reserved_balance(ALICE), deposit * 2);
+		assert_ok!(Exchange::add_stable_liquidity(
+			Origin::signed(ALICE),
+			pool_id,
+			vec![TOKENA_LIQUIDITY, TOKENA_LIQUIDITY],
+			0u128,
+			ALICE,
+			100
+		));
+		assert_ok!(Exchange::set_stable_pool_status(Origin::root(), pool_id, PoolStatus::Paused));
+		System::assert_last_event(crate::mock::Event::Exchange(
+			crate::Event::StablePoolStatusChanged(pool_id, PoolStatus::Paused),
+		));
+		assert_noop!(
+			Exchange::swap_stable(
+				Origin::signed(ALICE),
+				pool_id,
+				TOKENA,
+				TOKENB,
+				SWAP_VALUE,
+				0,
+				ALICE,
+				100
+			),
+			Error::<Test>::PoolPaused
+		);
+	})
+}
+
+const TOKEND: u128 = 4;
+const STABLE_LIQUIDITY: u128 = 1000_000_000_000_000;
+
+fn create_stable_pool_with_liquidity() -> u32 {
+	create_tokens();
+	set_balance();
+	assert_ok!(Exchange::create_stable_pool(Origin::signed(ALICE), vec![TOKENA, TOKENB], 100, 30));
+	assert_ok!(Exchange::add_stable_liquidity(
+		Origin::signed(ALICE),
+		0,
+		vec![STABLE_LIQUIDITY, STABLE_LIQUIDITY],
+		0u128,
+		ALICE,
+		100
+	));
+	0
+}
+
+#[test]
+fn create_stable_pool_should_not_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Exchange::create_stable_pool(Origin::signed(ALICE), vec![TOKENA], 100, 30),
+			Error::<Test>::InvalidStableTokens
+		);
+		assert_noop!(
+			Exchange::create_stable_pool(Origin::signed(ALICE), vec![TOKENA; 9], 100, 30),
+			Error::<Test>::InvalidStableTokens
+		);
+		assert_noop!(
+			Exchange::create_stable_pool(Origin::signed(ALICE), vec![TOKENA, TOKENB], 100, 30),
+			Error::<Test>::TokenAccountNotFound
+		);
+
+		create_tokens();
+		assert_noop!(
+			Exchange::create_stable_pool(Origin::signed(ALICE), vec![TOKENA, TOKENA], 100, 30),
+			Error::<Test>::TokenRepeat
+		);
+		assert_noop!(
+			Exchange::create_stable_pool(Origin::signed(ALICE), vec![TOKENA, TOKENB], 0, 30),
+			Error::<Test>::InvalidAmplification
+		);
+		assert_noop!(
+			Exchange::create_stable_pool(Origin::signed(ALICE), vec![TOKENA, TOKENB], 100, 31),
+			Error::<Test>::FeeTierNotFound
+		);
+
+		assert_ok!(Exchange::create_stable_pool(
+			Origin::signed(ALICE),
+			vec![TOKENA, TOKENB],
+			100,
+			30
+		));
+		System::assert_last_event(crate::mock::Event::Exchange(crate::Event::StablePoolCreated(
+			0,
+			vec![TOKENA, TOKENB],
+			100,
+			30,
+			ALICE,
+		)));
+		assert_eq!(Exchange::stable_reserves(0).into_inner(), vec![0, 0]);
+	})
+}
+
+#[test]
+fn stable_liquidity_should_work() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_stable_pool_with_liquidity();
+		let lp_token = Exchange::stable_pools(pool_id).unwrap().lp_token;
+
+		// a balanced first deposit mints its sum
+		assert_eq!(TokenFungible::balance_of(lp_token, ALICE), 2 * STABLE_LIQUIDITY);
+		assert_eq!(
+			Exchange::stable_reserves(pool_id).into_inner(),
+			vec![STABLE_LIQUIDITY, STABLE_LIQUIDITY]
+		);
+
+		assert_noop!(
+			Exchange::add_stable_liquidity(
+				Origin::signed(ALICE),
+				pool_id,
+				vec![STABLE_LIQUIDITY],
+				0u128,
+				ALICE,
+				100
+			),
+			Error::<Test>::InvalidAmounts
+		);
+
+		// an imbalanced deposit pays the fee on its imbalance
+		let liquidity = Exchange::get_stable_liquidity(pool_id, &[STABLE_LIQUIDITY, 0]).unwrap();
+		assert!(liquidity < STABLE_LIQUIDITY);
+		assert!(liquidity > STABLE_LIQUIDITY * 99 / 100);
+		assert_noop!(
+			Exchange::add_stable_liquidity(
+				Origin::signed(ALICE),
+				pool_id,
+				vec![STABLE_LIQUIDITY, 0],
+				liquidity + 1,
+				ALICE,
+				100
+			),
+			Error::<Test>::InsufficientLiquidityMinted
+		);
+
+		let amounts = Exchange::stable_liquidity_to_tokens(pool_id, STABLE_LIQUIDITY).unwrap();
+		assert_eq!(amounts, vec![STABLE_LIQUIDITY / 2, STABLE_LIQUIDITY / 2]);
+		assert_noop!(
+			Exchange::remove_stable_liquidity(
+				Origin::signed(ALICE),
+				pool_id,
+				STABLE_LIQUIDITY,
+				vec![STABLE_LIQUIDITY, 0],
+				BOB,
+				100
+			),
+			Error::<Test>::InsufficientAmount
+		);
+		assert_ok!(Exchange::remove_stable_liquidity(
+			Origin::signed(ALICE),
+			pool_id,
+			STABLE_LIQUIDITY,
+			vec![0, 0],
+			BOB,
+			100
+		));
+		System::assert_last_event(crate::mock::Event::Exchange(
+			crate::Event::StableLiquidityRemoved(pool_id, ALICE, amounts, STABLE_LIQUIDITY),
+		));
+		assert_eq!(TokenFungible::balance_of(TOKENA, BOB), STABLE_LIQUIDITY / 2);
+		assert_eq!(TokenFungible::balance_of(TOKENB, BOB), STABLE_LIQUIDITY / 2);
+		assert_eq!(TokenFungible::balance_of(lp_token, ALICE), STABLE_LIQUIDITY);
+	})
+}
+
+#[test]
+fn swap_stable_should_work() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_stable_pool_with_liquidity();
+
+		assert_noop!(
+			Exchange::get_stable_amount_out(pool_id, TOKENA, TOKENA, SWAP_VALUE),
+			Error::<Test>::TokenRepeat
+		);
+		assert_noop!(
+			Exchange::get_stable_amount_out(pool_id, TOKENA, TOKENC, SWAP_VALUE),
+			Error::<Test>::TokenNotInPool
+		);
+
+		// far less slippage than the constant product curve
+		let amount_out =
+			Exchange::get_stable_amount_out(pool_id, TOKENA, TOKENB, SWAP_VALUE * 10).unwrap();
+		let constant_product =
+			Exchange::get_amount_out(SWAP_VALUE * 10, STABLE_LIQUIDITY, STABLE_LIQUIDITY, 30)
+				.unwrap();
+		assert!(amount_out > constant_product);
+		assert!(amount_out > SWAP_VALUE * 10 * 996 / 1000);
+		assert!(amount_out < SWAP_VALUE * 10 * 997 / 1000);
+
+		assert_noop!(
+			Exchange::swap_stable(
+				Origin::signed(ALICE),
+				pool_id,
+				TOKENA,
+				TOKENB,
+				SWAP_VALUE * 10,
+				amount_out + 1,
+				BOB,
+				100
+			),
+			Error::<Test>::InsufficientOutAmount
+		);
+		assert_ok!(Exchange::swap_stable(
+			Origin::signed(ALICE),
+			pool_id,
+			TOKENA,
+			TOKENB,
+			SWAP_VALUE * 10,
+			amount_out,
+			BOB,
+			100
+		));
+		System::assert_last_event(crate::mock::Event::Exchange(crate::Event::StableSwap(
+			pool_id,
+			ALICE,
+			TOKENA,
+			TOKENB,
+			SWAP_VALUE * 10,
+			amount_out,
+			BOB,
+		)));
+		assert_eq!(TokenFungible::balance_of(TOKENB, BOB), amount_out);
+		assert_eq!(
+			Exchange::stable_reserves(pool_id).into_inner(),
+			vec![STABLE_LIQUIDITY + SWAP_VALUE * 10, STABLE_LIQUIDITY - amount_out]
+		);
+	})
+}
+
+#[test]
+fn ramp_amplification_should_work() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_stable_pool_with_liquidity();
+
+		assert_noop!(
+			Exchange::ramp_amplification(Origin::signed(ALICE), pool_id, 1000, 101),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Exchange::ramp_amplification(Origin::root(), 1, 1000, 101),
+			Error::<Test>::StablePoolNotFound
+		);
+		assert_noop!(
+			Exchange::ramp_amplification(Origin::root(), pool_id, 1001, 101),
+			Error::<Test>::InvalidRamp
+		);
+		assert_noop!(
+			Exchange::ramp_amplification(Origin::root(), pool_id, 1000, 1),
+			Error::<Test>::InvalidRamp
+		);
+
+		let amount_out =
+			Exchange::get_stable_amount_out(pool_id, TOKENA, TOKENB, SWAP_VALUE * 100).unwrap();
+		assert_ok!(Exchange::ramp_amplification(Origin::root(), pool_id, 1000, 101));
+		System::assert_last_event(crate::mock::Event::Exchange(crate::Event::AmplificationRamped(
+			pool_id, 10_000, 100_000, 101,
+		)));
+
+		System::set_block_number(51);
+		let pool = Exchange::stable_pools(pool_id).unwrap();
+		assert_eq!(Exchange::stable_amplification(&pool), 55_000);
+		// a higher amplification flattens the curve
+		assert!(
+			Exchange::get_stable_amount_out(pool_id, TOKENA, TOKENB, SWAP_VALUE * 100).unwrap() >
+				amount_out
+		);
+
+		assert_ok!(Exchange::stop_ramp_amplification(Origin::root(), pool_id));
+		System::assert_last_event(crate::mock::Event::Exchange(
+			crate::Event::AmplificationRampStopped(pool_id, 55_000),
+		));
+		System::set_block_number(101);
+		let pool = Exchange::stable_pools(pool_id).unwrap();
+		assert_eq!(Exchange::stable_amplification(&pool), 55_000);
+	})
+}
+
+#[test]
+fn stable_pool_should_normalize_decimals() {
+	new_test_ext().execute_with(|| {
+		create_tokens();
+		set_balance();
+		assert_ok!(TokenFungible::create_token(
+			Origin::signed(ALICE),
+			TOKEND,
+			b"USD6".to_vec(),
+			b"USD6".to_vec(),
+			6
+		));
+		assert_ok!(TokenFungible::mint(Origin::signed(ALICE), TOKEND, ALICE, 1_000_000_000));
+
+		assert_ok!(Exchange::create_stable_pool(
+			Origin::signed(ALICE),
+			vec![TOKENA, TOKENB, TOKEND],
+			100,
+			30
+		));
+		assert_eq!(
+			Exchange::stable_pools(0).unwrap().rates.into_inner(),
+			vec![1, 1, 1_000_000_000_000]
+		);
+		assert_ok!(Exchange::add_stable_liquidity(
+			Origin::signed(ALICE),
+			0,
+			vec![STABLE_LIQUIDITY * 100, STABLE_LIQUIDITY * 100, 100_000],
+			0u128,
+			ALICE,
+			100
+		));
+
+		// 0.001 of the 6 decimals token is worth about 0.001 of the others
+		let amount_out = Exchange::get_stable_amount_out(0, TOKEND, TOKENA, 1_000).unwrap();
+		assert!(amount_out > STABLE_LIQUIDITY * 996 / 1000);
+		assert!(amount_out < STABLE_LIQUIDITY * 997 / 1000);
+		let amount_out =
+			Exchange::get_stable_amount_out(0, TOKENA, TOKEND, STABLE_LIQUIDITY).unwrap();
+		assert_eq!(amount_out, 996);
+	})
+}
+
+#[test]
+fn create_weighted_pool_should_not_work() {
+	new_test_ext().execute_with(|| {
+		let half = Permill::from_percent(50);
+		let amounts = vec![STABLE_LIQUIDITY, STABLE_LIQUIDITY];
+		assert_noop!(
+			Exchange::create_weighted_pool(
+				Origin::signed(ALICE),
+				vec![TOKENA],
+				vec![Permill::one()],
+				30,
+				vec![STABLE_LIQUIDITY]
+			),
+			Error::<Test>::InvalidWeightedTokens
+		);
+		assert_noop!(
+			Exchange::create_weighted_pool(
+				Origin::signed(ALICE),
+				vec![TOKENA, TOKENB],
+				vec![half, half],
+				30,
+				amounts.clone()
+			),
+			Error::<Test>::TokenAccountNotFound
+		);
+
+		create_tokens();
+		set_balance();
+		assert_noop!(
+			Exchange::create_weighted_pool(
+				Origin::signed(ALICE),
+				vec![TOKENA, TOKENB],
+				vec![half, Permill::from_percent(40)],
+				30,
+				amounts.clone()
+			),
+			Error::<Test>::InvalidWeights
+		);
+		assert_noop!(
+			Exchange::create_weighted_pool(
+				Origin::signed(ALICE),
+				vec![TOKENA, TOKENB],
+				vec![Permill::from_parts(995_000), Permill::from_parts(5_000)],
+				30,
+				amounts.clone()
+			),
+			Error::<Test>::InvalidWeights
+		);
+		assert_noop!(
+			Exchange::create_weighted_pool(
+				Origin::signed(ALICE),
+				vec![TOKENA, TOKENB],
+				vec![half, half],
+				30,
+				vec![STABLE_LIQUIDITY, 0]
+			),
+			Error::<Test>::InvalidAmounts
+		);
+		assert_noop!(
+			Exchange::create_weighted_pool(
+				Origin::signed(ALICE),
+				vec![TOKENA, TOKENA],
+				vec![half, half],
+				30,
+				amounts.clone()
+			),
+			Error::<Test>::TokenRepeat
+		);
+		assert_noop!(
+			Exchange::create_weighted_pool(
+				Origin::signed(ALICE),
+				vec![TOKENA, TOKENB],
+				vec![half, half],
+				31,
+				amounts
+			),
+			Error::<Test>::FeeTierNotFound
+		);
+	})
+}
+
+#[test]
+fn weighted_pool_should_work() {
+	new_test_ext().execute_with(|| {
+		create_tokens();
+		set_balance();
+		let half = Permill::from_percent(50);
+		assert_ok!(Exchange::create_weighted_pool(
+			Origin::signed(ALICE),
+			vec![TOKENA, TOKENB],
+			vec![half, half],
+			30,
+			vec![STABLE_LIQUIDITY, STABLE_LIQUIDITY]
+		));
+		System::assert_last_event(crate::mock::Event::Exchange(
+			crate::Event::WeightedLiquidityAdded(
+				0,
+				ALICE,
+				vec![STABLE_LIQUIDITY, STABLE_LIQUIDITY],
+				INIT_WEIGHTED_SUPPLY,
+			),
+		));
+		let pool = Exchange::weighted_pools(0).unwrap();
+		assert_eq!(Exchange::weighted_lp_token_to_pool(pool.lp_token), Some(0));
+		assert_eq!(TokenFungible::balance_of(pool.lp_token, ALICE), INIT_WEIGHTED_SUPPLY);
+		assert_eq!(TokenFungible::token_decimals(pool.lp_token), WEIGHTED_DECIMALS);
+
+		// an even pool prices like a constant product pool
+		let amount_out = Exchange::get_weighted_amount_out(0, TOKENA, TOKENB, SWAP_VALUE).unwrap();
+		let expected =
+			Exchange::get_amount_out(SWAP_VALUE, STABLE_LIQUIDITY, STABLE_LIQUIDITY, 30).unwrap();
+		assert!(amount_out.max(expected) - amount_out.min(expected) <= 1);
+
+		let balance_b = TokenFungible::balance_of(TOKENB, BOB);
+		assert_ok!(Exchange::swap_weighted(
+			Origin::signed(ALICE),
+			0,
+			TOKENA,
+			TOKENB,
+			SWAP_VALUE,
+			amount_out,
+			BOB,
+			100
+		));
+		System::assert_last_event(crate::mock::Event::Exchange(crate::Event::WeightedSwap(
+			0, ALICE, TOKENA, TOKENB, SWAP_VALUE, amount_out, BOB,
+		)));
+		assert_eq!(TokenFungible::balance_of(TOKENB, BOB), balance_b + amount_out);
+		assert_eq!(
+			Exchange::weighted_reserves(0).into_inner(),
+			vec![STABLE_LIQUIDITY + SWAP_VALUE, STABLE_LIQUIDITY - amount_out]
+		);
+
+		assert_noop!(
+			Exchange::swap_weighted(
+				Origin::signed(ALICE),
+				0,
+				TOKENA,
+				TOKENB,
+				STABLE_LIQUIDITY,
+				0,
+				ALICE,
+				100
+			),
+			Error::<Test>::WeightedRatioExceeded
+		);
+
+		let reserves = Exchange::weighted_reserves(0).into_inner();
+		assert_noop!(
+			Exchange::add_weighted_liquidity(
+				Origin::signed(ALICE),
+				0,
+				INIT_WEIGHTED_SUPPLY,
+				vec![reserves[0], reserves[1] - 1],
+				ALICE,
+				100
+			),
+			Error::<Test>::ExcessiveInputAmount
+		);
+		assert_ok!(Exchange::add_weighted_liquidity(
+			Origin::signed(ALICE),
+			0,
+			INIT_WEIGHTED_SUPPLY,
+			reserves.clone(),
+			ALICE,
+			100
+		));
+		assert_eq!(
+			Exchange::weighted_reserves(0).into_inner(),
+			vec![reserves[0] * 2, reserves[1] * 2]
+		);
+
+		assert_ok!(Exchange::remove_weighted_liquidity(
+			Origin::signed(ALICE),
+			0,
+			INIT_WEIGHTED_SUPPLY,
+			reserves.clone(),
+			CHARLIE,
+			100
+		));
+		System::assert_last_event(crate::mock::Event::Exchange(
+			crate::Event::WeightedLiquidityRemoved(
+				0,
+				ALICE,
+				reserves.clone(),
+				INIT_WEIGHTED_SUPPLY,
+			),
+		));
+		assert_eq!(TokenFungible::balance_of(TOKENA, CHARLIE), reserves[0]);
+		assert_eq!(Exchange::weighted_reserves(0).into_inner(), reserves);
+	})
+}
+
+#[test]
+fn weighted_single_sided_liquidity_should_work() {
+	new_test_ext().execute_with(|| {
+		create_tokens();
+		set_balance();
+		assert_ok!(Exchange::create_weighted_pool(
+			Origin::signed(ALICE),
+			vec![TOKENA, TOKENB, TOKENC],
+			vec![Permill::from_percent(50), Permill::from_percent(25), Permill::from_percent(25)],
+			30,
+			vec![STABLE_LIQUIDITY, STABLE_LIQUIDITY, STABLE_LIQUIDITY]
+		));
+
+		// a token weighing twice as much is worth twice as much for the same balance
+		let amount_out = Exchange::get_weighted_amount_out(0, TOKENA, TOKENB, SWAP_VALUE).unwrap();
+		assert!(amount_out > SWAP_VALUE * 2 * 995 / 1000);
+		assert!(amount_out < SWAP_VALUE * 2 * 997 / 1000);
+
+		let amount_in = SWAP_VALUE * 10;
+		let liquidity = Exchange::get_weighted_single_in_liquidity(0, TOKENB, amount_in).unwrap();
+		assert_noop!(
+			Exchange::add_weighted_liquidity_single(
+				Origin::signed(ALICE),
+				0,
+				TOKENB,
+				amount_in,
+				liquidity + 1,
+				BOB,
+				100
+			),
+			Error::<Test>::InsufficientLiquidityMinted
+		);
+		assert_ok!(Exchange::add_weighted_liquidity_single(
+			Origin::signed(ALICE),
+			0,
+			TOKENB,
+			amount_in,
+			liquidity,
+			BOB,
+			100
+		));
+		System::assert_last_event(crate::mock::Event::Exchange(
+			crate::Event::WeightedLiquidityAdded(0, ALICE, vec![0, amount_in, 0], liquidity),
+		));
+
+		// the fee is paid on the way in and out
+		let amount_out = Exchange::get_weighted_single_out(0, TOKENB, liquidity).unwrap();
+		assert!(amount_out < amount_in);
+		assert!(amount_out > amount_in * 99 / 100);
+		assert_ok!(Exchange::remove_weighted_liquidity_single(
+			Origin::signed(BOB),
+			0,
+			TOKENB,
+			liquidity,
+			amount_out,
+			BOB,
+			100
+		));
+		System::assert_last_event(crate::mock::Event::Exchange(
+			crate::Event::WeightedLiquidityRemoved(0, BOB, vec![0, amount_out, 0], liquidity),
+		));
+		assert_eq!(TokenFungible::balance_of(TOKENB, BOB), amount_out);
+		assert_eq!(
+			Exchange::weighted_reserves(0).into_inner(),
+			vec![STABLE_LIQUIDITY, STABLE_LIQUIDITY + amount_in - amount_out, STABLE_LIQUIDITY]
+		);
+
+		assert_noop!(
+			Exchange::remove_weighted_liquidity_single(
+				Origin::signed(ALICE),
+				0,
+				TOKENA,
+				INIT_WEIGHTED_SUPPLY,
+				0,
+				ALICE,
+				100
+			),
+			Error::<Test>::WeightedRatioExceeded
+		);
+
+		assert_ok!(Exchange::set_weighted_pool_status(Origin::root(), 0, PoolStatus::Emergency));
+		System::assert_last_event(crate::mock::Event::Exchange(
+			crate::Event::WeightedPoolStatusChanged(0, PoolStatus::Emergency),
+		));
+		assert_noop!(
+			Exchange::add_weighted_liquidity_single(
+				Origin::signed(ALICE),
+				0,
+				TOKENB,
+				amount_in,
+				0,
+				ALICE,
+				100
+			),
+			Error::<Test>::PoolPaused
+		);
+		assert_ok!(Exchange::remove_weighted_liquidity(
+			Origin::signed(ALICE),
+			0,
+			INIT_WEIGHTED_SUPPLY / 2,
+			vec![0, 0, 0],
+			ALICE,
+			100
+		));
+	})
+}
@@ -0,0 +1,158 @@
+// This file is part of Web3Games.
+
+// Copyright (C) 2021-2022 Web3Games https://web3games.org
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The weighted constant product invariant of Balancer, in fixed point numbers of 18 decimals.
+
+use sp_core::U256;
+
+/// One in the fixed point numbers of this module.
+pub const ONE: u128 = 1_000_000_000_000_000_000;
+
+// the smallest term of the series approximating a fractional power
+const POW_PRECISION: u128 = ONE / 10_000_000_000;
+
+const MAX_ITERATIONS: u32 = 255;
+
+fn one() -> U256 {
+	U256::from(ONE)
+}
+
+/// `a * b`, rounded to the nearest fixed point number.
+pub fn mul(a: U256, b: U256) -> Option<U256> {
+	a.checked_mul(b)?.checked_add(one() / 2)?.checked_div(one())
+}
+
+/// `a / b`, rounded to the nearest fixed point number.
+pub fn div(a: U256, b: U256) -> Option<U256> {
+	if b.is_zero() {
+		return None
+	}
+	a.checked_mul(one())?.checked_add(b / 2)?.checked_div(b)
+}
+
+fn sub_sign(a: U256, b: U256) -> (U256, bool) {
+	if a >= b {
+		(a - b, false)
+	} else {
+		(b - a, true)
+	}
+}
+
+fn pow_int(mut base: U256, mut exp: U256) -> Option<U256> {
+	let mut result = if exp.low_u32() % 2 == 1 { base } else { one() };
+	exp /= 2;
+	while !exp.is_zero() {
+		base = mul(base, base)?;
+		if exp.low_u32() % 2 == 1 {
+			result = mul(result, base)?;
+		}
+		exp /= 2;
+	}
+	Some(result)
+}
+
+// the binomial series of base^exp for an exponent below one
+fn pow_approx(base: U256, exp: U256) -> Option<U256> {
+	let (x, x_negative) = sub_sign(base, one());
+	let mut term = one();
+	let mut sum = term;
+	let mut negative = false;
+	for i in 1..=MAX_ITERATIONS {
+		if term < U256::from(POW_PRECISION) {
+			return Some(sum)
+		}
+		let big_k = U256::from(i).checked_mul(one())?;
+		let (c, c_negative) = sub_sign(exp, big_k - one());
+		term = div(mul(term, mul(c, x)?)?, big_k)?;
+		if term.is_zero() {
+			return Some(sum)
+		}
+		if x_negative {
+			negative = !negative;
+		}
+		if c_negative {
+			negative = !negative;
+		}
+		sum = if negative { sum.checked_sub(term)? } else { sum.checked_add(term)? };
+	}
+
+	None
+}
+
+/// `base ^ exp` for a base between zero and two, exclusive.
+pub fn pow(base: U256, exp: U256) -> Option<U256> {
+	if base.is_zero() || base >= one() * 2 {
+		return None
+	}
+	let whole = exp / one();
+	let remain = exp % one();
+	let whole_pow = pow_int(base, whole)?;
+	if remain.is_zero() {
+		return Some(whole_pow)
+	}
+	mul(whole_pow, pow_approx(base, remain)?)
+}
+
+/// The amount of the output token paid for `amount_in` of the input token, where the weights
+/// and the fee are fixed point numbers.
+pub fn out_given_in(
+	balance_in: U256,
+	weight_in: U256,
+	balance_out: U256,
+	weight_out: U256,
+	amount_in: U256,
+	fee: U256,
+) -> Option<U256> {
+	let weight_ratio = div(weight_in, weight_out)?;
+	let adjusted_in = mul(amount_in, one().checked_sub(fee)?)?;
+	let y = div(balance_in, balance_in.checked_add(adjusted_in)?)?;
+	let y_pow = pow(y, weight_ratio)?;
+	mul(balance_out, one().checked_sub(y_pow)?)
+}
+
+/// The LP tokens minted for depositing `amount_in` of a single token of normalized weight
+/// `weight_in`. The part of the deposit which would have to be swapped pays the fee.
+pub fn pool_out_given_single_in(
+	balance_in: U256,
+	weight_in: U256,
+	supply: U256,
+	amount_in: U256,
+	fee: U256,
+) -> Option<U256> {
+	let zaz = mul(one().checked_sub(weight_in)?, fee)?;
+	let amount_in_after_fee = mul(amount_in, one().checked_sub(zaz)?)?;
+	let ratio_in = div(balance_in.checked_add(amount_in_after_fee)?, balance_in)?;
+	let pool_ratio = pow(ratio_in, weight_in)?;
+	mul(pool_ratio, supply)?.checked_sub(supply)
+}
+
+/// The amount of a single token of normalized weight `weight_out` withdrawn for burning
+/// `pool_in` LP tokens. The part of the withdrawal which would have to be swapped pays the fee.
+pub fn single_out_given_pool_in(
+	balance_out: U256,
+	weight_out: U256,
+	supply: U256,
+	pool_in: U256,
+	fee: U256,
+) -> Option<U256> {
+	let pool_ratio = div(supply.checked_sub(pool_in)?, supply)?;
+	let ratio_out = pow(pool_ratio, div(one(), weight_out)?)?;
+	let amount_out_before_fee = balance_out.checked_sub(mul(ratio_out, balance_out)?)?;
+	let zaz = mul(one().checked_sub(weight_out)?, fee)?;
+	mul(amount_out_before_fee, one().checked_sub(zaz)?)
+}
@@ -51,21 +51,50 @@ pub trait WeightInfo {
 	fn remove_liquidity() -> Weight;
 	fn swap_exact_tokens_for_tokens() -> Weight;
 	fn swap_tokens_for_exact_tokens() -> Weight;
+	fn set_fee_tier() -> Weight;
+	fn set_protocol_fee_share() -> Weight;
+	fn flash_swap() -> Weight;
+	fn flash_loan() -> Weight;
+	fn create_stable_pool() -> Weight;
+	fn add_stable_liquidity() -> Weight;
+	fn remove_stable_liquidity() -> Weight;
+	fn swap_stable() -> Weight;
+	fn ramp_amplification() -> Weight;
+	fn stop_ramp_amplification() -> Weight;
+	fn swap_exact_w3g_for_tokens() -> Weight;
+	fn swap_tokens_for_exact_w3g() -> Weight;
+	fn swap_exact_tokens_for_w3g() -> Weight;
+	fn swap_w3g_for_exact_tokens() -> Weight;
+	fn place_order() -> Weight;
+	fn cancel_order() -> Weight;
+	fn fill_order() -> Weight;
+	fn zap_in() -> Weight;
+	fn zap_out() -> Weight;
+	fn set_pool_status() -> Weight;
+	fn set_stable_pool_status() -> Weight;
+	fn create_weighted_pool() -> Weight;
+	fn add_weighted_liquidity() -> Weight;
+	fn remove_weighted_liquidity() -> Weight;
+	fn add_weighted_liquidity_single() -> Weight;
+	fn remove_weighted_liquidity_single() -> Weight;
+	fn swap_weighted() -> Weight;
+	fn set_weighted_pool_status() -> Weight;
 }
 
 /// Weights for pallet_exchange using the Web3Games node and recommended hardware.
 pub struct W3GWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for W3GWeight<T> {
-	// Storage: TokenFungible Tokens (r:2 w:1)
+	// Storage: TokenFungible Tokens (r:5 w:1)
+	// Storage: Exchange FeeTiers (r:1 w:0)
 	// Storage: Exchange Pools (r:1 w:1)
 	// Storage: Exchange NextPoolId (r:1 w:1)
 	// Storage: System Account (r:1 w:1)
-	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
 	// Storage: Exchange LpTokenToToken (r:0 w:1)
+	// Storage: Exchange PoolDeposits (r:0 w:1)
 	fn create_pool() -> Weight {
 		(58_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(6 as Weight))
-			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
 	}
 	// Storage: Exchange Pools (r:1 w:0)
 	// Storage: Exchange Reserves (r:1 w:1)
@@ -73,10 +102,14 @@ impl<T: frame_system::Config> WeightInfo for W3GWeight<T> {
 	// Storage: Exchange KLast (r:1 w:0)
 	// Storage: Exchange FeeTo (r:1 w:0)
 	// Storage: TokenFungible Tokens (r:1 w:1)
+	// Storage: Exchange FlashLocks (r:1 w:0)
+	// Storage: Exchange PriceCumulatives (r:1 w:1)
+	// Storage: Exchange ObservationIndex (r:1 w:1)
+	// Storage: Exchange Observations (r:1 w:1)
 	fn add_liquidity() -> Weight {
 		(84_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(11 as Weight))
-			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
 	}
 	// Storage: Exchange Pools (r:1 w:0)
 	// Storage: TokenFungible Balances (r:6 w:6)
@@ -84,54 +117,492 @@ impl<T: frame_system::Config> WeightInfo for W3GWeight<T> {
 	// Storage: Exchange KLast (r:1 w:0)
 	// Storage: Exchange FeeTo (r:1 w:0)
 	// Storage: TokenFungible Tokens (r:1 w:1)
+	// Storage: Exchange FlashLocks (r:1 w:0)
+	// Storage: Exchange PriceCumulatives (r:1 w:1)
+	// Storage: Exchange ObservationIndex (r:1 w:1)
+	// Storage: Exchange Observations (r:1 w:1)
 	fn remove_liquidity() -> Weight {
 		(91_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(11 as Weight))
-			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
 	}
 	// Storage: Exchange Pools (r:1 w:0)
 	// Storage: Exchange Reserves (r:1 w:1)
 	// Storage: TokenFungible Balances (r:4 w:4)
+	// Storage: Exchange FlashLocks (r:1 w:0)
+	// Storage: Exchange PriceCumulatives (r:1 w:1)
+	// Storage: Exchange ObservationIndex (r:1 w:1)
+	// Storage: Exchange Observations (r:1 w:1)
 	fn swap_exact_tokens_for_tokens() -> Weight {
 		(57_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(6 as Weight))
-			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
 	}
 	// Storage: Exchange Pools (r:1 w:0)
 	// Storage: Exchange Reserves (r:1 w:1)
 	// Storage: TokenFungible Balances (r:4 w:4)
+	// Storage: Exchange FlashLocks (r:1 w:0)
+	// Storage: Exchange PriceCumulatives (r:1 w:1)
+	// Storage: Exchange ObservationIndex (r:1 w:1)
+	// Storage: Exchange Observations (r:1 w:1)
 	fn swap_tokens_for_exact_tokens() -> Weight {
 		(57_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: Exchange FeeTiers (r:0 w:1)
+	fn set_fee_tier() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Exchange ProtocolFeeShare (r:0 w:1)
+	fn set_protocol_fee_share() -> Weight {
+		(11_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Exchange FlashLocks (r:1 w:1)
+	// Storage: Exchange Reserves (r:1 w:1)
+	// Storage: Exchange Pools (r:1 w:0)
+	// Storage: TokenFungible Balances (r:4 w:4)
+	// Storage: Exchange PriceCumulatives (r:1 w:1)
+	// Storage: Exchange ObservationIndex (r:1 w:1)
+	// Storage: Exchange Observations (r:1 w:1)
+	fn flash_swap() -> Weight {
+		(63_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: Exchange FlashLocks (r:1 w:1)
+	// Storage: Exchange Pools (r:1 w:0)
+	// Storage: Exchange Reserves (r:1 w:1)
+	// Storage: TokenFungible Balances (r:4 w:4)
+	// Storage: Exchange PriceCumulatives (r:1 w:1)
+	// Storage: Exchange ObservationIndex (r:1 w:1)
+	// Storage: Exchange Observations (r:1 w:1)
+	fn flash_loan() -> Weight {
+		(61_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: Exchange FeeTiers (r:1 w:0)
+	// Storage: TokenFungible Tokens (r:7 w:1)
+	// Storage: Exchange NextPoolId (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Exchange StablePools (r:0 w:1)
+	// Storage: Exchange StableReserves (r:0 w:1)
+	// Storage: Exchange StableLpTokenToPool (r:0 w:1)
+	// Storage: Exchange PoolDeposits (r:0 w:1)
+	fn create_stable_pool() -> Weight {
+		(62_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: Exchange StablePools (r:1 w:0)
+	// Storage: Exchange StableReserves (r:1 w:1)
+	// Storage: TokenFungible Tokens (r:1 w:1)
+	// Storage: TokenFungible Balances (r:5 w:5)
+	fn add_stable_liquidity() -> Weight {
+		(96_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: Exchange StablePools (r:1 w:0)
+	// Storage: TokenFungible Tokens (r:1 w:1)
+	// Storage: Exchange StableReserves (r:1 w:1)
+	// Storage: TokenFungible Balances (r:5 w:5)
+	fn remove_stable_liquidity() -> Weight {
+		(88_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: Exchange StablePools (r:1 w:0)
+	// Storage: Exchange StableReserves (r:1 w:1)
+	// Storage: TokenFungible Balances (r:4 w:4)
+	fn swap_stable() -> Weight {
+		(71_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(6 as Weight))
 			.saturating_add(T::DbWeight::get().writes(5 as Weight))
 	}
+	// Storage: Exchange StablePools (r:1 w:1)
+	fn ramp_amplification() -> Weight {
+		(19_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Exchange StablePools (r:1 w:1)
+	fn stop_ramp_amplification() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Exchange Pools (r:1 w:0)
+	// Storage: Exchange Reserves (r:1 w:1)
+	// Storage: TokenFungible Balances (r:4 w:4)
+	// Storage: Exchange FlashLocks (r:1 w:0)
+	// Storage: Exchange PriceCumulatives (r:1 w:1)
+	// Storage: Exchange ObservationIndex (r:1 w:1)
+	// Storage: Exchange Observations (r:1 w:1)
+	// Storage: WrapCurrency WrapToken (r:1 w:0)
+	// Storage: TokenFungible Tokens (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn swap_exact_w3g_for_tokens() -> Weight {
+		(72_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
+	}
+	// Storage: Exchange Pools (r:1 w:0)
+	// Storage: Exchange Reserves (r:1 w:1)
+	// Storage: TokenFungible Balances (r:4 w:4)
+	// Storage: Exchange FlashLocks (r:1 w:0)
+	// Storage: Exchange PriceCumulatives (r:1 w:1)
+	// Storage: Exchange ObservationIndex (r:1 w:1)
+	// Storage: Exchange Observations (r:1 w:1)
+	// Storage: WrapCurrency WrapToken (r:1 w:0)
+	// Storage: TokenFungible Tokens (r:1 w:1)
+	// Storage: System Account (r:3 w:3)
+	fn swap_tokens_for_exact_w3g() -> Weight {
+		(76_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(12 as Weight))
+	}
+	// Storage: Exchange Pools (r:1 w:0)
+	// Storage: Exchange Reserves (r:1 w:1)
+	// Storage: TokenFungible Balances (r:4 w:4)
+	// Storage: Exchange FlashLocks (r:1 w:0)
+	// Storage: Exchange PriceCumulatives (r:1 w:1)
+	// Storage: Exchange ObservationIndex (r:1 w:1)
+	// Storage: Exchange Observations (r:1 w:1)
+	// Storage: WrapCurrency WrapToken (r:1 w:0)
+	// Storage: TokenFungible Tokens (r:1 w:1)
+	// Storage: System Account (r:3 w:3)
+	fn swap_exact_tokens_for_w3g() -> Weight {
+		(76_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(12 as Weight))
+	}
+	// Storage: Exchange Pools (r:1 w:0)
+	// Storage: Exchange Reserves (r:1 w:1)
+	// Storage: TokenFungible Balances (r:4 w:4)
+	// Storage: Exchange FlashLocks (r:1 w:0)
+	// Storage: Exchange PriceCumulatives (r:1 w:1)
+	// Storage: Exchange ObservationIndex (r:1 w:1)
+	// Storage: Exchange Observations (r:1 w:1)
+	// Storage: WrapCurrency WrapToken (r:1 w:0)
+	// Storage: TokenFungible Tokens (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn swap_w3g_for_exact_tokens() -> Weight {
+		(72_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
+	}
+	// Storage: Exchange Pools (r:1 w:0)
+	// Storage: Exchange NextOrderId (r:1 w:1)
+	// Storage: TokenFungible Balances (r:2 w:2)
+	// Storage: Exchange Orders (r:0 w:1)
+	fn place_order() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Exchange Orders (r:1 w:1)
+	// Storage: TokenFungible Balances (r:2 w:2)
+	fn cancel_order() -> Weight {
+		(33_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Exchange Orders (r:1 w:1)
+	// Storage: Exchange Pools (r:2 w:0)
+	// Storage: Exchange Reserves (r:1 w:1)
+	// Storage: TokenFungible Balances (r:4 w:4)
+	// Storage: Exchange FlashLocks (r:1 w:0)
+	// Storage: Exchange PriceCumulatives (r:1 w:1)
+	// Storage: Exchange ObservationIndex (r:1 w:1)
+	// Storage: Exchange Observations (r:1 w:1)
+	fn fill_order() -> Weight {
+		(68_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: Exchange Pools (r:1 w:0)
+	// Storage: Exchange Reserves (r:1 w:1)
+	// Storage: TokenFungible Balances (r:8 w:8)
+	// Storage: Exchange KLast (r:1 w:0)
+	// Storage: Exchange FeeTo (r:1 w:0)
+	// Storage: TokenFungible Tokens (r:1 w:1)
+	// Storage: Exchange FlashLocks (r:1 w:0)
+	// Storage: Exchange PriceCumulatives (r:1 w:1)
+	// Storage: Exchange ObservationIndex (r:1 w:1)
+	// Storage: Exchange Observations (r:1 w:1)
+	fn zap_in() -> Weight {
+		(127_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(17 as Weight))
+			.saturating_add(T::DbWeight::get().writes(13 as Weight))
+	}
+	// Storage: Exchange Pools (r:1 w:0)
+	// Storage: Exchange Reserves (r:1 w:1)
+	// Storage: TokenFungible Balances (r:8 w:8)
+	// Storage: Exchange KLast (r:1 w:0)
+	// Storage: Exchange FeeTo (r:1 w:0)
+	// Storage: TokenFungible Tokens (r:1 w:1)
+	// Storage: Exchange FlashLocks (r:1 w:0)
+	// Storage: Exchange PriceCumulatives (r:1 w:1)
+	// Storage: Exchange ObservationIndex (r:1 w:1)
+	// Storage: Exchange Observations (r:1 w:1)
+	fn zap_out() -> Weight {
+		(134_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(17 as Weight))
+			.saturating_add(T::DbWeight::get().writes(13 as Weight))
+	}
+	// Storage: Exchange Pools (r:1 w:0)
+	// Storage: Exchange PoolStatuses (r:0 w:1)
+	fn set_pool_status() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Exchange StablePools (r:1 w:0)
+	// Storage: Exchange StablePoolStatuses (r:0 w:1)
+	fn set_stable_pool_status() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: System Account (r:1 w:1)
+	// Storage: Exchange NextPoolId (r:1 w:1)
+	// Storage: Exchange FeeTiers (r:1 w:0)
+	// Storage: TokenFungible Tokens (r:10 w:2)
+	// Storage: TokenFungible Balances (r:16 w:16)
+	// Storage: Exchange PoolDeposits (r:0 w:1)
+	// Storage: Exchange WeightedPools (r:0 w:1)
+	// Storage: Exchange WeightedReserves (r:0 w:1)
+	// Storage: Exchange WeightedLpTokenToPool (r:0 w:1)
+	fn create_weighted_pool() -> Weight {
+		(148_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(29 as Weight))
+			.saturating_add(T::DbWeight::get().writes(24 as Weight))
+	}
+	// Storage: Exchange WeightedPools (r:1 w:0)
+	// Storage: Exchange WeightedPoolStatuses (r:1 w:0)
+	// Storage: TokenFungible Tokens (r:1 w:1)
+	// Storage: Exchange WeightedReserves (r:1 w:1)
+	// Storage: TokenFungible Balances (r:16 w:16)
+	fn add_weighted_liquidity() -> Weight {
+		(124_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(20 as Weight))
+			.saturating_add(T::DbWeight::get().writes(18 as Weight))
+	}
+	// Storage: Exchange WeightedPools (r:1 w:0)
+	// Storage: Exchange WeightedPoolStatuses (r:1 w:0)
+	// Storage: TokenFungible Tokens (r:1 w:1)
+	// Storage: Exchange WeightedReserves (r:1 w:1)
+	// Storage: TokenFungible Balances (r:16 w:16)
+	fn remove_weighted_liquidity() -> Weight {
+		(118_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(20 as Weight))
+			.saturating_add(T::DbWeight::get().writes(18 as Weight))
+	}
+	// Storage: Exchange WeightedPools (r:1 w:0)
+	// Storage: Exchange WeightedPoolStatuses (r:1 w:0)
+	// Storage: Exchange WeightedReserves (r:1 w:1)
+	// Storage: TokenFungible Tokens (r:1 w:1)
+	// Storage: TokenFungible Balances (r:3 w:3)
+	fn add_weighted_liquidity_single() -> Weight {
+		(83_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Exchange WeightedPools (r:1 w:0)
+	// Storage: Exchange WeightedPoolStatuses (r:1 w:0)
+	// Storage: Exchange WeightedReserves (r:1 w:1)
+	// Storage: TokenFungible Tokens (r:1 w:1)
+	// Storage: TokenFungible Balances (r:3 w:3)
+	fn remove_weighted_liquidity_single() -> Weight {
+		(91_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Exchange WeightedPools (r:1 w:0)
+	// Storage: Exchange WeightedPoolStatuses (r:1 w:0)
+	// Storage: Exchange WeightedReserves (r:1 w:1)
+	// Storage: TokenFungible Balances (r:4 w:4)
+	fn swap_weighted() -> Weight {
+		(78_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Exchange WeightedPools (r:1 w:0)
+	// Storage: Exchange WeightedPoolStatuses (r:0 w:1)
+	fn set_weighted_pool_status() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
 	fn create_pool() -> Weight {
 		(58_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
 	}
 	fn add_liquidity() -> Weight {
 		(84_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(15 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(11 as Weight))
 	}
 	fn remove_liquidity() -> Weight {
 		(91_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(15 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(11 as Weight))
 	}
 	fn swap_exact_tokens_for_tokens() -> Weight {
 		(57_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
 	}
 	fn swap_tokens_for_exact_tokens() -> Weight {
 		(57_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	fn set_fee_tier() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_protocol_fee_share() -> Weight {
+		(11_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn flash_swap() -> Weight {
+		(63_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	fn flash_loan() -> Weight {
+		(61_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	fn create_stable_pool() -> Weight {
+		(62_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	fn add_stable_liquidity() -> Weight {
+		(96_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	fn remove_stable_liquidity() -> Weight {
+		(88_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	fn swap_stable() -> Weight {
+		(71_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
 	}
+	fn ramp_amplification() -> Weight {
+		(19_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn stop_ramp_amplification() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn swap_exact_w3g_for_tokens() -> Weight {
+		(72_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(11 as Weight))
+	}
+	fn swap_tokens_for_exact_w3g() -> Weight {
+		(76_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(15 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(12 as Weight))
+	}
+	fn swap_exact_tokens_for_w3g() -> Weight {
+		(76_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(15 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(12 as Weight))
+	}
+	fn swap_w3g_for_exact_tokens() -> Weight {
+		(72_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(11 as Weight))
+	}
+	fn place_order() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn cancel_order() -> Weight {
+		(33_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn fill_order() -> Weight {
+		(68_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(12 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	fn zap_in() -> Weight {
+		(127_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(17 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(13 as Weight))
+	}
+	fn zap_out() -> Weight {
+		(134_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(17 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(13 as Weight))
+	}
+	fn set_pool_status() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_stable_pool_status() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn create_weighted_pool() -> Weight {
+		(148_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(29 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(24 as Weight))
+	}
+	fn add_weighted_liquidity() -> Weight {
+		(124_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(20 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(18 as Weight))
+	}
+	fn remove_weighted_liquidity() -> Weight {
+		(118_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(20 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(18 as Weight))
+	}
+	fn add_weighted_liquidity_single() -> Weight {
+		(83_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn remove_weighted_liquidity_single() -> Weight {
+		(91_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn swap_weighted() -> Weight {
+		(78_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn set_weighted_pool_status() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 }
@@ -5,16 +5,25 @@
 use super::*;
 use crate::Pallet as Launchpad;
 use frame_benchmarking::{account, benchmarks};
-use frame_support::assert_ok;
+use frame_support::{assert_ok, traits::Hooks};
 use frame_system::{Pallet as System, RawOrigin};
 use pallet_token_fungible::Pallet as TokenFungible;
-use sp_runtime::traits::UniqueSaturatedFrom;
+use sp_runtime::{traits::UniqueSaturatedFrom, Percent};
 
 const W3G: u128 = 1;
 const USDT: u128 = 2;
 const W3G_DECIMALS: u128 = 1_000_000_000_000_000_000;
 const USDT_DECIMALS: u128 = 1_000_000;
 
+fn liquidity_config<T: Config>() -> LiquidityConfig<T::BlockNumber> {
+	LiquidityConfig {
+		buy_token_percent: Percent::from_percent(50),
+		sale_token_amount: 5 * W3G_DECIMALS,
+		fee: 30,
+		lock_period: T::BlockNumber::from(100u32),
+	}
+}
+
 fn setup<T: Config>() -> DispatchResult {
 	let alice: T::AccountId = account("alice", 0, 0);
 	let bob: T::AccountId = account("bob", 0, 0);
@@ -108,5 +117,78 @@ benchmarks! {
 		System::<T>::set_block_number(T::BlockNumber::from(21u32));
 	}: _(RawOrigin::Signed(alice),0)
 
+	set_liquidity_config {
+		let alice: T::AccountId = account("alice", 0, 0);
+		setup::<T>()?;
+		assert_ok!(Launchpad::<T>::create_pool(
+				RawOrigin::Signed(alice.clone()).into(),
+				T::BlockNumber::from(10u32),
+				T::BlockNumber::from(10u32),
+				W3G,
+				USDT,
+				10 * W3G_DECIMALS,
+				1 * USDT_DECIMALS,
+		));
+	}: _(RawOrigin::Signed(alice), 0, liquidity_config::<T>())
+
+	claim_liquidity {
+		let alice: T::AccountId = account("alice", 0, 0);
+		setup::<T>()?;
+		assert_ok!(Launchpad::<T>::create_pool(
+				RawOrigin::Signed(alice.clone()).into(),
+				T::BlockNumber::from(10u32),
+				T::BlockNumber::from(10u32),
+				W3G,
+				USDT,
+				10 * W3G_DECIMALS,
+				1 * USDT_DECIMALS,
+		));
+		let lp_token = W3G;
+		LiquidityLocks::<T>::insert(
+			0,
+			LiquidityLock {
+				lp_token,
+				amount: 1 * W3G_DECIMALS,
+				unlock_at: T::BlockNumber::from(120u32),
+			},
+		);
+		assert_ok!(TokenFungible::<T>::transfer(
+				RawOrigin::Signed(alice.clone()).into(),
+				<T as pallet_token_fungible::Config>::FungibleTokenId::unique_saturated_from(lp_token),
+				Launchpad::<T>::escrow_account_id(0),
+				1 * W3G_DECIMALS,
+		));
+		System::<T>::set_block_number(T::BlockNumber::from(120u32));
+	}: _(RawOrigin::Signed(alice), 0)
+
+	add_liquidity {
+		let alice: T::AccountId = account("alice", 0, 0);
+		let bob: T::AccountId = account("bob", 0, 0);
+		setup::<T>()?;
+		assert_ok!(Launchpad::<T>::create_pool(
+				RawOrigin::Signed(alice.clone()).into(),
+				T::BlockNumber::from(10u32),
+				T::BlockNumber::from(10u32),
+				W3G,
+				USDT,
+				10 * W3G_DECIMALS,
+				1 * USDT_DECIMALS,
+		));
+		assert_ok!(Launchpad::<T>::set_liquidity_config(
+				RawOrigin::Signed(alice.clone()).into(),
+				0,
+				liquidity_config::<T>(),
+		));
+		System::<T>::set_block_number(T::BlockNumber::from(10u32));
+		assert_ok!(Launchpad::<T>::buy_token(
+				RawOrigin::Signed(bob.clone()).into(),
+				0,
+				2
+		));
+		System::<T>::set_block_number(T::BlockNumber::from(20u32));
+	}: {
+		Launchpad::<T>::on_initialize(T::BlockNumber::from(20u32));
+	}
+
 	impl_benchmark_test_suite!(Launchpad, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -18,13 +18,17 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::{pallet_prelude::*, PalletId};
+use frame_support::{
+	pallet_prelude::*,
+	storage::{with_transaction, TransactionOutcome},
+	PalletId,
+};
 use frame_system::pallet_prelude::*;
-use pallet_support::FungibleMetadata;
+use pallet_support::{FungibleMetadata, LiquidityProvider};
 use primitives::Balance;
 use sp_runtime::{
-	traits::{AccountIdConversion, UniqueSaturatedFrom},
-	DispatchResult,
+	traits::{AccountIdConversion, Saturating, UniqueSaturatedFrom, UniqueSaturatedInto, Zero},
+	DispatchResult, Percent,
 };
 use sp_std::prelude::*;
 
@@ -62,6 +66,27 @@ pub struct ClaimInfo {
 	pub is_claimed: bool,
 }
 
+/// The liquidity added to an exchange pool of the sale token and the buy token at the end of a
+/// sale.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct LiquidityConfig<BlockNumber> {
+	/// The part of the raised buy tokens added to the pool
+	pub buy_token_percent: Percent,
+	/// The sale tokens set aside for the pool, paired with the buy tokens at the sale price
+	pub sale_token_amount: Balance,
+	/// The fee tier of the pool
+	pub fee: u32,
+	/// How long the LP tokens stay locked after the sale ends
+	pub lock_period: BlockNumber,
+}
+
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct LiquidityLock<BlockNumber> {
+	pub lp_token: FungibleTokenId,
+	pub amount: Balance,
+	pub unlock_at: BlockNumber,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -76,6 +101,18 @@ pub mod pallet {
 		/// This pallet id.
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
+
+		/// The exchange the liquidity of sales is added to.
+		type Exchange: LiquidityProvider<Self::AccountId, FungibleTokenIdOf<Self>>;
+
+		/// How much less of either token than at the sale price an existing exchange pool may
+		/// take for the liquidity to be added to it.
+		#[pallet::constant]
+		type LiquidityPriceTolerance: Get<Percent>;
+
+		/// The most sales whose liquidity is added at the same block.
+		#[pallet::constant]
+		type MaxLiquidityPerBlock: Get<u32>;
 	}
 
 	#[pallet::error]
@@ -89,6 +126,13 @@ pub mod pallet {
 		AlreadyClaim,
 		NotBuy,
 		NotOwner,
+		SaleStarted,
+		LiquidityConfigured,
+		InvalidLiquidityConfig,
+		NotRaised,
+		LiquidityNotFound,
+		LiquidityLocked,
+		LiquidityScheduleFull,
 	}
 
 	#[pallet::event]
@@ -98,6 +142,12 @@ pub mod pallet {
 		BuyToken(T::AccountId, u64, Balance),
 		Claim(T::AccountId, u64, Balance),
 		OwnerClaim(T::AccountId, u64, Balance, Balance),
+		LiquidityConfigSet(u64, LiquidityConfig<T::BlockNumber>),
+		/// \[pool_id, lp_token, sale_token_amount, buy_token_amount, liquidity\]
+		LiquidityAdded(u64, FungibleTokenId, Balance, Balance, Balance),
+		/// The liquidity could not be added and its sale tokens go back to the owner.
+		LiquidityFailed(u64, DispatchError),
+		LiquidityClaimed(T::AccountId, u64, FungibleTokenId, Balance),
 	}
 
 	#[pallet::storage]
@@ -113,12 +163,52 @@ pub mod pallet {
 	pub type AccountPoolIdLocked<T: Config> =
 		StorageMap<_, Blake2_128, (T::AccountId, u64), ClaimInfo>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn liquidity_configs)]
+	pub type LiquidityConfigs<T: Config> =
+		StorageMap<_, Blake2_128, u64, LiquidityConfig<T::BlockNumber>>;
+
+	/// The pools whose liquidity is added at a block, the end of their sale.
+	#[pallet::storage]
+	pub type PendingLiquidity<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<u64, T::MaxLiquidityPerBlock>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn liquidity_locks)]
+	pub type LiquidityLocks<T: Config> =
+		StorageMap<_, Blake2_128, u64, LiquidityLock<T::BlockNumber>>;
+
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let pool_ids = PendingLiquidity::<T>::take(now);
+
+			for pool_id in pool_ids.iter() {
+				let result = with_transaction(|| match Self::do_add_liquidity(*pool_id) {
+					Ok(_) => TransactionOutcome::Commit(Ok(())),
+					Err(e) => TransactionOutcome::Rollback(Err::<(), DispatchError>(e)),
+				});
+				if let Err(e) = result {
+					Self::refund_liquidity(*pool_id);
+					Self::deposit_event(Event::LiquidityFailed(*pool_id, e));
+				}
+			}
+
+			T::DbWeight::get().reads_writes(1, 1).saturating_add(
+				<T as pallet::Config>::WeightInfo::add_liquidity()
+					.saturating_mul(pool_ids.len() as Weight),
+			)
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -283,6 +373,75 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Add `buy_token_percent` of the buy tokens raised and sale tokens at the sale price to
+		/// the exchange pool of the pair at the end of the sale, locking the LP tokens for
+		/// `lock_period`. `sale_token_amount` sale tokens are set aside for it now, and what
+		/// the pool does not take is claimed with the unsold tokens.
+		///
+		/// At most `MaxLiquidityPerBlock` sales ending at the same block can add liquidity.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_liquidity_config())]
+		pub fn set_liquidity_config(
+			origin: OriginFor<T>,
+			pool_id: u64,
+			config: LiquidityConfig<T::BlockNumber>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let pool = Pools::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+
+			ensure!(pool.owner == sender, Error::<T>::NotOwner);
+			ensure!(Self::now() < pool.sale_start, Error::<T>::SaleStarted);
+			ensure!(!LiquidityConfigs::<T>::contains_key(pool_id), Error::<T>::LiquidityConfigured);
+			ensure!(
+				!config.buy_token_percent.is_zero() && !config.sale_token_amount.is_zero(),
+				Error::<T>::InvalidLiquidityConfig
+			);
+			ensure!(pool.token_price > 0, Error::<T>::InvalidLiquidityConfig);
+
+			PendingLiquidity::<T>::try_mutate(pool.sale_end, |pool_ids| {
+				pool_ids.try_push(pool_id).map_err(|_| Error::<T>::LiquidityScheduleFull)
+			})?;
+			pallet_token_fungible::Pallet::<T>::do_transfer(
+				FungibleTokenIdOf::<T>::unique_saturated_from(pool.sale_token_id),
+				&sender,
+				&pool.escrow_account,
+				config.sale_token_amount,
+			)?;
+
+			LiquidityConfigs::<T>::insert(pool_id, config.clone());
+
+			Self::deposit_event(Event::LiquidityConfigSet(pool_id, config));
+
+			Ok(())
+		}
+
+		/// Claim the LP tokens of the liquidity added at the end of a sale once unlocked.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::claim_liquidity())]
+		pub fn claim_liquidity(origin: OriginFor<T>, pool_id: u64) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let pool = Pools::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			let lock = LiquidityLocks::<T>::get(pool_id).ok_or(Error::<T>::LiquidityNotFound)?;
+
+			ensure!(pool.owner == sender, Error::<T>::NotOwner);
+			ensure!(Self::now() >= lock.unlock_at, Error::<T>::LiquidityLocked);
+
+			pallet_token_fungible::Pallet::<T>::do_transfer(
+				FungibleTokenIdOf::<T>::unique_saturated_from(lock.lp_token),
+				&pool.escrow_account,
+				&sender,
+				lock.amount,
+			)?;
+			LiquidityLocks::<T>::remove(pool_id);
+
+			Self::deposit_event(Event::LiquidityClaimed(
+				sender,
+				pool_id,
+				lock.lp_token,
+				lock.amount,
+			));
+
+			Ok(())
+		}
 	}
 }
 
@@ -294,4 +453,86 @@ impl<T: Config> Pallet<T> {
 	pub fn escrow_account_id(pool_id: u64) -> T::AccountId {
 		<T as pallet::Config>::PalletId::get().into_sub_account_truncating(pool_id)
 	}
+
+	fn do_add_liquidity(pool_id: u64) -> DispatchResult {
+		let pool = Pools::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+		let config = LiquidityConfigs::<T>::get(pool_id).ok_or(Error::<T>::LiquidityNotFound)?;
+		let sale_token = FungibleTokenIdOf::<T>::unique_saturated_from(pool.sale_token_id);
+		let buy_token = FungibleTokenIdOf::<T>::unique_saturated_from(pool.buy_token_id);
+
+		let raised =
+			pallet_token_fungible::Pallet::<T>::balance_of(buy_token, &pool.escrow_account);
+		let unit = 10u128
+			.checked_pow(pallet_token_fungible::Pallet::<T>::token_decimals(sale_token) as u32)
+			.ok_or(Error::<T>::ArithmeticOverflow)?;
+
+		// buyers pay `token_price` buy tokens for each `unit` of sale tokens
+		let mut buy_amount = config.buy_token_percent * raised;
+		let mut sale_amount = buy_amount
+			.checked_mul(unit)
+			.and_then(|amount| amount.checked_div(pool.token_price))
+			.ok_or(Error::<T>::ArithmeticOverflow)?;
+		if sale_amount > config.sale_token_amount {
+			sale_amount = config.sale_token_amount;
+			buy_amount = sale_amount
+				.checked_mul(pool.token_price)
+				.map(|amount| amount / unit)
+				.ok_or(Error::<T>::ArithmeticOverflow)?;
+		}
+		ensure!(!sale_amount.is_zero() && !buy_amount.is_zero(), Error::<T>::NotRaised);
+
+		// a pool created before the end of the sale must be at about the sale price
+		let min_percent =
+			Percent::from_percent(100).saturating_sub(T::LiquidityPriceTolerance::get());
+		T::Exchange::ensure_pool(&pool.owner, sale_token, buy_token, config.fee)?;
+		let (sale_amount, buy_amount, liquidity) = T::Exchange::add_liquidity(
+			&pool.escrow_account,
+			sale_token,
+			buy_token,
+			config.fee,
+			(sale_amount, buy_amount),
+			(min_percent * sale_amount, min_percent * buy_amount),
+			&pool.escrow_account,
+		)?;
+		let lp_token: FungibleTokenId = T::Exchange::lp_token(sale_token, buy_token, config.fee)
+			.ok_or(Error::<T>::PoolNotFound)?
+			.unique_saturated_into();
+
+		LiquidityLocks::<T>::insert(
+			pool_id,
+			LiquidityLock {
+				lp_token,
+				amount: liquidity,
+				unlock_at: Self::now().saturating_add(config.lock_period),
+			},
+		);
+		// the sale tokens the pool did not take are claimed with the unsold ones
+		Pools::<T>::mutate(pool_id, |maybe_pool| {
+			if let Some(pool) = maybe_pool {
+				pool.raise_amount = pool
+					.raise_amount
+					.saturating_add(config.sale_token_amount.saturating_sub(sale_amount));
+			}
+		});
+
+		Self::deposit_event(Event::LiquidityAdded(
+			pool_id,
+			lp_token,
+			sale_amount,
+			buy_amount,
+			liquidity,
+		));
+
+		Ok(())
+	}
+
+	fn refund_liquidity(pool_id: u64) {
+		if let Some(config) = LiquidityConfigs::<T>::get(pool_id) {
+			Pools::<T>::mutate(pool_id, |maybe_pool| {
+				if let Some(pool) = maybe_pool {
+					pool.raise_amount = pool.raise_amount.saturating_add(config.sale_token_amount);
+				}
+			});
+		}
+	}
 }
@@ -18,15 +18,17 @@
 
 use crate as pallet_launchpad;
 use frame_support::{
-	construct_runtime, parameter_types,
-	traits::{ConstU16, ConstU64},
+	construct_runtime, ensure, parameter_types,
+	traits::{ConstU16, ConstU64, Hooks},
 	PalletId,
 };
+use pallet_support::LiquidityProvider;
 use primitives::Balance;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	DispatchError, DispatchResult, Percent,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -111,12 +113,76 @@ impl pallet_token_fungible::Config for Test {
 
 parameter_types! {
 	pub const LaunchpadPalletId: PalletId = PalletId(*b"w3g/lpad");
+	pub const LiquidityPriceTolerance: Percent = Percent::from_percent(5);
+	pub const MaxLiquidityPerBlock: u32 = 2;
+	/// The reserves of the sale token and the buy token in the pool of `MockExchange`.
+	pub static PoolReserves: Option<(Balance, Balance)> = None;
+}
+
+pub const EXCHANGE_POOL: u64 = 99;
+pub const LP_TOKEN: u128 = 100;
+pub const EXCHANGE_FEE: u32 = 30;
+
+/// An exchange of a single pool with the fee `EXCHANGE_FEE`, taking all it is given at the price
+/// of `PoolReserves` if set, and minting the smaller amount as liquidity.
+pub struct MockExchange;
+
+impl LiquidityProvider<u64, u128> for MockExchange {
+	fn ensure_pool(_who: &u64, _token_a: u128, _token_b: u128, fee: u32) -> DispatchResult {
+		ensure!(fee == EXCHANGE_FEE, DispatchError::Other("FeeTierNotFound"));
+		if !TokenFungible::exists(LP_TOKEN) {
+			TokenFungible::do_create_token(
+				&EXCHANGE_POOL,
+				LP_TOKEN,
+				b"W3G-LP".to_vec(),
+				b"W3G-LP".to_vec(),
+				18,
+			)?;
+		}
+		Ok(())
+	}
+
+	fn lp_token(_token_a: u128, _token_b: u128, _fee: u32) -> Option<u128> {
+		Some(LP_TOKEN).filter(|id| TokenFungible::exists(*id))
+	}
+
+	fn add_liquidity(
+		who: &u64,
+		token_a: u128,
+		token_b: u128,
+		_fee: u32,
+		(amount_a, amount_b): (Balance, Balance),
+		(min_a, min_b): (Balance, Balance),
+		to: &u64,
+	) -> Result<(Balance, Balance, Balance), DispatchError> {
+		let (amount_a, amount_b) = match PoolReserves::get() {
+			Some((reserve_a, reserve_b)) if amount_a * reserve_b / reserve_a <= amount_b => {
+				let amount_b = amount_a * reserve_b / reserve_a;
+				ensure!(amount_b >= min_b, DispatchError::Other("InsufficientBAmount"));
+				(amount_a, amount_b)
+			},
+			Some((reserve_a, reserve_b)) => {
+				let amount_a = amount_b * reserve_a / reserve_b;
+				ensure!(amount_a >= min_a, DispatchError::Other("InsufficientAAmount"));
+				(amount_a, amount_b)
+			},
+			None => (amount_a, amount_b),
+		};
+		TokenFungible::do_transfer(token_a, who, &EXCHANGE_POOL, amount_a)?;
+		TokenFungible::do_transfer(token_b, who, &EXCHANGE_POOL, amount_b)?;
+		let liquidity = amount_a.min(amount_b);
+		TokenFungible::do_mint(LP_TOKEN, &EXCHANGE_POOL, *to, liquidity)?;
+		Ok((amount_a, amount_b, liquidity))
+	}
 }
 
 impl pallet_launchpad::Config for Test {
 	type Event = Event;
 	type PalletId = LaunchpadPalletId;
 	type WeightInfo = ();
+	type Exchange = MockExchange;
+	type LiquidityPriceTolerance = LiquidityPriceTolerance;
+	type MaxLiquidityPerBlock = MaxLiquidityPerBlock;
 }
 
 // Build genesis storage according to the mock runtime.
@@ -137,5 +203,6 @@ pub fn run_to_block(n: u64) {
 	while System::block_number() < n {
 		System::finalize();
 		System::set_block_number(System::block_number() + 1);
+		Launchpad::on_initialize(System::block_number());
 	}
 }
@@ -185,3 +185,257 @@ fn claim_should_work() {
 		assert_eq!(TokenFungible::balance_of(W3G, ALICE), (100 - 10 + 5) * W3G_DECIMALS);
 	})
 }
+
+fn liquidity_config() -> LiquidityConfig<u64> {
+	LiquidityConfig {
+		buy_token_percent: Percent::from_percent(50),
+		sale_token_amount: 5 * W3G_DECIMALS,
+		fee: EXCHANGE_FEE,
+		lock_period: 100,
+	}
+}
+
+#[test]
+fn set_liquidity_config_should_work() {
+	new_test_ext().execute_with(|| {
+		create_tokens();
+		set_balance();
+		assert_ok!(Launchpad::create_pool(
+			Origin::signed(ALICE),
+			10,
+			10,
+			W3G,
+			USDT,
+			10 * W3G_DECIMALS,
+			1 * USDT_DECIMALS
+		));
+
+		assert_noop!(
+			Launchpad::set_liquidity_config(Origin::signed(BOB), 0, liquidity_config()),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			Launchpad::set_liquidity_config(
+				Origin::signed(ALICE),
+				0,
+				LiquidityConfig { buy_token_percent: Percent::zero(), ..liquidity_config() }
+			),
+			Error::<Test>::InvalidLiquidityConfig
+		);
+
+		assert_ok!(Launchpad::set_liquidity_config(Origin::signed(ALICE), 0, liquidity_config()));
+		System::assert_last_event(crate::mock::Event::Launchpad(crate::Event::LiquidityConfigSet(
+			0,
+			liquidity_config(),
+		)));
+		assert_eq!(Launchpad::liquidity_configs(0), Some(liquidity_config()));
+		assert_eq!(
+			TokenFungible::balance_of(W3G, Launchpad::escrow_account_id(0)),
+			15 * W3G_DECIMALS
+		);
+		assert_noop!(
+			Launchpad::set_liquidity_config(Origin::signed(ALICE), 0, liquidity_config()),
+			Error::<Test>::LiquidityConfigured
+		);
+
+		assert_ok!(Launchpad::create_pool(
+			Origin::signed(ALICE),
+			1,
+			10,
+			W3G,
+			USDT,
+			10 * W3G_DECIMALS,
+			1 * USDT_DECIMALS
+		));
+		assert_noop!(
+			Launchpad::set_liquidity_config(Origin::signed(ALICE), 1, liquidity_config()),
+			Error::<Test>::SaleStarted
+		);
+
+		// the liquidity of a free sale could not be priced
+		assert_ok!(Launchpad::create_pool(
+			Origin::signed(ALICE),
+			10,
+			10,
+			W3G,
+			USDT,
+			10 * W3G_DECIMALS,
+			0
+		));
+		assert_noop!(
+			Launchpad::set_liquidity_config(Origin::signed(ALICE), 2, liquidity_config()),
+			Error::<Test>::InvalidLiquidityConfig
+		);
+	})
+}
+
+#[test]
+fn set_liquidity_config_should_be_bounded_per_block() {
+	new_test_ext().execute_with(|| {
+		create_tokens();
+		set_balance();
+		for (pool_id, sale_duration) in [(0, 10), (1, 10), (2, 10), (3, 11)] {
+			assert_ok!(Launchpad::create_pool(
+				Origin::signed(ALICE),
+				10,
+				sale_duration,
+				W3G,
+				USDT,
+				10 * W3G_DECIMALS,
+				1 * USDT_DECIMALS
+			));
+			if pool_id < 2 {
+				assert_ok!(Launchpad::set_liquidity_config(
+					Origin::signed(ALICE),
+					pool_id,
+					liquidity_config()
+				));
+			}
+		}
+		assert_eq!(PendingLiquidity::<Test>::get(20).into_inner(), vec![0, 1]);
+
+		// at most `MaxLiquidityPerBlock` sales ending at block 20
+		assert_noop!(
+			Launchpad::set_liquidity_config(Origin::signed(ALICE), 2, liquidity_config()),
+			Error::<Test>::LiquidityScheduleFull
+		);
+		assert_ok!(Launchpad::set_liquidity_config(Origin::signed(ALICE), 3, liquidity_config()));
+		assert_eq!(PendingLiquidity::<Test>::get(21).into_inner(), vec![3]);
+
+		run_to_block(20);
+		assert!(!PendingLiquidity::<Test>::contains_key(20));
+		assert_eq!(PendingLiquidity::<Test>::get(21).into_inner(), vec![3]);
+	})
+}
+
+#[test]
+fn liquidity_should_be_added_at_sale_end() {
+	new_test_ext().execute_with(|| {
+		create_tokens();
+		set_balance();
+		assert_ok!(Launchpad::create_pool(
+			Origin::signed(ALICE),
+			10,
+			10,
+			W3G,
+			USDT,
+			10 * W3G_DECIMALS,
+			1 * USDT_DECIMALS
+		));
+		assert_ok!(Launchpad::set_liquidity_config(Origin::signed(ALICE), 0, liquidity_config()));
+
+		run_to_block(10);
+		assert_ok!(Launchpad::buy_token(Origin::signed(BOB), 0, 2));
+		assert_ok!(Launchpad::buy_token(Origin::signed(CHARLIE), 0, 2));
+
+		// half of the 4 USDT raised is paired with 2 W3G at the sale price
+		run_to_block(20);
+		System::assert_last_event(crate::mock::Event::Launchpad(crate::Event::LiquidityAdded(
+			0,
+			LP_TOKEN,
+			2 * W3G_DECIMALS,
+			2 * USDT_DECIMALS,
+			2 * USDT_DECIMALS,
+		)));
+		let escrow_account = Launchpad::escrow_account_id(0);
+		assert_eq!(TokenFungible::balance_of(W3G, EXCHANGE_POOL), 2 * W3G_DECIMALS);
+		assert_eq!(TokenFungible::balance_of(USDT, EXCHANGE_POOL), 2 * USDT_DECIMALS);
+		assert_eq!(TokenFungible::balance_of(LP_TOKEN, escrow_account), 2 * USDT_DECIMALS);
+		assert_eq!(
+			Launchpad::liquidity_locks(0),
+			Some(LiquidityLock { lp_token: LP_TOKEN, amount: 2 * USDT_DECIMALS, unlock_at: 120 })
+		);
+
+		// the 6 W3G unsold and the 3 W3G the pool did not take go back to the owner
+		run_to_block(21);
+		assert_ok!(Launchpad::owner_claim(Origin::signed(ALICE), 0));
+		assert_eq!(TokenFungible::balance_of(W3G, ALICE), (100 - 15 + 9) * W3G_DECIMALS);
+		assert_eq!(TokenFungible::balance_of(USDT, ALICE), 2 * USDT_DECIMALS);
+
+		assert_noop!(
+			Launchpad::claim_liquidity(Origin::signed(ALICE), 0),
+			Error::<Test>::LiquidityLocked
+		);
+		run_to_block(120);
+		assert_noop!(Launchpad::claim_liquidity(Origin::signed(BOB), 0), Error::<Test>::NotOwner);
+		assert_ok!(Launchpad::claim_liquidity(Origin::signed(ALICE), 0));
+		System::assert_last_event(crate::mock::Event::Launchpad(crate::Event::LiquidityClaimed(
+			ALICE,
+			0,
+			LP_TOKEN,
+			2 * USDT_DECIMALS,
+		)));
+		assert_eq!(TokenFungible::balance_of(LP_TOKEN, ALICE), 2 * USDT_DECIMALS);
+		assert_noop!(
+			Launchpad::claim_liquidity(Origin::signed(ALICE), 0),
+			Error::<Test>::LiquidityNotFound
+		);
+	})
+}
+
+#[test]
+fn failed_liquidity_should_be_refunded() {
+	new_test_ext().execute_with(|| {
+		create_tokens();
+		set_balance();
+		assert_ok!(Launchpad::create_pool(
+			Origin::signed(ALICE),
+			10,
+			10,
+			W3G,
+			USDT,
+			10 * W3G_DECIMALS,
+			1 * USDT_DECIMALS
+		));
+		assert_ok!(Launchpad::set_liquidity_config(Origin::signed(ALICE), 0, liquidity_config()));
+
+		run_to_block(20);
+		System::assert_last_event(crate::mock::Event::Launchpad(crate::Event::LiquidityFailed(
+			0,
+			Error::<Test>::NotRaised.into(),
+		)));
+		assert_eq!(Launchpad::liquidity_locks(0), None);
+
+		run_to_block(21);
+		assert_ok!(Launchpad::owner_claim(Origin::signed(ALICE), 0));
+		assert_eq!(TokenFungible::balance_of(W3G, ALICE), 100 * W3G_DECIMALS);
+	})
+}
+
+#[test]
+fn liquidity_should_not_be_added_to_pool_off_the_sale_price() {
+	new_test_ext().execute_with(|| {
+		create_tokens();
+		set_balance();
+		assert_ok!(Launchpad::create_pool(
+			Origin::signed(ALICE),
+			10,
+			10,
+			W3G,
+			USDT,
+			10 * W3G_DECIMALS,
+			1 * USDT_DECIMALS
+		));
+		assert_ok!(Launchpad::set_liquidity_config(Origin::signed(ALICE), 0, liquidity_config()));
+
+		run_to_block(10);
+		assert_ok!(Launchpad::buy_token(Origin::signed(BOB), 0, 2));
+		assert_ok!(Launchpad::buy_token(Origin::signed(CHARLIE), 0, 2));
+
+		// the pool was created before the end of the sale at 2 USDT for a W3G
+		PoolReserves::set(Some((W3G_DECIMALS, 2 * USDT_DECIMALS)));
+		run_to_block(20);
+		System::assert_last_event(crate::mock::Event::Launchpad(crate::Event::LiquidityFailed(
+			0,
+			DispatchError::Other("InsufficientAAmount"),
+		)));
+		assert_eq!(Launchpad::liquidity_locks(0), None);
+		assert_eq!(TokenFungible::balance_of(W3G, EXCHANGE_POOL), 0);
+
+		// the 6 W3G unsold and the 5 W3G set aside go back to the owner with the 4 USDT raised
+		run_to_block(21);
+		assert_ok!(Launchpad::owner_claim(Origin::signed(ALICE), 0));
+		assert_eq!(TokenFungible::balance_of(W3G, ALICE), (100 - 15 + 11) * W3G_DECIMALS);
+		assert_eq!(TokenFungible::balance_of(USDT, ALICE), 4 * USDT_DECIMALS);
+	})
+}
@@ -50,6 +50,9 @@ pub trait WeightInfo {
 	fn buy_token() -> Weight;
 	fn claim() -> Weight;
 	fn owner_claim() -> Weight;
+	fn set_liquidity_config() -> Weight;
+	fn claim_liquidity() -> Weight;
+	fn add_liquidity() -> Weight;
 }
 
 /// Weights for pallet_launchpad using the Web3Games node and recommended hardware.
@@ -87,6 +90,43 @@ impl<T: frame_system::Config> WeightInfo for W3GWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(5 as Weight))
 			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
+	// Storage: Launchpad Pools (r:1 w:0)
+	// Storage: Launchpad LiquidityConfigs (r:1 w:1)
+	// Storage: TokenFungible Balances (r:2 w:2)
+	// Storage: Launchpad PendingLiquidity (r:1 w:1)
+	fn set_liquidity_config() -> Weight {
+		(31_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Launchpad Pools (r:1 w:0)
+	// Storage: Launchpad LiquidityLocks (r:1 w:1)
+	// Storage: TokenFungible Balances (r:2 w:2)
+	fn claim_liquidity() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Launchpad Pools (r:1 w:1)
+	// Storage: Launchpad LiquidityConfigs (r:1 w:0)
+	// Storage: TokenFungible Tokens (r:4 w:2)
+	// Storage: TokenFungible Balances (r:6 w:6)
+	// Storage: Exchange Pools (r:1 w:1)
+	// Storage: Exchange FeeTiers (r:1 w:0)
+	// Storage: Exchange NextPoolId (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Exchange Reserves (r:1 w:1)
+	// Storage: Exchange PoolStatuses (r:1 w:0)
+	// Storage: Exchange FlashLocks (r:1 w:0)
+	// Storage: Exchange Observations (r:1 w:1)
+	// Storage: Launchpad LiquidityLocks (r:0 w:1)
+	// Storage: Exchange PoolDeposits (r:0 w:1)
+	// Storage: Exchange LpTokenToToken (r:0 w:1)
+	fn add_liquidity() -> Weight {
+		(142_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(21 as Weight))
+			.saturating_add(T::DbWeight::get().writes(17 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -111,4 +151,19 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
 	}
+	fn set_liquidity_config() -> Weight {
+		(31_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn claim_liquidity() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn add_liquidity() -> Weight {
+		(142_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(21 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(17 as Weight))
+	}
 }
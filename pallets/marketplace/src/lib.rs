@@ -22,16 +22,26 @@ use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
 	dispatch::DispatchResult,
 	ensure,
-	traits::{Currency, ExistenceRequirement::KeepAlive, Get},
+	storage::{with_transaction, TransactionOutcome},
+	traits::{
+		BalanceStatus, Currency, ExistenceRequirement::KeepAlive, Get, ReservableCurrency,
+		StorageVersion,
+	},
+	weights::Weight,
 	PalletId,
 };
+use pallet_support::{MultiReceiver, NonFungibleReceiver};
 use scale_info::TypeInfo;
 use sp_runtime::{
-	traits::{AccountIdConversion, One, Saturating, UniqueSaturatedFrom},
-	RuntimeDebug,
+	helpers_128bit::multiply_by_rational,
+	traits::{
+		AccountIdConversion, One, Saturating, UniqueSaturatedFrom, UniqueSaturatedInto, Zero,
+	},
+	DispatchError, Permill, RuntimeDebug,
 };
 
 pub use pallet::*;
+pub mod migrations;
 
 #[cfg(test)]
 mod mock;
@@ -41,6 +51,9 @@ mod tests;
 
 pub const MIN_DURATION: u32 = 100;
 pub const MIN_PRICE: u32 = 10000;
+/// How many blocks past the requested end block an auction may be pushed when its end
+/// block is already full.
+pub const MAX_END_BLOCK_SEARCH: u32 = 10;
 
 pub type BalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
@@ -49,11 +62,19 @@ pub type OrderOf<T> = Order<
 	BalanceOf<T>,
 	<T as frame_system::Config>::BlockNumber,
 >;
+pub type DutchOrderOf<T> = DutchOrder<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+pub type OfferOf<T> = Offer<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+pub type AuctionOf<T> = Auction<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+>;
 
 type NonFungibleGroupId = u128;
 type NonFungibleTokenId = u128;
 type MultiGroupId = u128;
 type MultiTokenId = u128;
+type FungibleTokenId = u128;
 
 #[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
 pub enum Asset {
@@ -62,14 +83,100 @@ pub enum Asset {
 	ErrorToken,
 }
 
+impl Asset {
+	/// The collection `self` belongs to.
+	pub fn collection(&self) -> Option<Collection> {
+		match self {
+			Asset::NonFungibleToken(group_id, _) => Some(Collection::NonFungibleToken(*group_id)),
+			Asset::MultiToken(group_id, _) => Some(Collection::MultiToken(*group_id)),
+			Asset::ErrorToken => None,
+		}
+	}
+}
+
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum Collection {
+	NonFungibleToken(NonFungibleGroupId),
+	MultiToken(MultiGroupId),
+}
+
+/// The share of every sale of a collection paid to its creator.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Royalty<AccountId> {
+	pub recipient: AccountId,
+	pub rate: Permill,
+}
+
+/// How the price of a sale was split.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct PaymentBreakdown<AccountId, Balance> {
+	pub seller: AccountId,
+	pub seller_amount: Balance,
+	/// The platform fee and its recipient
+	pub platform_fee: Option<(AccountId, Balance)>,
+	/// The collection royalty and its recipient
+	pub royalty: Option<(AccountId, Balance)>,
+}
+
+/// What an order or a bid is paid with.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum PaymentAsset {
+	/// The native currency
+	Native,
+	/// A `pallet_token_fungible` token
+	Fungible(FungibleTokenId),
+}
+
+impl Default for PaymentAsset {
+	fn default() -> Self {
+		PaymentAsset::Native
+	}
+}
+
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
 pub struct Order<AccountId, Balance, BlockNumber> {
 	pub creater: AccountId,
+	pub payment_asset: PaymentAsset,
 	pub price: Balance,
 	pub start: BlockNumber,
 	pub duration: BlockNumber,
 }
 
+/// The declining price of a Dutch auction order, it falls linearly from `start_price` at
+/// `start_block` to `end_price` at `end_block`.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct DutchOrder<Balance, BlockNumber> {
+	pub start_price: Balance,
+	pub end_price: Balance,
+	pub start_block: BlockNumber,
+	pub end_block: BlockNumber,
+}
+
+/// An offer on an asset, listed or not. Its funds are held until it is accepted, cancelled,
+/// outbid or expires.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Offer<Balance, BlockNumber> {
+	pub payment_asset: PaymentAsset,
+	pub price: Balance,
+	/// The offer is released at this block
+	pub expire: BlockNumber,
+}
+
+/// An English auction, the highest bid at the end block wins.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Auction<AccountId, Balance, BlockNumber> {
+	pub creater: AccountId,
+	pub payment_asset: PaymentAsset,
+	/// The lowest accepted first bid
+	pub reserve_price: Balance,
+	/// Every bid must beat the highest one by at least this much
+	pub min_increment: Balance,
+	pub start: BlockNumber,
+	pub end: BlockNumber,
+	/// The highest bidder and its bid
+	pub highest_bid: Option<(AccountId, Balance)>,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -78,16 +185,42 @@ pub mod pallet {
 
 	#[pallet::config]
 	pub trait Config:
-		frame_system::Config + pallet_token_non_fungible::Config + pallet_token_multi::Config
+		frame_system::Config
+		+ pallet_token_fungible::Config
+		+ pallet_token_non_fungible::Config
+		+ pallet_token_multi::Config
 	{
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
-		type Currency: Currency<Self::AccountId>;
+		type Currency: ReservableCurrency<Self::AccountId>;
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
+		/// A bid placed this close to the end of an auction pushes the end back to this many
+		/// blocks from now.
+		#[pallet::constant]
+		type AntiSnipingPeriod: Get<Self::BlockNumber>;
+		/// The maximum number of auctions settled in a single block.
+		#[pallet::constant]
+		type MaxAuctionsPerBlock: Get<u32>;
+		/// The maximum number of open offers on an asset, a new offer on a full asset must beat
+		/// the lowest one.
+		#[pallet::constant]
+		type MaxOffersPerAsset: Get<u32>;
+		/// The maximum number of offers expiring in a single block.
+		#[pallet::constant]
+		type MaxOffersPerBlock: Get<u32>;
+		/// The highest platform fee the admin can set.
+		#[pallet::constant]
+		type MaxPlatformFee: Get<Permill>;
+		/// The highest royalty a collection owner can set.
+		#[pallet::constant]
+		type MaxRoyalty: Get<Permill>;
 	}
 
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// The pallet admin key.
@@ -95,11 +228,20 @@ pub mod pallet {
 	#[pallet::getter(fn admin_key)]
 	pub(super) type Admin<T: Config> = StorageValue<_, T::AccountId>;
 
-	/// The protocol fee point.
-	/// [0, 255] -> [0%, 2.55%]
+	/// The share of every sale paid to the fee recipient.
 	#[pallet::storage]
-	#[pallet::getter(fn point)]
-	pub(super) type Point<T: Config> = StorageValue<_, u8, ValueQuery>;
+	#[pallet::getter(fn platform_fee)]
+	pub(super) type PlatformFee<T: Config> = StorageValue<_, Permill, ValueQuery>;
+
+	/// The account the platform fee is paid to, the admin when unset.
+	#[pallet::storage]
+	#[pallet::getter(fn fee_recipient)]
+	pub(super) type FeeRecipient<T: Config> = StorageValue<_, T::AccountId>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn royalties)]
+	pub(super) type Royalties<T: Config> =
+		StorageMap<_, Blake2_128Concat, Collection, Royalty<T::AccountId>>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn orders)]
@@ -108,6 +250,48 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(super) type Bids<T: Config> = StorageMap<_, Blake2_128Concat, Asset, OrderOf<T>>;
 
+	/// The price schedule of the orders listed as Dutch auctions.
+	#[pallet::storage]
+	#[pallet::getter(fn dutch_orders)]
+	pub(super) type DutchOrders<T: Config> =
+		StorageMap<_, Blake2_128Concat, Asset, DutchOrderOf<T>>;
+
+	/// The open offers on an asset by bidder.
+	#[pallet::storage]
+	#[pallet::getter(fn offers)]
+	pub(super) type Offers<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, Asset, Blake2_128Concat, T::AccountId, OfferOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn offer_count)]
+	pub(super) type OfferCount<T: Config> = StorageMap<_, Blake2_128Concat, Asset, u32, ValueQuery>;
+
+	/// The offers to release at a block.
+	#[pallet::storage]
+	#[pallet::getter(fn offer_expiries)]
+	pub(super) type OfferExpiries<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<(Asset, T::AccountId), T::MaxOffersPerBlock>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn auctions)]
+	pub(super) type Auctions<T: Config> = StorageMap<_, Blake2_128Concat, Asset, AuctionOf<T>>;
+
+	/// The auctions to settle at a block.
+	#[pallet::storage]
+	#[pallet::getter(fn auction_end_time)]
+	pub(super) type AuctionEndTime<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<Asset, T::MaxAuctionsPerBlock>,
+		ValueQuery,
+	>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		/// The `AccountId` of the admin key.
@@ -139,6 +323,28 @@ pub mod pallet {
 		BidCreated(T::AccountId, Asset, OrderOf<T>),
 		BidCancelled(T::AccountId, Asset),
 		BidAccepted(T::AccountId, Asset, OrderOf<T>),
+		DutchOrderCreated(T::AccountId, Asset, DutchOrderOf<T>),
+		AuctionCreated(T::AccountId, Asset, AuctionOf<T>),
+		AuctionCancelled(T::AccountId, Asset),
+		/// \[bidder, asset, price\]
+		AuctionBid(T::AccountId, Asset, BalanceOf<T>),
+		/// \[asset, new_end\]
+		AuctionExtended(Asset, T::BlockNumber),
+		/// \[asset, winner\]
+		AuctionSettled(Asset, Option<(T::AccountId, BalanceOf<T>)>),
+		AuctionSettlementFailed(Asset, DispatchError),
+		OfferMade(T::AccountId, Asset, OfferOf<T>),
+		OfferCancelled(T::AccountId, Asset),
+		OfferOutbid(T::AccountId, Asset),
+		OfferExpired(T::AccountId, Asset),
+		/// \[owner, asset, bidder, price\]
+		OfferAccepted(T::AccountId, Asset, T::AccountId, BalanceOf<T>),
+		PlatformFeeSet(Permill),
+		FeeRecipientSet(Option<T::AccountId>),
+		/// \[collection, recipient, rate\]
+		RoyaltySet(Collection, T::AccountId, Permill),
+		/// The price of a sale of `asset` was paid out.
+		PaymentSettled(Asset, PaymentAsset, PaymentBreakdown<T::AccountId, BalanceOf<T>>),
 	}
 
 	// Errors inform users that something went wrong.
@@ -156,9 +362,69 @@ pub mod pallet {
 		NotSetAdmin,
 		NeedHigherPrice,
 		AssetTypeError,
+		PaymentAssetNotFound,
+		PaymentAssetMismatch,
+		AuctionNotFound,
+		AuctionEnded,
+		AuctionNotEnded,
+		AuctionHasBids,
+		AuctionScheduleFull,
+		InvalidEndBlock,
+		InvalidStartBlock,
+		InvalidPrice,
+		DutchOrderNotStarted,
+		DutchOrderNoBids,
+		OfferNotFound,
+		OfferExists,
+		OfferScheduleFull,
+		InvalidExpire,
+		AuctionExists,
+		OrderExists,
+		InsufficientHeldFunds,
+		FeeTooHigh,
+		RoyaltyTooHigh,
+		NotCollectionOwner,
 	}
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let assets = AuctionEndTime::<T>::take(now);
+
+			for asset in assets.iter() {
+				let result = with_transaction(|| match Self::do_settle_auction(*asset) {
+					Ok(_) => TransactionOutcome::Commit(Ok(())),
+					Err(e) => TransactionOutcome::Rollback(Err::<(), DispatchError>(e)),
+				});
+				if let Err(e) = result {
+					Self::deposit_event(Event::AuctionSettlementFailed(*asset, e));
+				}
+			}
+
+			let offers = OfferExpiries::<T>::take(now);
+			for (asset, bidder) in offers.iter() {
+				if let Some(offer) = Offers::<T>::get(asset, bidder) {
+					if let Err(e) = Self::do_remove_offer(*asset, bidder, offer) {
+						log::warn!(target: "runtime::marketplace", "failed to release an expired offer: {:?}", e);
+						continue
+					}
+					Self::deposit_event(Event::OfferExpired(bidder.clone(), *asset));
+				}
+			}
+
+			T::DbWeight::get()
+				.reads_writes(2, 2)
+				.saturating_add(
+					Self::settle_auction_weight().saturating_mul(assets.len() as Weight),
+				)
+				.saturating_add(
+					T::DbWeight::get().reads_writes(3, 4).saturating_mul(offers.len() as Weight),
+				)
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate::<T>()
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -171,12 +437,52 @@ pub mod pallet {
 		}
 
 		#[pallet::weight(10_000)]
-		pub fn set_service_fee_point(origin: OriginFor<T>, new_point: u8) -> DispatchResult {
+		pub fn set_platform_fee(origin: OriginFor<T>, fee: Permill) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			ensure!(Self::is_admin(who), Error::<T>::NotAdmin);
+			ensure!(fee <= T::MaxPlatformFee::get(), Error::<T>::FeeTooHigh);
 
-			Point::<T>::mutate(|point| *point = new_point);
+			PlatformFee::<T>::put(fee);
 
+			Self::deposit_event(Event::PlatformFeeSet(fee));
+			Ok(())
+		}
+
+		/// Pay the platform fee to `recipient`, or to the admin when `None`.
+		#[pallet::weight(10_000)]
+		pub fn set_fee_recipient(
+			origin: OriginFor<T>,
+			recipient: Option<T::AccountId>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::is_admin(who), Error::<T>::NotAdmin);
+
+			FeeRecipient::<T>::set(recipient.clone());
+
+			Self::deposit_event(Event::FeeRecipientSet(recipient));
+			Ok(())
+		}
+
+		/// Pay `rate` of every sale of `collection` to `recipient`, a zero `rate` removes the
+		/// royalty. Only the collection owner can set it.
+		#[pallet::weight(10_000)]
+		pub fn set_royalty(
+			origin: OriginFor<T>,
+			collection: Collection,
+			recipient: T::AccountId,
+			rate: Permill,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::is_collection_owner(collection, &who), Error::<T>::NotCollectionOwner);
+			ensure!(rate <= T::MaxRoyalty::get(), Error::<T>::RoyaltyTooHigh);
+
+			if rate.is_zero() {
+				Royalties::<T>::remove(collection);
+			} else {
+				Royalties::<T>::insert(collection, Royalty { recipient: recipient.clone(), rate });
+			}
+
+			Self::deposit_event(Event::RoyaltySet(collection, recipient, rate));
 			Ok(())
 		}
 
@@ -184,6 +490,7 @@ pub mod pallet {
 		pub fn create_order(
 			origin: OriginFor<T>,
 			asset: Asset,
+			payment_asset: PaymentAsset,
 			price: BalanceOf<T>,
 			duration: T::BlockNumber,
 		) -> DispatchResult {
@@ -191,11 +498,14 @@ pub mod pallet {
 
 			ensure!(price >= BalanceOf::<T>::from(MIN_PRICE), Error::<T>::TooLittlePrice);
 			ensure!(duration >= T::BlockNumber::from(MIN_DURATION), Error::<T>::TooLittleDuration);
+			ensure!(Self::payment_asset_exists(payment_asset), Error::<T>::PaymentAssetNotFound);
+			Self::ensure_not_listed(asset)?;
 
 			// check owner
 			Self::transfer_asset_to(who.clone(), asset, Self::account_id())?;
 
-			let order = Order { creater: who.clone(), price, start: Self::now(), duration };
+			let order =
+				Order { creater: who.clone(), payment_asset, price, start: Self::now(), duration };
 
 			Orders::<T>::insert(asset, order.clone());
 
@@ -203,7 +513,7 @@ pub mod pallet {
 			Ok(())
 		}
 
-		#[pallet::weight(10_000)]
+		#[pallet::weight(Pallet::<T>::asset_transfer_weight().saturating_add(10_000))]
 		pub fn cancel_order(origin: OriginFor<T>, asset: Asset) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
@@ -218,45 +528,32 @@ pub mod pallet {
 			Self::transfer_asset_to(Self::account_id(), asset, who.clone())?;
 
 			Orders::<T>::remove(asset);
+			DutchOrders::<T>::remove(asset);
 
 			Self::deposit_event(Event::OrderCancelled(who, asset));
 			Ok(())
 		}
 
-		#[pallet::weight(10_000)]
+		#[pallet::weight(Pallet::<T>::asset_transfer_weight().saturating_add(10_000))]
 		pub fn execute_order(origin: OriginFor<T>, asset: Asset) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			let order = Orders::<T>::get(asset).ok_or(Error::<T>::OrderNotFound)?;
+			let mut order = Orders::<T>::get(asset).ok_or(Error::<T>::OrderNotFound)?;
 
 			ensure!(order.start + order.duration >= Self::now(), Error::<T>::OrderExpired);
 
-			// Transfer service fee
-			let service_fee = match Admin::<T>::get() {
-				Some(admin) => {
-					let fee_point = Point::<T>::get();
-					let service_fee = Self::calculate_service_fee(order.price, fee_point);
-
-					// transfer `service_fee` to admin
-					<T as pallet::Config>::Currency::transfer(
-						&who,
-						&admin,
-						service_fee,
-						KeepAlive,
-					)?;
-
-					service_fee
-				},
-				None => BalanceOf::<T>::default(),
-			};
+			// Dutch auction orders are paid at their current price
+			if let Some(dutch_order) = DutchOrders::<T>::take(asset) {
+				ensure!(dutch_order.start_block <= Self::now(), Error::<T>::DutchOrderNotStarted);
+				order.price = Self::dutch_price(&dutch_order, Self::now());
+			}
 
 			//check Bids
 			if let Some(bid) = Bids::<T>::get(asset) {
 				Self::do_cancel_bid(asset, bid)?;
 			}
 
-			let to_seller = order.price.saturating_sub(service_fee);
-			<T as pallet::Config>::Currency::transfer(&who, &order.creater, to_seller, KeepAlive)?;
+			Self::pay_seller(asset, order.payment_asset, &who, &order.creater, order.price, false)?;
 
 			Self::transfer_asset_to(Self::account_id(), asset, who.clone())?;
 
@@ -270,6 +567,7 @@ pub mod pallet {
 		pub fn place_bid(
 			origin: OriginFor<T>,
 			asset: Asset,
+			payment_asset: PaymentAsset,
 			price: BalanceOf<T>,
 			duration: T::BlockNumber,
 		) -> DispatchResult {
@@ -280,6 +578,9 @@ pub mod pallet {
 
 			// check on expire time
 			ensure!(order.start + order.duration >= Self::now(), Error::<T>::OrderExpired);
+			ensure!(!DutchOrders::<T>::contains_key(asset), Error::<T>::DutchOrderNoBids);
+			// bids are paid with what the order asks for
+			ensure!(payment_asset == order.payment_asset, Error::<T>::PaymentAssetMismatch);
 			//check price
 			ensure!(price >= BalanceOf::<T>::from(MIN_PRICE), Error::<T>::TooLittlePrice);
 
@@ -293,11 +594,11 @@ pub mod pallet {
 				Self::do_cancel_bid(asset, bid)?;
 			}
 
-			//transfer price to admin
-			let admin = Admin::<T>::get().ok_or(Error::<T>::NotSetAdmin)?;
-			<T as Config>::Currency::transfer(&who, &admin, price, KeepAlive)?;
+			// hold the bid until it is accepted or cancelled
+			Self::hold_payment(payment_asset, &who, price)?;
 
-			let bid = Order { creater: who.clone(), price, start: Self::now(), duration };
+			let bid =
+				Order { creater: who.clone(), payment_asset, price, start: Self::now(), duration };
 			Bids::<T>::insert(asset, bid.clone());
 
 			Self::deposit_event(Event::BidCreated(who, asset, bid));
@@ -315,7 +616,7 @@ pub mod pallet {
 			Self::do_cancel_bid(asset, bid)
 		}
 
-		#[pallet::weight(10_000)]
+		#[pallet::weight(Pallet::<T>::asset_transfer_weight().saturating_add(10_000))]
 		pub fn accept_bid(origin: OriginFor<T>, asset: Asset) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
@@ -325,13 +626,15 @@ pub mod pallet {
 			let bid = Bids::<T>::get(asset).ok_or(Error::<T>::BidNotFound)?;
 			ensure!(bid.start + bid.duration >= Self::now(), Error::<T>::BidExpired);
 
-			let fee_point = Point::<T>::get();
-			let service_fee = Self::calculate_service_fee(bid.price, fee_point);
-			let to_seller = bid.price.saturating_sub(service_fee);
-
 			// Transfer bid amount to creater
-			let admin = Admin::<T>::get().ok_or(Error::<T>::NotSetAdmin)?;
-			<T as Config>::Currency::transfer(&admin, &order.creater, to_seller, KeepAlive)?;
+			Self::pay_seller(
+				asset,
+				bid.payment_asset,
+				&bid.creater,
+				&order.creater,
+				bid.price,
+				true,
+			)?;
 
 			Self::transfer_asset_to(Self::account_id(), asset, bid.creater.clone())?;
 
@@ -341,6 +644,245 @@ pub mod pallet {
 			Self::deposit_event(Event::BidAccepted(who, asset, bid));
 			Ok(())
 		}
+
+		/// List `asset` at a price falling linearly from `start_price` at `start_block` to
+		/// `end_price` at `end_block`, the order expires after `end_block`.
+		#[pallet::weight(10_000)]
+		pub fn create_dutch_order(
+			origin: OriginFor<T>,
+			asset: Asset,
+			payment_asset: PaymentAsset,
+			start_price: BalanceOf<T>,
+			end_price: BalanceOf<T>,
+			start_block: T::BlockNumber,
+			end_block: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let now = Self::now();
+
+			ensure!(end_price >= BalanceOf::<T>::from(MIN_PRICE), Error::<T>::TooLittlePrice);
+			ensure!(start_price >= end_price, Error::<T>::InvalidPrice);
+			ensure!(start_block >= now, Error::<T>::InvalidStartBlock);
+			ensure!(
+				end_block >= start_block.saturating_add(T::BlockNumber::from(MIN_DURATION)),
+				Error::<T>::InvalidEndBlock
+			);
+			ensure!(Self::payment_asset_exists(payment_asset), Error::<T>::PaymentAssetNotFound);
+			Self::ensure_not_listed(asset)?;
+
+			// check owner
+			Self::transfer_asset_to(who.clone(), asset, Self::account_id())?;
+
+			let order = Order {
+				creater: who.clone(),
+				payment_asset,
+				price: start_price,
+				start: now,
+				duration: end_block - now,
+			};
+			Orders::<T>::insert(asset, order);
+
+			let dutch_order = DutchOrder { start_price, end_price, start_block, end_block };
+			DutchOrders::<T>::insert(asset, dutch_order.clone());
+
+			Self::deposit_event(Event::DutchOrderCreated(who, asset, dutch_order));
+			Ok(())
+		}
+
+		#[pallet::weight(10_000)]
+		pub fn create_auction(
+			origin: OriginFor<T>,
+			asset: Asset,
+			payment_asset: PaymentAsset,
+			reserve_price: BalanceOf<T>,
+			min_increment: BalanceOf<T>,
+			end: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(reserve_price >= BalanceOf::<T>::from(MIN_PRICE), Error::<T>::TooLittlePrice);
+			ensure!(
+				end >= Self::now().saturating_add(T::BlockNumber::from(MIN_DURATION)),
+				Error::<T>::TooLittleDuration
+			);
+			ensure!(Self::payment_asset_exists(payment_asset), Error::<T>::PaymentAssetNotFound);
+			Self::ensure_not_listed(asset)?;
+
+			// check owner
+			Self::transfer_asset_to(who.clone(), asset, Self::account_id())?;
+
+			let end = Self::schedule_auction_end(asset, end)?;
+			let auction = Auction {
+				creater: who.clone(),
+				payment_asset,
+				reserve_price,
+				min_increment,
+				start: Self::now(),
+				end,
+				highest_bid: None,
+			};
+			Auctions::<T>::insert(asset, auction.clone());
+
+			Self::deposit_event(Event::AuctionCreated(who, asset, auction));
+			Ok(())
+		}
+
+		#[pallet::weight(Pallet::<T>::asset_transfer_weight().saturating_add(10_000))]
+		pub fn cancel_auction(origin: OriginFor<T>, asset: Asset) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let auction = Auctions::<T>::get(asset).ok_or(Error::<T>::AuctionNotFound)?;
+			ensure!(auction.creater == who, Error::<T>::NotSeller);
+			ensure!(auction.highest_bid.is_none(), Error::<T>::AuctionHasBids);
+
+			Self::transfer_asset_to(Self::account_id(), asset, who.clone())?;
+
+			Self::unschedule_auction_end(asset, auction.end);
+			Auctions::<T>::remove(asset);
+
+			Self::deposit_event(Event::AuctionCancelled(who, asset));
+			Ok(())
+		}
+
+		#[pallet::weight(10_000)]
+		pub fn bid_auction(
+			origin: OriginFor<T>,
+			asset: Asset,
+			price: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut auction = Auctions::<T>::get(asset).ok_or(Error::<T>::AuctionNotFound)?;
+			let now = Self::now();
+			ensure!(now < auction.end, Error::<T>::AuctionEnded);
+
+			match auction.highest_bid.take() {
+				Some((bidder, highest)) => {
+					ensure!(
+						price >= highest.saturating_add(auction.min_increment) && price > highest,
+						Error::<T>::NeedHigherPrice
+					);
+					// refund the outbid bidder
+					Self::release_payment(auction.payment_asset, &bidder, highest)?;
+				},
+				None => ensure!(price >= auction.reserve_price, Error::<T>::TooLittlePrice),
+			}
+			Self::hold_payment(auction.payment_asset, &who, price)?;
+			auction.highest_bid = Some((who.clone(), price));
+
+			// anti-sniping
+			let min_end = now.saturating_add(T::AntiSnipingPeriod::get());
+			if auction.end < min_end {
+				Self::unschedule_auction_end(asset, auction.end);
+				auction.end = Self::schedule_auction_end(asset, min_end)?;
+				Self::deposit_event(Event::AuctionExtended(asset, auction.end));
+			}
+			Auctions::<T>::insert(asset, auction);
+
+			Self::deposit_event(Event::AuctionBid(who, asset, price));
+			Ok(())
+		}
+
+		/// Settle an ended auction whose automatic settlement failed.
+		#[pallet::weight(Pallet::<T>::settle_auction_weight())]
+		pub fn settle_auction(origin: OriginFor<T>, asset: Asset) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let auction = Auctions::<T>::get(asset).ok_or(Error::<T>::AuctionNotFound)?;
+			ensure!(auction.end <= Self::now(), Error::<T>::AuctionNotEnded);
+
+			Self::do_settle_auction(asset)
+		}
+
+		/// Offer `price` for `asset`, whether it is listed or not. The funds are held until the
+		/// offer is accepted, cancelled, outbid or reaches `expire`.
+		#[pallet::weight(10_000)]
+		pub fn make_offer(
+			origin: OriginFor<T>,
+			asset: Asset,
+			payment_asset: PaymentAsset,
+			price: BalanceOf<T>,
+			expire: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(asset != Asset::ErrorToken, Error::<T>::AssetTypeError);
+			ensure!(price >= BalanceOf::<T>::from(MIN_PRICE), Error::<T>::TooLittlePrice);
+			ensure!(expire > Self::now(), Error::<T>::InvalidExpire);
+			ensure!(Self::payment_asset_exists(payment_asset), Error::<T>::PaymentAssetNotFound);
+			ensure!(!Offers::<T>::contains_key(asset, &who), Error::<T>::OfferExists);
+
+			// a full asset drops its lowest offer
+			if OfferCount::<T>::get(asset) >= T::MaxOffersPerAsset::get() {
+				let (bidder, lowest) = Offers::<T>::iter_prefix(asset)
+					.min_by_key(|(_, offer)| offer.price)
+					.ok_or(Error::<T>::OfferNotFound)?;
+				ensure!(price > lowest.price, Error::<T>::NeedHigherPrice);
+
+				Self::do_remove_offer(asset, &bidder, lowest)?;
+				Self::deposit_event(Event::OfferOutbid(bidder, asset));
+			}
+
+			Self::hold_payment(payment_asset, &who, price)?;
+
+			let expire = Self::schedule_offer_expiry(asset, &who, expire)?;
+			let offer = Offer { payment_asset, price, expire };
+			Offers::<T>::insert(asset, &who, offer.clone());
+			OfferCount::<T>::mutate(asset, |count| *count = count.saturating_add(1));
+
+			Self::deposit_event(Event::OfferMade(who, asset, offer));
+			Ok(())
+		}
+
+		#[pallet::weight(10_000)]
+		pub fn cancel_offer(origin: OriginFor<T>, asset: Asset) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let offer = Offers::<T>::get(asset, &who).ok_or(Error::<T>::OfferNotFound)?;
+			Self::do_remove_offer(asset, &who, offer)?;
+
+			Self::deposit_event(Event::OfferCancelled(who, asset));
+			Ok(())
+		}
+
+		/// Sell `asset` to the offer of `bidder`. A listed asset can only be sold by the seller
+		/// of its order, which is closed.
+		#[pallet::weight(Pallet::<T>::asset_transfer_weight().saturating_add(10_000))]
+		pub fn accept_offer(
+			origin: OriginFor<T>,
+			asset: Asset,
+			bidder: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let offer = Offers::<T>::get(asset, &bidder).ok_or(Error::<T>::OfferNotFound)?;
+			ensure!(!Auctions::<T>::contains_key(asset), Error::<T>::AuctionExists);
+
+			let from = match Orders::<T>::get(asset) {
+				Some(order) => {
+					ensure!(order.creater == who, Error::<T>::NotSeller);
+					if let Some(bid) = Bids::<T>::get(asset) {
+						Self::do_cancel_bid(asset, bid)?;
+					}
+					Orders::<T>::remove(asset);
+					DutchOrders::<T>::remove(asset);
+					Self::account_id()
+				},
+				None => who.clone(),
+			};
+
+			// fails unless the seller owns the asset
+			Self::transfer_asset_to(from, asset, bidder.clone())?;
+
+			Self::pay_seller(asset, offer.payment_asset, &bidder, &who, offer.price, true)?;
+
+			Self::unschedule_offer_expiry(asset, &bidder, offer.expire);
+			Offers::<T>::remove(asset, &bidder);
+			OfferCount::<T>::mutate(asset, |count| *count = count.saturating_sub(1));
+
+			Self::deposit_event(Event::OfferAccepted(who, asset, bidder, offer.price));
+			Ok(())
+		}
 	}
 }
 
@@ -356,12 +898,196 @@ impl<T: Config> Pallet<T> {
 		matches!(Admin::<T>::get(), Some(admin) if admin == who)
 	}
 
-	pub fn calculate_service_fee(value: BalanceOf<T>, fee_point: u8) -> BalanceOf<T> {
-		let point = BalanceOf::<T>::from(fee_point);
-		let base_point = BalanceOf::<T>::from(10000u16);
+	pub fn is_collection_owner(collection: Collection, who: &T::AccountId) -> bool {
+		match collection {
+			Collection::NonFungibleToken(group_id) => {
+				let id =
+					<T as pallet_token_non_fungible::Config>::NonFungibleTokenId::unique_saturated_from(group_id);
+				pallet_token_non_fungible::Pallet::<T>::exists(id) &&
+					pallet_token_non_fungible::Pallet::<T>::has_permission(id, who)
+			},
+			Collection::MultiToken(group_id) => {
+				let id = <T as pallet_token_multi::Config>::MultiTokenId::unique_saturated_from(
+					group_id,
+				);
+				pallet_token_multi::Pallet::<T>::exists(id) &&
+					pallet_token_multi::Pallet::<T>::has_permission(id, who)
+			},
+		}
+	}
 
-		// Impossible to overflow
-		value / base_point * point
+	/// Split `price` of a sale of `asset` between the fee recipient, the collection royalty
+	/// recipient and `seller`.
+	pub fn payment_breakdown(
+		asset: Asset,
+		seller: &T::AccountId,
+		price: BalanceOf<T>,
+	) -> PaymentBreakdown<T::AccountId, BalanceOf<T>> {
+		let platform_fee = FeeRecipient::<T>::get()
+			.or_else(Admin::<T>::get)
+			.map(|recipient| (recipient, PlatformFee::<T>::get() * price))
+			.filter(|(_, fee)| !fee.is_zero());
+		let royalty = asset
+			.collection()
+			.and_then(Royalties::<T>::get)
+			.map(|royalty| (royalty.recipient, royalty.rate * price))
+			.filter(|(_, royalty)| !royalty.is_zero());
+
+		let mut seller_amount = price;
+		for (_, amount) in platform_fee.iter().chain(royalty.iter()) {
+			seller_amount = seller_amount.saturating_sub(*amount);
+		}
+
+		PaymentBreakdown { seller: seller.clone(), seller_amount, platform_fee, royalty }
+	}
+
+	pub fn payment_asset_exists(payment_asset: PaymentAsset) -> bool {
+		match payment_asset {
+			PaymentAsset::Native => true,
+			PaymentAsset::Fungible(id) => pallet_token_fungible::Pallet::<T>::exists(
+				<T as pallet_token_fungible::Config>::FungibleTokenId::unique_saturated_from(id),
+			),
+		}
+	}
+
+	fn transfer_payment(
+		payment_asset: PaymentAsset,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		if from == to {
+			return Ok(())
+		}
+		match payment_asset {
+			PaymentAsset::Native => <T as Config>::Currency::transfer(from, to, amount, KeepAlive),
+			PaymentAsset::Fungible(id) => pallet_token_fungible::Pallet::<T>::do_transfer(
+				<T as pallet_token_fungible::Config>::FungibleTokenId::unique_saturated_from(id),
+				from,
+				to,
+				amount.unique_saturated_into(),
+			),
+		}
+	}
+
+	/// Set `amount` aside from `who`, native funds are reserved and tokens go to the pallet
+	/// account.
+	fn hold_payment(
+		payment_asset: PaymentAsset,
+		who: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		match payment_asset {
+			PaymentAsset::Native => <T as Config>::Currency::reserve(who, amount),
+			PaymentAsset::Fungible(_) =>
+				Self::transfer_payment(payment_asset, who, &Self::account_id(), amount),
+		}
+	}
+
+	/// Give `amount` held by `hold_payment` back to `who`.
+	fn release_payment(
+		payment_asset: PaymentAsset,
+		who: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		match payment_asset {
+			PaymentAsset::Native => {
+				<T as Config>::Currency::unreserve(who, amount);
+				Ok(())
+			},
+			PaymentAsset::Fungible(_) =>
+				Self::transfer_payment(payment_asset, &Self::account_id(), who, amount),
+		}
+	}
+
+	/// Pay `amount` held from `from` by `hold_payment` to `to`.
+	fn pay_held(
+		payment_asset: PaymentAsset,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		match payment_asset {
+			PaymentAsset::Native => {
+				let missing = <T as Config>::Currency::repatriate_reserved(
+					from,
+					to,
+					amount,
+					BalanceStatus::Free,
+				)?;
+				ensure!(missing.is_zero(), Error::<T>::InsufficientHeldFunds);
+				Ok(())
+			},
+			PaymentAsset::Fungible(_) =>
+				Self::transfer_payment(payment_asset, &Self::account_id(), to, amount),
+		}
+	}
+
+	/// Pay `price` of a sale of `asset` from `buyer`, out of its held funds when `held`, as
+	/// split by `payment_breakdown`.
+	fn pay_seller(
+		asset: Asset,
+		payment_asset: PaymentAsset,
+		buyer: &T::AccountId,
+		seller: &T::AccountId,
+		price: BalanceOf<T>,
+		held: bool,
+	) -> DispatchResult {
+		let breakdown = Self::payment_breakdown(asset, seller, price);
+
+		let payments = breakdown
+			.platform_fee
+			.iter()
+			.chain(breakdown.royalty.iter())
+			.map(|(recipient, amount)| (recipient, *amount))
+			.chain(sp_std::iter::once((seller, breakdown.seller_amount)));
+		for (recipient, amount) in payments {
+			if held {
+				Self::pay_held(payment_asset, buyer, recipient, amount)?;
+			} else {
+				Self::transfer_payment(payment_asset, buyer, recipient, amount)?;
+			}
+		}
+
+		Self::deposit_event(Event::PaymentSettled(asset, payment_asset, breakdown));
+		Ok(())
+	}
+
+	/// Release the funds of the offer of `bidder` on `asset` and remove it.
+	fn do_remove_offer(asset: Asset, bidder: &T::AccountId, offer: OfferOf<T>) -> DispatchResult {
+		Self::release_payment(offer.payment_asset, bidder, offer.price)?;
+		Self::unschedule_offer_expiry(asset, bidder, offer.expire);
+		Offers::<T>::remove(asset, bidder);
+		OfferCount::<T>::mutate(asset, |count| *count = count.saturating_sub(1));
+		Ok(())
+	}
+
+	fn schedule_offer_expiry(
+		asset: Asset,
+		bidder: &T::AccountId,
+		expire: T::BlockNumber,
+	) -> Result<T::BlockNumber, DispatchError> {
+		let mut at = expire;
+		for _ in 0..MAX_END_BLOCK_SEARCH {
+			if OfferExpiries::<T>::mutate(at, |offers| {
+				offers.try_push((asset, bidder.clone())).is_ok()
+			}) {
+				return Ok(at)
+			}
+			at = at.saturating_add(One::one());
+		}
+		Err(Error::<T>::OfferScheduleFull.into())
+	}
+
+	fn unschedule_offer_expiry(asset: Asset, bidder: &T::AccountId, expire: T::BlockNumber) {
+		OfferExpiries::<T>::mutate_exists(expire, |maybe_offers| {
+			if let Some(offers) = maybe_offers {
+				offers.retain(|(a, b)| !(*a == asset && b == bidder));
+				if offers.is_empty() {
+					*maybe_offers = None;
+				}
+			}
+		});
 	}
 
 	fn transfer_asset_to(from: T::AccountId, asset: Asset, to: T::AccountId) -> DispatchResult {
@@ -392,9 +1118,115 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// The price `asset` is sold at now, `None` when it is not listed.
+	pub fn get_current_price(asset: Asset) -> Option<BalanceOf<T>> {
+		let order = Orders::<T>::get(asset)?;
+		match DutchOrders::<T>::get(asset) {
+			Some(dutch_order) => Some(Self::dutch_price(&dutch_order, Self::now())),
+			None => Some(order.price),
+		}
+	}
+
+	pub fn dutch_price(dutch_order: &DutchOrderOf<T>, now: T::BlockNumber) -> BalanceOf<T> {
+		if now <= dutch_order.start_block {
+			return dutch_order.start_price
+		}
+		if now >= dutch_order.end_block {
+			return dutch_order.end_price
+		}
+
+		let drop: u128 = dutch_order
+			.start_price
+			.saturating_sub(dutch_order.end_price)
+			.unique_saturated_into();
+		let elapsed: u128 = (now - dutch_order.start_block).unique_saturated_into();
+		let duration: u128 =
+			(dutch_order.end_block - dutch_order.start_block).unique_saturated_into();
+		let discount = multiply_by_rational(drop, elapsed, duration).unwrap_or(drop);
+
+		dutch_order
+			.start_price
+			.saturating_sub(BalanceOf::<T>::unique_saturated_from(discount))
+	}
+
+	fn settle_auction_weight() -> Weight {
+		T::DbWeight::get()
+			.reads_writes(6, 6)
+			.saturating_add(Self::asset_transfer_weight())
+	}
+
+	/// The most weight the token receiver check of an asset transfer out of the pallet account
+	/// can use.
+	fn asset_transfer_weight() -> Weight {
+		<T as pallet_token_non_fungible::Config>::Receiver::max_weight()
+			.max(<T as pallet_token_multi::Config>::Receiver::max_weight())
+	}
+
+	/// An asset is held by the pallet for a single order, Dutch order or auction at a time, even
+	/// when its holders own several of it.
+	fn ensure_not_listed(asset: Asset) -> DispatchResult {
+		ensure!(!Auctions::<T>::contains_key(asset), Error::<T>::AuctionExists);
+		ensure!(
+			!Orders::<T>::contains_key(asset) && !DutchOrders::<T>::contains_key(asset),
+			Error::<T>::OrderExists
+		);
+		Ok(())
+	}
+
+	/// Put `asset` in the first block from `end` that still has room, returning that block.
+	fn schedule_auction_end(
+		asset: Asset,
+		end: T::BlockNumber,
+	) -> Result<T::BlockNumber, DispatchError> {
+		let mut at = end;
+		for _ in 0..MAX_END_BLOCK_SEARCH {
+			if AuctionEndTime::<T>::mutate(at, |assets| assets.try_push(asset).is_ok()) {
+				return Ok(at)
+			}
+			at = at.saturating_add(One::one());
+		}
+		Err(Error::<T>::AuctionScheduleFull.into())
+	}
+
+	fn unschedule_auction_end(asset: Asset, end: T::BlockNumber) {
+		AuctionEndTime::<T>::mutate_exists(end, |maybe_assets| {
+			if let Some(assets) = maybe_assets {
+				assets.retain(|a| *a != asset);
+				if assets.is_empty() {
+					*maybe_assets = None;
+				}
+			}
+		});
+	}
+
+	/// Hand the asset to the highest bidder and pay the seller, or give the asset back when
+	/// nobody bid.
+	pub fn do_settle_auction(asset: Asset) -> DispatchResult {
+		let auction = Auctions::<T>::get(asset).ok_or(Error::<T>::AuctionNotFound)?;
+
+		match auction.highest_bid.clone() {
+			Some((bidder, price)) => {
+				Self::pay_seller(
+					asset,
+					auction.payment_asset,
+					&bidder,
+					&auction.creater,
+					price,
+					true,
+				)?;
+				Self::transfer_asset_to(Self::account_id(), asset, bidder)?;
+			},
+			None => Self::transfer_asset_to(Self::account_id(), asset, auction.creater)?,
+		}
+
+		Auctions::<T>::remove(asset);
+
+		Self::deposit_event(Event::AuctionSettled(asset, auction.highest_bid));
+		Ok(())
+	}
+
 	fn do_cancel_bid(asset: Asset, bid: OrderOf<T>) -> DispatchResult {
-		let admin = Admin::<T>::get().ok_or(Error::<T>::NotSetAdmin)?;
-		<T as Config>::Currency::transfer(&admin, &bid.creater, bid.price, KeepAlive)?;
+		Self::release_payment(bid.payment_asset, &bid.creater, bid.price)?;
 		Bids::<T>::remove(asset);
 		Self::deposit_event(Event::BidCancelled(bid.creater, asset));
 		Ok(())
@@ -0,0 +1,177 @@
+// This file is part of Web3Games.
+
+// Copyright (C) 2021-2022 Web3Games https://web3games.org
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use frame_support::{
+	storage::migration::take_storage_value,
+	traits::{
+		ExistenceRequirement::AllowDeath, GetStorageVersion, PalletInfoAccess, StorageVersion,
+	},
+	weights::Weight,
+};
+
+/// Run every pending migration of the pallet storage.
+pub fn migrate<T: Config>() -> Weight {
+	let mut weight: Weight = 0;
+
+	if Pallet::<T>::on_chain_storage_version() < 1 {
+		weight = weight.saturating_add(v1::migrate::<T>());
+		StorageVersion::new(1).put::<Pallet<T>>();
+	}
+
+	if Pallet::<T>::on_chain_storage_version() < 2 {
+		weight = weight.saturating_add(v2::migrate::<T>());
+		StorageVersion::new(2).put::<Pallet<T>>();
+	}
+
+	if Pallet::<T>::on_chain_storage_version() < 3 {
+		weight = weight.saturating_add(v3::migrate::<T>());
+		StorageVersion::new(3).put::<Pallet<T>>();
+	}
+
+	weight.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+}
+
+/// Price every existing order and bid in the native currency.
+pub mod v1 {
+	use super::*;
+
+	#[derive(Decode)]
+	struct OldOrder<AccountId, Balance, BlockNumber> {
+		creater: AccountId,
+		price: Balance,
+		start: BlockNumber,
+		duration: BlockNumber,
+	}
+
+	type OldOrderOf<T> = OldOrder<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	fn upgrade<T: Config>(old: OldOrderOf<T>) -> OrderOf<T> {
+		Order {
+			creater: old.creater,
+			payment_asset: PaymentAsset::Native,
+			price: old.price,
+			start: old.start,
+			duration: old.duration,
+		}
+	}
+
+	pub fn migrate<T: Config>() -> Weight {
+		let mut count: u64 = 0;
+
+		Orders::<T>::translate::<OldOrderOf<T>, _>(|_, old| {
+			count += 1;
+			Some(upgrade::<T>(old))
+		});
+		Bids::<T>::translate::<OldOrderOf<T>, _>(|_, old| {
+			count += 1;
+			Some(upgrade::<T>(old))
+		});
+
+		log::info!(target: "runtime::marketplace", "priced {} orders and bids in the native currency", count);
+
+		T::DbWeight::get().reads_writes(count, count)
+	}
+}
+
+/// Move the funds of open bids from the admin account back to their bidders and hold them
+/// there.
+pub mod v2 {
+	use super::*;
+
+	/// Hand `price` held by the admin back to `bidder` and hold it again.
+	fn rehold<T: Config>(
+		admin: &T::AccountId,
+		payment_asset: PaymentAsset,
+		bidder: &T::AccountId,
+		price: BalanceOf<T>,
+	) -> DispatchResult {
+		match payment_asset {
+			PaymentAsset::Native => {
+				<T as Config>::Currency::transfer(admin, bidder, price, AllowDeath)?;
+				<T as Config>::Currency::reserve(bidder, price)
+			},
+			PaymentAsset::Fungible(_) => Pallet::<T>::transfer_payment(
+				payment_asset,
+				admin,
+				&Pallet::<T>::account_id(),
+				price,
+			),
+		}
+	}
+
+	pub fn migrate<T: Config>() -> Weight {
+		let admin = match Admin::<T>::get() {
+			Some(admin) => admin,
+			None => return T::DbWeight::get().reads(1),
+		};
+
+		let mut count: u64 = 0;
+		let mut failed: u64 = 0;
+
+		// A bid whose funds cannot be moved is dropped, its funds stay with the admin.
+		Bids::<T>::translate::<OrderOf<T>, _>(|_, bid| {
+			count += 1;
+			match rehold::<T>(&admin, bid.payment_asset, &bid.creater, bid.price) {
+				Ok(_) => Some(bid),
+				Err(_) => {
+					failed += 1;
+					None
+				},
+			}
+		});
+		Auctions::<T>::translate::<AuctionOf<T>, _>(|_, mut auction| {
+			if let Some((bidder, price)) = auction.highest_bid.clone() {
+				count += 1;
+				if rehold::<T>(&admin, auction.payment_asset, &bidder, price).is_err() {
+					failed += 1;
+					auction.highest_bid = None;
+				}
+			}
+			Some(auction)
+		});
+
+		log::info!(target: "runtime::marketplace", "moved the funds of {} bids to their bidders", count - failed);
+		if failed > 0 {
+			log::warn!(target: "runtime::marketplace", "dropped {} bids whose funds the admin could not return", failed);
+		}
+
+		T::DbWeight::get().reads_writes(count.saturating_mul(3) + 1, count.saturating_mul(4))
+	}
+}
+
+/// Turn the `Point` service fee, in basis points, into the `PlatformFee`.
+pub mod v3 {
+	use super::*;
+
+	pub fn migrate<T: Config>() -> Weight {
+		let pallet = <Pallet<T> as PalletInfoAccess>::name().as_bytes();
+
+		if let Some(point) = take_storage_value::<u8>(pallet, b"Point", &[]) {
+			let fee = Permill::from_parts(u32::from(point) * 100);
+			PlatformFee::<T>::put(fee);
+			log::info!(target: "runtime::marketplace", "set the platform fee to {:?}", fee);
+		}
+
+		T::DbWeight::get().reads_writes(1, 2)
+	}
+}
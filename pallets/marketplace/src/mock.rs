@@ -29,7 +29,7 @@ use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
-	Percent,
+	Percent, Permill,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -50,6 +50,7 @@ construct_runtime!(
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+		TokenFungible: pallet_token_fungible::{Pallet, Call, Storage, Event<T>},
 		TokenNonFungible: pallet_token_non_fungible::{Pallet, Call, Storage, Event<T>},
 		TokenMulti: pallet_token_multi::{Pallet, Call, Storage, Event<T>},
 		Marketplace: pallet_marketplace::{Pallet, Call, Storage,Config<T>, Event<T>},
@@ -107,6 +108,7 @@ impl pallet_timestamp::Config for Test {
 }
 
 parameter_types! {
+	pub const TokenFungiblePalletId: PalletId = PalletId(*b"w3g/tfpi");
 	pub const TokenNonFungiblePalletId: PalletId = PalletId(*b"w3g/tnfp");
 	pub const TokenMultiPalletId: PalletId = PalletId(*b"w3g/tmpi");
 	pub const StringLimit: u32 = 50;
@@ -114,6 +116,16 @@ parameter_types! {
 	pub const CreateCollectionDeposit: Balance = 500 * MILLICENTS;
 }
 
+impl pallet_token_fungible::Config for Test {
+	type Event = Event;
+	type PalletId = TokenFungiblePalletId;
+	type FungibleTokenId = u128;
+	type StringLimit = StringLimit;
+	type CreateTokenDeposit = CreateTokenDeposit;
+	type Currency = Balances;
+	type WeightInfo = ();
+}
+
 impl pallet_token_non_fungible::Config for Test {
 	type Event = Event;
 	type PalletId = TokenNonFungiblePalletId;
@@ -122,6 +134,7 @@ impl pallet_token_non_fungible::Config for Test {
 	type StringLimit = StringLimit;
 	type CreateTokenDeposit = CreateTokenDeposit;
 	type Currency = Balances;
+	type Receiver = ();
 	type WeightInfo = ();
 }
 
@@ -133,6 +146,7 @@ impl pallet_token_multi::Config for Test {
 	type StringLimit = StringLimit;
 	type CreateTokenDeposit = CreateTokenDeposit;
 	type Currency = Balances;
+	type Receiver = ();
 	type WeightInfo = ();
 }
 
@@ -140,12 +154,24 @@ parameter_types! {
 	pub const MarketplacePalletId: PalletId = PalletId(*b"w3g/mpct");
 	pub const FeesCollectorShareCut: Percent = Percent::from_percent(2);
 	pub const TreasuryAccount: AccountId = 10;
+	pub const AntiSnipingPeriod: u64 = 10;
+	pub const MaxAuctionsPerBlock: u32 = 2;
+	pub const MaxOffersPerAsset: u32 = 2;
+	pub const MaxOffersPerBlock: u32 = 2;
+	pub const MaxPlatformFee: Permill = Permill::from_percent(5);
+	pub const MaxRoyalty: Permill = Permill::from_percent(10);
 }
 
 impl pallet_marketplace::Config for Test {
 	type Event = Event;
 	type PalletId = MarketplacePalletId;
 	type Currency = Balances;
+	type AntiSnipingPeriod = AntiSnipingPeriod;
+	type MaxAuctionsPerBlock = MaxAuctionsPerBlock;
+	type MaxOffersPerAsset = MaxOffersPerAsset;
+	type MaxOffersPerBlock = MaxOffersPerBlock;
+	type MaxPlatformFee = MaxPlatformFee;
+	type MaxRoyalty = MaxRoyalty;
 }
 
 // Build genesis storage according to the mock runtime.
@@ -179,5 +205,6 @@ pub fn run_to_block(n: u64) {
 		System::set_block_number(System::block_number() + 1);
 		System::on_initialize(System::block_number());
 		Balances::on_initialize(System::block_number());
+		Marketplace::on_initialize(System::block_number());
 	}
 }
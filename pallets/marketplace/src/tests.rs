@@ -1,7 +1,10 @@
 use super::*;
 use crate::mock::*;
-use frame_support::{assert_noop, assert_ok};
-use sp_runtime::traits::UniqueSaturatedFrom;
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{GetStorageVersion, StorageVersion},
+};
+use sp_runtime::{traits::UniqueSaturatedFrom, Permill};
 
 const ADMIN: u64 = 0;
 const ALICE: u64 = 1;
@@ -26,6 +29,18 @@ fn create_non_fungible_token() {
 	assert_eq!(TokenNonFungible::owner_of(1, 2), Some(ALICE));
 }
 
+fn create_fungible_token() {
+	assert_ok!(TokenFungible::create_token(
+		Origin::signed(ADMIN),
+		1,
+		b"USDT".to_vec(),
+		b"USDT".to_vec(),
+		18
+	));
+	assert_ok!(TokenFungible::mint(Origin::signed(ADMIN), 1, BOB, 1000 * W3G));
+	assert_ok!(TokenFungible::mint(Origin::signed(ADMIN), 1, CHARLIE, 1000 * W3G));
+}
+
 #[test]
 fn create_order_should_work() {
 	new_test_ext().execute_with(|| {
@@ -33,13 +48,15 @@ fn create_order_should_work() {
 		assert_ok!(Marketplace::create_order(
 			Origin::signed(ALICE),
 			Asset::NonFungibleToken(1, 2),
+			PaymentAsset::Native,
 			100 * W3G,
 			100 * BLOCK
 		));
 		assert_eq!(
 			Marketplace::orders(Asset::NonFungibleToken(1, 2)),
 			Some(Order {
-				seller: ALICE,
+				creater: ALICE,
+				payment_asset: PaymentAsset::Native,
 				price: 100 * W3G,
 				start: 1 * BLOCK,
 				duration: 100 * BLOCK
@@ -47,3 +64,696 @@ fn create_order_should_work() {
 		)
 	})
 }
+
+#[test]
+fn create_order_with_unknown_payment_asset_should_fail() {
+	new_test_ext().execute_with(|| {
+		create_non_fungible_token();
+		assert_noop!(
+			Marketplace::create_order(
+				Origin::signed(ALICE),
+				Asset::NonFungibleToken(1, 2),
+				PaymentAsset::Fungible(1),
+				100 * W3G,
+				100 * BLOCK
+			),
+			Error::<Test>::PaymentAssetNotFound
+		);
+	})
+}
+
+#[test]
+fn execute_order_in_fungible_token_should_work() {
+	new_test_ext().execute_with(|| {
+		create_non_fungible_token();
+		create_fungible_token();
+		// 1% service fee
+		assert_ok!(Marketplace::set_platform_fee(Origin::signed(ADMIN), Permill::from_percent(1)));
+
+		assert_ok!(Marketplace::create_order(
+			Origin::signed(ALICE),
+			Asset::NonFungibleToken(1, 2),
+			PaymentAsset::Fungible(1),
+			100 * W3G,
+			100 * BLOCK
+		));
+
+		let native_before = Balances::free_balance(BOB);
+		assert_ok!(Marketplace::execute_order(Origin::signed(BOB), Asset::NonFungibleToken(1, 2)));
+
+		assert_eq!(TokenNonFungible::owner_of(1, 2), Some(BOB));
+		assert_eq!(TokenFungible::balance_of(1, BOB), 900 * W3G);
+		assert_eq!(TokenFungible::balance_of(1, ALICE), 99 * W3G);
+		assert_eq!(TokenFungible::balance_of(1, ADMIN), 1 * W3G);
+		assert_eq!(Balances::free_balance(BOB), native_before);
+	})
+}
+
+#[test]
+fn bid_in_fungible_token_should_work() {
+	new_test_ext().execute_with(|| {
+		create_non_fungible_token();
+		create_fungible_token();
+
+		assert_ok!(Marketplace::create_order(
+			Origin::signed(ALICE),
+			Asset::NonFungibleToken(1, 2),
+			PaymentAsset::Fungible(1),
+			100 * W3G,
+			100 * BLOCK
+		));
+
+		assert_noop!(
+			Marketplace::place_bid(
+				Origin::signed(BOB),
+				Asset::NonFungibleToken(1, 2),
+				PaymentAsset::Native,
+				50 * W3G,
+				10 * BLOCK
+			),
+			Error::<Test>::PaymentAssetMismatch
+		);
+
+		assert_ok!(Marketplace::place_bid(
+			Origin::signed(BOB),
+			Asset::NonFungibleToken(1, 2),
+			PaymentAsset::Fungible(1),
+			50 * W3G,
+			10 * BLOCK
+		));
+		assert_eq!(TokenFungible::balance_of(1, Marketplace::account_id()), 50 * W3G);
+
+		// a higher bid refunds the previous bidder in the same token
+		assert_ok!(Marketplace::place_bid(
+			Origin::signed(CHARLIE),
+			Asset::NonFungibleToken(1, 2),
+			PaymentAsset::Fungible(1),
+			60 * W3G,
+			10 * BLOCK
+		));
+		assert_eq!(TokenFungible::balance_of(1, BOB), 1000 * W3G);
+
+		assert_ok!(Marketplace::accept_bid(Origin::signed(ALICE), Asset::NonFungibleToken(1, 2)));
+		assert_eq!(TokenNonFungible::owner_of(1, 2), Some(CHARLIE));
+		assert_eq!(TokenFungible::balance_of(1, ALICE), 60 * W3G);
+		assert_eq!(TokenFungible::balance_of(1, CHARLIE), 940 * W3G);
+	})
+}
+
+#[test]
+fn english_auction_should_work() {
+	new_test_ext().execute_with(|| {
+		create_non_fungible_token();
+		let asset = Asset::NonFungibleToken(1, 2);
+
+		assert_ok!(Marketplace::create_auction(
+			Origin::signed(ALICE),
+			asset,
+			PaymentAsset::Native,
+			10 * W3G,
+			1 * W3G,
+			101 * BLOCK
+		));
+		assert_eq!(TokenNonFungible::owner_of(1, 2), Some(Marketplace::account_id()));
+
+		assert_noop!(
+			Marketplace::bid_auction(Origin::signed(BOB), asset, 5 * W3G),
+			Error::<Test>::TooLittlePrice
+		);
+		assert_ok!(Marketplace::bid_auction(Origin::signed(BOB), asset, 10 * W3G));
+		assert_noop!(
+			Marketplace::bid_auction(Origin::signed(CHARLIE), asset, 10 * W3G + W3G / 2),
+			Error::<Test>::NeedHigherPrice
+		);
+		assert_noop!(
+			Marketplace::cancel_auction(Origin::signed(ALICE), asset),
+			Error::<Test>::AuctionHasBids
+		);
+
+		let bob_balance = Balances::free_balance(BOB);
+		assert_ok!(Marketplace::bid_auction(Origin::signed(CHARLIE), asset, 11 * W3G));
+		// the outbid bidder is refunded
+		assert_eq!(Balances::free_balance(BOB), bob_balance + 10 * W3G);
+
+		let alice_balance = Balances::free_balance(ALICE);
+		run_to_block(101 * BLOCK);
+
+		assert_eq!(TokenNonFungible::owner_of(1, 2), Some(CHARLIE));
+		assert_eq!(Balances::free_balance(ALICE), alice_balance + 11 * W3G);
+		assert_eq!(Marketplace::auctions(asset), None);
+		System::assert_last_event(crate::mock::Event::Marketplace(crate::Event::AuctionSettled(
+			asset,
+			Some((CHARLIE, 11 * W3G)),
+		)));
+	})
+}
+
+#[test]
+fn auction_anti_sniping_should_work() {
+	new_test_ext().execute_with(|| {
+		create_non_fungible_token();
+		let asset = Asset::NonFungibleToken(1, 2);
+
+		assert_ok!(Marketplace::create_auction(
+			Origin::signed(ALICE),
+			asset,
+			PaymentAsset::Native,
+			10 * W3G,
+			1 * W3G,
+			101 * BLOCK
+		));
+
+		run_to_block(95 * BLOCK);
+		assert_ok!(Marketplace::bid_auction(Origin::signed(BOB), asset, 10 * W3G));
+		assert_eq!(Marketplace::auctions(asset).unwrap().end, 105 * BLOCK);
+		assert!(Marketplace::auction_end_time(101 * BLOCK).is_empty());
+
+		run_to_block(101 * BLOCK);
+		assert!(Marketplace::auctions(asset).is_some());
+
+		run_to_block(105 * BLOCK);
+		assert_eq!(Marketplace::auctions(asset), None);
+		assert_eq!(TokenNonFungible::owner_of(1, 2), Some(BOB));
+	})
+}
+
+#[test]
+fn auction_settlement_should_be_bounded_per_block() {
+	new_test_ext().execute_with(|| {
+		create_non_fungible_token();
+		assert_ok!(TokenNonFungible::mint(Origin::signed(ALICE), 1, ALICE, 3));
+		assert_ok!(TokenNonFungible::mint(Origin::signed(ALICE), 1, ALICE, 4));
+
+		for token_id in 2..=4 {
+			assert_ok!(Marketplace::create_auction(
+				Origin::signed(ALICE),
+				Asset::NonFungibleToken(1, token_id),
+				PaymentAsset::Native,
+				10 * W3G,
+				1 * W3G,
+				101 * BLOCK
+			));
+		}
+		// the end block is full, the last auction ends one block later
+		assert_eq!(Marketplace::auction_end_time(101 * BLOCK).len(), 2);
+		assert_eq!(Marketplace::auctions(Asset::NonFungibleToken(1, 4)).unwrap().end, 102 * BLOCK);
+
+		run_to_block(101 * BLOCK);
+		assert_eq!(TokenNonFungible::owner_of(1, 2), Some(ALICE));
+		assert_eq!(TokenNonFungible::owner_of(1, 3), Some(ALICE));
+		assert!(Marketplace::auctions(Asset::NonFungibleToken(1, 4)).is_some());
+
+		run_to_block(102 * BLOCK);
+		assert_eq!(TokenNonFungible::owner_of(1, 4), Some(ALICE));
+	})
+}
+
+#[test]
+fn listed_multi_token_should_not_be_listed_again() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TokenMulti::create_token(
+			Origin::signed(ALICE),
+			1,
+			b"https://web3games.com/".to_vec()
+		));
+		assert_ok!(TokenMulti::mint(Origin::signed(ALICE), 1, ALICE, 2, 1));
+		assert_ok!(TokenMulti::mint(Origin::signed(ALICE), 1, BOB, 2, 1));
+		let asset = Asset::MultiToken(1, 2);
+
+		assert_ok!(Marketplace::create_auction(
+			Origin::signed(ALICE),
+			asset,
+			PaymentAsset::Native,
+			10 * W3G,
+			1 * W3G,
+			101 * BLOCK
+		));
+		// another holder of the token can't replace the auction
+		assert_noop!(
+			Marketplace::create_auction(
+				Origin::signed(BOB),
+				asset,
+				PaymentAsset::Native,
+				10 * W3G,
+				1 * W3G,
+				101 * BLOCK
+			),
+			Error::<Test>::AuctionExists
+		);
+		assert_noop!(
+			Marketplace::create_order(
+				Origin::signed(BOB),
+				asset,
+				PaymentAsset::Native,
+				10 * W3G,
+				100 * BLOCK
+			),
+			Error::<Test>::AuctionExists
+		);
+		assert_noop!(
+			Marketplace::create_dutch_order(
+				Origin::signed(BOB),
+				asset,
+				PaymentAsset::Native,
+				20 * W3G,
+				10 * W3G,
+				1 * BLOCK,
+				101 * BLOCK
+			),
+			Error::<Test>::AuctionExists
+		);
+		assert_eq!(Marketplace::auctions(asset).unwrap().creater, ALICE);
+		assert_eq!(TokenMulti::balance_of(1, (2, BOB)), 1);
+
+		assert_ok!(Marketplace::cancel_auction(Origin::signed(ALICE), asset));
+		assert_ok!(Marketplace::create_order(
+			Origin::signed(BOB),
+			asset,
+			PaymentAsset::Native,
+			10 * W3G,
+			100 * BLOCK
+		));
+		assert_noop!(
+			Marketplace::create_auction(
+				Origin::signed(ALICE),
+				asset,
+				PaymentAsset::Native,
+				10 * W3G,
+				1 * W3G,
+				101 * BLOCK
+			),
+			Error::<Test>::OrderExists
+		);
+		assert_noop!(
+			Marketplace::create_order(
+				Origin::signed(ALICE),
+				asset,
+				PaymentAsset::Native,
+				10 * W3G,
+				100 * BLOCK
+			),
+			Error::<Test>::OrderExists
+		);
+		assert_eq!(TokenMulti::balance_of(1, (2, ALICE)), 1);
+		assert_eq!(TokenMulti::balance_of(1, (2, Marketplace::account_id())), 1);
+	})
+}
+
+#[test]
+fn dutch_order_should_work() {
+	new_test_ext().execute_with(|| {
+		create_non_fungible_token();
+		let asset = Asset::NonFungibleToken(1, 2);
+
+		assert_noop!(
+			Marketplace::create_dutch_order(
+				Origin::signed(ALICE),
+				asset,
+				PaymentAsset::Native,
+				10 * W3G,
+				20 * W3G,
+				11 * BLOCK,
+				111 * BLOCK
+			),
+			Error::<Test>::InvalidPrice
+		);
+		assert_ok!(Marketplace::create_dutch_order(
+			Origin::signed(ALICE),
+			asset,
+			PaymentAsset::Native,
+			20 * W3G,
+			10 * W3G,
+			11 * BLOCK,
+			111 * BLOCK
+		));
+		assert_eq!(Marketplace::get_current_price(asset), Some(20 * W3G));
+		assert_noop!(
+			Marketplace::execute_order(Origin::signed(BOB), asset),
+			Error::<Test>::DutchOrderNotStarted
+		);
+		assert_noop!(
+			Marketplace::place_bid(
+				Origin::signed(BOB),
+				asset,
+				PaymentAsset::Native,
+				15 * W3G,
+				10 * BLOCK
+			),
+			Error::<Test>::DutchOrderNoBids
+		);
+
+		// halfway through the price is halfway down
+		run_to_block(61 * BLOCK);
+		assert_eq!(Marketplace::get_current_price(asset), Some(15 * W3G));
+
+		let bob_balance = Balances::free_balance(BOB);
+		assert_ok!(Marketplace::execute_order(Origin::signed(BOB), asset));
+		assert_eq!(Balances::free_balance(BOB), bob_balance - 15 * W3G);
+		assert_eq!(TokenNonFungible::owner_of(1, 2), Some(BOB));
+		assert_eq!(Marketplace::dutch_orders(asset), None);
+		assert_eq!(Marketplace::get_current_price(asset), None);
+	})
+}
+
+#[test]
+fn dutch_price_should_stop_at_end_price() {
+	new_test_ext().execute_with(|| {
+		let dutch_order = DutchOrder {
+			start_price: 20 * W3G,
+			end_price: 10 * W3G,
+			start_block: 11 * BLOCK,
+			end_block: 111 * BLOCK,
+		};
+
+		assert_eq!(Marketplace::dutch_price(&dutch_order, 1 * BLOCK), 20 * W3G);
+		assert_eq!(Marketplace::dutch_price(&dutch_order, 36 * BLOCK), 17 * W3G + W3G / 2);
+		assert_eq!(Marketplace::dutch_price(&dutch_order, 111 * BLOCK), 10 * W3G);
+		assert_eq!(Marketplace::dutch_price(&dutch_order, 500 * BLOCK), 10 * W3G);
+	})
+}
+
+#[test]
+fn offer_on_unlisted_asset_should_work() {
+	new_test_ext().execute_with(|| {
+		create_non_fungible_token();
+		let asset = Asset::NonFungibleToken(1, 2);
+
+		assert_ok!(Marketplace::make_offer(
+			Origin::signed(BOB),
+			asset,
+			PaymentAsset::Native,
+			10 * W3G,
+			50 * BLOCK
+		));
+		assert_noop!(
+			Marketplace::make_offer(
+				Origin::signed(BOB),
+				asset,
+				PaymentAsset::Native,
+				11 * W3G,
+				50 * BLOCK
+			),
+			Error::<Test>::OfferExists
+		);
+		assert_ok!(Marketplace::make_offer(
+			Origin::signed(CHARLIE),
+			asset,
+			PaymentAsset::Native,
+			12 * W3G,
+			50 * BLOCK
+		));
+		assert_eq!(Balances::reserved_balance(BOB), 10 * W3G);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 12 * W3G);
+		assert_eq!(Marketplace::offer_count(asset), 2);
+
+		assert_noop!(
+			Marketplace::accept_offer(Origin::signed(BOB), asset, CHARLIE),
+			pallet_token_non_fungible::Error::<Test>::NotOwnerOrApproved
+		);
+
+		let alice_balance = Balances::free_balance(ALICE);
+		assert_ok!(Marketplace::accept_offer(Origin::signed(ALICE), asset, CHARLIE));
+		assert_eq!(TokenNonFungible::owner_of(1, 2), Some(CHARLIE));
+		assert_eq!(Balances::free_balance(ALICE), alice_balance + 12 * W3G);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+		assert_eq!(Marketplace::offer_count(asset), 1);
+
+		assert_ok!(Marketplace::cancel_offer(Origin::signed(BOB), asset));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Marketplace::offers(asset, BOB), None);
+	})
+}
+
+#[test]
+fn offer_outbid_and_expiry_should_release_funds() {
+	new_test_ext().execute_with(|| {
+		create_non_fungible_token();
+		let asset = Asset::NonFungibleToken(1, 2);
+
+		assert_ok!(Marketplace::make_offer(
+			Origin::signed(BOB),
+			asset,
+			PaymentAsset::Native,
+			10 * W3G,
+			50 * BLOCK
+		));
+		assert_ok!(Marketplace::make_offer(
+			Origin::signed(CHARLIE),
+			asset,
+			PaymentAsset::Native,
+			12 * W3G,
+			60 * BLOCK
+		));
+
+		// the asset is full, the lowest offer has to be beaten
+		assert_noop!(
+			Marketplace::make_offer(
+				Origin::signed(ADMIN),
+				asset,
+				PaymentAsset::Native,
+				9 * W3G,
+				50 * BLOCK
+			),
+			Error::<Test>::NeedHigherPrice
+		);
+		assert_ok!(Marketplace::make_offer(
+			Origin::signed(ADMIN),
+			asset,
+			PaymentAsset::Native,
+			11 * W3G,
+			50 * BLOCK
+		));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Marketplace::offers(asset, BOB), None);
+
+		run_to_block(50 * BLOCK);
+		assert_eq!(Balances::reserved_balance(ADMIN), 0);
+		assert_eq!(Marketplace::offers(asset, ADMIN), None);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 12 * W3G);
+
+		run_to_block(60 * BLOCK);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+		assert_eq!(Marketplace::offer_count(asset), 0);
+		System::assert_last_event(crate::mock::Event::Marketplace(crate::Event::OfferExpired(
+			CHARLIE, asset,
+		)));
+	})
+}
+
+#[test]
+fn accept_offer_on_listed_asset_should_work() {
+	new_test_ext().execute_with(|| {
+		create_non_fungible_token();
+		create_fungible_token();
+		let asset = Asset::NonFungibleToken(1, 2);
+
+		assert_ok!(Marketplace::create_order(
+			Origin::signed(ALICE),
+			asset,
+			PaymentAsset::Native,
+			100 * W3G,
+			100 * BLOCK
+		));
+		assert_ok!(Marketplace::make_offer(
+			Origin::signed(BOB),
+			asset,
+			PaymentAsset::Fungible(1),
+			80 * W3G,
+			50 * BLOCK
+		));
+		assert_eq!(TokenFungible::balance_of(1, Marketplace::account_id()), 80 * W3G);
+
+		assert_noop!(
+			Marketplace::accept_offer(Origin::signed(CHARLIE), asset, BOB),
+			Error::<Test>::NotSeller
+		);
+		assert_ok!(Marketplace::accept_offer(Origin::signed(ALICE), asset, BOB));
+
+		assert_eq!(TokenNonFungible::owner_of(1, 2), Some(BOB));
+		assert_eq!(TokenFungible::balance_of(1, ALICE), 80 * W3G);
+		assert_eq!(TokenFungible::balance_of(1, Marketplace::account_id()), 0);
+		assert_eq!(Marketplace::orders(asset), None);
+	})
+}
+
+#[test]
+fn bid_should_reserve_funds() {
+	new_test_ext().execute_with(|| {
+		create_non_fungible_token();
+		let asset = Asset::NonFungibleToken(1, 2);
+
+		assert_ok!(Marketplace::create_order(
+			Origin::signed(ALICE),
+			asset,
+			PaymentAsset::Native,
+			100 * W3G,
+			100 * BLOCK
+		));
+		assert_ok!(Marketplace::place_bid(
+			Origin::signed(BOB),
+			asset,
+			PaymentAsset::Native,
+			50 * W3G,
+			10 * BLOCK
+		));
+		assert_eq!(Balances::reserved_balance(BOB), 50 * W3G);
+
+		assert_ok!(Marketplace::place_bid(
+			Origin::signed(CHARLIE),
+			asset,
+			PaymentAsset::Native,
+			60 * W3G,
+			10 * BLOCK
+		));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 60 * W3G);
+
+		let admin_balance = Balances::free_balance(ADMIN);
+		let alice_balance = Balances::free_balance(ALICE);
+		assert_ok!(Marketplace::accept_bid(Origin::signed(ALICE), asset));
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+		assert_eq!(Balances::free_balance(ALICE), alice_balance + 60 * W3G);
+		assert_eq!(Balances::free_balance(ADMIN), admin_balance);
+		assert_eq!(TokenNonFungible::owner_of(1, 2), Some(CHARLIE));
+	})
+}
+
+#[test]
+fn migration_should_hold_bids_on_bidders() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(1).put::<Marketplace>();
+
+		// a bid placed before the migration has its funds at the admin
+		assert_ok!(Balances::transfer(Origin::signed(BOB), ADMIN, 50 * W3G));
+		let bid = Order {
+			creater: BOB,
+			payment_asset: PaymentAsset::Native,
+			price: 50 * W3G,
+			start: 1 * BLOCK,
+			duration: 10 * BLOCK,
+		};
+		Bids::<Test>::insert(Asset::NonFungibleToken(1, 2), bid.clone());
+
+		crate::migrations::migrate::<Test>();
+
+		assert_eq!(Balances::reserved_balance(BOB), 50 * W3G);
+		assert_eq!(Balances::free_balance(ADMIN), 100 * W3G);
+		assert_eq!(Bids::<Test>::get(Asset::NonFungibleToken(1, 2)), Some(bid));
+		assert_eq!(Marketplace::on_chain_storage_version(), 3);
+	})
+}
+
+#[test]
+fn set_royalty_should_work() {
+	new_test_ext().execute_with(|| {
+		create_non_fungible_token();
+		let collection = Collection::NonFungibleToken(1);
+
+		assert_noop!(
+			Marketplace::set_royalty(
+				Origin::signed(BOB),
+				collection,
+				BOB,
+				Permill::from_percent(5)
+			),
+			Error::<Test>::NotCollectionOwner
+		);
+		assert_noop!(
+			Marketplace::set_royalty(
+				Origin::signed(ALICE),
+				Collection::NonFungibleToken(9),
+				ALICE,
+				Permill::from_percent(5)
+			),
+			Error::<Test>::NotCollectionOwner
+		);
+		assert_noop!(
+			Marketplace::set_royalty(
+				Origin::signed(ALICE),
+				collection,
+				ALICE,
+				Permill::from_percent(11)
+			),
+			Error::<Test>::RoyaltyTooHigh
+		);
+		assert_ok!(Marketplace::set_royalty(
+			Origin::signed(ALICE),
+			collection,
+			CHARLIE,
+			Permill::from_percent(5)
+		));
+		assert_eq!(
+			Marketplace::royalties(collection),
+			Some(Royalty { recipient: CHARLIE, rate: Permill::from_percent(5) })
+		);
+
+		assert_ok!(Marketplace::set_royalty(
+			Origin::signed(ALICE),
+			collection,
+			CHARLIE,
+			Permill::zero()
+		));
+		assert_eq!(Marketplace::royalties(collection), None);
+	})
+}
+
+#[test]
+fn sale_should_pay_platform_fee_and_royalty() {
+	new_test_ext().execute_with(|| {
+		create_non_fungible_token();
+		let asset = Asset::NonFungibleToken(1, 2);
+		const TREASURY: u64 = 10;
+
+		assert_noop!(
+			Marketplace::set_platform_fee(Origin::signed(ADMIN), Permill::from_percent(6)),
+			Error::<Test>::FeeTooHigh
+		);
+		assert_ok!(Marketplace::set_platform_fee(Origin::signed(ADMIN), Permill::from_percent(2)));
+		assert_ok!(Marketplace::set_fee_recipient(Origin::signed(ADMIN), Some(TREASURY)));
+		assert_ok!(Marketplace::set_royalty(
+			Origin::signed(ALICE),
+			Collection::NonFungibleToken(1),
+			CHARLIE,
+			Permill::from_percent(5)
+		));
+
+		assert_ok!(Marketplace::create_order(
+			Origin::signed(ALICE),
+			asset,
+			PaymentAsset::Native,
+			100 * W3G,
+			100 * BLOCK
+		));
+
+		let alice_balance = Balances::free_balance(ALICE);
+		let charlie_balance = Balances::free_balance(CHARLIE);
+		assert_ok!(Marketplace::execute_order(Origin::signed(BOB), asset));
+
+		assert_eq!(Balances::free_balance(TREASURY), 2 * W3G);
+		assert_eq!(Balances::free_balance(CHARLIE), charlie_balance + 5 * W3G);
+		assert_eq!(Balances::free_balance(ALICE), alice_balance + 93 * W3G);
+		System::assert_has_event(crate::mock::Event::Marketplace(crate::Event::PaymentSettled(
+			asset,
+			PaymentAsset::Native,
+			PaymentBreakdown {
+				seller: ALICE,
+				seller_amount: 93 * W3G,
+				platform_fee: Some((TREASURY, 2 * W3G)),
+				royalty: Some((CHARLIE, 5 * W3G)),
+			},
+		)));
+	})
+}
+
+#[test]
+fn migration_should_convert_point_to_platform_fee() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(2).put::<Marketplace>();
+		frame_support::storage::migration::put_storage_value(b"Marketplace", b"Point", &[], 100u8);
+
+		crate::migrations::migrate::<Test>();
+
+		assert_eq!(Marketplace::platform_fee(), Permill::from_percent(1));
+		assert_eq!(Marketplace::on_chain_storage_version(), 3);
+	})
+}
@@ -0,0 +1,150 @@
+// This file is part of Web3Games.
+
+// Copyright (C) 2021-2022 Web3Games https://web3games.org
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, DeserializeOwned, Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+pub use pallet_tokens_rpc_runtime_api::TokensRuntimeApi;
+use primitives::Balance;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::{marker::PhantomData, sync::Arc};
+
+pub struct TokensRpc<Client, Block> {
+	client: Arc<Client>,
+	_marker: PhantomData<Block>,
+}
+
+impl<Client, Block> TokensRpc<Client, Block> {
+	pub fn new(client: Arc<Client>) -> Self {
+		Self { client, _marker: PhantomData }
+	}
+}
+
+#[rpc(client, server)]
+pub trait TokensRpcApi<BlockHash, AccountId> {
+	#[method(name = "tokens_fungibleBalances")]
+	fn fungible_balances(
+		&self,
+		who: AccountId,
+		start: Option<u128>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(u128, Balance)>>;
+
+	#[method(name = "tokens_nonFungibleTokens")]
+	fn non_fungible_tokens(
+		&self,
+		who: AccountId,
+		start: Option<(u128, u128)>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(u128, u128)>>;
+
+	#[method(name = "tokens_multiTokens")]
+	fn multi_tokens(
+		&self,
+		who: AccountId,
+		start: Option<(u128, u128)>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(u128, u128, Balance)>>;
+}
+
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+#[async_trait]
+impl<C, Block, AccountId> TokensRpcApiServer<<Block as BlockT>::Hash, AccountId>
+	for TokensRpc<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: TokensRuntimeApi<Block, AccountId>,
+	AccountId: Codec + DeserializeOwned + Send + Sync + 'static,
+{
+	fn fungible_balances(
+		&self,
+		who: AccountId,
+		start: Option<u128>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(u128, Balance)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.fungible_balances(&at, who, start, limit)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn non_fungible_tokens(
+		&self,
+		who: AccountId,
+		start: Option<(u128, u128)>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(u128, u128)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.non_fungible_tokens(&at, who, start, limit)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn multi_tokens(
+		&self,
+		who: AccountId,
+		start: Option<(u128, u128)>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(u128, u128, Balance)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.multi_tokens(&at, who, start, limit).map_err(runtime_error_into_rpc_err)
+	}
+}
+
+fn runtime_error_into_rpc_err(err: impl std::fmt::Display) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		"error in tokens api",
+		Some(err.to_string()),
+	))
+	.into()
+}
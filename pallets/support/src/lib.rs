@@ -18,8 +18,10 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use primitives::TokenIndex;
+use frame_support::weights::Weight;
+use primitives::{Balance, TokenIndex};
 use sp_core::H160;
+use sp_runtime::{DispatchError, DispatchResult, FixedU128};
 use sp_std::prelude::*;
 
 /// This trait ensure we can convert EVM Address to FungibleTokenId,
@@ -72,4 +74,139 @@ pub trait MultiMetadata {
 	type TokenId;
 
 	fn uri(id: Self::MultiTokenId, token_id: Self::TokenId) -> Vec<u8>;
+	fn total_supply(id: Self::MultiTokenId, token_id: Self::TokenId) -> Balance;
+	fn exists(id: Self::MultiTokenId, token_id: Self::TokenId) -> bool;
+}
+
+/// Asked whether a contract recipient accepts a non-fungible token transfer.
+pub trait NonFungibleReceiver<AccountId, NonFungibleTokenId, TokenId> {
+	/// Return `false` to reject the transfer. Accounts that are not contracts always accept.
+	fn on_received(
+		operator: &AccountId,
+		from: &AccountId,
+		to: &AccountId,
+		id: NonFungibleTokenId,
+		token_id: TokenId,
+	) -> bool;
+
+	/// The most weight `on_received` can use, charged by every transfer that asks it.
+	fn max_weight() -> Weight;
+}
+
+impl<AccountId, NonFungibleTokenId, TokenId>
+	NonFungibleReceiver<AccountId, NonFungibleTokenId, TokenId> for ()
+{
+	fn max_weight() -> Weight {
+		0
+	}
+
+	fn on_received(
+		_operator: &AccountId,
+		_from: &AccountId,
+		_to: &AccountId,
+		_id: NonFungibleTokenId,
+		_token_id: TokenId,
+	) -> bool {
+		true
+	}
+}
+
+/// Asked whether a contract recipient accepts a multi token transfer.
+pub trait MultiReceiver<AccountId, MultiTokenId, TokenId> {
+	/// Return `false` to reject the transfer. Accounts that are not contracts always accept.
+	fn on_received(
+		operator: &AccountId,
+		from: &AccountId,
+		to: &AccountId,
+		id: MultiTokenId,
+		token_id: TokenId,
+		amount: Balance,
+	) -> bool;
+
+	/// Return `false` to reject the batch transfer.
+	fn on_batch_received(
+		operator: &AccountId,
+		from: &AccountId,
+		to: &AccountId,
+		id: MultiTokenId,
+		token_ids: &[TokenId],
+		amounts: &[Balance],
+	) -> bool;
+
+	/// The most weight `on_received` or `on_batch_received` can use, charged by every transfer
+	/// that asks them.
+	fn max_weight() -> Weight;
+}
+
+impl<AccountId, MultiTokenId, TokenId> MultiReceiver<AccountId, MultiTokenId, TokenId> for () {
+	fn max_weight() -> Weight {
+		0
+	}
+
+	fn on_received(
+		_operator: &AccountId,
+		_from: &AccountId,
+		_to: &AccountId,
+		_id: MultiTokenId,
+		_token_id: TokenId,
+		_amount: Balance,
+	) -> bool {
+		true
+	}
+
+	fn on_batch_received(
+		_operator: &AccountId,
+		_from: &AccountId,
+		_to: &AccountId,
+		_id: MultiTokenId,
+		_token_ids: &[TokenId],
+		_amounts: &[Balance],
+	) -> bool {
+		true
+	}
+}
+
+/// A manipulation resistant price source, such as the time weighted average prices of the
+/// exchange pools.
+pub trait PriceOracle<FungibleTokenId, BlockNumber> {
+	/// The average price of `token_a` in `token_b` over the last `window` blocks, from the pool
+	/// of the pair with `fee`. `None` when the pool has no observation that old.
+	fn consult(
+		token_a: FungibleTokenId,
+		token_b: FungibleTokenId,
+		fee: u32,
+		window: BlockNumber,
+	) -> Option<FixedU128>;
+}
+
+/// The constant product pools of an exchange, for pallets which seed them with liquidity.
+pub trait LiquidityProvider<AccountId, FungibleTokenId> {
+	/// Create the pool of `token_a` and `token_b` with the fee tier `fee` unless it exists,
+	/// with `who` paying for it.
+	fn ensure_pool(
+		who: &AccountId,
+		token_a: FungibleTokenId,
+		token_b: FungibleTokenId,
+		fee: u32,
+	) -> DispatchResult;
+
+	/// The LP token of the pool of `token_a` and `token_b` with `fee`.
+	fn lp_token(
+		token_a: FungibleTokenId,
+		token_b: FungibleTokenId,
+		fee: u32,
+	) -> Option<FungibleTokenId>;
+
+	/// Deposit at most `amounts` of `token_a` and `token_b` from `who` at the price of the pool,
+	/// minting the liquidity to `to`. Fails if the price of the pool would take less than
+	/// `min_amounts` of them. Returns the amounts deposited and the liquidity.
+	fn add_liquidity(
+		who: &AccountId,
+		token_a: FungibleTokenId,
+		token_b: FungibleTokenId,
+		fee: u32,
+		amounts: (Balance, Balance),
+		min_amounts: (Balance, Balance),
+		to: &AccountId,
+	) -> Result<(Balance, Balance, Balance), DispatchError>;
 }
@@ -20,7 +20,7 @@
 
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
-	dispatch::DispatchResult,
+	dispatch::{DispatchError, DispatchResult},
 	ensure,
 	traits::{Currency, Get, ReservableCurrency},
 	BoundedVec, PalletId,
@@ -29,7 +29,7 @@ use pallet_support::FungibleMetadata;
 use primitives::Balance;
 use scale_info::TypeInfo;
 use sp_runtime::{
-	traits::{AtLeast32BitUnsigned, TrailingZeroInput},
+	traits::{AtLeast32BitUnsigned, Bounded, One, TrailingZeroInput, Zero},
 	RuntimeDebug,
 };
 use sp_std::prelude::*;
@@ -39,6 +39,7 @@ mod benchmarking;
 
 pub use pallet::*;
 
+pub mod migrations;
 pub mod weights;
 pub use weights::WeightInfo;
 
@@ -66,8 +67,12 @@ pub mod pallet {
 	use frame_support::{dispatch::DispatchResult, pallet_prelude::*};
 	use frame_system::pallet_prelude::*;
 
+	/// The current storage version.
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
@@ -131,6 +136,18 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// Reverse index of `Balances`: the tokens each account holds a non-zero balance of.
+	#[pallet::storage]
+	pub(super) type AccountTokens<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::FungibleTokenId,
+		(),
+		OptionQuery,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -154,10 +171,15 @@ pub mod pallet {
 		InsufficientTokens,
 		ConfuseBehavior,
 		ApproveToCurrentOwner,
+		ReservedId,
 	}
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate::<T>()
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -245,12 +267,45 @@ impl<T: Config> Pallet<T> {
 		Tokens::<T>::get(id).unwrap().total_supply
 	}
 
+	/// The first id of the range kept for tokens created by other pallets, such as the LP tokens
+	/// of the exchange: ids with the highest bit set.
+	pub fn reserved_token_id_start() -> T::FungibleTokenId {
+		T::FungibleTokenId::max_value() / 2u32.into() + One::one()
+	}
+
+	pub fn is_reserved_token_id(id: T::FungibleTokenId) -> bool {
+		id >= Self::reserved_token_id_start()
+	}
+
 	pub fn do_create_token(
 		who: &T::AccountId,
 		id: T::FungibleTokenId,
 		name: Vec<u8>,
 		symbol: Vec<u8>,
 		decimals: u8,
+	) -> DispatchResult {
+		ensure!(!Self::is_reserved_token_id(id), Error::<T>::ReservedId);
+		Self::insert_token(who, id, name, symbol, decimals)
+	}
+
+	/// Create a token with an id of the reserved range, which `do_create_token` rejects.
+	pub fn do_create_reserved_token(
+		who: &T::AccountId,
+		id: T::FungibleTokenId,
+		name: Vec<u8>,
+		symbol: Vec<u8>,
+		decimals: u8,
+	) -> DispatchResult {
+		ensure!(Self::is_reserved_token_id(id), Error::<T>::InvalidId);
+		Self::insert_token(who, id, name, symbol, decimals)
+	}
+
+	fn insert_token(
+		who: &T::AccountId,
+		id: T::FungibleTokenId,
+		name: Vec<u8>,
+		symbol: Vec<u8>,
+		decimals: u8,
 	) -> DispatchResult {
 		let bounded_name: BoundedVec<u8, T::StringLimit> =
 			name.clone().try_into().map_err(|_| Error::<T>::BadMetadata)?;
@@ -406,10 +461,12 @@ impl<T: Config> Pallet<T> {
 		to: &T::AccountId,
 		amount: Balance,
 	) -> DispatchResult {
-		Balances::<T>::try_mutate(id, to, |balance| -> DispatchResult {
-			*balance = balance.checked_add(amount).ok_or(Error::<T>::NumOverflow)?;
-			Ok(())
-		})?;
+		let new_balance =
+			Balances::<T>::try_mutate(id, to, |balance| -> Result<Balance, DispatchError> {
+				*balance = balance.checked_add(amount).ok_or(Error::<T>::NumOverflow)?;
+				Ok(*balance)
+			})?;
+		Self::update_account_index(id, to, new_balance);
 
 		Ok(())
 	}
@@ -419,14 +476,43 @@ impl<T: Config> Pallet<T> {
 		from: &T::AccountId,
 		amount: Balance,
 	) -> DispatchResult {
-		Balances::<T>::try_mutate(id, from, |balance| -> DispatchResult {
-			*balance = balance.checked_sub(amount).ok_or(Error::<T>::NumOverflow)?;
-			Ok(())
-		})?;
+		let new_balance =
+			Balances::<T>::try_mutate(id, from, |balance| -> Result<Balance, DispatchError> {
+				*balance = balance.checked_sub(amount).ok_or(Error::<T>::NumOverflow)?;
+				Ok(*balance)
+			})?;
+		Self::update_account_index(id, from, new_balance);
 
 		Ok(())
 	}
 
+	fn update_account_index(id: T::FungibleTokenId, who: &T::AccountId, balance: Balance) {
+		if balance.is_zero() {
+			AccountTokens::<T>::remove(who, id);
+		} else {
+			AccountTokens::<T>::insert(who, id, ());
+		}
+	}
+
+	/// The non-zero balances of `who`, at most `limit` of them, starting after the token `start`.
+	pub fn balances_of(
+		who: &T::AccountId,
+		start: Option<T::FungibleTokenId>,
+		limit: u32,
+	) -> Vec<(T::FungibleTokenId, Balance)> {
+		let tokens = match start {
+			Some(start) => AccountTokens::<T>::iter_key_prefix_from(
+				who,
+				AccountTokens::<T>::hashed_key_for(who, start),
+			),
+			None => AccountTokens::<T>::iter_key_prefix(who),
+		};
+		tokens
+			.take(limit as usize)
+			.map(|id| (id, Balances::<T>::get(id, who)))
+			.collect()
+	}
+
 	fn maybe_check_permission(id: T::FungibleTokenId, who: &T::AccountId) -> DispatchResult {
 		let token = Tokens::<T>::get(id);
 		ensure!(*who == token.unwrap().owner, Error::<T>::NoPermission);
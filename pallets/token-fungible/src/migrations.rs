@@ -0,0 +1,56 @@
+// This file is part of Web3Games.
+
+// Copyright (C) 2021-2022 Web3Games https://web3games.org
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use frame_support::{
+	traits::{GetStorageVersion, StorageVersion},
+	weights::Weight,
+};
+
+/// Run every pending migration of the pallet storage.
+pub fn migrate<T: Config>() -> Weight {
+	let mut weight: Weight = 0;
+
+	if Pallet::<T>::on_chain_storage_version() < 1 {
+		weight = weight.saturating_add(v1::migrate::<T>());
+		StorageVersion::new(1).put::<Pallet<T>>();
+	}
+
+	weight.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+}
+
+/// Build the `AccountTokens` index from the existing balances.
+pub mod v1 {
+	use super::*;
+
+	pub fn migrate<T: Config>() -> Weight {
+		let mut reads: u64 = 0;
+		let mut writes: u64 = 0;
+		for (id, who, balance) in Balances::<T>::iter() {
+			reads += 1;
+			if !balance.is_zero() {
+				AccountTokens::<T>::insert(who, id, ());
+				writes += 1;
+			}
+		}
+
+		log::info!(target: "runtime::token-fungible", "indexed {} fungible balances", writes);
+
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+}
@@ -65,6 +65,52 @@ fn create_token_should_not_work() {
 	})
 }
 
+#[test]
+fn create_token_in_reserved_range_should_not_work() {
+	new_test_ext().execute_with(|| {
+		let start = TokenFungible::reserved_token_id_start();
+		assert_eq!(start, 1 << 31);
+		for id in [start, start + 1, u32::MAX] {
+			assert_noop!(
+				TokenFungible::create_token(
+					Origin::signed(ALICE),
+					id,
+					b"W3G".to_vec(),
+					b"W3G".to_vec(),
+					18
+				),
+				Error::<Test>::ReservedId
+			);
+		}
+		assert_ok!(TokenFungible::create_token(
+			Origin::signed(ALICE),
+			start - 1,
+			b"W3G".to_vec(),
+			b"W3G".to_vec(),
+			18
+		));
+
+		assert_noop!(
+			TokenFungible::do_create_reserved_token(
+				&ALICE,
+				1,
+				b"W3G".to_vec(),
+				b"W3G".to_vec(),
+				18
+			),
+			Error::<Test>::InvalidId
+		);
+		assert_ok!(TokenFungible::do_create_reserved_token(
+			&ALICE,
+			start,
+			b"W3G".to_vec(),
+			b"W3G".to_vec(),
+			18
+		));
+		assert_eq!(TokenFungible::exists(start), true);
+	})
+}
+
 #[test]
 fn mint_should_work() {
 	new_test_ext().execute_with(|| {
@@ -279,3 +325,35 @@ fn transfer_from_should_not_work() {
 		assert_eq!(TokenFungible::total_supply(1), 100);
 	})
 }
+
+#[test]
+fn balances_of_should_work() {
+	new_test_ext().execute_with(|| {
+		for id in 1..=3 {
+			assert_ok!(TokenFungible::create_token(
+				Origin::signed(ALICE),
+				id,
+				b"W3G".to_vec(),
+				b"W3G".to_vec(),
+				18
+			));
+			assert_ok!(TokenFungible::mint(Origin::signed(ALICE), id, ALICE, 100 * id as Balance));
+		}
+
+		let mut balances = TokenFungible::balances_of(&ALICE, None, 10);
+		balances.sort();
+		assert_eq!(balances, vec![(1, 100), (2, 200), (3, 300)]);
+
+		let first = TokenFungible::balances_of(&ALICE, None, 2);
+		assert_eq!(first.len(), 2);
+		let rest = TokenFungible::balances_of(&ALICE, Some(first[1].0), 10);
+		assert_eq!(rest.len(), 1);
+		assert!(!first.contains(&rest[0]));
+
+		assert_ok!(TokenFungible::transfer(Origin::signed(ALICE), 2, BOB, 200));
+		let mut balances = TokenFungible::balances_of(&ALICE, None, 10);
+		balances.sort();
+		assert_eq!(balances, vec![(1, 100), (3, 300)]);
+		assert_eq!(TokenFungible::balances_of(&BOB, None, 10), vec![(2, 200)]);
+	})
+}
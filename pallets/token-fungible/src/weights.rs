@@ -65,10 +65,11 @@ impl<T: frame_system::Config> WeightInfo for W3GWeight<T> {
 	}
 	// Storage: TokenFungible Tokens (r:1 w:1)
 	// Storage: TokenFungible Balances (r:1 w:1)
+	// Storage: TokenFungible AccountTokens (r:0 w:1)
 	fn mint() -> Weight {
 		(20_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
 	// Storage: TokenFungible Balances (r:1 w:0)
 	// Storage: TokenFungible Allowances (r:1 w:1)
@@ -79,23 +80,26 @@ impl<T: frame_system::Config> WeightInfo for W3GWeight<T> {
 	}
 	// Storage: TokenFungible Tokens (r:1 w:1)
 	// Storage: TokenFungible Balances (r:1 w:1)
+	// Storage: TokenFungible AccountTokens (r:0 w:1)
 	fn burn() -> Weight {
 		(19_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
 	// Storage: TokenFungible Balances (r:2 w:2)
+	// Storage: TokenFungible AccountTokens (r:0 w:2)
 	fn transfer() -> Weight {
 		(20_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
 	// Storage: TokenFungible Allowances (r:1 w:1)
 	// Storage: TokenFungible Balances (r:2 w:2)
+	// Storage: TokenFungible AccountTokens (r:0 w:2)
 	fn transfer_from() -> Weight {
 		(23_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(3 as Weight))
-			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
 	}
 }
 
@@ -109,7 +113,7 @@ impl WeightInfo for () {
 	fn mint() -> Weight {
 		(20_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 	}
 	fn approve() -> Weight {
 		(18_000_000 as Weight)
@@ -119,16 +123,16 @@ impl WeightInfo for () {
 	fn burn() -> Weight {
 		(19_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 	}
 	fn transfer() -> Weight {
 		(20_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
 	}
 	fn transfer_from() -> Weight {
 		(23_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
 	}
 }
@@ -49,6 +49,24 @@ benchmarks! {
 		let _ = TokenMulti::<T>::mint_batch(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice.clone())),1u32.into(), alice.clone(), vec![1u32.into(), 2u32.into(), 3u32.into(), 4u32.into(), 5u32.into()], vec![10u128; 5]);
 	}: _(RawOrigin::Signed(alice), 1u32.into(), vec![1u32.into(), 2u32.into(), 3u32.into(), 4u32.into(), 5u32.into()], vec![5u128; 5])
 
+	burn_from {
+		let alice: T::AccountId = account("alice", 0, SEED);
+		let bob: T::AccountId = account("bob", 0, SEED);
+
+		let _ = TokenMulti::<T>::create_token(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice.clone())), 1u32.into(), vec![0u8; 20]);
+		let _ = TokenMulti::<T>::mint(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice.clone())), 1u32.into(), alice.clone(), 1u32.into(), 10u128);
+		let _ = TokenMulti::<T>::set_approval_for_all(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice.clone())), 1u32.into(), bob.clone(), true);
+	}: _(RawOrigin::Signed(bob), 1u32.into(), alice.clone(), 1u32.into(), 5u128)
+
+	burn_batch_from {
+		let alice: T::AccountId = account("alice", 0, SEED);
+		let bob: T::AccountId = account("bob", 0, SEED);
+
+		let _ = TokenMulti::<T>::create_token(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice.clone())), 1u32.into(), vec![0u8; 20]);
+		let _ = TokenMulti::<T>::mint_batch(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice.clone())), 1u32.into(), alice.clone(), vec![1u32.into(), 2u32.into(), 3u32.into(), 4u32.into(), 5u32.into()], vec![10u128; 5]);
+		let _ = TokenMulti::<T>::set_approval_for_all(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice.clone())), 1u32.into(), bob.clone(), true);
+	}: _(RawOrigin::Signed(bob), 1u32.into(), alice.clone(), vec![1u32.into(), 2u32.into(), 3u32.into(), 4u32.into(), 5u32.into()], vec![5u128; 5])
+
 	transfer_from {
 		let alice: T::AccountId = account("alice", 0, SEED);
 		let bob: T::AccountId = account("bob", 0, SEED);
@@ -64,6 +82,19 @@ benchmarks! {
 		let _ = TokenMulti::<T>::create_token(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice.clone())), 1u32.into(), vec![0u8; 20]);
 		let _ = TokenMulti::<T>::mint_batch(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice.clone())), 1u32.into(), alice.clone(), vec![1u32.into(), 2u32.into(), 3u32.into(), 4u32.into(), 5u32.into()], vec![10u128; 5]);
 	}: _(RawOrigin::Signed(alice.clone()), 1u32.into(), alice.clone(), bob.clone(), vec![1u32.into(), 2u32.into(), 3u32.into(), 4u32.into(), 5u32.into()], vec![5u128; 5])
+
+	set_max_supply {
+		let alice: T::AccountId = account("alice", 0, SEED);
+
+		let _ = TokenMulti::<T>::create_token(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice.clone())), 1u32.into(), vec![0u8; 20]);
+		let _ = TokenMulti::<T>::mint(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice.clone())), 1u32.into(), alice.clone(), 1u32.into(), 10u128);
+	}: _(RawOrigin::Signed(alice), 1u32.into(), 1u32.into(), Some(100u128))
+
+	set_uri {
+		let alice: T::AccountId = account("alice", 0, SEED);
+
+		let _ = TokenMulti::<T>::create_token(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice.clone())), 1u32.into(), vec![0u8; 20]);
+	}: _(RawOrigin::Signed(alice), 1u32.into(), 1u32.into(), Some(vec![0u8; 20]))
 }
 
 impl_benchmark_test_suite!(TokenMulti, crate::mock::new_test_ext(), crate::mock::Test,);
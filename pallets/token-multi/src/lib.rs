@@ -25,10 +25,13 @@ use frame_support::{
 	traits::{Currency, Get, ReservableCurrency},
 	BoundedVec, PalletId,
 };
-use pallet_support::MultiMetadata;
+use pallet_support::{MultiMetadata, MultiReceiver};
 use primitives::Balance;
 use scale_info::TypeInfo;
-use sp_runtime::{traits::AtLeast32BitUnsigned, RuntimeDebug};
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, UniqueSaturatedInto, Zero},
+	RuntimeDebug,
+};
 use sp_std::prelude::*;
 
 pub use pallet::*;
@@ -36,6 +39,7 @@ pub use pallet::*;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migrations;
 pub mod weights;
 pub use weights::WeightInfo;
 
@@ -45,6 +49,9 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+const ID_PLACEHOLDER: &[u8] = b"{id}";
+const HEX_CHARS: &[u8; 16] = b"0123456789abcdef";
+
 type BalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
@@ -61,6 +68,9 @@ pub mod pallet {
 	use frame_support::{dispatch::DispatchResult, pallet_prelude::*};
 	use frame_system::pallet_prelude::*;
 
+	/// The current storage version.
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
@@ -86,12 +96,16 @@ pub mod pallet {
 
 		type Currency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
 
+		/// Asked whether a contract recipient accepts transferred tokens.
+		type Receiver: MultiReceiver<Self::AccountId, Self::MultiTokenId, Self::TokenId>;
+
 		/// runtime weights.
 		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::storage]
@@ -127,6 +141,54 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn token_supply)]
+	pub(super) type TokenSupply<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::MultiTokenId,
+		Blake2_128Concat,
+		T::TokenId,
+		Balance,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn max_supply)]
+	pub(super) type MaxSupply<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::MultiTokenId,
+		Blake2_128Concat,
+		T::TokenId,
+		Balance,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn token_uri)]
+	pub(super) type TokenUris<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::MultiTokenId,
+		Blake2_128Concat,
+		T::TokenId,
+		BoundedVec<u8, T::StringLimit>,
+		OptionQuery,
+	>;
+
+	/// Reverse index of `Balances`: the tokens each account holds a non-zero balance of.
+	#[pallet::storage]
+	pub(super) type AccountTokens<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		(T::MultiTokenId, T::TokenId),
+		(),
+		OptionQuery,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -135,6 +197,10 @@ pub mod pallet {
 		BatchMint(T::MultiTokenId, T::AccountId, Vec<T::TokenId>, Vec<Balance>),
 		Burn(T::MultiTokenId, T::AccountId, T::TokenId, Balance),
 		BatchBurn(T::MultiTokenId, T::AccountId, Vec<T::TokenId>, Vec<Balance>),
+		/// An operator burned tokens. \[id, operator, from, token_id, amount\]
+		BurnFrom(T::MultiTokenId, T::AccountId, T::AccountId, T::TokenId, Balance),
+		/// An operator burned a batch of tokens. \[id, operator, from, token_ids, amounts\]
+		BatchBurnFrom(T::MultiTokenId, T::AccountId, T::AccountId, Vec<T::TokenId>, Vec<Balance>),
 		Transferred(T::MultiTokenId, T::AccountId, T::AccountId, T::TokenId, Balance),
 		BatchTransferred(
 			T::MultiTokenId,
@@ -144,6 +210,9 @@ pub mod pallet {
 			Vec<Balance>,
 		),
 		ApprovalForAll(T::MultiTokenId, T::AccountId, T::AccountId, bool),
+		MaxSupplySet(T::MultiTokenId, T::TokenId, Option<Balance>),
+		/// The uri of a token id changed. \[id, token_id, uri\]
+		URI(T::MultiTokenId, T::TokenId, Vec<u8>),
 	}
 
 	#[pallet::error]
@@ -161,10 +230,17 @@ pub mod pallet {
 		ConfuseBehavior,
 		InsufficientTokens,
 		InsufficientAuthorizedTokens,
+		MaxSupplyExceeded,
+		InvalidMaxSupply,
+		TransferRejected,
 	}
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate::<T>()
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -189,7 +265,7 @@ pub mod pallet {
 			Self::do_set_approval_for_all(&who, id, &operator, approved)
 		}
 
-		#[pallet::weight(T::WeightInfo::transfer_from())]
+		#[pallet::weight(T::WeightInfo::transfer_from().saturating_add(T::Receiver::max_weight()))]
 		pub fn transfer_from(
 			origin: OriginFor<T>,
 			id: T::MultiTokenId,
@@ -202,7 +278,9 @@ pub mod pallet {
 			Self::do_transfer_from(&who, id, &from, &to, token_id, amount)
 		}
 
-		#[pallet::weight(T::WeightInfo::batch_transfer_from())]
+		#[pallet::weight(
+			T::WeightInfo::batch_transfer_from().saturating_add(T::Receiver::max_weight())
+		)]
 		pub fn batch_transfer_from(
 			origin: OriginFor<T>,
 			id: T::MultiTokenId,
@@ -261,6 +339,52 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 			Self::do_batch_burn(&who, id, token_ids, amounts)
 		}
+
+		#[pallet::weight(T::WeightInfo::burn_from())]
+		pub fn burn_from(
+			origin: OriginFor<T>,
+			id: T::MultiTokenId,
+			from: T::AccountId,
+			token_id: T::TokenId,
+			amount: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_burn_from(&who, id, &from, token_id, amount)
+		}
+
+		#[pallet::weight(T::WeightInfo::burn_batch_from())]
+		pub fn burn_batch_from(
+			origin: OriginFor<T>,
+			id: T::MultiTokenId,
+			from: T::AccountId,
+			token_ids: Vec<T::TokenId>,
+			amounts: Vec<Balance>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_batch_burn_from(&who, id, &from, token_ids, amounts)
+		}
+
+		#[pallet::weight(T::WeightInfo::set_max_supply())]
+		pub fn set_max_supply(
+			origin: OriginFor<T>,
+			id: T::MultiTokenId,
+			token_id: T::TokenId,
+			max_supply: Option<Balance>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_set_max_supply(&who, id, token_id, max_supply)
+		}
+
+		#[pallet::weight(T::WeightInfo::set_uri())]
+		pub fn set_uri(
+			origin: OriginFor<T>,
+			id: T::MultiTokenId,
+			token_id: T::TokenId,
+			uri: Option<Vec<u8>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_set_uri(&who, id, token_id, uri)
+		}
 	}
 }
 
@@ -316,6 +440,7 @@ impl<T: Config> Pallet<T> {
 		Tokens::<T>::try_mutate(id, |maybe_token| -> DispatchResult {
 			let token = maybe_token.as_mut().ok_or(Error::<T>::Unknown)?;
 
+			Self::increase_supply(id, token_id, amount)?;
 			Self::increase_balance(id, to, token_id, amount)?;
 
 			let new_total_supply = token.total_supply.saturating_add(amount);
@@ -347,6 +472,7 @@ impl<T: Config> Pallet<T> {
 			Tokens::<T>::try_mutate(id, |maybe_token| -> DispatchResult {
 				let token = maybe_token.as_mut().ok_or(Error::<T>::Unknown)?;
 
+				Self::increase_supply(id, token_id, amount)?;
 				Self::increase_balance(id, to, token_id, amount)?;
 
 				let new_total_supply = token.total_supply.saturating_add(amount);
@@ -366,17 +492,25 @@ impl<T: Config> Pallet<T> {
 		token_id: T::TokenId,
 		amount: Balance,
 	) -> DispatchResult {
-		Tokens::<T>::try_mutate(id, |maybe_token| -> DispatchResult {
-			let token = maybe_token.as_mut().ok_or(Error::<T>::Unknown)?;
+		Self::burn_tokens(id, who, token_id, amount)?;
 
-			Self::decrease_balance(id, who, token_id, amount)?;
+		Self::deposit_event(Event::Burn(id, who.clone(), token_id, amount));
 
-			let new_total_supply = token.total_supply.saturating_sub(amount);
-			token.total_supply = new_total_supply;
-			Ok(())
-		})?;
+		Ok(())
+	}
 
-		Self::deposit_event(Event::Burn(id, who.clone(), token_id, amount));
+	pub fn do_burn_from(
+		who: &T::AccountId,
+		id: T::MultiTokenId,
+		from: &T::AccountId,
+		token_id: T::TokenId,
+		amount: Balance,
+	) -> DispatchResult {
+		ensure!(Self::owner_or_approved(id, &who, &from), Error::<T>::NotOwnerOrApproved);
+
+		Self::burn_tokens(id, from, token_id, amount)?;
+
+		Self::deposit_event(Event::BurnFrom(id, who.clone(), from.clone(), token_id, amount));
 
 		Ok(())
 	}
@@ -391,25 +525,60 @@ impl<T: Config> Pallet<T> {
 
 		let n = token_ids.len();
 		for i in 0..n {
-			let token_id = token_ids[i];
-			let amount = amounts[i];
+			Self::burn_tokens(id, who, token_ids[i], amounts[i])?;
+		}
 
-			Tokens::<T>::try_mutate(id, |maybe_token| -> DispatchResult {
-				let token = maybe_token.as_mut().ok_or(Error::<T>::Unknown)?;
+		Self::deposit_event(Event::BatchBurn(id, who.clone(), token_ids, amounts));
 
-				Self::decrease_balance(id, who, token_id, amount)?;
+		Ok(())
+	}
 
-				let new_total_supply = token.total_supply.saturating_sub(amount);
-				token.total_supply = new_total_supply;
-				Ok(())
-			})?;
+	pub fn do_batch_burn_from(
+		who: &T::AccountId,
+		id: T::MultiTokenId,
+		from: &T::AccountId,
+		token_ids: Vec<T::TokenId>,
+		amounts: Vec<Balance>,
+	) -> DispatchResult {
+		ensure!(Self::owner_or_approved(id, &who, &from), Error::<T>::NotOwnerOrApproved);
+		ensure!(token_ids.len() == amounts.len(), Error::<T>::LengthMismatch);
+
+		let n = token_ids.len();
+		for i in 0..n {
+			Self::burn_tokens(id, from, token_ids[i], amounts[i])?;
 		}
 
-		Self::deposit_event(Event::BatchBurn(id, who.clone(), token_ids, amounts));
+		Self::deposit_event(Event::BatchBurnFrom(
+			id,
+			who.clone(),
+			from.clone(),
+			token_ids,
+			amounts,
+		));
 
 		Ok(())
 	}
 
+	fn burn_tokens(
+		id: T::MultiTokenId,
+		from: &T::AccountId,
+		token_id: T::TokenId,
+		amount: Balance,
+	) -> DispatchResult {
+		Tokens::<T>::try_mutate(id, |maybe_token| -> DispatchResult {
+			let token = maybe_token.as_mut().ok_or(Error::<T>::Unknown)?;
+
+			Self::decrease_balance(id, from, token_id, amount)?;
+			Self::decrease_supply(id, token_id, amount);
+
+			let new_total_supply = token.total_supply.saturating_sub(amount);
+			token.total_supply = new_total_supply;
+			Ok(())
+		})
+	}
+
+	/// Asks `T::Receiver` whether `to` accepts the tokens, callers must charge
+	/// `T::Receiver::max_weight()` on top of their own weight.
 	pub fn do_transfer_from(
 		who: &T::AccountId,
 		id: T::MultiTokenId,
@@ -432,11 +601,18 @@ impl<T: Config> Pallet<T> {
 
 		Self::increase_balance(id, to, token_id, amount)?;
 
+		ensure!(
+			T::Receiver::on_received(who, from, to, id, token_id, amount),
+			Error::<T>::TransferRejected
+		);
+
 		Self::deposit_event(Event::Transferred(id, from.clone(), to.clone(), token_id, amount));
 
 		Ok(())
 	}
 
+	/// Asks `T::Receiver` whether `to` accepts the tokens, callers must charge
+	/// `T::Receiver::max_weight()` on top of their own weight.
 	pub fn do_batch_transfer_from(
 		who: &T::AccountId,
 		id: T::MultiTokenId,
@@ -467,6 +643,11 @@ impl<T: Config> Pallet<T> {
 			Self::increase_balance(id, to, token_id, amount)?;
 		}
 
+		ensure!(
+			T::Receiver::on_batch_received(who, from, to, id, &token_ids, &amounts),
+			Error::<T>::TransferRejected
+		);
+
 		Self::deposit_event(Event::BatchTransferred(
 			id,
 			from.clone(),
@@ -478,6 +659,97 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	pub fn do_set_max_supply(
+		who: &T::AccountId,
+		id: T::MultiTokenId,
+		token_id: T::TokenId,
+		max_supply: Option<Balance>,
+	) -> DispatchResult {
+		ensure!(Self::exists(id), Error::<T>::InvalidId);
+		ensure!(Self::has_permission(id, &who), Error::<T>::NoPermission);
+		if let Some(max_supply) = max_supply {
+			ensure!(
+				max_supply >= TokenSupply::<T>::get(id, token_id),
+				Error::<T>::InvalidMaxSupply
+			);
+		}
+
+		MaxSupply::<T>::set(id, token_id, max_supply);
+
+		Self::deposit_event(Event::MaxSupplySet(id, token_id, max_supply));
+
+		Ok(())
+	}
+
+	pub fn do_set_uri(
+		who: &T::AccountId,
+		id: T::MultiTokenId,
+		token_id: T::TokenId,
+		uri: Option<Vec<u8>>,
+	) -> DispatchResult {
+		ensure!(Self::exists(id), Error::<T>::InvalidId);
+		ensure!(Self::has_permission(id, &who), Error::<T>::NoPermission);
+
+		match uri {
+			Some(uri) => {
+				let bounded_uri: BoundedVec<u8, T::StringLimit> =
+					uri.try_into().map_err(|_| Error::<T>::BadMetadata)?;
+				TokenUris::<T>::insert(id, token_id, bounded_uri);
+			},
+			None => TokenUris::<T>::remove(id, token_id),
+		}
+
+		Self::deposit_event(Event::URI(id, token_id, Self::resolve_uri(id, token_id)));
+
+		Ok(())
+	}
+
+	/// Resolve the uri of a token id.
+	///
+	/// A per token id uri takes precedence over the base uri. Every `{id}` in the uri is
+	/// replaced by the token id as 64 lowercase hex characters, following ERC-1155. A base
+	/// uri without `{id}` gets the decimal token id appended.
+	pub fn resolve_uri(id: T::MultiTokenId, token_id: T::TokenId) -> Vec<u8> {
+		let (uri, is_base) = match TokenUris::<T>::get(id, token_id) {
+			Some(uri) => (uri.to_vec(), false),
+			None => match Tokens::<T>::get(id) {
+				Some(token) => (token.uri.to_vec(), true),
+				None => return Vec::new(),
+			},
+		};
+		let token_id: u128 = token_id.unique_saturated_into();
+
+		if uri.windows(ID_PLACEHOLDER.len()).any(|w| w == ID_PLACEHOLDER) {
+			Self::substitute_id(&uri, token_id)
+		} else if is_base {
+			let token_id_buf: Vec<u8> = token_id.to_string().as_bytes().to_vec();
+			uri.into_iter().chain(token_id_buf).collect::<Vec<_>>()
+		} else {
+			uri
+		}
+	}
+
+	fn substitute_id(uri: &[u8], token_id: u128) -> Vec<u8> {
+		let mut hex_id = vec![b'0'; 32];
+		for byte in token_id.to_be_bytes() {
+			hex_id.push(HEX_CHARS[(byte >> 4) as usize]);
+			hex_id.push(HEX_CHARS[(byte & 0x0f) as usize]);
+		}
+
+		let mut result = Vec::with_capacity(uri.len() + hex_id.len());
+		let mut i = 0;
+		while i < uri.len() {
+			if uri[i..].starts_with(ID_PLACEHOLDER) {
+				result.extend_from_slice(&hex_id);
+				i += ID_PLACEHOLDER.len();
+			} else {
+				result.push(uri[i]);
+				i += 1;
+			}
+		}
+		result
+	}
+
 	pub fn balance_of_batch(
 		id: T::MultiTokenId,
 		accounts: &Vec<T::AccountId>,
@@ -504,10 +776,16 @@ impl<T: Config> Pallet<T> {
 		token_id: T::TokenId,
 		amount: Balance,
 	) -> DispatchResult {
-		Balances::<T>::try_mutate(id, (token_id, to), |balance| -> DispatchResult {
-			*balance = balance.checked_add(amount).ok_or(Error::<T>::NumOverflow)?;
-			Ok(())
-		})?;
+		let new_balance = Balances::<T>::try_mutate(
+			id,
+			(token_id, to),
+			|balance| -> Result<Balance, DispatchError> {
+				*balance = balance.checked_add(amount).ok_or(Error::<T>::NumOverflow)?;
+				Ok(*balance)
+			},
+		)?;
+		Self::update_account_index(id, to, token_id, new_balance);
+
 		Ok(())
 	}
 
@@ -517,19 +795,75 @@ impl<T: Config> Pallet<T> {
 		token_id: T::TokenId,
 		amount: Balance,
 	) -> DispatchResult {
-		Balances::<T>::try_mutate(id, (token_id, from), |balance| -> DispatchResult {
-			*balance = balance.checked_sub(amount).ok_or(Error::<T>::NumOverflow)?;
-			Ok(())
-		})?;
+		let new_balance = Balances::<T>::try_mutate(
+			id,
+			(token_id, from),
+			|balance| -> Result<Balance, DispatchError> {
+				*balance = balance.checked_sub(amount).ok_or(Error::<T>::NumOverflow)?;
+				Ok(*balance)
+			},
+		)?;
+		Self::update_account_index(id, from, token_id, new_balance);
 
 		Ok(())
 	}
 
+	fn update_account_index(
+		id: T::MultiTokenId,
+		who: &T::AccountId,
+		token_id: T::TokenId,
+		balance: Balance,
+	) {
+		if balance.is_zero() {
+			AccountTokens::<T>::remove(who, (id, token_id));
+		} else {
+			AccountTokens::<T>::insert(who, (id, token_id), ());
+		}
+	}
+
+	/// The non-zero holdings of `who`, at most `limit` of them, starting after the token `start`.
+	pub fn holdings_of(
+		who: &T::AccountId,
+		start: Option<(T::MultiTokenId, T::TokenId)>,
+		limit: u32,
+	) -> Vec<(T::MultiTokenId, T::TokenId, Balance)> {
+		let tokens = match start {
+			Some(start) => AccountTokens::<T>::iter_key_prefix_from(
+				who,
+				AccountTokens::<T>::hashed_key_for(who, start),
+			),
+			None => AccountTokens::<T>::iter_key_prefix(who),
+		};
+		tokens
+			.take(limit as usize)
+			.map(|(id, token_id)| (id, token_id, Balances::<T>::get(id, (token_id, who))))
+			.collect()
+	}
+
+	fn increase_supply(
+		id: T::MultiTokenId,
+		token_id: T::TokenId,
+		amount: Balance,
+	) -> DispatchResult {
+		TokenSupply::<T>::try_mutate(id, token_id, |supply| -> DispatchResult {
+			let new_supply = supply.checked_add(amount).ok_or(Error::<T>::NumOverflow)?;
+			if let Some(max_supply) = MaxSupply::<T>::get(id, token_id) {
+				ensure!(new_supply <= max_supply, Error::<T>::MaxSupplyExceeded);
+			}
+			*supply = new_supply;
+			Ok(())
+		})
+	}
+
+	fn decrease_supply(id: T::MultiTokenId, token_id: T::TokenId, amount: Balance) {
+		TokenSupply::<T>::mutate(id, token_id, |supply| *supply = supply.saturating_sub(amount));
+	}
+
 	fn owner_or_approved(id: T::MultiTokenId, who: &T::AccountId, owner: &T::AccountId) -> bool {
 		*who == *owner || Self::is_approved_for_all(id, (owner, who))
 	}
 
-	fn has_permission(id: T::MultiTokenId, who: &T::AccountId) -> bool {
+	pub fn has_permission(id: T::MultiTokenId, who: &T::AccountId) -> bool {
 		let token = Tokens::<T>::get(id).unwrap();
 		*who == token.owner
 	}
@@ -543,9 +877,14 @@ where
 	type TokenId = T::TokenId;
 
 	fn uri(id: Self::MultiTokenId, token_id: T::TokenId) -> Vec<u8> {
-		let base_uri_buf: Vec<u8> = Tokens::<T>::get(id).unwrap().uri.to_vec();
-		let token_id: u128 = token_id.into();
-		let token_id_buf: Vec<u8> = token_id.to_string().as_bytes().to_vec();
-		base_uri_buf.into_iter().chain(token_id_buf).collect::<Vec<_>>()
+		Self::resolve_uri(id, token_id)
+	}
+
+	fn total_supply(id: Self::MultiTokenId, token_id: Self::TokenId) -> Balance {
+		TokenSupply::<T>::get(id, token_id)
+	}
+
+	fn exists(id: Self::MultiTokenId, token_id: Self::TokenId) -> bool {
+		TokenSupply::<T>::get(id, token_id) > 0
 	}
 }
@@ -0,0 +1,94 @@
+// This file is part of Web3Games.
+
+// Copyright (C) 2021-2022 Web3Games https://web3games.org
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use frame_support::{
+	traits::{GetStorageVersion, StorageVersion},
+	weights::Weight,
+};
+
+/// Run every pending migration of the pallet storage.
+pub fn migrate<T: Config>() -> Weight {
+	let mut weight: Weight = 0;
+
+	if Pallet::<T>::on_chain_storage_version() < 1 {
+		weight = weight.saturating_add(v1::migrate::<T>());
+		StorageVersion::new(1).put::<Pallet<T>>();
+	}
+
+	if Pallet::<T>::on_chain_storage_version() < 2 {
+		weight = weight.saturating_add(v2::migrate::<T>());
+		StorageVersion::new(2).put::<Pallet<T>>();
+	}
+
+	weight.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+}
+
+/// Build `TokenSupply` from the existing balances.
+///
+/// The migration runs in the upgrade block and touches every `Balances` entry with two reads
+/// and a write. With RocksDB weights that fits a block for about ten thousand balances, and the
+/// upgrade assumes the chain holds fewer. Larger chains need to split it over several blocks;
+/// a warning is logged when it overran the block.
+pub mod v1 {
+	use super::*;
+
+	pub fn migrate<T: Config>() -> Weight {
+		let mut count: u64 = 0;
+		for (id, (token_id, _), balance) in Balances::<T>::iter() {
+			TokenSupply::<T>::mutate(id, token_id, |supply| {
+				*supply = supply.saturating_add(balance)
+			});
+			count += 1;
+		}
+
+		log::info!(target: "runtime::token-multi", "migrated {} balances into TokenSupply", count);
+
+		let weight = T::DbWeight::get().reads_writes(count.saturating_mul(2), count);
+		if weight > T::BlockWeights::get().max_block {
+			log::warn!(
+				target: "runtime::token-multi",
+				"migrating {} balances used more than the block weight",
+				count
+			);
+		}
+
+		weight
+	}
+}
+
+/// Build the `AccountTokens` index from the existing balances.
+pub mod v2 {
+	use super::*;
+
+	pub fn migrate<T: Config>() -> Weight {
+		let mut reads: u64 = 0;
+		let mut writes: u64 = 0;
+		for (id, (token_id, who), balance) in Balances::<T>::iter() {
+			reads += 1;
+			if !balance.is_zero() {
+				AccountTokens::<T>::insert(who, (id, token_id), ());
+				writes += 1;
+			}
+		}
+
+		log::info!(target: "runtime::token-multi", "indexed {} multi token balances", writes);
+
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+}
@@ -20,8 +20,10 @@ use crate as pallet_token_multi;
 use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{ConstU16, ConstU64},
+	weights::Weight,
 	PalletId,
 };
+use pallet_support::MultiReceiver;
 use primitives::Balance;
 use sp_core::H256;
 use sp_runtime::{
@@ -98,6 +100,38 @@ parameter_types! {
 	pub const CreateTokenDeposit: Balance = 500 * MILLICENTS;
 }
 
+/// Account that stands in for a contract rejecting every token it receives.
+pub const REJECTING_RECEIVER: u64 = 100;
+
+pub struct RejectingReceiver;
+impl MultiReceiver<u64, u32, u128> for RejectingReceiver {
+	fn on_received(
+		_operator: &u64,
+		_from: &u64,
+		to: &u64,
+		_id: u32,
+		_token_id: u128,
+		_amount: Balance,
+	) -> bool {
+		*to != REJECTING_RECEIVER
+	}
+
+	fn on_batch_received(
+		_operator: &u64,
+		_from: &u64,
+		to: &u64,
+		_id: u32,
+		_token_ids: &[u128],
+		_amounts: &[Balance],
+	) -> bool {
+		*to != REJECTING_RECEIVER
+	}
+
+	fn max_weight() -> Weight {
+		0
+	}
+}
+
 impl pallet_token_multi::Config for Test {
 	type Event = Event;
 	type PalletId = TokenMultiPalletId;
@@ -106,6 +140,7 @@ impl pallet_token_multi::Config for Test {
 	type StringLimit = StringLimit;
 	type CreateTokenDeposit = CreateTokenDeposit;
 	type Currency = Balances;
+	type Receiver = RejectingReceiver;
 	type WeightInfo = ();
 }
 
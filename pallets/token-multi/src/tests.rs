@@ -331,3 +331,304 @@ fn batch_transfer_from_should_work() {
 		assert_eq!(TokenMulti::balance_of(1, (3, BOB)), 50);
 	})
 }
+
+#[test]
+fn token_supply_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TokenMulti::create_token(
+			Origin::signed(ALICE),
+			1,
+			b"https://web3games.com/".to_vec()
+		));
+		assert_eq!(<TokenMulti as MultiMetadata>::exists(1, 1), false);
+
+		assert_ok!(TokenMulti::mint(Origin::signed(ALICE), 1, ALICE, 1, 100));
+		assert_ok!(TokenMulti::mint(Origin::signed(ALICE), 1, BOB, 1, 50));
+		assert_ok!(TokenMulti::mint_batch(
+			Origin::signed(ALICE),
+			1,
+			ALICE,
+			vec![1, 2],
+			vec![10u128; 2]
+		));
+		assert_eq!(TokenMulti::token_supply(1, 1), 160);
+		assert_eq!(TokenMulti::token_supply(1, 2), 10);
+		assert_eq!(<TokenMulti as MultiMetadata>::exists(1, 1), true);
+
+		assert_ok!(TokenMulti::burn(Origin::signed(ALICE), 1, 1, 60));
+		assert_ok!(TokenMulti::burn_batch(Origin::signed(ALICE), 1, vec![1, 2], vec![50, 10]));
+		assert_eq!(TokenMulti::token_supply(1, 1), 50);
+		assert_eq!(TokenMulti::token_supply(1, 2), 0);
+		assert_eq!(<TokenMulti as MultiMetadata>::total_supply(1, 1), 50);
+		assert_eq!(<TokenMulti as MultiMetadata>::exists(1, 2), false);
+	})
+}
+
+#[test]
+fn set_max_supply_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TokenMulti::create_token(
+			Origin::signed(ALICE),
+			1,
+			b"https://web3games.com/".to_vec()
+		));
+		assert_ok!(TokenMulti::set_max_supply(Origin::signed(ALICE), 1, 1, Some(100)));
+		assert_eq!(TokenMulti::max_supply(1, 1), Some(100));
+
+		assert_ok!(TokenMulti::mint(Origin::signed(ALICE), 1, ALICE, 1, 60));
+		assert_ok!(TokenMulti::mint(Origin::signed(ALICE), 1, BOB, 1, 40));
+		assert_noop!(
+			TokenMulti::mint(Origin::signed(ALICE), 1, ALICE, 1, 1),
+			Error::<Test>::MaxSupplyExceeded
+		);
+		assert_noop!(
+			TokenMulti::mint_batch(Origin::signed(ALICE), 1, ALICE, vec![2, 1], vec![1, 1]),
+			Error::<Test>::MaxSupplyExceeded
+		);
+
+		// burning frees room under the cap
+		assert_ok!(TokenMulti::burn(Origin::signed(BOB), 1, 1, 10));
+		assert_ok!(TokenMulti::mint(Origin::signed(ALICE), 1, ALICE, 1, 10));
+
+		assert_ok!(TokenMulti::set_max_supply(Origin::signed(ALICE), 1, 1, None));
+		assert_ok!(TokenMulti::mint(Origin::signed(ALICE), 1, ALICE, 1, 1000));
+		assert_eq!(TokenMulti::token_supply(1, 1), 1100);
+	})
+}
+
+#[test]
+fn set_max_supply_should_not_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TokenMulti::set_max_supply(Origin::signed(ALICE), 1, 1, Some(100)),
+			Error::<Test>::InvalidId
+		);
+		assert_ok!(TokenMulti::create_token(
+			Origin::signed(ALICE),
+			1,
+			b"https://web3games.com/".to_vec()
+		));
+		assert_noop!(
+			TokenMulti::set_max_supply(Origin::signed(BOB), 1, 1, Some(100)),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(TokenMulti::mint(Origin::signed(ALICE), 1, ALICE, 1, 100));
+		assert_noop!(
+			TokenMulti::set_max_supply(Origin::signed(ALICE), 1, 1, Some(99)),
+			Error::<Test>::InvalidMaxSupply
+		);
+	})
+}
+
+#[test]
+fn uri_template_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TokenMulti::create_token(
+			Origin::signed(ALICE),
+			1,
+			b"https://web3games.com/{id}.json".to_vec()
+		));
+		assert_eq!(
+			TokenMulti::uri(1, 314592),
+			b"https://web3games.com/000000000000000000000000000000000000000000000000000000000004cce0.json"
+				.to_vec()
+		);
+	})
+}
+
+#[test]
+fn set_uri_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TokenMulti::create_token(
+			Origin::signed(ALICE),
+			1,
+			b"https://web3games.com/".to_vec()
+		));
+		assert_ok!(TokenMulti::set_uri(
+			Origin::signed(ALICE),
+			1,
+			2,
+			Some(b"ipfs://hash/{id}".to_vec())
+		));
+		assert_eq!(
+			TokenMulti::uri(1, 2),
+			b"ipfs://hash/0000000000000000000000000000000000000000000000000000000000000002"
+				.to_vec()
+		);
+		assert_eq!(TokenMulti::uri(1, 1), b"https://web3games.com/1".to_vec());
+
+		assert_ok!(TokenMulti::set_uri(
+			Origin::signed(ALICE),
+			1,
+			2,
+			Some(b"ipfs://other".to_vec())
+		));
+		assert_eq!(TokenMulti::uri(1, 2), b"ipfs://other".to_vec());
+
+		assert_ok!(TokenMulti::set_uri(Origin::signed(ALICE), 1, 2, None));
+		assert_eq!(TokenMulti::token_uri(1, 2), None);
+		assert_eq!(TokenMulti::uri(1, 2), b"https://web3games.com/2".to_vec());
+		System::assert_last_event(crate::mock::Event::TokenMulti(crate::Event::URI(
+			1,
+			2,
+			b"https://web3games.com/2".to_vec(),
+		)));
+	})
+}
+
+#[test]
+fn set_uri_should_not_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TokenMulti::set_uri(Origin::signed(ALICE), 1, 1, Some(b"ipfs://hash".to_vec())),
+			Error::<Test>::InvalidId
+		);
+		assert_ok!(TokenMulti::create_token(
+			Origin::signed(ALICE),
+			1,
+			b"https://web3games.com/".to_vec()
+		));
+		assert_noop!(
+			TokenMulti::set_uri(Origin::signed(BOB), 1, 1, Some(b"ipfs://hash".to_vec())),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			TokenMulti::set_uri(Origin::signed(ALICE), 1, 1, Some(vec![0u8; 51])),
+			Error::<Test>::BadMetadata
+		);
+	})
+}
+
+#[test]
+fn transfer_to_rejecting_receiver_should_not_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TokenMulti::create_token(
+			Origin::signed(ALICE),
+			1,
+			b"https://web3games.com/".to_vec()
+		));
+		assert_ok!(TokenMulti::mint_batch(
+			Origin::signed(ALICE),
+			1,
+			ALICE,
+			vec![1, 2],
+			vec![100u128; 2]
+		));
+		assert_noop!(
+			TokenMulti::transfer_from(Origin::signed(ALICE), 1, ALICE, REJECTING_RECEIVER, 1, 50),
+			Error::<Test>::TransferRejected
+		);
+		assert_noop!(
+			TokenMulti::batch_transfer_from(
+				Origin::signed(ALICE),
+				1,
+				ALICE,
+				REJECTING_RECEIVER,
+				vec![1, 2],
+				vec![50, 50]
+			),
+			Error::<Test>::TransferRejected
+		);
+	})
+}
+
+#[test]
+fn burn_from_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TokenMulti::create_token(
+			Origin::signed(ALICE),
+			1,
+			b"https://web3games.com/".to_vec()
+		));
+		assert_ok!(TokenMulti::mint_batch(
+			Origin::signed(ALICE),
+			1,
+			BOB,
+			vec![1, 2],
+			vec![100u128; 2]
+		));
+		assert_ok!(TokenMulti::set_approval_for_all(Origin::signed(BOB), 1, ALICE, true));
+
+		assert_ok!(TokenMulti::burn_from(Origin::signed(ALICE), 1, BOB, 1, 30));
+		assert_eq!(TokenMulti::balance_of(1, (1, BOB)), 70);
+		assert_eq!(TokenMulti::token_supply(1, 1), 70);
+
+		assert_ok!(TokenMulti::burn_batch_from(
+			Origin::signed(ALICE),
+			1,
+			BOB,
+			vec![1, 2],
+			vec![20, 40]
+		));
+		assert_eq!(TokenMulti::balance_of(1, (1, BOB)), 50);
+		assert_eq!(TokenMulti::balance_of(1, (2, BOB)), 60);
+		System::assert_last_event(crate::mock::Event::TokenMulti(crate::Event::BatchBurnFrom(
+			1,
+			ALICE,
+			BOB,
+			vec![1, 2],
+			vec![20, 40],
+		)));
+	})
+}
+
+#[test]
+fn burn_from_should_not_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TokenMulti::create_token(
+			Origin::signed(ALICE),
+			1,
+			b"https://web3games.com/".to_vec()
+		));
+		assert_ok!(TokenMulti::mint(Origin::signed(ALICE), 1, BOB, 1, 100));
+		assert_noop!(
+			TokenMulti::burn_from(Origin::signed(ALICE), 1, BOB, 1, 30),
+			Error::<Test>::NotOwnerOrApproved
+		);
+		assert_noop!(
+			TokenMulti::burn_batch_from(Origin::signed(ALICE), 1, BOB, vec![1], vec![30]),
+			Error::<Test>::NotOwnerOrApproved
+		);
+
+		assert_ok!(TokenMulti::set_approval_for_all(Origin::signed(BOB), 1, ALICE, true));
+		assert_noop!(
+			TokenMulti::burn_from(Origin::signed(ALICE), 1, BOB, 1, 101),
+			Error::<Test>::NumOverflow
+		);
+	})
+}
+
+#[test]
+fn holdings_of_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TokenMulti::create_token(
+			Origin::signed(ALICE),
+			1,
+			b"https://web3games.com/".to_vec()
+		));
+		assert_ok!(TokenMulti::mint_batch(
+			Origin::signed(ALICE),
+			1,
+			ALICE,
+			vec![1, 2, 3],
+			vec![10, 20, 30]
+		));
+
+		let mut holdings = TokenMulti::holdings_of(&ALICE, None, 10);
+		holdings.sort();
+		assert_eq!(holdings, vec![(1, 1, 10), (1, 2, 20), (1, 3, 30)]);
+
+		let mut pages = TokenMulti::holdings_of(&ALICE, None, 2);
+		assert_eq!(pages.len(), 2);
+		let (id, token_id, _) = pages[1];
+		pages.extend(TokenMulti::holdings_of(&ALICE, Some((id, token_id)), 2));
+		pages.sort();
+		assert_eq!(pages, holdings);
+
+		assert_ok!(TokenMulti::transfer_from(Origin::signed(ALICE), 1, ALICE, BOB, 2, 20));
+		let mut holdings = TokenMulti::holdings_of(&ALICE, None, 10);
+		holdings.sort();
+		assert_eq!(holdings, vec![(1, 1, 10), (1, 3, 30)]);
+		assert_eq!(TokenMulti::holdings_of(&BOB, None, 10), vec![(1, 2, 20)]);
+	})
+}
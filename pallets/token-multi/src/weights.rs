@@ -54,6 +54,10 @@ pub trait WeightInfo {
 	fn burn_batch() -> Weight;
 	fn transfer_from() -> Weight;
 	fn batch_transfer_from() -> Weight;
+	fn set_max_supply() -> Weight;
+	fn set_uri() -> Weight;
+	fn burn_from() -> Weight;
+	fn burn_batch_from() -> Weight;
 }
 
 /// Weights for pallet_token_multi using the Web3Games node and recommended hardware.
@@ -66,18 +70,24 @@ impl<T: frame_system::Config> WeightInfo for W3GWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
 	// Storage: TokenMulti Tokens (r:1 w:1)
+	// Storage: TokenMulti TokenSupply (r:1 w:1)
+	// Storage: TokenMulti MaxSupply (r:1 w:0)
 	// Storage: TokenMulti Balances (r:1 w:1)
+	// Storage: TokenMulti AccountTokens (r:0 w:1)
 	fn mint() -> Weight {
 		(21_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
 	// Storage: TokenMulti Tokens (r:1 w:1)
+	// Storage: TokenMulti TokenSupply (r:5 w:5)
+	// Storage: TokenMulti MaxSupply (r:5 w:0)
 	// Storage: TokenMulti Balances (r:5 w:5)
+	// Storage: TokenMulti AccountTokens (r:0 w:5)
 	fn mint_batch() -> Weight {
 		(39_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(6 as Weight))
-			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+			.saturating_add(T::DbWeight::get().reads(16 as Weight))
+			.saturating_add(T::DbWeight::get().writes(16 as Weight))
 	}
 	// Storage: TokenMulti Tokens (r:1 w:0)
 	// Storage: TokenMulti OperatorApprovals (r:0 w:1)
@@ -88,29 +98,70 @@ impl<T: frame_system::Config> WeightInfo for W3GWeight<T> {
 	}
 	// Storage: TokenMulti Tokens (r:1 w:1)
 	// Storage: TokenMulti Balances (r:1 w:1)
+	// Storage: TokenMulti TokenSupply (r:1 w:1)
+	// Storage: TokenMulti AccountTokens (r:0 w:1)
 	fn burn() -> Weight {
 		(19_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
 	// Storage: TokenMulti Tokens (r:1 w:1)
 	// Storage: TokenMulti Balances (r:5 w:5)
+	// Storage: TokenMulti TokenSupply (r:5 w:5)
+	// Storage: TokenMulti AccountTokens (r:0 w:5)
 	fn burn_batch() -> Weight {
 		(43_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(6 as Weight))
-			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(16 as Weight))
 	}
 	// Storage: TokenMulti Balances (r:2 w:2)
+	// Storage: TokenMulti AccountTokens (r:0 w:2)
 	fn transfer_from() -> Weight {
 		(20_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
 	// Storage: TokenMulti Balances (r:10 w:10)
+	// Storage: TokenMulti AccountTokens (r:0 w:10)
 	fn batch_transfer_from() -> Weight {
 		(58_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(10 as Weight))
-			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(20 as Weight))
+	}
+	// Storage: TokenMulti Tokens (r:1 w:0)
+	// Storage: TokenMulti TokenSupply (r:1 w:0)
+	// Storage: TokenMulti MaxSupply (r:0 w:1)
+	fn set_max_supply() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TokenMulti Tokens (r:1 w:0)
+	// Storage: TokenMulti TokenUris (r:1 w:1)
+	fn set_uri() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TokenMulti OperatorApprovals (r:1 w:0)
+	// Storage: TokenMulti Tokens (r:1 w:1)
+	// Storage: TokenMulti Balances (r:1 w:1)
+	// Storage: TokenMulti TokenSupply (r:1 w:1)
+	// Storage: TokenMulti AccountTokens (r:0 w:1)
+	fn burn_from() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: TokenMulti OperatorApprovals (r:1 w:0)
+	// Storage: TokenMulti Tokens (r:1 w:1)
+	// Storage: TokenMulti Balances (r:5 w:5)
+	// Storage: TokenMulti TokenSupply (r:5 w:5)
+	// Storage: TokenMulti AccountTokens (r:0 w:5)
+	fn burn_batch_from() -> Weight {
+		(46_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes(16 as Weight))
 	}
 }
 
@@ -123,13 +174,13 @@ impl WeightInfo for () {
 	}
 	fn mint() -> Weight {
 		(21_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
 	}
 	fn mint_batch() -> Weight {
 		(39_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(16 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(16 as Weight))
 	}
 	fn set_approval_for_all() -> Weight {
 		(16_000_000 as Weight)
@@ -138,22 +189,42 @@ impl WeightInfo for () {
 	}
 	fn burn() -> Weight {
 		(19_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
 	}
 	fn burn_batch() -> Weight {
 		(43_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(16 as Weight))
 	}
 	fn transfer_from() -> Weight {
 		(20_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
 	}
 	fn batch_transfer_from() -> Weight {
 		(58_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(20 as Weight))
+	}
+	fn set_max_supply() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_uri() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn burn_from() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn burn_batch_from() -> Weight {
+		(46_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(12 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(16 as Weight))
 	}
 }
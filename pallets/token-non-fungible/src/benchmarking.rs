@@ -29,6 +29,15 @@ benchmarks! {
 		let _ = TokenNonFungible::<T>::mint(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice.clone())), 1u32.into(), alice.clone(), 1u32.into());
 	}: _(RawOrigin::Signed(alice), 1u32.into(), 1u32.into())
 
+	burn_from {
+		let alice: T::AccountId = account("alice", 0, SEED);
+		let bob: T::AccountId = account("bob", 0, SEED);
+
+		let _ = TokenNonFungible::<T>::create_token(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice.clone())), 1u32.into(), vec![0u8; 10], vec![0u8; 10], vec![0u8; 20]);
+		let _ = TokenNonFungible::<T>::mint(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice.clone())), 1u32.into(), alice.clone(), 1u32.into());
+		let _ = TokenNonFungible::<T>::set_approve_for_all(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice.clone())), 1u32.into(), bob.clone(), true);
+	}: _(RawOrigin::Signed(bob), 1u32.into(), 1u32.into())
+
 	approve {
 		let alice: T::AccountId = account("alice", 0, SEED);
 		let bob: T::AccountId = account("bob", 0, SEED);
@@ -25,11 +25,11 @@ use frame_support::{
 	traits::{Currency, Get, ReservableCurrency},
 	BoundedVec, PalletId,
 };
-use pallet_support::{NonFungibleEnumerable, NonFungibleMetadata};
+use pallet_support::{NonFungibleEnumerable, NonFungibleMetadata, NonFungibleReceiver};
 use primitives::TokenIndex;
 use scale_info::TypeInfo;
 use sp_runtime::{
-	traits::{AtLeast32BitUnsigned, One, TrailingZeroInput},
+	traits::{AtLeast32BitUnsigned, One, TrailingZeroInput, Zero},
 	RuntimeDebug,
 };
 use sp_std::prelude::*;
@@ -39,6 +39,7 @@ pub use pallet::*;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migrations;
 pub mod weights;
 pub use weights::WeightInfo;
 
@@ -91,12 +92,19 @@ pub mod pallet {
 
 		type Currency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
 
+		/// Asked whether a contract recipient accepts a transferred token.
+		type Receiver: NonFungibleReceiver<Self::AccountId, Self::NonFungibleTokenId, Self::TokenId>;
+
 		/// runtime weights.
 		type WeightInfo: WeightInfo;
 	}
 
+	/// The current storage version.
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::storage]
@@ -208,6 +216,18 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// Reverse index of `Balances`: the collections each account owns at least one token of.
+	#[pallet::storage]
+	pub(super) type AccountCollections<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::NonFungibleTokenId,
+		(),
+		OptionQuery,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -215,6 +235,8 @@ pub mod pallet {
 		Transfer(T::NonFungibleTokenId, T::AccountId, T::AccountId, T::TokenId),
 		Approval(T::NonFungibleTokenId, T::AccountId, T::AccountId, T::TokenId),
 		ApprovalForAll(T::NonFungibleTokenId, T::AccountId, T::AccountId, bool),
+		/// An operator burned a token. \[id, operator, owner, token_id\]
+		BurnFrom(T::NonFungibleTokenId, T::AccountId, T::AccountId, T::TokenId),
 	}
 
 	#[pallet::error]
@@ -234,10 +256,15 @@ pub mod pallet {
 		ConfuseBehavior,
 		TransferTokenNotOwn,
 		NotFound,
+		TransferRejected,
 	}
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate::<T>()
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -275,7 +302,7 @@ pub mod pallet {
 			Self::do_set_approve_for_all(&who, id, &operator, approved)
 		}
 
-		#[pallet::weight(T::WeightInfo::transfer_from())]
+		#[pallet::weight(T::WeightInfo::transfer_from().saturating_add(T::Receiver::max_weight()))]
 		pub fn transfer_from(
 			origin: OriginFor<T>,
 			id: T::NonFungibleTokenId,
@@ -307,6 +334,16 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 			Self::do_burn(&who, id, token_id)
 		}
+
+		#[pallet::weight(T::WeightInfo::burn_from())]
+		pub fn burn_from(
+			origin: OriginFor<T>,
+			id: T::NonFungibleTokenId,
+			token_id: T::TokenId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_burn_from(&who, id, token_id)
+		}
 	}
 }
 
@@ -389,6 +426,8 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Asks `T::Receiver` whether `to` accepts the token, callers must charge
+	/// `T::Receiver::max_weight()` on top of their own weight.
 	pub fn do_transfer_from(
 		who: &T::AccountId,
 		id: T::NonFungibleTokenId,
@@ -402,6 +441,10 @@ impl<T: Config> Pallet<T> {
 			Error::<T>::NotOwnerOrApproved
 		);
 		Self::do_transfer(id, from, to, token_id)?;
+		ensure!(
+			T::Receiver::on_received(who, from, to, id, token_id),
+			Error::<T>::TransferRejected
+		);
 		Ok(())
 	}
 
@@ -440,6 +483,8 @@ impl<T: Config> Pallet<T> {
 
 		Balances::<T>::insert(id, from, new_balance_from);
 		Balances::<T>::insert(id, to, new_balance_to);
+		Self::update_account_index(id, from, new_balance_from);
+		Self::update_account_index(id, to, new_balance_to);
 		Owners::<T>::insert(id, token_id, to);
 
 		Self::deposit_event(Event::Transfer(id.clone(), from.clone(), to.clone(), token_id));
@@ -468,6 +513,7 @@ impl<T: Config> Pallet<T> {
 		Self::add_token_to_owner_enumeration(id, to, token_id)?;
 
 		Balances::<T>::insert(id, to, new_balance);
+		Self::update_account_index(id, to, new_balance);
 		Owners::<T>::insert(id, token_id, to);
 
 		Self::deposit_event(Event::Transfer(
@@ -488,6 +534,29 @@ impl<T: Config> Pallet<T> {
 		let owner = Self::owner_of(id, token_id).ok_or(Error::<T>::NotFound)?;
 		ensure!(who == &owner, Error::<T>::NotTokenOwner);
 
+		Self::burn_token(id, owner, token_id)
+	}
+
+	pub fn do_burn_from(
+		who: &T::AccountId,
+		id: T::NonFungibleTokenId,
+		token_id: T::TokenId,
+	) -> DispatchResult {
+		let owner = Self::owner_of(id, token_id).ok_or(Error::<T>::NotFound)?;
+		ensure!(Self::is_approved_or_owner(id, who, token_id)?, Error::<T>::NotOwnerOrApproved);
+
+		Self::burn_token(id, owner.clone(), token_id)?;
+
+		Self::deposit_event(Event::BurnFrom(id, who.clone(), owner, token_id));
+
+		Ok(())
+	}
+
+	fn burn_token(
+		id: T::NonFungibleTokenId,
+		owner: T::AccountId,
+		token_id: T::TokenId,
+	) -> DispatchResult {
 		let balance = Self::balance_of(id, &owner);
 
 		let new_balance = match balance.checked_sub(One::one()) {
@@ -501,6 +570,7 @@ impl<T: Config> Pallet<T> {
 		Self::clear_approval(id, token_id)?;
 
 		Balances::<T>::insert(id, &owner, new_balance);
+		Self::update_account_index(id, &owner, new_balance);
 		Owners::<T>::remove(id, token_id);
 
 		Self::deposit_event(Event::Transfer(id.clone(), owner, Self::zero_account_id(), token_id));
@@ -508,6 +578,64 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	fn update_account_index(id: T::NonFungibleTokenId, who: &T::AccountId, balance: u32) {
+		if balance.is_zero() {
+			AccountCollections::<T>::remove(who, id);
+		} else {
+			AccountCollections::<T>::insert(who, id, ());
+		}
+	}
+
+	/// The tokens owned by `who`, at most `limit` of them, starting after the token `start`.
+	/// When `who` no longer owns `start`, its collection is listed again from its first token.
+	pub fn tokens_of(
+		who: &T::AccountId,
+		start: Option<(T::NonFungibleTokenId, T::TokenId)>,
+		limit: u32,
+	) -> Vec<(T::NonFungibleTokenId, T::TokenId)> {
+		let limit = limit as usize;
+		let mut tokens = Vec::new();
+
+		let collections = match start {
+			Some((id, token_id)) => {
+				let next_index = OwnedTokensIndex::<T>::try_get(id, (who, token_id))
+					.map(|index| index.saturating_add(1))
+					.unwrap_or_default();
+				Self::collect_owned_tokens(who, id, next_index, limit, &mut tokens);
+				AccountCollections::<T>::iter_key_prefix_from(
+					who,
+					AccountCollections::<T>::hashed_key_for(who, id),
+				)
+			},
+			None => AccountCollections::<T>::iter_key_prefix(who),
+		};
+
+		for id in collections {
+			if tokens.len() >= limit {
+				break
+			}
+			Self::collect_owned_tokens(who, id, 0, limit, &mut tokens);
+		}
+
+		tokens
+	}
+
+	fn collect_owned_tokens(
+		who: &T::AccountId,
+		id: T::NonFungibleTokenId,
+		from_index: TokenIndex,
+		limit: usize,
+		tokens: &mut Vec<(T::NonFungibleTokenId, T::TokenId)>,
+	) {
+		let balance = Self::balance_of(id, who);
+		for index in from_index..balance {
+			if tokens.len() >= limit {
+				return
+			}
+			tokens.push((id, OwnedTokens::<T>::get(id, (who, index))));
+		}
+	}
+
 	fn is_approved_or_owner(
 		id: T::NonFungibleTokenId,
 		spender: &T::AccountId,
@@ -520,7 +648,7 @@ impl<T: Config> Pallet<T> {
 			Self::is_approved_for_all(id, (&owner, spender)))
 	}
 
-	fn has_permission(id: T::NonFungibleTokenId, who: &T::AccountId) -> bool {
+	pub fn has_permission(id: T::NonFungibleTokenId, who: &T::AccountId) -> bool {
 		let token = Tokens::<T>::get(id).unwrap();
 		*who == token.owner
 	}
@@ -20,8 +20,10 @@ use crate as pallet_token_non_fungible;
 use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{ConstU16, ConstU64},
+	weights::Weight,
 	PalletId,
 };
+use pallet_support::NonFungibleReceiver;
 pub use pallet_token_non_fungible::{Error, Event as TokenFungibleEvent};
 use primitives::Balance;
 use sp_core::H256;
@@ -99,6 +101,20 @@ parameter_types! {
 	pub const CreateTokenDeposit: Balance = 500 * MILLICENTS;
 }
 
+/// Account that stands in for a contract rejecting every token it receives.
+pub const REJECTING_RECEIVER: u64 = 100;
+
+pub struct RejectingReceiver;
+impl NonFungibleReceiver<u64, u32, u128> for RejectingReceiver {
+	fn on_received(_operator: &u64, _from: &u64, to: &u64, _id: u32, _token_id: u128) -> bool {
+		*to != REJECTING_RECEIVER
+	}
+
+	fn max_weight() -> Weight {
+		0
+	}
+}
+
 impl pallet_token_non_fungible::Config for Test {
 	type Event = Event;
 	type PalletId = TokenNonFungiblePalletId;
@@ -107,6 +123,7 @@ impl pallet_token_non_fungible::Config for Test {
 	type StringLimit = StringLimit;
 	type CreateTokenDeposit = CreateTokenDeposit;
 	type Currency = Balances;
+	type Receiver = RejectingReceiver;
 	type WeightInfo = ();
 }
 
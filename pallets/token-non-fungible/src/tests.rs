@@ -283,3 +283,129 @@ fn transfer_from_should_not_work() {
 		assert_eq!(TokenNonFungible::total_supply(1), 2);
 	})
 }
+
+#[test]
+fn transfer_to_rejecting_receiver_should_not_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TokenNonFungible::create_token(
+			Origin::signed(ALICE),
+			1,
+			b"W3G".to_vec(),
+			b"W3G".to_vec(),
+			b"https://web3games.com/".to_vec(),
+		));
+		assert_ok!(TokenNonFungible::mint(Origin::signed(ALICE), 1, ALICE, 0));
+		assert_noop!(
+			TokenNonFungible::transfer_from(Origin::signed(ALICE), 1, ALICE, REJECTING_RECEIVER, 0),
+			Error::<Test>::TransferRejected
+		);
+		assert_eq!(TokenNonFungible::owner_of(1, 0), Some(ALICE));
+	})
+}
+
+#[test]
+fn burn_from_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TokenNonFungible::create_token(
+			Origin::signed(ALICE),
+			1,
+			b"W3G".to_vec(),
+			b"W3G".to_vec(),
+			b"https://web3games.com/".to_vec(),
+		));
+		assert_ok!(TokenNonFungible::mint(Origin::signed(ALICE), 1, BOB, 0));
+		assert_ok!(TokenNonFungible::mint(Origin::signed(ALICE), 1, BOB, 1));
+
+		assert_ok!(TokenNonFungible::approve(Origin::signed(BOB), 1, ALICE, 0));
+		assert_ok!(TokenNonFungible::burn_from(Origin::signed(ALICE), 1, 0));
+		assert_ok!(TokenNonFungible::set_approve_for_all(Origin::signed(BOB), 1, CHARLIE, true));
+		assert_ok!(TokenNonFungible::burn_from(Origin::signed(CHARLIE), 1, 1));
+
+		assert_eq!(TokenNonFungible::owner_of(1, 0), None);
+		assert_eq!(TokenNonFungible::owner_of(1, 1), None);
+		assert_eq!(TokenNonFungible::balance_of(1, BOB), 0);
+		assert_eq!(TokenNonFungible::total_supply(1), 0);
+	})
+}
+
+#[test]
+fn burn_from_should_not_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TokenNonFungible::burn_from(Origin::signed(ALICE), 1, 0),
+			Error::<Test>::NotFound
+		);
+		assert_ok!(TokenNonFungible::create_token(
+			Origin::signed(ALICE),
+			1,
+			b"W3G".to_vec(),
+			b"W3G".to_vec(),
+			b"https://web3games.com/".to_vec(),
+		));
+		assert_ok!(TokenNonFungible::mint(Origin::signed(ALICE), 1, BOB, 0));
+		assert_noop!(
+			TokenNonFungible::burn_from(Origin::signed(ALICE), 1, 0),
+			Error::<Test>::NotOwnerOrApproved
+		);
+	})
+}
+
+#[test]
+fn tokens_of_should_work() {
+	new_test_ext().execute_with(|| {
+		for id in 1..=2 {
+			assert_ok!(TokenNonFungible::create_token(
+				Origin::signed(ALICE),
+				id,
+				b"W3G".to_vec(),
+				b"W3G".to_vec(),
+				b"https://web3games.com/".to_vec(),
+			));
+		}
+		assert_ok!(TokenNonFungible::mint(Origin::signed(ALICE), 1, ALICE, 1));
+		assert_ok!(TokenNonFungible::mint(Origin::signed(ALICE), 1, ALICE, 2));
+		assert_ok!(TokenNonFungible::mint(Origin::signed(ALICE), 2, ALICE, 3));
+
+		let mut tokens = TokenNonFungible::tokens_of(&ALICE, None, 10);
+		tokens.sort();
+		assert_eq!(tokens, vec![(1, 1), (1, 2), (2, 3)]);
+
+		let mut pages = TokenNonFungible::tokens_of(&ALICE, None, 2);
+		assert_eq!(pages.len(), 2);
+		pages.extend(TokenNonFungible::tokens_of(&ALICE, pages.last().copied(), 2));
+		pages.sort();
+		assert_eq!(pages, tokens);
+
+		assert_ok!(TokenNonFungible::transfer_from(Origin::signed(ALICE), 2, ALICE, BOB, 3));
+		let mut tokens = TokenNonFungible::tokens_of(&ALICE, None, 10);
+		tokens.sort();
+		assert_eq!(tokens, vec![(1, 1), (1, 2)]);
+		assert_eq!(TokenNonFungible::tokens_of(&BOB, None, 10), vec![(2, 3)]);
+
+		assert_ok!(TokenNonFungible::burn(Origin::signed(BOB), 2, 3));
+		assert_eq!(TokenNonFungible::tokens_of(&BOB, None, 10), vec![]);
+	})
+}
+
+#[test]
+fn tokens_of_should_resume_after_cursor_transfer() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TokenNonFungible::create_token(
+			Origin::signed(ALICE),
+			1,
+			b"W3G".to_vec(),
+			b"W3G".to_vec(),
+			b"https://web3games.com/".to_vec(),
+		));
+		for token_id in 1..=3 {
+			assert_ok!(TokenNonFungible::mint(Origin::signed(ALICE), 1, ALICE, token_id));
+		}
+
+		let page = TokenNonFungible::tokens_of(&ALICE, None, 2);
+		assert_eq!(page, vec![(1, 1), (1, 2)]);
+
+		assert_ok!(TokenNonFungible::transfer_from(Origin::signed(ALICE), 1, ALICE, BOB, 2));
+		let page = TokenNonFungible::tokens_of(&ALICE, page.last().copied(), 2);
+		assert!(page.contains(&(1, 3)));
+	})
+}
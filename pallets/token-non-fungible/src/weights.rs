@@ -52,6 +52,7 @@ pub trait WeightInfo {
 	fn approve() -> Weight;
 	fn set_approve_for_all() -> Weight;
 	fn transfer_from() -> Weight;
+	fn burn_from() -> Weight;
 }
 
 /// Weights for pallet_token_non_fungible using the Web3Games node and recommended hardware.
@@ -71,10 +72,11 @@ impl<T: frame_system::Config> WeightInfo for W3GWeight<T> {
 	// Storage: TokenNonFungible OwnedTokens (r:0 w:1)
 	// Storage: TokenNonFungible AllTokens (r:0 w:1)
 	// Storage: TokenNonFungible OwnedTokensIndex (r:0 w:1)
+	// Storage: TokenNonFungible AccountCollections (r:0 w:1)
 	fn mint() -> Weight {
 		(34_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(4 as Weight))
-			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
 	}
 	// Storage: TokenNonFungible Owners (r:1 w:1)
 	// Storage: TokenNonFungible Balances (r:1 w:1)
@@ -84,10 +86,11 @@ impl<T: frame_system::Config> WeightInfo for W3GWeight<T> {
 	// Storage: TokenNonFungible OwnedTokensIndex (r:1 w:1)
 	// Storage: TokenNonFungible TokenApprovals (r:0 w:1)
 	// Storage: TokenNonFungible OwnedTokens (r:0 w:1)
+	// Storage: TokenNonFungible AccountCollections (r:0 w:1)
 	fn burn() -> Weight {
 		(49_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(6 as Weight))
-			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
 	}
 	// Storage: TokenNonFungible Owners (r:1 w:0)
 	// Storage: TokenNonFungible TokenApprovals (r:0 w:1)
@@ -106,10 +109,26 @@ impl<T: frame_system::Config> WeightInfo for W3GWeight<T> {
 	// Storage: TokenNonFungible OwnedTokensIndex (r:1 w:2)
 	// Storage: TokenNonFungible TokenApprovals (r:0 w:1)
 	// Storage: TokenNonFungible OwnedTokens (r:0 w:2)
+	// Storage: TokenNonFungible AccountCollections (r:0 w:2)
 	fn transfer_from() -> Weight {
 		(43_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(4 as Weight))
-			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+	}
+	// Storage: TokenNonFungible Owners (r:1 w:1)
+	// Storage: TokenNonFungible TokenApprovals (r:1 w:1)
+	// Storage: TokenNonFungible OperatorApprovals (r:1 w:0)
+	// Storage: TokenNonFungible Balances (r:1 w:1)
+	// Storage: TokenNonFungible TotalSupply (r:1 w:1)
+	// Storage: TokenNonFungible AllTokensIndex (r:1 w:1)
+	// Storage: TokenNonFungible AllTokens (r:1 w:1)
+	// Storage: TokenNonFungible OwnedTokensIndex (r:1 w:1)
+	// Storage: TokenNonFungible OwnedTokens (r:0 w:1)
+	// Storage: TokenNonFungible AccountCollections (r:0 w:1)
+	fn burn_from() -> Weight {
+		(53_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
 	}
 }
 
@@ -123,12 +142,12 @@ impl WeightInfo for () {
 	fn mint() -> Weight {
 		(34_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
 	}
 	fn burn() -> Weight {
 		(49_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
 	}
 	fn approve() -> Weight {
 		(16_000_000 as Weight)
@@ -142,6 +161,11 @@ impl WeightInfo for () {
 	fn transfer_from() -> Weight {
 		(43_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
+	}
+	fn burn_from() -> Weight {
+		(53_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
 	}
 }
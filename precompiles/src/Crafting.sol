@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: MIT
+// @custom:address 0x0000000000000000000000000000000000000407
+pragma solidity ^0.8.0;
+
+interface Crafting {
+    /// Inputs and outputs are (kind, id, token_id, amount) tuples.
+    /// Input kinds: 0 multi token, 1 non fungible token, 2 fungible token.
+    /// Output kinds: 0 multi token, 1 non fungible token.
+    struct Item {
+        uint8 kind;
+        uint256 id;
+        uint256 token_id;
+        uint256 amount;
+    }
+    struct Outcome {
+        uint32 weight;
+        Item[] outputs;
+    }
+
+    function create_recipe(Item[] calldata inputs,Item[] calldata outputs,Outcome[] calldata outcomes) external;
+    /// token_ids are burned for the non fungible token inputs, in their order, craft after craft.
+    /// Recipes with outcomes can't be crafted here, as a caller could revert until it likes the roll.
+    function craft(uint256 recipe_id,uint256 times,uint256[] calldata token_ids) external;
+    function set_recipe_enabled(uint256 recipe_id,bool enabled) external;
+    function remove_recipe(uint256 recipe_id) external;
+}
@@ -3,13 +3,27 @@
 pragma solidity ^0.8.0;
 
 interface Exchange {
-    function create_pool(uint256 token_a,uint256 token_b) external;
-    function add_liquidity(uint256 token_a,uint256 token_b,uint256 amount_a_desired,uint256 amount_b_desired,uint256 amount_a_min,uint256 amount_b_min,address to,uint256 deadline) external;
-    function add_liquidity_w3g(uint256 token,uint256 amount_w3g_desired,uint256 amount_desired,uint256 amount_w3g_min,uint256 amount_min,address to,uint256 deadline) external;
-    function remove_liquidity(uint256 token_a,uint256 token_b,uint256 liquidity,uint256 amount_a_min,uint256 amount_b_min,address to,uint256 deadline) external;
-    function remove_liquidity_w3g(uint256 token,uint256 liquidity,uint256 amount_w3g_min,uint256 amount_min,address to,uint256 deadline) external;
+    function create_pool(uint256 token_a,uint256 token_b,uint32 fee) external;
+    function add_liquidity(uint256 token_a,uint256 token_b,uint32 fee,uint256 amount_a_desired,uint256 amount_b_desired,uint256 amount_a_min,uint256 amount_b_min,address to,uint256 deadline) external;
+    function add_liquidity_w3g(uint256 token,uint32 fee,uint256 amount_w3g_desired,uint256 amount_desired,uint256 amount_w3g_min,uint256 amount_min,address to,uint256 deadline) external;
+    function remove_liquidity(uint256 token_a,uint256 token_b,uint32 fee,uint256 liquidity,uint256 amount_a_min,uint256 amount_b_min,address to,uint256 deadline) external;
+    function remove_liquidity_w3g(uint256 token,uint32 fee,uint256 liquidity,uint256 amount_w3g_min,uint256 amount_min,address to,uint256 deadline) external;
     function swap_exact_tokens_for_tokens(uint256 amount_in,uint256 amount_out_min,uint256[] memory path,address to,uint256 deadline) external;
     function swap_exact_w3g_for_tokens(uint256 amount_in_w3g,uint256 amount_out_min,uint256[] memory path,address to,uint256 deadline) external;
     function swap_tokens_for_exact_tokens(uint256 amount_out,uint256 amount_in_max,uint256[] memory path,address to,uint256 deadline) external;
     function swap_tokens_for_exact_w3g(uint256 amount_out_w3g,uint256 amount_in_max,uint256[] memory path,address to,uint256 deadline) external;
+    function swap_exact_tokens_for_w3g(uint256 amount_in,uint256 amount_out_w3g_min,uint256[] memory path,address to,uint256 deadline) external;
+    function swap_w3g_for_exact_tokens(uint256 amount_out,uint256 amount_in_w3g_max,uint256[] memory path,address to,uint256 deadline) external;
+    function zap_in(uint256 token_in,uint256 amount_in,uint256 token_a,uint256 token_b,uint32 fee,uint256 min_liquidity,address to,uint256 deadline) external;
+    function zap_out(uint256 token_a,uint256 token_b,uint32 fee,uint256 liquidity,uint256 token_out,uint256 amount_out_min,address to,uint256 deadline) external;
+    function flash_swap(uint256 token_a,uint256 token_b,uint32 fee,uint256 amount_a_out,uint256 amount_b_out,bytes memory data) external;
+    function flash_loan(uint256 token,uint256 pair_token,uint32 fee,uint256 amount,bytes memory data) external;
+    function flash_repay(uint256 token_a,uint256 token_b,uint32 fee,uint256 token,uint256 amount) external;
+    function consult(uint256 token_a,uint256 token_b,uint32 fee,uint256 window) external view returns (uint256 price_a,uint256 price_b);
+}
+
+// Implemented by flash borrowers, which must repay the pool through flash_repay and answer with the callback selector.
+interface FlashBorrower {
+    function onFlashSwap(uint256 token_a,uint256 token_b,uint32 fee,uint256 amount_a_out,uint256 amount_b_out,bytes memory data) external returns (bytes4);
+    function onFlashLoan(uint256 token,uint256 pair_token,uint32 fee,uint256 amount,uint256 premium,bytes memory data) external returns (bytes4);
 }
@@ -7,4 +7,6 @@ interface Launchpad {
     function buy_token(uint256 pool_id,uint256 amount) external;
     function owner_claim(uint256 pool_id) external;
     function claim(uint256 pool_id) external;
+    function set_liquidity_config(uint256 pool_id,uint256 buy_token_percent,uint256 sale_token_amount,uint256 fee,uint256 lock_period) external;
+    function claim_liquidity(uint256 pool_id) external;
 }
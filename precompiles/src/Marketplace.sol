@@ -4,9 +4,14 @@ pragma solidity ^0.8.0;
 
 interface Marketplace {
     function create_order(uint256 group_id,uint256 token_id,uint256 asset_type,uint256 price,uint256 duration) external;
+    function create_order_in_token(uint256 group_id,uint256 token_id,uint256 asset_type,uint256 payment_token_id,uint256 price,uint256 duration) external;
     function cancel_order(uint256 group_id,uint256 token_id,uint256 asset_type) external;
     function execute_order(uint256 group_id,uint256 token_id,uint256 asset_type) external;
     function place_bid(uint256 group_id,uint256 token_id,uint256 asset_type,uint256 price,uint256 duration) external;
+    function place_bid_in_token(uint256 group_id,uint256 token_id,uint256 asset_type,uint256 payment_token_id,uint256 price,uint256 duration) external;
     function cancel_bid(uint256 group_id,uint256 token_id,uint256 asset_type) external;
     function accept_bid(uint256 group_id,uint256 token_id,uint256 asset_type) external;
+    function create_dutch_order(uint256 group_id,uint256 token_id,uint256 asset_type,uint256 start_price,uint256 end_price,uint256 start_block,uint256 end_block) external;
+    function create_dutch_order_in_token(uint256 group_id,uint256 token_id,uint256 asset_type,uint256 payment_token_id,uint256 start_price,uint256 end_price,uint256 start_block,uint256 end_block) external;
+    function get_current_price(uint256 group_id,uint256 token_id,uint256 asset_type) external view returns (uint256 price);
 }
@@ -0,0 +1,223 @@
+// This file is part of Web3Games.
+
+// Copyright (C) 2021-2022 Web3Games https://web3games.org
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use fp_evm::{PrecompileHandle, PrecompileOutput};
+use frame_support::dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo};
+use pallet_crafting::{CraftingInput, CraftingOutput};
+use pallet_evm::{AddressMapping, PrecompileSet};
+use precompile_utils::prelude::*;
+use sp_core::H160;
+use sp_std::{fmt::Debug, marker::PhantomData, prelude::*};
+
+#[generate_function_selector]
+#[derive(Debug, PartialEq)]
+enum Action {
+	CreateRecipe = "create_recipe((uint8,uint256,uint256,uint256)[],(uint8,uint256,uint256,uint256)[],(uint32,(uint8,uint256,uint256,uint256)[])[])",
+	Craft = "craft(uint256,uint256,uint256[])",
+	SetRecipeEnabled = "set_recipe_enabled(uint256,bool)",
+	RemoveRecipe = "remove_recipe(uint256)",
+}
+
+pub struct CraftingExtension<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> PrecompileSet for CraftingExtension<Runtime>
+where
+	Runtime: pallet_crafting::Config + pallet_evm::Config,
+	Runtime::Call: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	<Runtime::Call as Dispatchable>::Origin: From<Option<Runtime::AccountId>>,
+	Runtime::Call: From<pallet_crafting::Call<Runtime>>,
+{
+	fn execute(&self, handle: &mut impl PrecompileHandle) -> Option<EvmResult<PrecompileOutput>> {
+		let result = {
+			let selector = match handle.read_selector() {
+				Ok(selector) => selector,
+				Err(e) => return Some(Err(e)),
+			};
+			if let Err(err) = handle.check_function_modifier(match selector {
+				Action::CreateRecipe |
+				Action::Craft |
+				Action::SetRecipeEnabled |
+				Action::RemoveRecipe => FunctionModifier::NonPayable,
+			}) {
+				return Some(Err(err))
+			}
+			match selector {
+				Action::CreateRecipe => Self::create_recipe(handle),
+				Action::Craft => Self::craft(handle),
+				Action::SetRecipeEnabled => Self::set_recipe_enabled(handle),
+				Action::RemoveRecipe => Self::remove_recipe(handle),
+			}
+		};
+		Some(result)
+	}
+	fn is_precompile(&self, _address: H160) -> bool {
+		true
+	}
+}
+
+impl<Runtime> CraftingExtension<Runtime> {
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<Runtime> CraftingExtension<Runtime>
+where
+	Runtime: pallet_crafting::Config + pallet_evm::Config,
+	Runtime::Call: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	<Runtime::Call as Dispatchable>::Origin: From<Option<Runtime::AccountId>>,
+	Runtime::Call: From<pallet_crafting::Call<Runtime>>,
+{
+	/// Inputs and outputs are `(kind, id, token_id, amount)` tuples. Input kinds are `0` for a
+	/// multi token, `1` for a non fungible token and `2` for a fungible token, output kinds
+	/// are `0` for a multi token and `1` for a non fungible token. Unused fields are ignored.
+	fn create_recipe(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let mut input = handle.read_input()?;
+		input.expect_arguments(3)?;
+
+		let inputs = input
+			.read::<Vec<(u8, u128, u128, u128)>>()?
+			.into_iter()
+			.map(crafting_input)
+			.collect::<EvmResult<Vec<_>>>()?;
+		let outputs = input
+			.read::<Vec<(u8, u128, u128, u128)>>()?
+			.into_iter()
+			.map(crafting_output)
+			.collect::<EvmResult<Vec<_>>>()?;
+		let outcomes = input
+			.read::<Vec<(u32, Vec<(u8, u128, u128, u128)>)>>()?
+			.into_iter()
+			.map(|(weight, outputs)| {
+				Ok((
+					weight,
+					outputs.into_iter().map(crafting_output).collect::<EvmResult<Vec<_>>>()?,
+				))
+			})
+			.collect::<EvmResult<Vec<_>>>()?;
+
+		{
+			let caller: Runtime::AccountId =
+				Runtime::AddressMapping::into_account_id(handle.context().caller);
+
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(caller).into(),
+				pallet_crafting::Call::<Runtime>::create_recipe { inputs, outputs, outcomes },
+			)?;
+		}
+
+		// Return call information
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn craft(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let mut input = handle.read_input()?;
+		input.expect_arguments(3)?;
+
+		let recipe_id = input.read::<u64>()?;
+		let times = input.read::<u32>()?;
+		let token_ids = input.read::<Vec<u128>>()?;
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		pallet_crafting::Pallet::<Runtime>::ensure_deterministic(recipe_id)
+			.map_err(|_| revert("Recipe not found or randomized"))?;
+
+		{
+			let caller: Runtime::AccountId =
+				Runtime::AddressMapping::into_account_id(handle.context().caller);
+
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(caller).into(),
+				pallet_crafting::Call::<Runtime>::craft { recipe_id, times, token_ids },
+			)?;
+		}
+
+		// Return call information
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn set_recipe_enabled(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let mut input = handle.read_input()?;
+		input.expect_arguments(2)?;
+
+		let recipe_id = input.read::<u64>()?;
+		let enabled = input.read::<bool>()?;
+
+		{
+			let caller: Runtime::AccountId =
+				Runtime::AddressMapping::into_account_id(handle.context().caller);
+
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(caller).into(),
+				pallet_crafting::Call::<Runtime>::set_recipe_enabled { recipe_id, enabled },
+			)?;
+		}
+
+		// Return call information
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn remove_recipe(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let mut input = handle.read_input()?;
+		input.expect_arguments(1)?;
+
+		let recipe_id = input.read::<u64>()?;
+
+		{
+			let caller: Runtime::AccountId =
+				Runtime::AddressMapping::into_account_id(handle.context().caller);
+
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(caller).into(),
+				pallet_crafting::Call::<Runtime>::remove_recipe { recipe_id },
+			)?;
+		}
+
+		// Return call information
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+}
+
+fn crafting_input(
+	(kind, id, token_id, amount): (u8, u128, u128, u128),
+) -> EvmResult<CraftingInput> {
+	match kind {
+		0 => Ok(CraftingInput::MultiToken(id, token_id, amount)),
+		1 => Ok(CraftingInput::NonFungibleToken(id)),
+		2 => Ok(CraftingInput::FungibleToken(id, amount)),
+		_ => Err(revert("Unknown input kind")),
+	}
+}
+
+fn crafting_output(
+	(kind, id, token_id, amount): (u8, u128, u128, u128),
+) -> EvmResult<CraftingOutput> {
+	match kind {
+		0 => Ok(CraftingOutput::MultiToken(id, token_id, amount)),
+		1 => Ok(CraftingOutput::NonFungibleToken(id)),
+		_ => Err(revert("Unknown output kind")),
+	}
+}
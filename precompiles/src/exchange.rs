@@ -16,12 +16,17 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use fp_evm::{PrecompileHandle, PrecompileOutput};
-use frame_support::dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo};
-use pallet_evm::{AddressMapping, PrecompileSet};
+use fp_evm::{Context, ExitReason, PrecompileFailure, PrecompileHandle, PrecompileOutput};
+use frame_support::{
+	dispatch::{DispatchError, Dispatchable, GetDispatchInfo, PostDispatchInfo},
+	traits::Get,
+};
+use pallet_evm::{AddressMapping, GasWeightMapping, PrecompileSet};
+use pallet_exchange::WeightInfo;
 use precompile_utils::prelude::*;
 use primitives::{Balance, BlockNumber};
-use sp_core::H160;
+use sp_core::{H160, H256, U256};
+use sp_runtime::FixedPointNumber;
 use sp_std::{fmt::Debug, marker::PhantomData, prelude::*};
 
 pub type FungibleTokenIdOf<Runtime> = <Runtime as pallet_token_fungible::Config>::FungibleTokenId;
@@ -29,17 +34,37 @@ pub type FungibleTokenIdOf<Runtime> = <Runtime as pallet_token_fungible::Config>
 #[generate_function_selector]
 #[derive(Debug, PartialEq)]
 enum Action {
-	CreatePool = "create_pool(uint256,uint256)",
-	AddLiquidity = "add_liquidity(uint256,uint256,uint256,uint256,uint256,uint256,address,uint256)",
-	AddLiquidityW3G = "add_liquidity_w3g(uint256,uint256,uint256,uint256,uint256,address,uint256)",
-	RemoveLiquidity = "remove_liquidity(uint256,uint256,uint256,uint256,uint256,address,uint256)",
-	RemoveLiquidityW3G = "remove_liquidity_w3g(uint256,uint256,uint256,uint256,address,uint256)",
+	CreatePool = "create_pool(uint256,uint256,uint32)",
+	AddLiquidity =
+		"add_liquidity(uint256,uint256,uint32,uint256,uint256,uint256,uint256,address,uint256)",
+	AddLiquidityW3G =
+		"add_liquidity_w3g(uint256,uint32,uint256,uint256,uint256,uint256,address,uint256)",
+	RemoveLiquidity =
+		"remove_liquidity(uint256,uint256,uint32,uint256,uint256,uint256,address,uint256)",
+	RemoveLiquidityW3G =
+		"remove_liquidity_w3g(uint256,uint32,uint256,uint256,uint256,address,uint256)",
 	SwapExactTokensForTokens =
 		"swap_exact_tokens_for_tokens(uint256,uint256,uint256[],address,uint256)",
 	SwapExactW3GForTokens = "swap_exact_w3g_for_tokens(uint256,uint256,uint256[],address,uint256)",
 	SwapTokensForExactTokens =
 		"swap_tokens_for_exact_tokens(uint256,uint256,uint256[],address,uint256)",
 	SwapTokensForExactW3G = "swap_tokens_for_exact_w3g(uint256,uint256,uint256[],address,uint256)",
+	SwapExactTokensForW3G = "swap_exact_tokens_for_w3g(uint256,uint256,uint256[],address,uint256)",
+	SwapW3GForExactTokens = "swap_w3g_for_exact_tokens(uint256,uint256,uint256[],address,uint256)",
+	ZapIn = "zap_in(uint256,uint256,uint256,uint256,uint32,uint256,address,uint256)",
+	ZapOut = "zap_out(uint256,uint256,uint32,uint256,uint256,uint256,address,uint256)",
+	FlashSwap = "flash_swap(uint256,uint256,uint32,uint256,uint256,bytes)",
+	FlashLoan = "flash_loan(uint256,uint256,uint32,uint256,bytes)",
+	FlashRepay = "flash_repay(uint256,uint256,uint32,uint256,uint256)",
+	Consult = "consult(uint256,uint256,uint32,uint256)",
+}
+
+/// The callbacks invoked on flash borrowers, which must answer with their own selector.
+#[generate_function_selector]
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum FlashAction {
+	OnFlashSwap = "onFlashSwap(uint256,uint256,uint32,uint256,uint256,bytes)",
+	OnFlashLoan = "onFlashLoan(uint256,uint256,uint32,uint256,uint256,bytes)",
 }
 
 pub struct ExchangeExtension<Runtime>(PhantomData<Runtime>);
@@ -68,7 +93,15 @@ where
 				Action::SwapExactTokensForTokens |
 				Action::SwapExactW3GForTokens |
 				Action::SwapTokensForExactTokens |
-				Action::SwapTokensForExactW3G => FunctionModifier::NonPayable,
+				Action::SwapTokensForExactW3G |
+				Action::SwapExactTokensForW3G |
+				Action::SwapW3GForExactTokens |
+				Action::ZapIn |
+				Action::ZapOut |
+				Action::FlashSwap |
+				Action::FlashLoan |
+				Action::FlashRepay => FunctionModifier::NonPayable,
+				Action::Consult => FunctionModifier::View,
 			}) {
 				return Some(Err(err))
 			}
@@ -82,6 +115,14 @@ where
 				Action::SwapExactW3GForTokens => Self::swap_exact_w3g_for_tokens(handle),
 				Action::SwapTokensForExactTokens => Self::swap_tokens_for_exact_tokens(handle),
 				Action::SwapTokensForExactW3G => Self::swap_tokens_for_exact_w3g(handle),
+				Action::SwapExactTokensForW3G => Self::swap_exact_tokens_for_w3g(handle),
+				Action::SwapW3GForExactTokens => Self::swap_w3g_for_exact_tokens(handle),
+				Action::ZapIn => Self::zap_in(handle),
+				Action::ZapOut => Self::zap_out(handle),
+				Action::FlashSwap => Self::flash_swap(handle),
+				Action::FlashLoan => Self::flash_loan(handle),
+				Action::FlashRepay => Self::flash_repay(handle),
+				Action::Consult => Self::consult(handle),
 			}
 		};
 		Some(result)
@@ -108,9 +149,10 @@ where
 {
 	fn create_pool(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
 		let mut input = EvmDataReader::new_skip_selector(handle.input())?;
-		input.expect_arguments(2)?;
+		input.expect_arguments(3)?;
 		let token_a: FungibleTokenIdOf<Runtime> = input.read::<u128>()?.into();
 		let token_b: FungibleTokenIdOf<Runtime> = input.read::<u128>()?.into();
+		let fee = input.read::<u32>()?;
 		{
 			// Build call with origin.
 			let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
@@ -118,16 +160,17 @@ where
 			RuntimeHelper::<Runtime>::try_dispatch(
 				handle,
 				Some(origin).into(),
-				pallet_exchange::Call::<Runtime>::create_pool { token_a, token_b },
+				pallet_exchange::Call::<Runtime>::create_pool { token_a, token_b, fee },
 			)?;
 		}
 		Ok(succeed(EvmDataWriter::new().write(true).build()))
 	}
 	fn add_liquidity(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
 		let mut input = EvmDataReader::new_skip_selector(handle.input())?;
-		input.expect_arguments(8)?;
+		input.expect_arguments(9)?;
 		let token_a: FungibleTokenIdOf<Runtime> = input.read::<u128>()?.into();
 		let token_b: FungibleTokenIdOf<Runtime> = input.read::<u128>()?.into();
+		let fee = input.read::<u32>()?;
 		let amount_a_desired: Balance = input.read::<u128>()?.into();
 		let amount_b_desired: Balance = input.read::<u128>()?.into();
 		let amount_a_min: Balance = input.read::<u128>()?.into();
@@ -145,6 +188,7 @@ where
 				pallet_exchange::Call::<Runtime>::add_liquidity {
 					token_a,
 					token_b,
+					fee,
 					amount_a_desired,
 					amount_b_desired,
 					amount_a_min,
@@ -159,8 +203,9 @@ where
 	}
 	fn add_liquidity_w3g(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
 		let mut input = EvmDataReader::new_skip_selector(handle.input())?;
-		input.expect_arguments(7)?;
+		input.expect_arguments(8)?;
 		let token: FungibleTokenIdOf<Runtime> = input.read::<u128>()?.into();
+		let fee = input.read::<u32>()?;
 		let amount_w3g_desired: Balance = input.read::<u128>()?.into();
 		let amount_desired: Balance = input.read::<u128>()?.into();
 		let amount_w3g_min: Balance = input.read::<u128>()?.into();
@@ -177,6 +222,7 @@ where
 				Some(origin).into(),
 				pallet_exchange::Call::<Runtime>::add_liquidity_w3g {
 					token,
+					fee,
 					amount_w3g_desired,
 					amount_desired,
 					amount_w3g_min,
@@ -191,9 +237,10 @@ where
 	}
 	fn remove_liquidity(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
 		let mut input = EvmDataReader::new_skip_selector(handle.input())?;
-		input.expect_arguments(7)?;
+		input.expect_arguments(8)?;
 		let token_a: FungibleTokenIdOf<Runtime> = input.read::<u128>()?.into();
 		let token_b: FungibleTokenIdOf<Runtime> = input.read::<u128>()?.into();
+		let fee = input.read::<u32>()?;
 		let liquidity: Balance = input.read::<u128>()?.into();
 		let amount_a_min: Balance = input.read::<u128>()?.into();
 		let amount_b_min: Balance = input.read::<u128>()?.into();
@@ -210,6 +257,7 @@ where
 				pallet_exchange::Call::<Runtime>::remove_liquidity {
 					token_a,
 					token_b,
+					fee,
 					liquidity,
 					amount_a_min,
 					amount_b_min,
@@ -223,8 +271,9 @@ where
 	}
 	fn remove_liquidity_w3g(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
 		let mut input = EvmDataReader::new_skip_selector(handle.input())?;
-		input.expect_arguments(6)?;
+		input.expect_arguments(7)?;
 		let token: FungibleTokenIdOf<Runtime> = input.read::<u128>()?.into();
+		let fee = input.read::<u32>()?;
 		let liquidity: Balance = input.read::<u128>()?.into();
 		let amount_w3g_min: Balance = input.read::<u128>()?.into();
 		let amount_min: Balance = input.read::<u128>()?.into();
@@ -240,6 +289,7 @@ where
 				Some(origin).into(),
 				pallet_exchange::Call::<Runtime>::remove_liquidity_w3g {
 					token,
+					fee,
 					liquidity,
 					amount_w3g_min,
 					amount_min,
@@ -387,4 +437,314 @@ where
 
 		Ok(succeed(EvmDataWriter::new().write(true).build()))
 	}
+	fn swap_exact_tokens_for_w3g(
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		let mut input = EvmDataReader::new_skip_selector(handle.input())?;
+		input.expect_arguments(5)?;
+		let amount_in: Balance = input.read::<u128>()?.into();
+		let amount_out_w3g_min: Balance = input.read::<u128>()?.into();
+		let u128_path = input.read::<Vec<u128>>()?;
+		let mut path: Vec<FungibleTokenIdOf<Runtime>> = vec![];
+		for i in 0..u128_path.len() {
+			path.push(u128_path[i].into())
+		}
+		let to: H160 = input.read::<Address>()?.into();
+		let deadline = input.read::<BlockNumber>()?.into();
+		let to: Runtime::AccountId = Runtime::AddressMapping::into_account_id(to);
+		{
+			// Build call with origin.
+			let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(origin).into(),
+				pallet_exchange::Call::<Runtime>::swap_exact_tokens_for_w3g {
+					amount_in,
+					amount_out_w3g_min,
+					path,
+					to,
+					deadline,
+				},
+			)?;
+		}
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+	fn swap_w3g_for_exact_tokens(
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		let mut input = EvmDataReader::new_skip_selector(handle.input())?;
+		input.expect_arguments(5)?;
+		let amount_out: Balance = input.read::<u128>()?.into();
+		let amount_in_w3g_max: Balance = input.read::<u128>()?.into();
+		let u128_path = input.read::<Vec<u128>>()?;
+		let mut path: Vec<FungibleTokenIdOf<Runtime>> = vec![];
+		for i in 0..u128_path.len() {
+			path.push(u128_path[i].into())
+		}
+		let to: H160 = input.read::<Address>()?.into();
+		let deadline = input.read::<BlockNumber>()?.into();
+		let to: Runtime::AccountId = Runtime::AddressMapping::into_account_id(to);
+		{
+			// Build call with origin.
+			let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(origin).into(),
+				pallet_exchange::Call::<Runtime>::swap_w3g_for_exact_tokens {
+					amount_out,
+					amount_in_w3g_max,
+					path,
+					to,
+					deadline,
+				},
+			)?;
+		}
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn zap_in(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let mut input = EvmDataReader::new_skip_selector(handle.input())?;
+		input.expect_arguments(8)?;
+		let token_in: FungibleTokenIdOf<Runtime> = input.read::<u128>()?.into();
+		let amount_in: Balance = input.read::<u128>()?.into();
+		let token_a: FungibleTokenIdOf<Runtime> = input.read::<u128>()?.into();
+		let token_b: FungibleTokenIdOf<Runtime> = input.read::<u128>()?.into();
+		let fee = input.read::<u32>()?;
+		let min_liquidity: Balance = input.read::<u128>()?.into();
+		let to: H160 = input.read::<Address>()?.into();
+		let deadline = input.read::<BlockNumber>()?.into();
+		let to: Runtime::AccountId = Runtime::AddressMapping::into_account_id(to);
+		{
+			// Build call with origin.
+			let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(origin).into(),
+				pallet_exchange::Call::<Runtime>::zap_in {
+					token_in,
+					amount_in,
+					token_a,
+					token_b,
+					fee,
+					min_liquidity,
+					to,
+					deadline,
+				},
+			)?;
+		}
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn zap_out(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let mut input = EvmDataReader::new_skip_selector(handle.input())?;
+		input.expect_arguments(8)?;
+		let token_a: FungibleTokenIdOf<Runtime> = input.read::<u128>()?.into();
+		let token_b: FungibleTokenIdOf<Runtime> = input.read::<u128>()?.into();
+		let fee = input.read::<u32>()?;
+		let liquidity: Balance = input.read::<u128>()?.into();
+		let token_out: FungibleTokenIdOf<Runtime> = input.read::<u128>()?.into();
+		let amount_out_min: Balance = input.read::<u128>()?.into();
+		let to: H160 = input.read::<Address>()?.into();
+		let deadline = input.read::<BlockNumber>()?.into();
+		let to: Runtime::AccountId = Runtime::AddressMapping::into_account_id(to);
+		{
+			// Build call with origin.
+			let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(origin).into(),
+				pallet_exchange::Call::<Runtime>::zap_out {
+					token_a,
+					token_b,
+					fee,
+					liquidity,
+					token_out,
+					amount_out_min,
+					to,
+					deadline,
+				},
+			)?;
+		}
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn flash_swap(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let mut input = EvmDataReader::new_skip_selector(handle.input())?;
+		input.expect_arguments(6)?;
+		let raw_token_a = input.read::<u128>()?;
+		let raw_token_b = input.read::<u128>()?;
+		let token_a: FungibleTokenIdOf<Runtime> = raw_token_a.into();
+		let token_b: FungibleTokenIdOf<Runtime> = raw_token_b.into();
+		let fee = input.read::<u32>()?;
+		let amount_a_out: Balance = input.read::<u128>()?.into();
+		let amount_b_out: Balance = input.read::<u128>()?.into();
+		let data = input.read::<Bytes>()?;
+
+		handle.record_cost(<Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(
+			<Runtime as pallet_exchange::Config>::WeightInfo::flash_swap(),
+		))?;
+
+		let borrower = handle.context().caller;
+		let who: Runtime::AccountId = Runtime::AddressMapping::into_account_id(borrower);
+		pallet_exchange::Pallet::<Runtime>::begin_flash_swap(
+			&who,
+			token_a,
+			token_b,
+			fee,
+			amount_a_out,
+			amount_b_out,
+		)
+		.map_err(dispatch_failure)?;
+
+		let action = FlashAction::OnFlashSwap;
+		let callback_input = EvmDataWriter::new_with_selector(action)
+			.write(raw_token_a)
+			.write(raw_token_b)
+			.write(fee)
+			.write(amount_a_out)
+			.write(amount_b_out)
+			.write(data)
+			.build();
+		flash_callback(handle, borrower, action, callback_input)?;
+
+		pallet_exchange::Pallet::<Runtime>::end_flash_swap(
+			&who,
+			token_a,
+			token_b,
+			fee,
+			amount_a_out,
+			amount_b_out,
+		)
+		.map_err(dispatch_failure)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+	fn flash_loan(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let mut input = EvmDataReader::new_skip_selector(handle.input())?;
+		input.expect_arguments(5)?;
+		let raw_token = input.read::<u128>()?;
+		let raw_pair_token = input.read::<u128>()?;
+		let token: FungibleTokenIdOf<Runtime> = raw_token.into();
+		let pair_token: FungibleTokenIdOf<Runtime> = raw_pair_token.into();
+		let fee = input.read::<u32>()?;
+		let amount: Balance = input.read::<u128>()?.into();
+		let data = input.read::<Bytes>()?;
+
+		handle.record_cost(<Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(
+			<Runtime as pallet_exchange::Config>::WeightInfo::flash_loan(),
+		))?;
+
+		let borrower = handle.context().caller;
+		let who: Runtime::AccountId = Runtime::AddressMapping::into_account_id(borrower);
+		let premium = pallet_exchange::Pallet::<Runtime>::begin_flash_loan(
+			&who, token, pair_token, fee, amount,
+		)
+		.map_err(dispatch_failure)?;
+
+		let action = FlashAction::OnFlashLoan;
+		let callback_input = EvmDataWriter::new_with_selector(action)
+			.write(raw_token)
+			.write(raw_pair_token)
+			.write(fee)
+			.write(amount)
+			.write(premium)
+			.write(data)
+			.build();
+		flash_callback(handle, borrower, action, callback_input)?;
+
+		pallet_exchange::Pallet::<Runtime>::end_flash_loan(
+			&who, token, pair_token, fee, amount, premium,
+		)
+		.map_err(dispatch_failure)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+	fn flash_repay(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let mut input = EvmDataReader::new_skip_selector(handle.input())?;
+		input.expect_arguments(5)?;
+		let token_a: FungibleTokenIdOf<Runtime> = input.read::<u128>()?.into();
+		let token_b: FungibleTokenIdOf<Runtime> = input.read::<u128>()?.into();
+		let fee = input.read::<u32>()?;
+		let token: FungibleTokenIdOf<Runtime> = input.read::<u128>()?.into();
+		let amount: Balance = input.read::<u128>()?.into();
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost() * 4)?;
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost() * 2)?;
+
+		let who: Runtime::AccountId =
+			Runtime::AddressMapping::into_account_id(handle.context().caller);
+		pallet_exchange::Pallet::<Runtime>::flash_repay(&who, token_a, token_b, fee, token, amount)
+			.map_err(dispatch_failure)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+	fn consult(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let mut input = EvmDataReader::new_skip_selector(handle.input())?;
+		input.expect_arguments(4)?;
+		let token_a: FungibleTokenIdOf<Runtime> = input.read::<u128>()?.into();
+		let token_b: FungibleTokenIdOf<Runtime> = input.read::<u128>()?.into();
+		let fee = input.read::<u32>()?;
+		let window = input.read::<BlockNumber>()?.into();
+
+		let max_observations = <Runtime as pallet_exchange::Config>::MaxObservations::get();
+		handle.record_cost(
+			RuntimeHelper::<Runtime>::db_read_gas_cost() * (3 + u64::from(max_observations)),
+		)?;
+
+		let (price_a, price_b) =
+			pallet_exchange::Pallet::<Runtime>::consult(token_a, token_b, fee, window)
+				.map_err(dispatch_failure)?;
+
+		Ok(succeed(
+			EvmDataWriter::new()
+				.write(price_a.into_inner())
+				.write(price_b.into_inner())
+				.build(),
+		))
+	}
+}
+
+fn dispatch_failure(error: DispatchError) -> PrecompileFailure {
+	revert(<&'static str>::from(error))
+}
+
+/// Call the flash callback of the borrower, and revert unless it answers with the callback
+/// selector.
+fn flash_callback(
+	handle: &mut impl PrecompileHandle,
+	borrower: H160,
+	action: FlashAction,
+	input: Vec<u8>,
+) -> EvmResult {
+	let context = Context {
+		address: borrower,
+		caller: handle.context().address,
+		apparent_value: U256::zero(),
+	};
+	let (reason, output) = handle.call(borrower, None, input, None, false, &context);
+
+	match reason {
+		ExitReason::Succeed(_) => {
+			let selector = u32::from(action).to_be_bytes();
+			let accepted = EvmDataReader::new(&output)
+				.read::<H256>()
+				.map(|answer| answer.as_bytes()[0..4] == selector)
+				.unwrap_or(false);
+			if accepted {
+				Ok(())
+			} else {
+				Err(revert("flash callback rejected"))
+			}
+		},
+		ExitReason::Revert(_) if !output.is_empty() => Err(revert(output)),
+		_ => Err(revert("flash callback failed")),
+	}
 }
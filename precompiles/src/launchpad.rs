@@ -22,6 +22,7 @@ use pallet_evm::{AddressMapping, PrecompileSet};
 use precompile_utils::prelude::*;
 use primitives::{Balance, BlockNumber};
 use sp_core::H160;
+use sp_runtime::Percent;
 use sp_std::{fmt::Debug, marker::PhantomData, prelude::*};
 
 #[generate_function_selector]
@@ -31,6 +32,8 @@ enum Action {
 	BuyToken = "buy_token(uint256,uint256)",
 	Claim = "claim(uint256)",
 	OwnerClaim = "owner_claim(uint256)",
+	SetLiquidityConfig = "set_liquidity_config(uint256,uint256,uint256,uint256,uint256)",
+	ClaimLiquidity = "claim_liquidity(uint256)",
 }
 
 pub struct LaunchpadExtension<Runtime>(PhantomData<Runtime>);
@@ -49,8 +52,12 @@ where
 				Err(e) => return Some(Err(e)),
 			};
 			if let Err(err) = handle.check_function_modifier(match selector {
-				Action::CreatePool | Action::BuyToken | Action::OwnerClaim | Action::Claim =>
-					FunctionModifier::NonPayable,
+				Action::CreatePool |
+				Action::BuyToken |
+				Action::OwnerClaim |
+				Action::Claim |
+				Action::SetLiquidityConfig |
+				Action::ClaimLiquidity => FunctionModifier::NonPayable,
 			}) {
 				return Some(Err(err))
 			}
@@ -59,6 +66,8 @@ where
 				Action::BuyToken => Self::buy_token(handle),
 				Action::OwnerClaim => Self::owner_claim(handle),
 				Action::Claim => Self::claim(handle),
+				Action::SetLiquidityConfig => Self::set_liquidity_config(handle),
+				Action::ClaimLiquidity => Self::claim_liquidity(handle),
 			}
 		};
 		Some(result)
@@ -179,4 +188,60 @@ where
 		// Return call information
 		Ok(succeed(EvmDataWriter::new().write(true).build()))
 	}
+
+	fn set_liquidity_config(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let mut input = handle.read_input()?;
+		input.expect_arguments(5)?;
+
+		let pool_id = input.read::<u64>()?.into();
+		let buy_token_percent = Percent::from_percent(input.read::<u8>()?);
+		let sale_token_amount = input.read::<Balance>()?.into();
+		let fee = input.read::<u32>()?.into();
+		let lock_period = input.read::<BlockNumber>()?.into();
+
+		{
+			let caller: Runtime::AccountId =
+				Runtime::AddressMapping::into_account_id(handle.context().caller);
+
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(caller).into(),
+				pallet_launchpad::Call::<Runtime>::set_liquidity_config {
+					pool_id,
+					config: pallet_launchpad::LiquidityConfig {
+						buy_token_percent,
+						sale_token_amount,
+						fee,
+						lock_period,
+					},
+				},
+			)?;
+		}
+
+		// Return call information
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn claim_liquidity(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let mut input = handle.read_input()?;
+		input.expect_arguments(1)?;
+
+		let pool_id = input.read::<u64>()?.into();
+
+		{
+			let caller: Runtime::AccountId =
+				Runtime::AddressMapping::into_account_id(handle.context().caller);
+
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(caller).into(),
+				pallet_launchpad::Call::<Runtime>::claim_liquidity { pool_id },
+			)?;
+		}
+
+		// Return call information
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
 }
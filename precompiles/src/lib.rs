@@ -30,14 +30,17 @@ use pallet_support::AccountMapping;
 use sp_core::H160;
 use sp_std::{marker::PhantomData, prelude::*};
 
+mod crafting;
 mod exchange;
 mod farming;
 mod launchpad;
 mod marketplace;
+mod receiver;
 mod token_fungible;
 mod token_multi;
 mod token_non_fungible;
 
+pub use crafting::CraftingExtension;
 pub use exchange::ExchangeExtension;
 pub use farming::FarmingExtension;
 pub use launchpad::LaunchpadExtension;
@@ -93,7 +96,8 @@ where
 		+ pallet_exchange::Config
 		+ pallet_marketplace::Config
 		+ pallet_farming::Config
-		+ pallet_launchpad::Config,
+		+ pallet_launchpad::Config
+		+ pallet_crafting::Config,
 	R::Call: From<pallet_token_fungible::Call<R>>,
 	R::Call: From<pallet_token_non_fungible::Call<R>>,
 	R::Call: From<pallet_token_multi::Call<R>>,
@@ -101,6 +105,7 @@ where
 	R::Call: From<pallet_marketplace::Call<R>>,
 	R::Call: From<pallet_farming::Call<R>>,
 	R::Call: From<pallet_launchpad::Call<R>>,
+	R::Call: From<pallet_crafting::Call<R>>,
 	<R as pallet_token_fungible::Config>::FungibleTokenId: From<u128> + Into<u128>,
 	<R as pallet_exchange::Config>::PoolId: From<u128> + Into<u128>,
 	<R as pallet_token_non_fungible::Config>::NonFungibleTokenId: From<u128> + Into<u128>,
@@ -131,6 +136,7 @@ where
 			a if a == hash(1028) => MarketplaceExtension::<R>::new().execute(handle),
 			a if a == hash(1029) => FarmingExtension::<R>::new().execute(handle),
 			a if a == hash(1030) => LaunchpadExtension::<R>::new().execute(handle),
+			a if a == hash(1031) => CraftingExtension::<R>::new().execute(handle),
 			a if &a.to_fixed_bytes()[0..4] == FT_PRECOMPILE_ADDRESS_PREFIX =>
 			// Some(<FungibleTokenExtension<R> as Precompile>::execute(handle)),
 				FungibleTokenExtension::<R>::new().execute(handle),
@@ -19,10 +19,10 @@
 use fp_evm::{PrecompileHandle, PrecompileOutput};
 use frame_support::{
 	dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo},
-	sp_runtime::traits::UniqueSaturatedFrom,
+	sp_runtime::traits::{UniqueSaturatedFrom, UniqueSaturatedInto},
 };
 use pallet_evm::{AddressMapping, PrecompileSet};
-use pallet_marketplace::{Asset, BalanceOf};
+use pallet_marketplace::{Asset, BalanceOf, PaymentAsset};
 use precompile_utils::prelude::*;
 use primitives::BlockNumber;
 use sp_core::H160;
@@ -32,11 +32,17 @@ use sp_std::{fmt::Debug, marker::PhantomData, prelude::*};
 #[derive(Debug, PartialEq)]
 enum Action {
 	CreateOrder = "create_order(uint256,uint256,uint256,uint256,uint256)",
+	CreateOrderInToken = "create_order_in_token(uint256,uint256,uint256,uint256,uint256,uint256)",
 	CancelOrder = "cancel_order(uint256,uint256,uint256)",
 	ExecuteOrder = "execute_order(uint256,uint256,uint256)",
 	PlaceBid = "place_bid(uint256,uint256,uint256,uint256,uint256)",
+	PlaceBidInToken = "place_bid_in_token(uint256,uint256,uint256,uint256,uint256,uint256)",
 	CancelBid = "cancel_bid(uint256,uint256,uint256)",
 	AcceptBid = "accept_bid(uint256,uint256,uint256)",
+	CreateDutchOrder =
+		"create_dutch_order(uint256,uint256,uint256,uint256,uint256,uint256,uint256)",
+	CreateDutchOrderInToken = "create_dutch_order_in_token(uint256,uint256,uint256,uint256,uint256,uint256,uint256,uint256)",
+	GetCurrentPrice = "get_current_price(uint256,uint256,uint256)",
 }
 
 pub struct MarketplaceExtension<Runtime>(PhantomData<Runtime>);
@@ -56,21 +62,31 @@ where
 			};
 			if let Err(err) = handle.check_function_modifier(match selector {
 				Action::CreateOrder |
+				Action::CreateOrderInToken |
 				Action::CancelOrder |
 				Action::ExecuteOrder |
 				Action::PlaceBid |
+				Action::PlaceBidInToken |
 				Action::CancelBid |
-				Action::AcceptBid => FunctionModifier::NonPayable,
+				Action::AcceptBid |
+				Action::CreateDutchOrder |
+				Action::CreateDutchOrderInToken => FunctionModifier::NonPayable,
+				Action::GetCurrentPrice => FunctionModifier::View,
 			}) {
 				return Some(Err(err))
 			}
 			match selector {
 				Action::CreateOrder => Self::create_order(handle),
+				Action::CreateOrderInToken => Self::create_order_in_token(handle),
 				Action::CancelOrder => Self::cancel_order(handle),
 				Action::ExecuteOrder => Self::execute_order(handle),
 				Action::PlaceBid => Self::place_bid(handle),
+				Action::PlaceBidInToken => Self::place_bid_in_token(handle),
 				Action::CancelBid => Self::cancel_bid(handle),
 				Action::AcceptBid => Self::accept_bid(handle),
+				Action::CreateDutchOrder => Self::create_dutch_order(handle),
+				Action::CreateDutchOrderInToken => Self::create_dutch_order_in_token(handle),
+				Action::GetCurrentPrice => Self::get_current_price(handle),
 			}
 		};
 		Some(result)
@@ -118,6 +134,44 @@ where
 				Some(caller).into(),
 				pallet_marketplace::Call::<Runtime>::create_order {
 					asset,
+					payment_asset: PaymentAsset::Native,
+					price: BalanceOf::<Runtime>::unique_saturated_from(price),
+					duration:<Runtime as frame_system::pallet::Config>::BlockNumber::unique_saturated_from(duration)
+				},
+			)?;
+		}
+
+		// Return call information
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn create_order_in_token(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let mut input = handle.read_input()?;
+		input.expect_arguments(6)?;
+
+		let group_id = input.read::<u128>()?.into();
+		let token_id = input.read::<u128>()?.into();
+		let asset_type = input.read::<u128>()?.into();
+		let payment_token_id = input.read::<u128>()?.into();
+		let price: u128 = input.read::<u128>()?.into();
+		let duration = input.read::<BlockNumber>()?;
+
+		let asset = match asset_type {
+			0 => Asset::NonFungibleToken(group_id, token_id),
+			1 => Asset::MultiToken(group_id, token_id),
+			_ => Asset::ErrorToken,
+		};
+		{
+			let caller: Runtime::AccountId =
+				Runtime::AddressMapping::into_account_id(handle.context().caller);
+
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(caller).into(),
+				pallet_marketplace::Call::<Runtime>::create_order {
+					asset,
+					payment_asset: PaymentAsset::Fungible(payment_token_id),
 					price: BalanceOf::<Runtime>::unique_saturated_from(price),
 					duration:<Runtime as frame_system::pallet::Config>::BlockNumber::unique_saturated_from(duration)
 				},
@@ -213,6 +267,44 @@ where
 				Some(caller).into(),
 				pallet_marketplace::Call::<Runtime>::place_bid {
 					asset,
+					payment_asset: PaymentAsset::Native,
+					price: BalanceOf::<Runtime>::unique_saturated_from(price),
+					duration:<Runtime as frame_system::pallet::Config>::BlockNumber::unique_saturated_from(duration)
+				},
+			)?;
+		}
+
+		// Return call information
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn place_bid_in_token(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let mut input = handle.read_input()?;
+		input.expect_arguments(6)?;
+
+		let group_id = input.read::<u128>()?.into();
+		let token_id = input.read::<u128>()?.into();
+		let asset_type = input.read::<u128>()?.into();
+		let payment_token_id = input.read::<u128>()?.into();
+		let price: u128 = input.read::<u128>()?.into();
+		let duration = input.read::<BlockNumber>()?;
+
+		let asset = match asset_type {
+			0 => Asset::NonFungibleToken(group_id, token_id),
+			1 => Asset::MultiToken(group_id, token_id),
+			_ => Asset::ErrorToken,
+		};
+		{
+			let caller: Runtime::AccountId =
+				Runtime::AddressMapping::into_account_id(handle.context().caller);
+
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(caller).into(),
+				pallet_marketplace::Call::<Runtime>::place_bid {
+					asset,
+					payment_asset: PaymentAsset::Fungible(payment_token_id),
 					price: BalanceOf::<Runtime>::unique_saturated_from(price),
 					duration:<Runtime as frame_system::pallet::Config>::BlockNumber::unique_saturated_from(duration)
 				},
@@ -282,4 +374,110 @@ where
 		// Return call information
 		Ok(succeed(EvmDataWriter::new().write(true).build()))
 	}
+
+	fn create_dutch_order(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let mut input = handle.read_input()?;
+		input.expect_arguments(7)?;
+
+		let group_id = input.read::<u128>()?.into();
+		let token_id = input.read::<u128>()?.into();
+		let asset_type = input.read::<u128>()?.into();
+		let start_price: u128 = input.read::<u128>()?.into();
+		let end_price: u128 = input.read::<u128>()?.into();
+		let start_block = input.read::<BlockNumber>()?;
+		let end_block = input.read::<BlockNumber>()?;
+
+		let asset = match asset_type {
+			0 => Asset::NonFungibleToken(group_id, token_id),
+			1 => Asset::MultiToken(group_id, token_id),
+			_ => Asset::ErrorToken,
+		};
+		{
+			let caller: Runtime::AccountId =
+				Runtime::AddressMapping::into_account_id(handle.context().caller);
+
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(caller).into(),
+				pallet_marketplace::Call::<Runtime>::create_dutch_order {
+					asset,
+					payment_asset: PaymentAsset::Native,
+					start_price: BalanceOf::<Runtime>::unique_saturated_from(start_price),
+					end_price: BalanceOf::<Runtime>::unique_saturated_from(end_price),
+					start_block:<Runtime as frame_system::pallet::Config>::BlockNumber::unique_saturated_from(start_block),
+					end_block:<Runtime as frame_system::pallet::Config>::BlockNumber::unique_saturated_from(end_block)
+				},
+			)?;
+		}
+
+		// Return call information
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn create_dutch_order_in_token(
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		let mut input = handle.read_input()?;
+		input.expect_arguments(8)?;
+
+		let group_id = input.read::<u128>()?.into();
+		let token_id = input.read::<u128>()?.into();
+		let asset_type = input.read::<u128>()?.into();
+		let payment_token_id = input.read::<u128>()?.into();
+		let start_price: u128 = input.read::<u128>()?.into();
+		let end_price: u128 = input.read::<u128>()?.into();
+		let start_block = input.read::<BlockNumber>()?;
+		let end_block = input.read::<BlockNumber>()?;
+
+		let asset = match asset_type {
+			0 => Asset::NonFungibleToken(group_id, token_id),
+			1 => Asset::MultiToken(group_id, token_id),
+			_ => Asset::ErrorToken,
+		};
+		{
+			let caller: Runtime::AccountId =
+				Runtime::AddressMapping::into_account_id(handle.context().caller);
+
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(caller).into(),
+				pallet_marketplace::Call::<Runtime>::create_dutch_order {
+					asset,
+					payment_asset: PaymentAsset::Fungible(payment_token_id),
+					start_price: BalanceOf::<Runtime>::unique_saturated_from(start_price),
+					end_price: BalanceOf::<Runtime>::unique_saturated_from(end_price),
+					start_block:<Runtime as frame_system::pallet::Config>::BlockNumber::unique_saturated_from(start_block),
+					end_block:<Runtime as frame_system::pallet::Config>::BlockNumber::unique_saturated_from(end_block)
+				},
+			)?;
+		}
+
+		// Return call information
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn get_current_price(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost() * 2)?;
+
+		let mut input = handle.read_input()?;
+		input.expect_arguments(3)?;
+
+		let group_id = input.read::<u128>()?.into();
+		let token_id = input.read::<u128>()?.into();
+		let asset_type = input.read::<u128>()?.into();
+
+		let asset = match asset_type {
+			0 => Asset::NonFungibleToken(group_id, token_id),
+			1 => Asset::MultiToken(group_id, token_id),
+			_ => Asset::ErrorToken,
+		};
+
+		let price: u128 = pallet_marketplace::Pallet::<Runtime>::get_current_price(asset)
+			.ok_or_else(|| revert("Order not found"))?
+			.unique_saturated_into();
+
+		Ok(succeed(EvmDataWriter::new().write(price).build()))
+	}
 }
@@ -0,0 +1,66 @@
+// This file is part of Web3Games.
+
+// Copyright (C) 2021-2022 Web3Games https://web3games.org
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use fp_evm::{Context, ExitReason, PrecompileHandle};
+use precompile_utils::prelude::*;
+use sp_core::{H160, H256, U256};
+use sp_std::prelude::*;
+
+#[generate_function_selector]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ReceiverAction {
+	OnERC721Received = "onERC721Received(address,address,uint256,bytes)",
+	OnERC1155Received = "onERC1155Received(address,address,uint256,uint256,bytes)",
+	OnERC1155BatchReceived = "onERC1155BatchReceived(address,address,uint256[],uint256[],bytes)",
+}
+
+/// Call the receiver hook of `to` when it is a contract, and revert unless it answers with the
+/// hook selector. Accounts without code always accept.
+pub fn check_receiver<Runtime: pallet_evm::Config>(
+	handle: &mut impl PrecompileHandle,
+	to: H160,
+	action: ReceiverAction,
+	input: Vec<u8>,
+	rejected: &'static str,
+) -> EvmResult {
+	handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+	if pallet_evm::AccountCodes::<Runtime>::decode_len(to).unwrap_or(0) == 0 {
+		return Ok(())
+	}
+
+	let context =
+		Context { address: to, caller: handle.context().address, apparent_value: U256::zero() };
+	let (reason, output) = handle.call(to, None, input, None, false, &context);
+
+	match reason {
+		ExitReason::Succeed(_) => {
+			let selector = u32::from(action).to_be_bytes();
+			let accepted = EvmDataReader::new(&output)
+				.read::<H256>()
+				.map(|answer| answer.as_bytes()[0..4] == selector)
+				.unwrap_or(false);
+			if accepted {
+				Ok(())
+			} else {
+				Err(revert(rejected))
+			}
+		},
+		ExitReason::Revert(_) if !output.is_empty() => Err(revert(output)),
+		_ => Err(revert(rejected)),
+	}
+}
@@ -16,16 +16,27 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{MT_PRECOMPILE_ADDRESS_PREFIX, TOKEN_MULTI_CREATE_SELECTOR};
+use crate::{
+	receiver::{check_receiver, ReceiverAction},
+	MT_PRECOMPILE_ADDRESS_PREFIX, TOKEN_MULTI_CREATE_SELECTOR,
+};
 use fp_evm::{PrecompileHandle, PrecompileOutput, PrecompileSet};
 use frame_support::dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo};
 use pallet_evm::AddressMapping;
 use pallet_support::{MultiMetadata, TokenIdConversion};
 use precompile_utils::prelude::*;
 use primitives::{Balance, TokenId};
-use sp_core::H160;
+use sp_core::{H160, H256};
 use sp_std::{fmt::Debug, marker::PhantomData, prelude::*};
 
+/// Solidity selector of the URI log, which is the Keccak of the Log signature.
+pub const SELECTOR_LOG_URI: [u8; 32] = keccak256!("URI(string,uint256)");
+
+/// ERC-165 interface ids reported by `supportsInterface`.
+const INTERFACE_ID_ERC165: [u8; 4] = [0x01, 0xff, 0xc9, 0xa7];
+const INTERFACE_ID_ERC1155: [u8; 4] = [0xd9, 0xb6, 0x7a, 0x26];
+const INTERFACE_ID_ERC1155_METADATA_URI: [u8; 4] = [0x0e, 0x89, 0x34, 0x1c];
+
 pub type MultiTokenIdOf<Runtime> = <Runtime as pallet_token_multi::Config>::MultiTokenId;
 
 #[generate_function_selector]
@@ -39,9 +50,15 @@ enum Action {
 	MintBatch = "mintBatch(address,uint256[],uint256[])",
 	Burn = "burn(uint256,uint256)",
 	BurnBatch = "burnBatch(uint256[],uint256[])",
+	BurnFrom = "burn(address,uint256,uint256)",
+	BurnBatchFrom = "burnBatch(address,uint256[],uint256[])",
 	URI = "uri(uint256)",
 	SetApprovalForAll = "setApprovalForAll(address,bool)",
 	IsApprovedForAll = "isApprovedForAll(address,address)",
+	TotalSupply = "totalSupply(uint256)",
+	Exists = "exists(uint256)",
+	SetURI = "setURI(uint256,string)",
+	SupportsInterface = "supportsInterface(bytes4)",
 }
 pub struct MultiTokenExtension<Runtime>(PhantomData<Runtime>);
 
@@ -95,6 +112,9 @@ where
 						Action::URI |
 						Action::BalanceOfBatch |
 						Action::IsApprovedForAll |
+						Action::TotalSupply |
+						Action::Exists |
+						Action::SupportsInterface |
 						Action::BalanceOf => FunctionModifier::View,
 						Action::SafeTransferFrom |
 						Action::SafeBatchTransferFrom |
@@ -102,6 +122,9 @@ where
 						Action::MintBatch |
 						Action::Burn |
 						Action::SetApprovalForAll |
+						Action::SetURI |
+						Action::BurnFrom |
+						Action::BurnBatchFrom |
 						Action::BurnBatch => FunctionModifier::NonPayable,
 					}) {
 						return Some(Err(err))
@@ -111,6 +134,9 @@ where
 						Action::BalanceOf => Self::balance_of(multi_token_id, handle),
 						Action::BalanceOfBatch => Self::balance_of_batch(multi_token_id, handle),
 						Action::URI => Self::uri(multi_token_id, handle),
+						Action::TotalSupply => Self::total_supply(multi_token_id, handle),
+						Action::Exists => Self::token_exists(multi_token_id, handle),
+						Action::SupportsInterface => Self::supports_interface(handle),
 						// runtime methods (dispatchable)
 						Action::SafeTransferFrom => Self::transfer_from(multi_token_id, handle),
 						Action::SafeBatchTransferFrom =>
@@ -119,10 +145,13 @@ where
 						Action::MintBatch => Self::mint_batch(multi_token_id, handle),
 						Action::Burn => Self::burn(multi_token_id, handle),
 						Action::BurnBatch => Self::burn_batch(multi_token_id, handle),
+						Action::BurnFrom => Self::burn_from(multi_token_id, handle),
+						Action::BurnBatchFrom => Self::burn_batch_from(multi_token_id, handle),
 						Action::SetApprovalForAll =>
 							Self::set_approval_for_all(multi_token_id, handle),
 						Action::IsApprovedForAll =>
 							Self::is_approval_for_all(multi_token_id, handle),
+						Action::SetURI => Self::set_uri(multi_token_id, handle),
 					}
 				};
 				return Some(result)
@@ -228,12 +257,14 @@ where
 		handle: &mut impl PrecompileHandle,
 	) -> EvmResult<PrecompileOutput> {
 		let mut input = EvmDataReader::new_skip_selector(handle.input())?;
-		input.expect_arguments(4)?;
+		input.expect_arguments(5)?;
 
 		let from: H160 = input.read::<Address>()?.into();
 		let to: H160 = input.read::<Address>()?.into();
-		let token_id: Runtime::TokenId = input.read::<TokenId>()?.into();
+		let raw_token_id = input.read::<TokenId>()?;
+		let token_id: Runtime::TokenId = raw_token_id.into();
 		let amount = input.read::<Balance>()?;
+		let data = input.read::<Bytes>()?;
 
 		{
 			// Build call with origin.
@@ -254,6 +285,23 @@ where
 				},
 			)?;
 		}
+
+		let action = ReceiverAction::OnERC1155Received;
+		let receiver_input = EvmDataWriter::new_with_selector(action)
+			.write(Address(handle.context().caller))
+			.write(Address(from))
+			.write(raw_token_id)
+			.write(amount)
+			.write(data)
+			.build();
+		check_receiver::<Runtime>(
+			handle,
+			to,
+			action,
+			receiver_input,
+			"ERC1155: transfer to non ERC1155Receiver implementer",
+		)?;
+
 		Ok(succeed(EvmDataWriter::new().write(true).build()))
 	}
 
@@ -262,16 +310,15 @@ where
 		handle: &mut impl PrecompileHandle,
 	) -> EvmResult<PrecompileOutput> {
 		let mut input = EvmDataReader::new_skip_selector(handle.input())?;
-		input.expect_arguments(4)?;
+		input.expect_arguments(5)?;
 
 		let from: H160 = input.read::<Address>()?.into();
 		let to: H160 = input.read::<Address>()?.into();
-		let token_ids: Vec<Runtime::TokenId> = input
-			.read::<Vec<TokenId>>()?
-			.iter()
-			.map(|&a| Runtime::TokenId::from(a))
-			.collect();
+		let raw_token_ids = input.read::<Vec<TokenId>>()?;
+		let token_ids: Vec<Runtime::TokenId> =
+			raw_token_ids.iter().map(|&a| Runtime::TokenId::from(a)).collect();
 		let amounts = input.read::<Vec<Balance>>()?;
+		let data = input.read::<Bytes>()?;
 
 		{
 			// Build call with origin.
@@ -288,10 +335,27 @@ where
 					from,
 					to,
 					token_ids,
-					amounts,
+					amounts: amounts.clone(),
 				},
 			)?;
 		}
+
+		let action = ReceiverAction::OnERC1155BatchReceived;
+		let receiver_input = EvmDataWriter::new_with_selector(action)
+			.write(Address(handle.context().caller))
+			.write(Address(from))
+			.write(raw_token_ids)
+			.write(amounts)
+			.write(data)
+			.build();
+		check_receiver::<Runtime>(
+			handle,
+			to,
+			action,
+			receiver_input,
+			"ERC1155: transfer to non ERC1155Receiver implementer",
+		)?;
+
 		Ok(succeed(EvmDataWriter::new().write(true).build()))
 	}
 
@@ -403,6 +467,65 @@ where
 		Ok(succeed(EvmDataWriter::new().write(true).build()))
 	}
 
+	fn burn_from(
+		id: MultiTokenIdOf<Runtime>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		let mut input = EvmDataReader::new_skip_selector(handle.input())?;
+		input.expect_arguments(3)?;
+
+		let from: H160 = input.read::<Address>()?.into();
+		let token_id: Runtime::TokenId = input.read::<TokenId>()?.into();
+		let amount = input.read::<Balance>()?;
+
+		{
+			// Build call with origin.
+			let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+			let from: Runtime::AccountId = Runtime::AddressMapping::into_account_id(from);
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(origin).into(),
+				pallet_token_multi::Call::<Runtime>::burn_from { id, from, token_id, amount },
+			)?;
+		}
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn burn_batch_from(
+		id: MultiTokenIdOf<Runtime>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		let mut input = EvmDataReader::new_skip_selector(handle.input())?;
+		input.expect_arguments(3)?;
+
+		let from: H160 = input.read::<Address>()?.into();
+		let token_ids: Vec<Runtime::TokenId> = input
+			.read::<Vec<TokenId>>()?
+			.iter()
+			.map(|&a| Runtime::TokenId::from(a))
+			.collect();
+		let amounts = input.read::<Vec<Balance>>()?;
+
+		{
+			// Build call with origin.
+			let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+			let from: Runtime::AccountId = Runtime::AddressMapping::into_account_id(from);
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(origin).into(),
+				pallet_token_multi::Call::<Runtime>::burn_batch_from {
+					id,
+					from,
+					token_ids,
+					amounts,
+				},
+			)?;
+		}
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
 	fn uri(
 		id: MultiTokenIdOf<Runtime>,
 		handle: &mut impl PrecompileHandle,
@@ -412,11 +535,91 @@ where
 
 		let token_id: Runtime::TokenId = input.read::<TokenId>()?.into();
 
-		let uri = pallet_token_multi::Pallet::<Runtime>::uri(id, token_id);
+		let uri = <pallet_token_multi::Pallet<Runtime> as MultiMetadata>::uri(id, token_id);
 
 		// Build output.
 		Ok(succeed(EvmDataWriter::new().write::<Bytes>(uri.as_slice().into()).build()))
 	}
+
+	fn total_supply(
+		id: MultiTokenIdOf<Runtime>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		let mut input = EvmDataReader::new_skip_selector(handle.input())?;
+		input.expect_arguments(1)?;
+
+		let token_id: Runtime::TokenId = input.read::<TokenId>()?.into();
+
+		let total_supply: Balance =
+			<pallet_token_multi::Pallet<Runtime> as MultiMetadata>::total_supply(id, token_id);
+
+		// Build output.
+		Ok(succeed(EvmDataWriter::new().write(total_supply).build()))
+	}
+
+	fn token_exists(
+		id: MultiTokenIdOf<Runtime>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		let mut input = EvmDataReader::new_skip_selector(handle.input())?;
+		input.expect_arguments(1)?;
+
+		let token_id: Runtime::TokenId = input.read::<TokenId>()?.into();
+
+		let exists = <pallet_token_multi::Pallet<Runtime> as MultiMetadata>::exists(id, token_id);
+
+		// Build output.
+		Ok(succeed(EvmDataWriter::new().write(exists).build()))
+	}
+
+	fn set_uri(
+		id: MultiTokenIdOf<Runtime>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		let mut input = EvmDataReader::new_skip_selector(handle.input())?;
+		input.expect_arguments(2)?;
+
+		let token_id: Runtime::TokenId = input.read::<TokenId>()?.into();
+		let uri: Vec<u8> = input.read::<Bytes>()?.into();
+		let uri = if uri.is_empty() { None } else { Some(uri) };
+
+		{
+			// Build call with origin.
+			let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(origin).into(),
+				pallet_token_multi::Call::<Runtime>::set_uri { id, token_id, uri },
+			)?;
+		}
+
+		let uri = <pallet_token_multi::Pallet<Runtime> as MultiMetadata>::uri(id, token_id);
+		let token_id: u128 = token_id.into();
+		let data = EvmDataWriter::new().write::<Bytes>(uri.as_slice().into()).build();
+		handle.record_log_costs_manual(2, data.len())?;
+		let mut topic = [0u8; 32];
+		topic[16..32].copy_from_slice(&token_id.to_be_bytes());
+		log2(handle.context().address, SELECTOR_LOG_URI, topic, data).record(handle)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn supports_interface(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let mut input = EvmDataReader::new_skip_selector(handle.input())?;
+		input.expect_arguments(1)?;
+
+		let interface_id: H256 = input.read::<H256>()?;
+		let interface_id = &interface_id.as_bytes()[0..4];
+
+		let supported = interface_id == INTERFACE_ID_ERC165 ||
+			interface_id == INTERFACE_ID_ERC1155 ||
+			interface_id == INTERFACE_ID_ERC1155_METADATA_URI;
+
+		// Build output.
+		Ok(succeed(EvmDataWriter::new().write(supported).build()))
+	}
+
 	fn is_approval_for_all(
 		id: MultiTokenIdOf<Runtime>,
 		handle: &mut impl PrecompileHandle,
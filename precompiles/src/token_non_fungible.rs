@@ -16,7 +16,10 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{NFT_PRECOMPILE_ADDRESS_PREFIX, TOKEN_NON_FUNGIBLE_CREATE_SELECTOR};
+use crate::{
+	receiver::{check_receiver, ReceiverAction},
+	NFT_PRECOMPILE_ADDRESS_PREFIX, TOKEN_NON_FUNGIBLE_CREATE_SELECTOR,
+};
 use fp_evm::PrecompileOutput;
 use frame_support::dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo};
 use pallet_evm::{AddressMapping, PrecompileHandle, PrecompileSet};
@@ -40,8 +43,11 @@ enum Action {
 	BalanceOf = "balanceOf(address)",
 	OwnerOf = "ownerOf(uint256)",
 	TransferFrom = "transferFrom(address,address,uint256)",
+	SafeTransferFrom = "safeTransferFrom(address,address,uint256)",
+	SafeTransferFromWithData = "safeTransferFrom(address,address,uint256,bytes)",
 	Mint = "mint(address,uint256)",
 	Burn = "burn(uint256)",
+	BurnFrom = "burnFrom(uint256)",
 	Name = "name()",
 	Symbol = "symbol()",
 	TokenURI = "tokenURI(uint256)",
@@ -111,8 +117,13 @@ where
 						Action::TokenOfOwnerByIndex |
 						Action::TokenByIndex |
 						Action::BalanceOf => FunctionModifier::View,
-						Action::TransferFrom | Action::Mint | Action::Burn | Action::Approve =>
-							FunctionModifier::NonPayable,
+						Action::TransferFrom |
+						Action::SafeTransferFrom |
+						Action::SafeTransferFromWithData |
+						Action::Mint |
+						Action::Burn |
+						Action::BurnFrom |
+						Action::Approve => FunctionModifier::NonPayable,
 					}) {
 						return Some(Err(err))
 					}
@@ -129,8 +140,13 @@ where
 						Action::OwnerOf => Self::owner_of(non_fungible_token_id, handle),
 						// call methods (dispatchable)
 						Action::TransferFrom => Self::transfer_from(non_fungible_token_id, handle),
+						Action::SafeTransferFrom =>
+							Self::safe_transfer_from(non_fungible_token_id, handle, false),
+						Action::SafeTransferFromWithData =>
+							Self::safe_transfer_from(non_fungible_token_id, handle, true),
 						Action::Mint => Self::mint(non_fungible_token_id, handle),
 						Action::Burn => Self::burn(non_fungible_token_id, handle),
+						Action::BurnFrom => Self::burn_from(non_fungible_token_id, handle),
 						Action::Approve => Self::approve(non_fungible_token_id, handle),
 					}
 				};
@@ -291,6 +307,38 @@ where
 		Ok(succeed(EvmDataWriter::new().write(true).build()))
 	}
 
+	fn safe_transfer_from(
+		id: NonFungibleTokenIdOf<Runtime>,
+		handle: &mut impl PrecompileHandle,
+		with_data: bool,
+	) -> EvmResult<PrecompileOutput> {
+		let mut input = EvmDataReader::new_skip_selector(handle.input())?;
+		input.expect_arguments(if with_data { 4 } else { 3 })?;
+		let from: H160 = input.read::<Address>()?.into();
+		let to: H160 = input.read::<Address>()?.into();
+		let token_id = input.read::<TokenId>()?;
+		let data = if with_data { input.read::<Bytes>()? } else { Bytes::from(&[][..]) };
+
+		let output = Self::transfer_from(id, handle)?;
+
+		let action = ReceiverAction::OnERC721Received;
+		let receiver_input = EvmDataWriter::new_with_selector(action)
+			.write(Address(handle.context().caller))
+			.write(Address(from))
+			.write(token_id)
+			.write(data)
+			.build();
+		check_receiver::<Runtime>(
+			handle,
+			to,
+			action,
+			receiver_input,
+			"ERC721: transfer to non ERC721Receiver implementer",
+		)?;
+
+		Ok(output)
+	}
+
 	fn mint(
 		id: NonFungibleTokenIdOf<Runtime>,
 		handle: &mut impl PrecompileHandle,
@@ -342,6 +390,30 @@ where
 		Ok(succeed(EvmDataWriter::new().write(true).build()))
 	}
 
+	fn burn_from(
+		id: NonFungibleTokenIdOf<Runtime>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		let mut input = EvmDataReader::new_skip_selector(handle.input())?;
+		input.expect_arguments(1)?;
+
+		let token_id = input.read::<TokenId>()?;
+
+		{
+			let caller: Runtime::AccountId =
+				Runtime::AddressMapping::into_account_id(handle.context().caller);
+			let token_id: Runtime::TokenId = token_id.into();
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(caller).into(),
+				pallet_token_non_fungible::Call::<Runtime>::burn_from { id, token_id },
+			)?;
+		}
+		// Return call information
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
 	fn name(
 		id: NonFungibleTokenIdOf<Runtime>,
 		_handle: &mut impl PrecompileHandle,
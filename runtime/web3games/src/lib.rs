@@ -400,6 +400,181 @@ impl pallet_contracts::Config for Runtime {
 	type MaxStorageKeyLen = ConstU32<128>;
 }
 
+/// Ink! selector of `on_non_fungible_received`, the first four bytes of its blake2_256 hash.
+const ON_NON_FUNGIBLE_RECEIVED_SELECTOR: [u8; 4] = [0xec, 0xe7, 0xa3, 0xa0];
+/// Ink! selector of `on_multi_received`.
+const ON_MULTI_RECEIVED_SELECTOR: [u8; 4] = [0x93, 0x57, 0xbe, 0x3c];
+/// Ink! selector of `on_multi_batch_received`.
+const ON_MULTI_BATCH_RECEIVED_SELECTOR: [u8; 4] = [0x57, 0x83, 0xef, 0xa6];
+/// Gas limit of the receiver check call into a contract.
+const RECEIVER_CALL_GAS_LIMIT: Weight = WEIGHT_PER_SECOND / 100;
+
+/// Calls the receiver message of `pallet_contracts` recipients, which must answer with its own
+/// selector to accept the tokens. Accounts without code always accept.
+pub struct ContractsTokenReceiver;
+
+impl ContractsTokenReceiver {
+	/// The code hash lookup plus the receiver call itself.
+	fn call_weight() -> Weight {
+		RECEIVER_CALL_GAS_LIMIT.saturating_add(RocksDbWeight::get().reads(1))
+	}
+
+	fn accepts(operator: &AccountId, to: &AccountId, selector: [u8; 4], args: Vec<u8>) -> bool {
+		if pallet_contracts::Pallet::<Runtime>::code_hash(to).is_none() {
+			return true
+		}
+
+		let input = selector.iter().cloned().chain(args).collect::<Vec<_>>();
+		let result = Contracts::bare_call(
+			operator.clone(),
+			to.clone(),
+			0,
+			RECEIVER_CALL_GAS_LIMIT,
+			// the operator does not pay for storage the receiver sets up
+			Some(0),
+			input,
+			false,
+		);
+		match result.result {
+			Ok(value) => !value.did_revert() && value.data[..] == selector,
+			Err(_) => false,
+		}
+	}
+}
+
+impl pallet_support::NonFungibleReceiver<AccountId, TokenAssetId, TokenId>
+	for ContractsTokenReceiver
+{
+	fn on_received(
+		operator: &AccountId,
+		from: &AccountId,
+		to: &AccountId,
+		id: TokenAssetId,
+		token_id: TokenId,
+	) -> bool {
+		Self::accepts(
+			operator,
+			to,
+			ON_NON_FUNGIBLE_RECEIVED_SELECTOR,
+			(operator, from, id, token_id).encode(),
+		)
+	}
+
+	fn max_weight() -> Weight {
+		Self::call_weight()
+	}
+}
+
+impl pallet_support::MultiReceiver<AccountId, TokenAssetId, TokenId> for ContractsTokenReceiver {
+	fn on_received(
+		operator: &AccountId,
+		from: &AccountId,
+		to: &AccountId,
+		id: TokenAssetId,
+		token_id: TokenId,
+		amount: Balance,
+	) -> bool {
+		Self::accepts(
+			operator,
+			to,
+			ON_MULTI_RECEIVED_SELECTOR,
+			(operator, from, id, token_id, amount).encode(),
+		)
+	}
+
+	fn on_batch_received(
+		operator: &AccountId,
+		from: &AccountId,
+		to: &AccountId,
+		id: TokenAssetId,
+		token_ids: &[TokenId],
+		amounts: &[Balance],
+	) -> bool {
+		Self::accepts(
+			operator,
+			to,
+			ON_MULTI_BATCH_RECEIVED_SELECTOR,
+			(operator, from, id, token_ids, amounts).encode(),
+		)
+	}
+
+	fn max_weight() -> Weight {
+		Self::call_weight()
+	}
+}
+
+/// Ink! selector of `on_flash_swap`.
+const ON_FLASH_SWAP_SELECTOR: [u8; 4] = [0x20, 0x41, 0xd3, 0x90];
+/// Ink! selector of `on_flash_loan`.
+const ON_FLASH_LOAN_SELECTOR: [u8; 4] = [0x08, 0x48, 0x1e, 0x7f];
+
+/// Calls the flash callback message of `pallet_contracts` borrowers from the exchange account,
+/// which must answer with its own selector. Accounts without code are not called back.
+///
+/// The callback is a new call stack, so the re-entrancy check of `pallet_contracts` does not
+/// see a borrower that started the flash swap or loan through the chain extension: such a
+/// borrower is re-entered while its own call is still in progress and has to keep its storage
+/// consistent before borrowing. The pool itself stays locked until it is repaid.
+pub struct ContractsFlashCallback;
+
+impl ContractsFlashCallback {
+	fn call(who: &AccountId, selector: [u8; 4], args: Vec<u8>) -> bool {
+		if pallet_contracts::Pallet::<Runtime>::code_hash(who).is_none() {
+			return true
+		}
+
+		let input = selector.iter().cloned().chain(args).collect::<Vec<_>>();
+		let result = Contracts::bare_call(
+			Exchange::account_id(),
+			who.clone(),
+			0,
+			MaxFlashCallbackWeight::get(),
+			// the exchange account never pays for storage of the borrower
+			Some(0),
+			input,
+			false,
+		);
+		match result.result {
+			Ok(value) => !value.did_revert() && value.data[..] == selector,
+			Err(_) => false,
+		}
+	}
+}
+
+impl pallet_exchange::FlashCallback<AccountId, TokenAssetId> for ContractsFlashCallback {
+	fn on_flash_swap(
+		who: &AccountId,
+		token_a: TokenAssetId,
+		token_b: TokenAssetId,
+		fee: u32,
+		amount_a_out: Balance,
+		amount_b_out: Balance,
+		data: &[u8],
+	) -> bool {
+		Self::call(
+			who,
+			ON_FLASH_SWAP_SELECTOR,
+			(token_a, token_b, fee, amount_a_out, amount_b_out, data).encode(),
+		)
+	}
+
+	fn on_flash_loan(
+		who: &AccountId,
+		token: TokenAssetId,
+		pair_token: TokenAssetId,
+		fee: u32,
+		amount: Balance,
+		premium: Balance,
+		data: &[u8],
+	) -> bool {
+		Self::call(
+			who,
+			ON_FLASH_LOAN_SELECTOR,
+			(token, pair_token, fee, amount, premium, data).encode(),
+		)
+	}
+}
+
 impl pallet_sudo::Config for Runtime {
 	type Event = Event;
 	type Call = Call;
@@ -565,6 +740,10 @@ impl Contains<AccountId> for DustRemovalWhitelist {
 parameter_types! {
 	pub const CreateTokenDeposit: Balance = 500 * MILLICENTS;
 	pub const CreatePoolDeposit: Balance = 500 * MILLICENTS;
+	pub const MaxFlashCallbackWeight: Weight = WEIGHT_PER_SECOND / 20;
+	pub const MaxObservations: u32 = 24;
+	pub const ObservationPeriod: BlockNumber = HOURS;
+	pub const MaxOrdersPerBlock: u32 = 20;
 	pub const CreateCollectionDeposit: Balance = 500 * MILLICENTS;
 }
 
@@ -645,6 +824,7 @@ impl pallet_token_non_fungible::Config for Runtime {
 	type StringLimit = StringLimit;
 	type CreateTokenDeposit = CreateTokenDeposit;
 	type Currency = Balances;
+	type Receiver = ContractsTokenReceiver;
 	type WeightInfo = pallet_token_non_fungible::weights::W3GWeight<Runtime>;
 }
 
@@ -656,6 +836,7 @@ impl pallet_token_multi::Config for Runtime {
 	type StringLimit = StringLimit;
 	type CreateTokenDeposit = CreateTokenDeposit;
 	type Currency = Balances;
+	type Receiver = ContractsTokenReceiver;
 	type WeightInfo = pallet_token_multi::weights::W3GWeight<Runtime>;
 }
 
@@ -670,7 +851,14 @@ impl pallet_exchange::Config for Runtime {
 	type CreatePoolDeposit = CreatePoolDeposit;
 	type WW3G = WW3G;
 	type Currency = Balances;
-	type Randomness = RandomnessCollectiveFlip;
+	type UpdateOrigin = EnsureRoot<AccountId>;
+	type PauseOrigin = EnsureRoot<AccountId>;
+	type FlashCallback = ContractsFlashCallback;
+	type MaxFlashCallbackWeight = MaxFlashCallbackWeight;
+	type MaxObservations = MaxObservations;
+	type ObservationPeriod = ObservationPeriod;
+	type OrderId = u128;
+	type MaxOrdersPerBlock = MaxOrdersPerBlock;
 	type WeightInfo = pallet_exchange::weights::W3GWeight<Runtime>;
 }
 
@@ -689,10 +877,26 @@ impl pallet_proxy_pay::Config for Runtime {
 	type PalletId = ProxyPayPalletId;
 }
 
+parameter_types! {
+	pub const AntiSnipingPeriod: BlockNumber = 10 * MINUTES;
+	// every settlement may call a contract receiver, see `ContractsTokenReceiver`
+	pub const MaxAuctionsPerBlock: u32 = 10;
+	pub const MaxOffersPerAsset: u32 = 50;
+	pub const MaxOffersPerBlock: u32 = 50;
+	pub const MaxPlatformFee: Permill = Permill::from_percent(5);
+	pub const MaxRoyalty: Permill = Permill::from_percent(10);
+}
+
 impl pallet_marketplace::Config for Runtime {
 	type Event = Event;
 	type PalletId = MarketplacePalletId;
 	type Currency = Balances;
+	type AntiSnipingPeriod = AntiSnipingPeriod;
+	type MaxAuctionsPerBlock = MaxAuctionsPerBlock;
+	type MaxOffersPerAsset = MaxOffersPerAsset;
+	type MaxOffersPerBlock = MaxOffersPerBlock;
+	type MaxPlatformFee = MaxPlatformFee;
+	type MaxRoyalty = MaxRoyalty;
 }
 
 parameter_types! {
@@ -735,10 +939,28 @@ impl pallet_farming::Config for Runtime {
 	type WeightInfo = pallet_farming::weights::W3GWeight<Runtime>;
 }
 
+parameter_types! {
+	pub const LiquidityPriceTolerance: Percent = Percent::from_percent(5);
+	pub const MaxLiquidityPerBlock: u32 = 10;
+}
+
 impl pallet_launchpad::Config for Runtime {
 	type Event = Event;
 	type PalletId = FarmingPalletId;
 	type WeightInfo = pallet_launchpad::weights::W3GWeight<Runtime>;
+	type Exchange = Exchange;
+	type LiquidityPriceTolerance = LiquidityPriceTolerance;
+	type MaxLiquidityPerBlock = MaxLiquidityPerBlock;
+}
+
+impl pallet_crafting::Config for Runtime {
+	type Event = Event;
+	type MaxInputs = ConstU32<16>;
+	type MaxOutputs = ConstU32<16>;
+	type MaxOutcomes = ConstU32<32>;
+	type MaxCraftTimes = ConstU32<50>;
+	type Randomness = RandomnessCollectiveFlip;
+	type WeightInfo = pallet_crafting::weights::W3GWeight<Runtime>;
 }
 
 construct_runtime!(
@@ -783,7 +1005,8 @@ construct_runtime!(
 		CallSwitchgear: pallet_call_switchgear,
 		TransactionStorage: pallet_transaction_storage,
 		Farming: pallet_farming,
-		Launchpad: pallet_launchpad
+		Launchpad: pallet_launchpad,
+		Crafting: pallet_crafting
 	}
 );
 
@@ -919,6 +1142,7 @@ mod benches {
 		[pallet_exchange, Exchange]
 		[pallet_farming, Farming]
 		[pallet_launchpad, Launchpad]
+		[pallet_crafting, Crafting]
 	);
 }
 
@@ -1266,9 +1490,10 @@ impl_runtime_apis! {
 			token_0: u128,
 			amount_0: Balance,
 			token_1: u128,
-			amount_1: Balance
+			amount_1: Balance,
+			fee: u32,
 		) -> Option<Balance> {
-			if let Ok(liquidity) = Exchange::get_liquidity(token_0,amount_0,token_1,amount_1) {
+			if let Ok(liquidity) = Exchange::get_liquidity(token_0,amount_0,token_1,amount_1,fee) {
 				Some(liquidity)
 			} else {
 				None
@@ -1278,8 +1503,9 @@ impl_runtime_apis! {
 			supply: Balance,
 			token_0: u128,
 			token_1: u128,
+			fee: u32,
 		) -> Option<Balance> {
-			if let Ok((reserve_a,reserve_b)) = Exchange::get_reserves(token_0,token_1) {
+			if let Ok((reserve_a,reserve_b)) = Exchange::get_reserves(token_0,token_1,fee) {
 				if let Ok(amount) = Exchange::quote(supply,reserve_a,reserve_b) {
 					Some(amount)
 				}else{
@@ -1299,6 +1525,150 @@ impl_runtime_apis! {
 				None
 			}
 		}
+		fn consult(
+			token_a: u128,
+			token_b: u128,
+			fee: u32,
+			window: BlockNumber,
+		) -> Option<(u128, u128)> {
+			Exchange::consult(token_a, token_b, fee, window)
+				.ok()
+				.map(|(price_a, price_b)| (price_a.into_inner(), price_b.into_inner()))
+		}
+		fn get_stable_amount_out(
+			pool_id: u128,
+			token_in: u128,
+			token_out: u128,
+			amount_in: Balance,
+		) -> Option<Balance> {
+			Exchange::get_stable_amount_out(pool_id, token_in, token_out, amount_in).ok()
+		}
+		fn get_stable_estimate_lp_token(pool_id: u128, amounts: Vec<Balance>) -> Option<Balance> {
+			Exchange::get_stable_liquidity(pool_id, &amounts).ok()
+		}
+		fn get_stable_liquidity_to_tokens(
+			pool_id: u128,
+			lp_balance: Balance,
+		) -> Option<Vec<Balance>> {
+			Exchange::stable_liquidity_to_tokens(pool_id, lp_balance).ok()
+		}
+		fn find_route_out(
+			token_in: u128,
+			token_out: u128,
+			amount_in: Balance,
+			max_hops: u32,
+		) -> Option<pallet_exchange::Route<u128>> {
+			Exchange::find_route_out(token_in, token_out, amount_in, max_hops).ok()
+		}
+		fn find_route_in(
+			token_in: u128,
+			token_out: u128,
+			amount_out: Balance,
+			max_hops: u32,
+		) -> Option<pallet_exchange::Route<u128>> {
+			Exchange::find_route_in(token_in, token_out, amount_out, max_hops).ok()
+		}
+		fn get_pools(offset: u32, limit: u32) -> Vec<pallet_exchange::PoolInfo<u128>> {
+			Exchange::get_pools(offset, limit)
+		}
+		fn get_pool(
+			token_a: u128,
+			token_b: u128,
+			fee: u32,
+		) -> Option<pallet_exchange::PoolInfo<u128>> {
+			Exchange::get_pool_info(token_a, token_b, fee)
+		}
+		fn get_pool_by_lp_token(lp_token: u128) -> Option<pallet_exchange::PoolInfo<u128>> {
+			Exchange::get_pool_info_by_lp_token(lp_token)
+		}
+		fn get_stable_lp_token(pool_id: u128) -> Option<u128> {
+			Exchange::stable_pools(pool_id).map(|pool| pool.lp_token)
+		}
+		fn get_stable_pool_by_lp_token(lp_token: u128) -> Option<u128> {
+			Exchange::stable_lp_token_to_pool(lp_token)
+		}
+		fn get_stable_pool_status(pool_id: u128) -> Option<pallet_exchange::PoolStatus> {
+			Exchange::stable_pools(pool_id).map(|_| Exchange::stable_pool_status(pool_id))
+		}
+		fn get_weighted_amount_out(
+			pool_id: u128,
+			token_in: u128,
+			token_out: u128,
+			amount_in: Balance,
+		) -> Option<Balance> {
+			Exchange::get_weighted_amount_out(pool_id, token_in, token_out, amount_in).ok()
+		}
+		fn get_weighted_estimate_lp_token(
+			pool_id: u128,
+			token_in: u128,
+			amount_in: Balance,
+		) -> Option<Balance> {
+			Exchange::get_weighted_single_in_liquidity(pool_id, token_in, amount_in).ok()
+		}
+		fn get_weighted_estimate_out_token(
+			pool_id: u128,
+			token_out: u128,
+			lp_balance: Balance,
+		) -> Option<Balance> {
+			Exchange::get_weighted_single_out(pool_id, token_out, lp_balance).ok()
+		}
+		fn get_weighted_liquidity_to_tokens(
+			pool_id: u128,
+			lp_balance: Balance,
+		) -> Option<Vec<Balance>> {
+			Exchange::weighted_liquidity_to_tokens(pool_id, lp_balance).ok()
+		}
+		fn get_weighted_pool_by_lp_token(lp_token: u128) -> Option<u128> {
+			Exchange::weighted_lp_token_to_pool(lp_token)
+		}
+		fn get_weighted_pool_status(pool_id: u128) -> Option<pallet_exchange::PoolStatus> {
+			Exchange::weighted_pools(pool_id).map(|_| Exchange::weighted_pool_status(pool_id))
+		}
+		fn get_lp_positions(who: AccountId) -> Vec<pallet_exchange::LpPosition<u128>> {
+			Exchange::get_lp_positions(&who)
+		}
+	}
+
+	impl pallet_token_multi_rpc_runtime_api::TokenMultiRuntimeApi<Block> for Runtime {
+		fn total_supply(id: u128, token_id: u128) -> Balance {
+			<TokenMulti as pallet_support::MultiMetadata>::total_supply(id, token_id)
+		}
+
+		fn exists(id: u128, token_id: u128) -> bool {
+			<TokenMulti as pallet_support::MultiMetadata>::exists(id, token_id)
+		}
+
+		fn max_supply(id: u128, token_id: u128) -> Option<Balance> {
+			TokenMulti::max_supply(id, token_id)
+		}
+	}
+
+	impl pallet_marketplace_rpc_runtime_api::MarketplaceRuntimeApi<Block> for Runtime {
+		fn get_current_price(asset: pallet_marketplace::Asset) -> Option<Balance> {
+			Martketplace::get_current_price(asset)
+		}
+	}
+
+	impl pallet_tokens_rpc_runtime_api::TokensRuntimeApi<Block, AccountId> for Runtime {
+		fn fungible_balances(who: AccountId, start: Option<u128>, limit: u32) -> Vec<(u128, Balance)> {
+			TokenFungible::balances_of(&who, start, limit)
+		}
+
+		fn non_fungible_tokens(
+			who: AccountId,
+			start: Option<(u128, u128)>,
+			limit: u32,
+		) -> Vec<(u128, u128)> {
+			TokenNonFungible::tokens_of(&who, start, limit)
+		}
+
+		fn multi_tokens(
+			who: AccountId,
+			start: Option<(u128, u128)>,
+			limit: u32,
+		) -> Vec<(u128, u128, Balance)> {
+			TokenMulti::holdings_of(&who, start, limit)
+		}
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
@@ -1322,6 +1692,7 @@ impl_runtime_apis! {
 			list_benchmark!(list, extra, pallet_exchange, Exchange);
 			list_benchmark!(list, extra, pallet_farming, Farming);
 			list_benchmark!(list, extra, pallet_launchpad, Launchpad);
+			list_benchmark!(list, extra, pallet_crafting, Crafting);
 
 			let storage_info = AllPalletsWithSystem::storage_info();
 			return (list, storage_info)
@@ -1362,6 +1733,7 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, pallet_exchange, Exchange);
 			add_benchmark!(params, batches, pallet_farming, Farming);
 			add_benchmark!(params, batches, pallet_launchpad, Launchpad);
+			add_benchmark!(params, batches, pallet_crafting, Crafting);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)